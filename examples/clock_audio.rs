@@ -20,7 +20,9 @@ use core::time::Duration as StdDuration;
 use defmt::info;
 use defmt_rtt as _;
 use device_envoy::audio_player::SilenceClip;
-use device_envoy::audio_player::{AtEnd, Gain, VOICE_22050_HZ, Volume, audio_player};
+use device_envoy::audio_player::{
+    AtEnd, Gain, InterruptPolicy, Priority, VOICE_22050_HZ, Volume, audio_player,
+};
 use device_envoy::button::PressedTo;
 use device_envoy::clock_sync::{ClockSync, ClockSyncStatic, ONE_MINUTE, ONE_SECOND, h12_m_s};
 use device_envoy::flash_array::FlashArray;
@@ -224,11 +226,25 @@ async fn inner_main(spawner: Spawner) -> Result<Infallible> {
 
                 match clock_audio_mode {
                     ClockAudioMode::HoursMinutes => {
-                        audio_player8.play([MODE_HH_MM_TONE], AtEnd::Stop);
+                        audio_player8
+                            .play_with_priority(
+                                [MODE_HH_MM_TONE],
+                                AtEnd::Stop,
+                                Priority::High,
+                                InterruptPolicy::Preempt,
+                            )
+                            .await;
                         info!("Mode changed: hh:mm (minute tick)");
                     }
                     ClockAudioMode::MinutesSeconds => {
-                        audio_player8.play([MODE_MM_SS_TONE], AtEnd::Stop);
+                        audio_player8
+                            .play_with_priority(
+                                [MODE_MM_SS_TONE],
+                                AtEnd::Stop,
+                                Priority::High,
+                                InterruptPolicy::Preempt,
+                            )
+                            .await;
                         info!("Mode changed: mm:ss (second tick)");
                     }
                 }
@@ -237,7 +253,15 @@ async fn inner_main(spawner: Spawner) -> Result<Infallible> {
                 let (hours, minutes, seconds) = h12_m_s(&tick.local_time);
                 match clock_audio_mode {
                     ClockAudioMode::HoursMinutes => {
-                        audio_player8.play([HH_MM_TICK_TONE], AtEnd::Stop);
+                        // Skipped, rather than cutting off a mode announcement.
+                        audio_player8
+                            .play_with_priority(
+                                [HH_MM_TICK_TONE],
+                                AtEnd::Stop,
+                                Priority::Low,
+                                InterruptPolicy::Preempt,
+                            )
+                            .await;
                         info!(
                             "hh:mm {:02}:{:02} (since sync: {}s)",
                             hours,
@@ -246,7 +270,15 @@ async fn inner_main(spawner: Spawner) -> Result<Infallible> {
                         );
                     }
                     ClockAudioMode::MinutesSeconds => {
-                        audio_player8.play([MM_SS_TICK_TONE], AtEnd::Stop);
+                        // Skipped, rather than cutting off a mode announcement.
+                        audio_player8
+                            .play_with_priority(
+                                [MM_SS_TICK_TONE],
+                                AtEnd::Stop,
+                                Priority::Low,
+                                InterruptPolicy::Preempt,
+                            )
+                            .await;
                         info!(
                             "mm:ss {:02}:{:02} (since sync: {}s)",
                             minutes,
@@ -3,7 +3,7 @@
 //!
 //! This example cycles through multiple display modes using a button:
 //! 1. **Test Pattern**: RGBY corners (Red top-left, Green top-right, Blue bottom-left, Yellow bottom-right)
-//! 2. **Santa**: 65-frame video at 10 FPS
+//! 2. **Santa**: 65-frame video at 10 FPS, stored compressed and decoded frame-by-frame
 //! 3. **Cat**: Video converted from user's camera roll (when generated)
 //!
 //! Press the button at any time to advance to the next mode.
@@ -85,6 +85,7 @@ use defmt::info;
 use defmt_rtt as _;
 use device_envoy::Result;
 use device_envoy::button::{Button, PressedTo};
+use device_envoy::compressed_animation;
 use device_envoy::led_strip::Current;
 use device_envoy::led_strip::Gamma;
 use device_envoy::led2d;
@@ -138,6 +139,12 @@ const SANTA_FRAME_COUNT: usize = 1;
 const SANTA_FRAMES: [([[RGB8; 12]; 8], Duration); SANTA_FRAME_COUNT] =
     [([[colors::BLACK; 12]; 8], SANTA_FRAME_DURATION)];
 
+// Santa frames compressed at compile time; only the compressed bytes are stored in flash,
+// and frames are decoded one at a time during playback.
+compressed_animation! {
+    static SANTA_ANIMATION = SANTA_FRAMES;
+}
+
 // Cat video frames
 // include!(concat!(
 //     env!("CARGO_MANIFEST_DIR"),
@@ -251,10 +258,7 @@ async fn inner_main(spawner: Spawner) -> Result<()> {
                 mode = mode.next();
             }
             Mode::Santa => {
-                let frames_with_duration = SANTA_FRAMES
-                    .iter()
-                    .map(|&(frame, duration)| (frame.into(), duration));
-                led_12x8.animate(frames_with_duration)?;
+                led_12x8.animate_compressed(&SANTA_ANIMATION)?;
                 button.wait_for_press().await;
                 mode = mode.next();
             }
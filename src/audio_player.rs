@@ -1,4 +1,4 @@
-//! A device abstraction for playing audio clips over I²S hardware,
+//! A device abstraction for playing audio clips over I²S hardware (or PWM on one pin),
 //! with runtime sequencing, volume control, and compression.
 //!
 //! This page provides the primary documentation for generated audio player
@@ -17,10 +17,26 @@
 //! - Any sample rate supported by your hardware
 //! - Either:
 //!   - Uncompressed: 16-bit PCM (s16le)
-//!   - Compressed: IMA ADPCM in WAV (mono; ~25% the size of PCM; ideal for speech)
-//! - Mono input audio (duplicated to left/right on I²S output)
-//! - For ffmpeg conversion commands, see "Preparing audio files" at [`pcm_clip!`] and
-//!   [`adpcm_clip!`](macro@crate::audio_player::adpcm_clip).
+//!   - Compressed: IMA ADPCM in WAV (~25% the size of PCM; ideal for speech)
+//!   - Compressed: QOA (~20% the size of PCM; good quality, including music)
+//!   - Compressed: G.711 μ-law or A-law in WAV (50% the size of PCM; cheapest to decode)
+//! - Mono input audio (duplicated to left/right on I²S output) or stereo input audio
+//!   (`channels: 2` in [`pcm_clip!`], [`adpcm_clip!`](macro@crate::audio_player::adpcm_clip),
+//!   and [`qoa_clip!`](macro@crate::audio_player::qoa_clip))
+//! - Optional multi-voice mixing: several clip sequences, each with its own gain and pan, at once
+//! - Click-free transitions: per-clip fade-in/out, crossfades between clips, and fade-out on stop
+//! - Priority-aware playback: enqueue, preempt only lower-priority audio, or duck other voices
+//! - Playback position and clip/sequence events, for syncing lights or motion to audio
+//! - Audio generated at runtime, via [`AudioStream`] and [`AudioRingBuffer`]
+//! - Synthesized melodies from note notation, with several waveforms and ADSR envelopes
+//! - Optional runtime effects on the mixed output: equalizer, echo, and a limiter against clipping
+//! - Output to an I²S DAC/amp, or PWM on any one GPIO pin for a piezo or transistor-driven
+//!   speaker (`pwm_pin` in [`audio_player!`]; the same clips play either way)
+//! - For ffmpeg conversion commands, see "Preparing audio files" at [`pcm_clip!`],
+//!   [`adpcm_clip!`](macro@crate::audio_player::adpcm_clip),
+//!   [`qoa_clip!`](macro@crate::audio_player::qoa_clip),
+//!   [`mulaw_clip!`](macro@crate::audio_player::mulaw_clip), and
+//!   [`alaw_clip!`](macro@crate::audio_player::alaw_clip).
 //!
 //! **After reading the examples below, see also:**
 //!
@@ -42,14 +58,39 @@
 //!   (includes syntax details).
 //!   See [`AdpcmClipGenerated`](adpcm_clip_generated::AdpcmClipGenerated) for
 //!   sample generated items.
+//! - [`qoa_clip!`](macro@crate::audio_player::qoa_clip) - Macro to "compile in" a compressed (QOA)
+//!   clip from an external `.qoa` file (includes syntax details).
+//! - [`mulaw_clip!`](macro@crate::audio_player::mulaw_clip) and
+//!   [`alaw_clip!`](macro@crate::audio_player::alaw_clip) - Macros to "compile in" a compressed
+//!   (G.711) WAV clip from an external file (includes syntax details).
 //! - [`tone!`](macro@crate::tone) - Macro to generate tone audio clips.
+//! - [`notes!`](macro@crate::notes) and [`Melody`] - Note notation and a synthesized melody that
+//!   stores only its note list. See also [`Waveform`], [`Adsr`], and [`Note`].
+//! - [`AudioVoice`] - Handle to one voice of a multi-voice player, with its own queue, gain, and [`Pan`].
+//! - [`AudioStream`] and [`AudioRingBuffer`] - Audio produced at runtime, pulled by the player in blocks.
+//! - [`FadedClip`] - Wraps a clip with a fade-in and/or fade-out envelope.
+//! - [`Priority`], [`InterruptPolicy`], and [`PlayOutcome`] - Inputs and result of
+//!   [`play_with_priority`](audio_player_generated::AudioPlayerGenerated::play_with_priority).
+//! - [`PlaybackPosition`] and [`PlaybackEvent`] - Where playback is, and what just started or ended.
+//! - [`Dsp`] - Effects chain for
+//!   [`set_dsp`](audio_player_generated::AudioPlayerGenerated::set_dsp), built from
+//!   [`EqBand`], [`Echo`], and [`Limiter`].
 //! - [`SilenceClip`] - An audio clip of silence for a specific duration. Memory-efficient because it stores no audio sample data.
 //! - [`PcmClip`] and [`PcmClipBuf`] - Unsized and sized const-friendly uncompressed (PCM) clip types.
 //! - [`AdpcmClip`] and [`AdpcmClipBuf`] - Unsized and sized const-friendly compressed (ADPCM) clip types.
+//! - [`QoaClip`] and [`QoaClipBuf`] - Unsized and sized const-friendly compressed (QOA) clip types.
+//! - [`MulawClip`], [`MulawClipBuf`], [`AlawClip`], and [`AlawClipBuf`] - Unsized and sized
+//!   const-friendly compressed (G.711 μ-law and A-law) clip types.
+//! - [`StereoPcmClip`], [`StereoPcmClipBuf`], [`StereoAdpcmClip`], [`StereoAdpcmClipBuf`],
+//!   [`StereoQoaClip`], and [`StereoQoaClipBuf`] - Stereo (two-channel) versions of the clip
+//!   types above.
+//! - `AudioRenderer` (host tests only) - Stands in for the device task and records the
+//!   final mix, as the device would send it to I²S, into frames or a WAV file.
 //!
 //! # Example: Play "Mary Had a Little Lamb" (Phrase) Once
 //!
 //! This example plays the opening phrase (`E D C D E E E`) and then stops.
+//! For smoother notes and less flash, see the next example.
 //!
 //! ```rust,no_run
 //! # #![no_std]
@@ -110,6 +151,62 @@
 //! }
 //! ```
 //!
+//! # Example: Synthesize a Melody from Note Notation
+//!
+//! This example plays the same phrase as a [`Melody`]. The notes are synthesized
+//! during playback, so only the note list is stored in flash. An [`Adsr`] envelope
+//! shapes each note, so there are no clicks at note boundaries.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{Adsr, AtEnd, Melody, VOICE_22050_HZ, Volume, Waveform, audio_player},
+//!     notes,
+//! };
+//! use core::time::Duration as StdDuration;
+//!
+//! audio_player! {
+//!     AudioPlayer8 {
+//!         data_pin: PIN_8,
+//!         bit_clock_pin: PIN_9,
+//!         word_select_pin: PIN_10,
+//!         sample_rate_hz: VOICE_22050_HZ,
+//!         max_volume: Volume::percent(50),
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     // Each note is `<name><octave>:<length>`; `4` is a quarter note and `2` a half note.
+//!     const MARY: &AudioPlayer8Playable = &Melody::new(notes!("E4 D4 C4 D4 E4 E4 E4:2"))
+//!         .with_tempo(132)
+//!         .with_waveform(Waveform::Triangle)
+//!         .with_adsr(Adsr::new(
+//!             StdDuration::from_millis(5),
+//!             StdDuration::from_millis(120),
+//!             60,
+//!             StdDuration::from_millis(60),
+//!         ));
+//!     // A melody can also be rendered to a `PcmClipBuf` at compile time with `with_pcm`.
+//!
+//!     let p = embassy_rp::init(Default::default());
+//!     let audio_player8 = AudioPlayer8::new(p.PIN_8, p.PIN_9, p.PIN_10, p.PIO0, p.DMA_CH0, spawner)?;
+//!
+//!     audio_player8.play([MARY], AtEnd::Stop);
+//!
+//!     core::future::pending().await // run forever
+//! }
+//! ```
+//!
 //! # Example: Compiling in an External Audio Clip and Runtime Volume Changes
 //!
 //! This example shows how to "compile in" an audio clip from an external file,
@@ -312,232 +409,799 @@
 //!     core::future::pending().await // run forever
 //! }
 //! ```
-#![cfg_attr(all(test, feature = "host"), allow(dead_code))]
-
-// TODO Add a realtime tone Playable (sine + ASR envelope) that uses parameter-only storage and matches ADPCM playback performance.
-
-pub mod adpcm_clip_generated;
-pub mod audio_player_generated;
-#[cfg(all(test, feature = "host"))]
-mod host_tests;
-pub mod pcm_clip_generated;
-
-#[cfg(target_os = "none")]
-use core::ops::ControlFlow;
-use core::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
-use core::sync::atomic::{AtomicI32, Ordering};
-use core::time::Duration;
-
-#[cfg(target_os = "none")]
-use crate::pio_irqs::PioIrqMap;
-#[cfg(target_os = "none")]
-use embassy_rp::Peri;
-#[cfg(target_os = "none")]
-use embassy_rp::dma::Channel;
-#[cfg(target_os = "none")]
-use embassy_rp::gpio::Pin;
-#[cfg(target_os = "none")]
-use embassy_rp::pio::{Instance, Pio, PioPin};
-#[cfg(target_os = "none")]
-use embassy_rp::pio_programs::i2s::{PioI2sOut, PioI2sOutProgram};
-use embassy_sync::{blocking_mutex::raw::CriticalSectionRawMutex, signal::Signal};
-use heapless::Vec;
-
-#[cfg(target_os = "none")]
-const BIT_DEPTH_BITS: u32 = 16;
-#[cfg(target_os = "none")]
-const SAMPLE_BUFFER_LEN: usize = 256;
-const I16_ABS_MAX_I64: i64 = -(i16::MIN as i64);
-
-// Common audio sample-rate constants in hertz.
-
-/// Narrowband telephony sample rate.
-pub const NARROWBAND_8000_HZ: u32 = 8_000;
-/// Wideband voice sample rate.
-pub const VOICE_16000_HZ: u32 = 16_000;
-/// Common low-memory voice/music sample rate.
-///
-/// Convenience constant: any sample rate supported by your hardware setup may
-/// be used.
-pub const VOICE_22050_HZ: u32 = 22_050;
-/// Compact-disc sample rate.
-pub const CD_44100_HZ: u32 = 44_100;
-/// Pro-audio sample rate.
-pub const PRO_48000_HZ: u32 = 48_000;
-
-/// Absolute playback loudness setting for the whole player.
-///
-/// `Volume` is used by the player-level controls
-/// [`max_volume`, `initial_volume`](macro@crate::audio_player), and
-/// [`set_volume`](audio_player_generated::AudioPlayerGenerated::set_volume),
-/// which set the absolute playback loudness behavior for the whole player.
-///
-/// This is different from [`Gain`] and [`PcmClipBuf::with_gain`], which
-/// adjust the relative loudness of individual clips.
-///
-/// See the [audio_player module documentation](mod@crate::audio_player) for
-/// usage examples.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct Volume(i16);
-
-impl Volume {
-    /// Silence.
-    pub const MUTE: Self = Self(0);
-
-    /// Maximum playback volume.
-    pub const MAX: Self = Self(i16::MAX);
-
-    /// Creates a volume from a percentage of full scale.
-    ///
-    /// Values above `100` are clamped to `100`.
-    ///
-    /// See the [audio_player module documentation](mod@crate::audio_player) for
-    /// usage examples.
-    #[must_use]
-    pub const fn percent(percent: u8) -> Self {
-        let percent = if percent > 100 { 100 } else { percent };
-        let value_i32 = (percent as i32 * i16::MAX as i32) / 100;
-        Self(value_i32 as i16)
-    }
-
-    /// Creates a humorous "goes to 11" demo volume scale.
-    ///
-    /// `0..=11` maps to `0..=100%` using a perceptual curve
-    /// (roughly logarithmic, but not mathematically exact).
-    ///
-    /// Values above `11` clamp to `11`.
-    ///
-    /// See the [audio_player module documentation](mod@crate::audio_player) for
-    /// usage examples.
-    #[must_use]
-    pub const fn spinal_tap(spinal_tap: u8) -> Self {
-        let spinal_tap = if spinal_tap > 11 { 11 } else { spinal_tap };
-        let percent = match spinal_tap {
-            0 => 0,
-            1 => 1,
-            2 => 3,
-            3 => 6,
-            4 => 13,
-            5 => 25,
-            6 => 35,
-            7 => 50,
-            8 => 71,
-            9 => 89,
-            10 => 100,
-            11 => 100,
-            _ => 100,
-        };
-        Self::percent(percent)
-    }
-
-    #[must_use]
-    const fn to_i16(self) -> i16 {
-        self.0
-    }
-
-    #[must_use]
-    const fn from_i16(value_i16: i16) -> Self {
-        Self(value_i16)
-    }
-}
-
-/// Relative loudness adjustment for audio clips.
-///
-/// Use `Gain` with [`PcmClipBuf::with_gain`] to make a clip louder or quieter
-/// before playback.
-///
-/// `with_gain` is intended for const clip definitions, so the adjusted samples
-/// are precomputed at compile time with no extra runtime work.
-///
-/// You can set gain by percent or by dB:
-/// - [`Gain::percent`] where `100` means unchanged and values above `100` are louder.
-/// - [`Gain::db`] where positive dB is louder and negative dB is quieter.
-///
-/// This is different from [`Volume`] used by
-/// [`max_volume`, `initial_volume`](macro@crate::audio_player), and
-/// [`set_volume`](audio_player_generated::AudioPlayerGenerated::set_volume),
-/// which set the absolute playback loudness behavior for the whole player.
-///
-/// See the [audio_player module documentation](mod@crate::audio_player) for
-/// usage examples.
-#[derive(Clone, Copy, Debug, PartialEq, Eq)]
-pub struct Gain(i32);
-
-impl Gain {
-    /// Silence.
-    pub const MUTE: Self = Self(0);
-
-    /// Creates a gain from percentage.
-    ///
-    /// `100` is unity gain. Values above `100` boost the signal.
-    ///
-    /// See the [audio_player module documentation](mod@crate::audio_player) for
-    /// usage examples.
-    #[must_use]
-    pub const fn percent(percent: u16) -> Self {
-        let value_i32 = (percent as i32 * i16::MAX as i32) / 100;
-        Self(value_i32)
-    }
-
-    /// Creates gain from dB with a bounded boost range.
-    ///
-    /// Values above `+12 dB` clamp to `+12 dB`.
-    /// Values below `-96 dB` clamp to `-96 dB`.
-    ///
-    /// See [`PcmClipBuf::with_gain`] for usage.
-    #[must_use]
-    pub const fn db(db: i8) -> Self {
-        const DB_UPPER_LIMIT: i8 = 12;
-        const DB_LOWER_LIMIT: i8 = -96;
-        let db = if db > DB_UPPER_LIMIT {
-            DB_UPPER_LIMIT
-        } else if db < DB_LOWER_LIMIT {
-            DB_LOWER_LIMIT
-        } else {
-            db
-        };
-
-        if db == 0 {
-            return Self::percent(100);
-        }
-
-        // Fixed-point multipliers for 10^(+/-1/20) (approximately +/-1 dB in amplitude).
-        const DB_STEP_DOWN_Q15: i32 = 29_205;
-        const DB_STEP_UP_Q15: i32 = 36_781;
-        const ONE_Q15: i32 = 32_768;
-        const ROUND_Q15: i32 = 16_384;
-        let step_q15_i32 = if db > 0 {
-            DB_STEP_UP_Q15
-        } else {
-            DB_STEP_DOWN_Q15
-        };
-        let db_steps_u8 = if db > 0 { db as u8 } else { (-db) as u8 };
-        let mut scale_q15_i32 = ONE_Q15;
-        let mut step_index = 0_u8;
-        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
-        while step_index < db_steps_u8 {
-            scale_q15_i32 = (scale_q15_i32 * step_q15_i32 + ROUND_Q15) / ONE_Q15;
-            step_index += 1;
-        }
-
-        let gain_i64 = (i16::MAX as i64 * scale_q15_i32 as i64 + ROUND_Q15 as i64) / ONE_Q15 as i64;
-        let gain_i32 = if gain_i64 > i32::MAX as i64 {
-            i32::MAX
-        } else {
-            gain_i64 as i32
-        };
-        Self(gain_i32)
-    }
-
-    #[must_use]
-    const fn linear(self) -> i32 {
-        self.0
-    }
-}
-
-#[must_use]
-#[doc(hidden)]
-/// This uses [`core::time::Duration`] for clip timing.
+//!
+//! # Example: Sound Effects over Background Music
+//!
+//! This example sets `voices: 2` so that two clip sequences play at once.
+//! Voice `0` loops a quiet melody while voice `1` plays a chime on each button press.
+//! Each voice has its own queue, [`AtEnd`], [`Gain`], and [`Pan`]; the voices are summed
+//! (with saturation, so loud overlaps clip rather than wrap around).
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{AtEnd, Gain, Pan, VOICE_22050_HZ, audio_player},
+//!     button::{Button, PressedTo},
+//!     tone,
+//! };
+//! use core::time::Duration as StdDuration;
+//!
+//! audio_player! {
+//!     AudioPlayer8 {
+//!         data_pin: PIN_8,
+//!         bit_clock_pin: PIN_9,
+//!         word_select_pin: PIN_10,
+//!         sample_rate_hz: VOICE_22050_HZ,
+//!         voices: 2, // optional, defaults to 1
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     const SAMPLE_RATE_HZ: u32 = AudioPlayer8::SAMPLE_RATE_HZ;
+//!     const NOTE_DURATION: StdDuration = StdDuration::from_millis(300);
+//!     const NOTE_C4: &AudioPlayer8Playable = &tone!(262, SAMPLE_RATE_HZ, NOTE_DURATION);
+//!     const NOTE_E4: &AudioPlayer8Playable = &tone!(330, SAMPLE_RATE_HZ, NOTE_DURATION);
+//!     const NOTE_G4: &AudioPlayer8Playable = &tone!(392, SAMPLE_RATE_HZ, NOTE_DURATION);
+//!     const CHIME: &AudioPlayer8Playable =
+//!         &tone!(1_760, SAMPLE_RATE_HZ, StdDuration::from_millis(120));
+//!
+//!     let p = embassy_rp::init(Default::default());
+//!     let mut button = Button::new(p.PIN_13, PressedTo::Ground);
+//!     let audio_player8 = AudioPlayer8::new(p.PIN_8, p.PIN_9, p.PIN_10, p.PIO0, p.DMA_CH0, spawner)?;
+//!
+//!     let music = audio_player8.voice(0);
+//!     let effects = audio_player8.voice(1);
+//!     music.set_gain(Gain::percent(30));
+//!     music.play([NOTE_C4, NOTE_E4, NOTE_G4, NOTE_E4], AtEnd::Loop);
+//!
+//!     loop {
+//!         // Alternate the chime between the left and right speakers.
+//!         for pan in [Pan::percent(-80), Pan::percent(80)] {
+//!             button.wait_for_press().await;
+//!             effects.set_pan(pan);
+//!             // Restarts the chime on voice 1; the music on voice 0 keeps playing.
+//!             effects.play([CHIME], AtEnd::Stop);
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! # Example: Fades and Crossfades
+//!
+//! Cutting straight from one clip to another, or stopping mid-clip, can click.
+//! This example fades a chord in with [`FadedClip`], crossfades between chords with
+//! [`play_with_crossfade`](audio_player_generated::AudioPlayerGenerated::play_with_crossfade),
+//! and fades out with
+//! [`stop_with_fade`](audio_player_generated::AudioPlayerGenerated::stop_with_fade)
+//! when the button is pressed.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{AtEnd, FadedClip, VOICE_22050_HZ, audio_player},
+//!     button::{Button, PressedTo},
+//!     tone,
+//! };
+//! use core::time::Duration as StdDuration;
+//!
+//! audio_player! {
+//!     AudioPlayer8 {
+//!         data_pin: PIN_8,
+//!         bit_clock_pin: PIN_9,
+//!         word_select_pin: PIN_10,
+//!         sample_rate_hz: VOICE_22050_HZ,
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     const SAMPLE_RATE_HZ: u32 = AudioPlayer8::SAMPLE_RATE_HZ;
+//!     const CHORD_DURATION: StdDuration = StdDuration::from_secs(2);
+//!     const LOW: &AudioPlayer8Playable = &tone!(220, SAMPLE_RATE_HZ, CHORD_DURATION);
+//!     const HIGH: &AudioPlayer8Playable = &tone!(330, SAMPLE_RATE_HZ, CHORD_DURATION);
+//!     // Ramps up from silence over the first half second.
+//!     const LOW_FADED_IN: &AudioPlayer8Playable =
+//!         &FadedClip::new(LOW).with_fade_in(StdDuration::from_millis(500));
+//!
+//!     let p = embassy_rp::init(Default::default());
+//!     let mut button = Button::new(p.PIN_13, PressedTo::Ground);
+//!     let audio_player8 = AudioPlayer8::new(p.PIN_8, p.PIN_9, p.PIN_10, p.PIO0, p.DMA_CH0, spawner)?;
+//!
+//!     loop {
+//!         // Each chord blends into the next over 300 ms, including HIGH back into LOW_FADED_IN.
+//!         audio_player8.play_with_crossfade(
+//!             [LOW_FADED_IN, HIGH],
+//!             AtEnd::Loop,
+//!             StdDuration::from_millis(300),
+//!         );
+//!         button.wait_for_press().await;
+//!         audio_player8.stop_with_fade(StdDuration::from_secs(1));
+//!         audio_player8.wait_until_stopped().await;
+//!         button.wait_for_press().await;
+//!     }
+//! }
+//! ```
+//!
+//! # Example: Announcements That Ticks Can't Interrupt
+//!
+//! Plain `play` always replaces what is playing, so a tick could cut off an announcement.
+//! [`play_with_priority`](audio_player_generated::AudioPlayerGenerated::play_with_priority)
+//! instead compares [`Priority`]s and follows an [`InterruptPolicy`]. Here, ticks are
+//! dropped while an announcement plays, and announcements duck the background music on
+//! voice `1`. Each call returns a [`PlayOutcome`] once the player has decided.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{
+//!         AtEnd, Gain, InterruptPolicy, PlayOutcome, Priority, VOICE_22050_HZ, audio_player,
+//!     },
+//!     button::{Button, PressedTo},
+//!     tone,
+//! };
+//! use core::time::Duration as StdDuration;
+//! use embassy_futures::select::{Either, select};
+//! use embassy_time::Timer;
+//!
+//! audio_player! {
+//!     AudioPlayer8 {
+//!         data_pin: PIN_8,
+//!         bit_clock_pin: PIN_9,
+//!         word_select_pin: PIN_10,
+//!         sample_rate_hz: VOICE_22050_HZ,
+//!         voices: 2,
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     const SAMPLE_RATE_HZ: u32 = AudioPlayer8::SAMPLE_RATE_HZ;
+//!     const TICK: &AudioPlayer8Playable = &tone!(1_000, SAMPLE_RATE_HZ, StdDuration::from_millis(30));
+//!     const ANNOUNCEMENT: &AudioPlayer8Playable =
+//!         &tone!(440, SAMPLE_RATE_HZ, StdDuration::from_millis(1_500));
+//!     const MUSIC: &AudioPlayer8Playable = &tone!(220, SAMPLE_RATE_HZ, StdDuration::from_secs(1));
+//!
+//!     let p = embassy_rp::init(Default::default());
+//!     let mut button = Button::new(p.PIN_13, PressedTo::Ground);
+//!     let audio_player8 = AudioPlayer8::new(p.PIN_8, p.PIN_9, p.PIN_10, p.PIO0, p.DMA_CH0, spawner)?;
+//!     audio_player8.voice(1).play([MUSIC], AtEnd::Loop);
+//!
+//!     let mut skipped_tick_count = 0_u32;
+//!     loop {
+//!         match select(Timer::after_secs(1), button.wait_for_press()).await {
+//!             Either::First(()) => {
+//!                 // Dropped (instead of interrupting) while the announcement plays.
+//!                 let play_outcome = audio_player8
+//!                     .play_with_priority([TICK], AtEnd::Stop, Priority::Low, InterruptPolicy::Preempt)
+//!                     .await;
+//!                 if play_outcome == PlayOutcome::Dropped {
+//!                     skipped_tick_count += 1;
+//!                 }
+//!             }
+//!             Either::Second(()) => {
+//!                 // Cuts off a tick, and lowers the music to 20% while it plays.
+//!                 audio_player8
+//!                     .play_with_priority(
+//!                         [ANNOUNCEMENT],
+//!                         AtEnd::Stop,
+//!                         Priority::High,
+//!                         InterruptPolicy::Duck(Gain::percent(20)),
+//!                     )
+//!                     .await;
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! # Example: Flash an LED in Step with Each Clip
+//!
+//! [`wait_for_event`](audio_player_generated::AudioPlayerGenerated::wait_for_event)
+//! reports each [`PlaybackEvent`] soon after the audio is written to the output, and
+//! [`position`](audio_player_generated::AudioPlayerGenerated::position) reports the
+//! current clip and sample offset, for example to drive a progress bar.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{AtEnd, PlaybackEvent, VOICE_22050_HZ, audio_player},
+//!     led::{Led, LedStatic, OnLevel},
+//!     tone,
+//! };
+//! use core::time::Duration as StdDuration;
+//! use embassy_rp::gpio::Level;
+//!
+//! audio_player! {
+//!     AudioPlayer8 {
+//!         data_pin: PIN_8,
+//!         bit_clock_pin: PIN_9,
+//!         word_select_pin: PIN_10,
+//!         sample_rate_hz: VOICE_22050_HZ,
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     const SAMPLE_RATE_HZ: u32 = AudioPlayer8::SAMPLE_RATE_HZ;
+//!     const BEAT_DURATION: StdDuration = StdDuration::from_millis(250);
+//!     const LOW: &AudioPlayer8Playable = &tone!(262, SAMPLE_RATE_HZ, BEAT_DURATION);
+//!     const HIGH: &AudioPlayer8Playable = &tone!(523, SAMPLE_RATE_HZ, BEAT_DURATION);
+//!
+//!     let p = embassy_rp::init(Default::default());
+//!     static LED_STATIC: LedStatic = Led::new_static();
+//!     let led = Led::new(&LED_STATIC, p.PIN_25, OnLevel::High, spawner)?;
+//!     let audio_player8 = AudioPlayer8::new(p.PIN_8, p.PIN_9, p.PIN_10, p.PIO0, p.DMA_CH0, spawner)?;
+//!
+//!     audio_player8.play([LOW, HIGH, LOW, HIGH], AtEnd::Loop);
+//!     loop {
+//!         // The LED is on during the high notes (clip indexes 1 and 3).
+//!         match audio_player8.wait_for_event().await {
+//!             PlaybackEvent::ClipStarted { clip_index } => {
+//!                 led.set_level(if clip_index % 2 == 1 { Level::High } else { Level::Low });
+//!             }
+//!             PlaybackEvent::ClipEnded { .. } | PlaybackEvent::SequenceEnded => {}
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! # Example: Tame a Small Speaker with EQ, Echo, and a Limiter
+//!
+//! This example shapes the whole player's output with a [`Dsp`] chain: a low shelf
+//! cuts boom the speaker can't reproduce, a peaking band adds presence, an echo adds
+//! some space, and a limiter keeps the boosted, echoing note from clipping. Each button press
+//! toggles between the chain and [`Dsp::BYPASS`]. The `max_echo_delay` field reserves
+//! the echo's delay memory.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{
+//!         AtEnd, Dsp, Echo, EqBand, Gain, Limiter, SilenceClip, VOICE_22050_HZ, Volume,
+//!         audio_player,
+//!     },
+//!     button::{Button, PressedTo},
+//!     tone,
+//! };
+//! use core::time::Duration as StdDuration;
+//!
+//! audio_player! {
+//!     AudioPlayer8 {
+//!         data_pin: PIN_8,
+//!         bit_clock_pin: PIN_9,
+//!         word_select_pin: PIN_10,
+//!         sample_rate_hz: VOICE_22050_HZ,
+//!         max_echo_delay: StdDuration::from_millis(200), // optional, defaults to no echo
+//!     }
+//! }
+//!
+//! const SPEAKER_DSP: Dsp = Dsp::new()
+//!     .with_eq_band(EqBand::low_shelf(150, -9))
+//!     .with_eq_band(EqBand::peaking(2_500, 4, 100))
+//!     .with_echo(Echo::new(StdDuration::from_millis(180), Volume::percent(30), Volume::percent(40)))
+//!     .with_limiter(Limiter::DEFAULT);
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     const SAMPLE_RATE_HZ: u32 = AudioPlayer8::SAMPLE_RATE_HZ;
+//!     const NOTE: &AudioPlayer8Playable =
+//!         &tone!(440, SAMPLE_RATE_HZ, StdDuration::from_millis(250)).with_gain(Gain::db(-3));
+//!     const REST: &AudioPlayer8Playable = &SilenceClip::new(StdDuration::from_millis(750));
+//!
+//!     let p = embassy_rp::init(Default::default());
+//!     let mut button = Button::new(p.PIN_13, PressedTo::Ground);
+//!     let audio_player8 = AudioPlayer8::new(p.PIN_8, p.PIN_9, p.PIN_10, p.PIO0, p.DMA_CH0, spawner)?;
+//!
+//!     audio_player8.play([NOTE, REST], AtEnd::Loop);
+//!     loop {
+//!         for dsp in [SPEAKER_DSP, Dsp::BYPASS] {
+//!             audio_player8.set_dsp(dsp);
+//!             button.wait_for_press().await;
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! # Example: Play Through a Speaker on One GPIO Pin (PWM)
+//!
+//! Without an I²S amp, give [`audio_player!`] a `pwm_pin` instead of the three I²S pins.
+//! A piezo can connect directly to the pin; a small speaker needs a transistor
+//! (and, for cleaner sound, an RC low-pass filter). Stereo audio is mixed to mono.
+//! Clips, volume, voices, and effects all work as with I²S.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{AtEnd, VOICE_22050_HZ, Volume, audio_player},
+//!     tone,
+//! };
+//! use core::time::Duration as StdDuration;
+//!
+//! audio_player! {
+//!     Buzzer15 {
+//!         pwm_pin: PIN_15,
+//!         sample_rate_hz: VOICE_22050_HZ,
+//!         max_volume: Volume::percent(50),
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     const BEEP: &Buzzer15Playable =
+//!         &tone!(880, VOICE_22050_HZ, StdDuration::from_millis(150));
+//!
+//!     let p = embassy_rp::init(Default::default());
+//!     // `new` takes the one pin in place of the three I²S pins.
+//!     let buzzer15 = Buzzer15::new(p.PIN_15, p.PIO0, p.DMA_CH0, spawner)?;
+//!
+//!     buzzer15.play([BEEP, BEEP], AtEnd::Stop);
+//!
+//!     core::future::pending().await // run forever
+//! }
+//! ```
+//!
+//! # Example: Stream Audio Generated at Runtime
+//!
+//! Audio that isn't known at compile time (synthesized, recorded, or received over
+//! the network) can be played through an [`AudioStream`]. This example uses an
+//! [`AudioRingBuffer`]: one task writes samples into it while the player plays them.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{AtEnd, AudioRingBuffer, VOICE_22050_HZ, audio_player},
+//! };
+//!
+//! audio_player! {
+//!     AudioPlayer8 {
+//!         data_pin: PIN_8,
+//!         bit_clock_pin: PIN_9,
+//!         word_select_pin: PIN_10,
+//!         sample_rate_hz: VOICE_22050_HZ,
+//!     }
+//! }
+//!
+//! // Holds about 46 ms of audio at 22.05 kHz.
+//! static BEEPS: AudioRingBuffer<VOICE_22050_HZ, 1024> = AudioRingBuffer::new();
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     let p = embassy_rp::init(Default::default());
+//!     let audio_player8 = AudioPlayer8::new(p.PIN_8, p.PIN_9, p.PIN_10, p.PIO0, p.DMA_CH0, spawner)?;
+//!
+//!     spawner.spawn(beep_task())?;
+//!     let beeps: &'static AudioPlayer8Playable = &BEEPS;
+//!     audio_player8.play([beeps], AtEnd::Stop);
+//!     core::future::pending().await // run forever
+//! }
+//!
+//! // Synthesizes a square wave whose pitch rises with each beep.
+//! #[embassy_executor::task]
+//! async fn beep_task() -> ! {
+//!     let mut period_samples = 100_usize;
+//!     let mut phase = 0_usize;
+//!     loop {
+//!         let mut block = [0_i16; 64];
+//!         for _ in 0..100 {
+//!             for sample in &mut block {
+//!                 *sample = if phase < period_samples / 2 { 4_000 } else { -4_000 };
+//!                 phase = (phase + 1) % period_samples;
+//!             }
+//!             // Waits while the buffer is full.
+//!             BEEPS.write(&block).await;
+//!         }
+//!         period_samples = if period_samples > 20 { period_samples - 10 } else { 100 };
+//!         phase = 0;
+//!     }
+//! }
+//! ```
+#![cfg_attr(all(test, feature = "host"), allow(dead_code))]
+
+pub mod adpcm_clip_generated;
+pub mod audio_player_generated;
+mod dsp;
+mod g711;
+#[cfg(all(test, feature = "host"))]
+mod host_tests;
+mod mixer;
+pub mod pcm_clip_generated;
+#[cfg(any(target_os = "none", test))]
+mod pwm;
+mod qoa;
+#[cfg(feature = "host")]
+mod render;
+mod stereo;
+mod stream;
+mod synth;
+
+pub use dsp::{Dsp, Echo, EqBand, Limiter};
+#[doc(hidden)]
+pub use g711::{
+    __alaw_clip_from_bytes, __mulaw_clip_from_bytes, __parse_alaw_wav_header,
+    __parse_mulaw_wav_header, ParsedG711WavHeader,
+};
+pub use g711::{AlawClip, AlawClipBuf, MulawClip, MulawClipBuf};
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub use pwm::pwm_device_loop;
+#[doc(hidden)]
+pub use qoa::{
+    __parse_qoa_header, __qoa_clip_from_bytes, __qoa_data_len_for_pcm_samples,
+    __stereo_qoa_clip_from_bytes, __stereo_qoa_data_len_for_pcm_frames, ParsedQoaHeader,
+};
+pub use qoa::{QoaClip, QoaClipBuf, StereoQoaClip, StereoQoaClipBuf};
+#[cfg(feature = "host")]
+pub use render::AudioRenderer;
+#[doc(hidden)]
+pub use stereo::{
+    __resample_stereo_pcm_clip, __stereo_adpcm_clip_from_parts,
+    __stereo_adpcm_data_len_for_pcm_frames,
+    __stereo_adpcm_data_len_for_pcm_frames_with_block_align, __stereo_adpcm_samples_per_block,
+    __stereo_pcm_clip_from_frames, __stereo_pcm_with_adpcm_block_align,
+};
+pub use stereo::{StereoAdpcmClip, StereoAdpcmClipBuf, StereoPcmClip, StereoPcmClipBuf};
+pub use stream::{AudioRingBuffer, AudioStream};
+#[doc(hidden)]
+pub use synth::{__note_count, __parse_notes};
+pub use synth::{Adsr, Melody, Note, Waveform};
+
+use core::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use core::sync::atomic::{AtomicI8, AtomicI32, Ordering};
+use core::time::Duration;
+
+#[cfg(target_os = "none")]
+use crate::pio_irqs::PioIrqMap;
+use core::cell::Cell;
+#[cfg(target_os = "none")]
+use embassy_rp::Peri;
+#[cfg(target_os = "none")]
+use embassy_rp::dma::Channel;
+#[cfg(target_os = "none")]
+use embassy_rp::gpio::Pin;
+#[cfg(target_os = "none")]
+use embassy_rp::pio::{Pio, PioPin};
+#[cfg(target_os = "none")]
+use embassy_rp::pio_programs::i2s::{PioI2sOut, PioI2sOutProgram};
+
+use embassy_sync::blocking_mutex::{Mutex as BlockingMutex, raw::CriticalSectionRawMutex};
+use embassy_sync::{channel, mutex::Mutex, signal::Signal};
+use heapless::Vec;
+
+#[cfg(target_os = "none")]
+use mixer::Mixer;
+
+#[cfg(target_os = "none")]
+const BIT_DEPTH_BITS: u32 = 16;
+/// Stereo frames the device loop renders and sends to I²S at a time.
+const SAMPLE_BUFFER_LEN: usize = 256;
+/// Number of [`PlaybackEvent`]s each voice holds until they are read.
+const PLAYBACK_EVENT_CAPACITY: usize = 8;
+const I16_ABS_MAX_I64: i64 = -(i16::MIN as i64);
+
+// Common audio sample-rate constants in hertz.
+
+/// Narrowband telephony sample rate.
+pub const NARROWBAND_8000_HZ: u32 = 8_000;
+/// Wideband voice sample rate.
+pub const VOICE_16000_HZ: u32 = 16_000;
+/// Common low-memory voice/music sample rate.
+///
+/// Convenience constant: any sample rate supported by your hardware setup may
+/// be used.
+pub const VOICE_22050_HZ: u32 = 22_050;
+/// Compact-disc sample rate.
+pub const CD_44100_HZ: u32 = 44_100;
+/// Pro-audio sample rate.
+pub const PRO_48000_HZ: u32 = 48_000;
+
+/// Absolute playback loudness setting for the whole player.
+///
+/// `Volume` is used by the player-level controls
+/// [`max_volume`, `initial_volume`](macro@crate::audio_player), and
+/// [`set_volume`](audio_player_generated::AudioPlayerGenerated::set_volume),
+/// which set the absolute playback loudness behavior for the whole player.
+///
+/// This is different from [`Gain`] and [`PcmClipBuf::with_gain`], which
+/// adjust the relative loudness of individual clips.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Volume(i16);
+
+impl Volume {
+    /// Silence.
+    pub const MUTE: Self = Self(0);
+
+    /// Maximum playback volume.
+    pub const MAX: Self = Self(i16::MAX);
+
+    /// Creates a volume from a percentage of full scale.
+    ///
+    /// Values above `100` are clamped to `100`.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    #[must_use]
+    pub const fn percent(percent: u8) -> Self {
+        let percent = if percent > 100 { 100 } else { percent };
+        let value_i32 = (percent as i32 * i16::MAX as i32) / 100;
+        Self(value_i32 as i16)
+    }
+
+    /// Creates a humorous "goes to 11" demo volume scale.
+    ///
+    /// `0..=11` maps to `0..=100%` using a perceptual curve
+    /// (roughly logarithmic, but not mathematically exact).
+    ///
+    /// Values above `11` clamp to `11`.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    #[must_use]
+    pub const fn spinal_tap(spinal_tap: u8) -> Self {
+        let spinal_tap = if spinal_tap > 11 { 11 } else { spinal_tap };
+        let percent = match spinal_tap {
+            0 => 0,
+            1 => 1,
+            2 => 3,
+            3 => 6,
+            4 => 13,
+            5 => 25,
+            6 => 35,
+            7 => 50,
+            8 => 71,
+            9 => 89,
+            10 => 100,
+            11 => 100,
+            _ => 100,
+        };
+        Self::percent(percent)
+    }
+
+    #[must_use]
+    pub(crate) const fn to_i16(self) -> i16 {
+        self.0
+    }
+
+    #[must_use]
+    pub(crate) const fn from_i16(value_i16: i16) -> Self {
+        Self(value_i16)
+    }
+}
+
+/// Relative loudness adjustment for audio clips.
+///
+/// Use `Gain` with [`PcmClipBuf::with_gain`] to make a clip louder or quieter
+/// before playback.
+///
+/// `with_gain` is intended for const clip definitions, so the adjusted samples
+/// are precomputed at compile time with no extra runtime work.
+///
+/// You can set gain by percent or by dB:
+/// - [`Gain::percent`] where `100` means unchanged and values above `100` are louder.
+/// - [`Gain::db`] where positive dB is louder and negative dB is quieter.
+///
+/// This is different from [`Volume`] used by
+/// [`max_volume`, `initial_volume`](macro@crate::audio_player), and
+/// [`set_volume`](audio_player_generated::AudioPlayerGenerated::set_volume),
+/// which set the absolute playback loudness behavior for the whole player.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Gain(i32);
+
+impl Gain {
+    /// Silence.
+    pub const MUTE: Self = Self(0);
+
+    /// Creates a gain from percentage.
+    ///
+    /// `100` is unity gain. Values above `100` boost the signal.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    #[must_use]
+    pub const fn percent(percent: u16) -> Self {
+        let value_i32 = (percent as i32 * i16::MAX as i32) / 100;
+        Self(value_i32)
+    }
+
+    /// Creates gain from dB with a bounded boost range.
+    ///
+    /// Values above `+12 dB` clamp to `+12 dB`.
+    /// Values below `-96 dB` clamp to `-96 dB`.
+    ///
+    /// See [`PcmClipBuf::with_gain`] for usage.
+    #[must_use]
+    pub const fn db(db: i8) -> Self {
+        const DB_UPPER_LIMIT: i8 = 12;
+        const DB_LOWER_LIMIT: i8 = -96;
+        let db = if db > DB_UPPER_LIMIT {
+            DB_UPPER_LIMIT
+        } else if db < DB_LOWER_LIMIT {
+            DB_LOWER_LIMIT
+        } else {
+            db
+        };
+
+        if db == 0 {
+            return Self::percent(100);
+        }
+
+        // Fixed-point multipliers for 10^(+/-1/20) (approximately +/-1 dB in amplitude).
+        const DB_STEP_DOWN_Q15: i32 = 29_205;
+        const DB_STEP_UP_Q15: i32 = 36_781;
+        const ONE_Q15: i32 = 32_768;
+        const ROUND_Q15: i32 = 16_384;
+        let step_q15_i32 = if db > 0 {
+            DB_STEP_UP_Q15
+        } else {
+            DB_STEP_DOWN_Q15
+        };
+        let db_steps_u8 = if db > 0 { db as u8 } else { (-db) as u8 };
+        let mut scale_q15_i32 = ONE_Q15;
+        let mut step_index = 0_u8;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while step_index < db_steps_u8 {
+            scale_q15_i32 = ((scale_q15_i32 as i64 * step_q15_i32 as i64 + ROUND_Q15 as i64)
+                / ONE_Q15 as i64) as i32;
+            step_index += 1;
+        }
+
+        let gain_i64 = (i16::MAX as i64 * scale_q15_i32 as i64 + ROUND_Q15 as i64) / ONE_Q15 as i64;
+        let gain_i32 = if gain_i64 > i32::MAX as i64 {
+            i32::MAX
+        } else {
+            gain_i64 as i32
+        };
+        Self(gain_i32)
+    }
+
+    #[must_use]
+    const fn linear(self) -> i32 {
+        self.0
+    }
+
+    #[must_use]
+    const fn from_linear(linear_i32: i32) -> Self {
+        Self(linear_i32)
+    }
+}
+
+/// Left/right placement of a voice in the stereo output.
+///
+/// Set it at runtime with [`AudioVoice::set_pan`]. Mono clips are placed between the
+/// speakers; for stereo clips, pan acts as a balance control.
+///
+/// [`Pan::CENTER`] (the default) leaves both channels at full level, so centered
+/// voices sound exactly as they would without panning. Panning toward one side
+/// fades the other side down; [`Pan::LEFT`] and [`Pan::RIGHT`] silence the opposite
+/// channel entirely.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Pan(i8);
+
+impl Pan {
+    /// Full left: the right channel is silent.
+    pub const LEFT: Self = Self(-100);
+
+    /// Centered: both channels at full level.
+    pub const CENTER: Self = Self(0);
+
+    /// Full right: the left channel is silent.
+    pub const RIGHT: Self = Self(100);
+
+    /// Creates a pan position from percent.
+    ///
+    /// `-100` is full left, `0` is center, and `100` is full right.
+    /// Values outside `-100..=100` clamp.
+    #[must_use]
+    pub const fn percent(percent: i8) -> Self {
+        let percent = if percent > 100 {
+            100
+        } else if percent < -100 {
+            -100
+        } else {
+            percent
+        };
+        Self(percent)
+    }
+
+    /// Returns the pan position in percent (`-100` to `100`).
+    #[must_use]
+    pub const fn to_percent(self) -> i8 {
+        self.0
+    }
+
+    /// Splits a linear gain into `(left, right)` linear gains for this position.
+    #[must_use]
+    const fn split_linear(self, linear_i32: i32) -> (i32, i32) {
+        let pan_percent_i64 = self.0 as i64;
+        let left_linear_i32 = if pan_percent_i64 > 0 {
+            (linear_i32 as i64 * (100 - pan_percent_i64) / 100) as i32
+        } else {
+            linear_i32
+        };
+        let right_linear_i32 = if pan_percent_i64 < 0 {
+            (linear_i32 as i64 * (100 + pan_percent_i64) / 100) as i32
+        } else {
+            linear_i32
+        };
+        (left_linear_i32, right_linear_i32)
+    }
+}
+
+#[must_use]
+#[doc(hidden)]
+/// This uses [`core::time::Duration`] for clip timing.
 pub const fn __samples_for_duration(duration: core::time::Duration, sample_rate_hz: u32) -> usize {
     assert!(sample_rate_hz > 0, "sample_rate_hz must be > 0");
     let sample_rate_hz_u64 = sample_rate_hz as u64;
@@ -651,6 +1315,7 @@ const fn clamp_i64_to_i16(value_i64: i64) -> i16 {
 ///
 /// See the [audio_player module documentation](mod@crate::audio_player) for
 /// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum AtEnd {
     /// Repeat the full clip sequence forever.
     Loop,
@@ -658,6 +1323,101 @@ pub enum AtEnd {
     Stop,
 }
 
+/// Importance of a [`AudioVoice::play_with_priority`] request.
+///
+/// Sequences started with plain [`AudioVoice::play`] have [`Priority::Normal`].
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Priority {
+    /// Background cues, such as ticks, that can be skipped.
+    Low,
+    /// Ordinary sounds.
+    Normal,
+    /// Announcements and alerts.
+    High,
+    /// Sounds that must never be cut off by other requests.
+    Critical,
+}
+
+/// How a [`AudioVoice::play_with_priority`] request treats a sequence that is
+/// already playing on the voice.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InterruptPolicy {
+    /// Play after the current sequence ends.
+    ///
+    /// Each voice holds one queued sequence. A later request replaces it only if
+    /// it has higher priority; otherwise the later request is dropped.
+    Enqueue,
+    /// Replace the current sequence only if this request has higher priority;
+    /// otherwise drop this request.
+    Preempt,
+    /// Like [`Self::Preempt`], and while this sequence plays, lower all other voices
+    /// to the given [`Gain`].
+    ///
+    /// Ducking lowers other voices, not this one: a sequence already playing on this
+    /// voice is replaced, as with [`Self::Preempt`]. Play the audio to duck, such as
+    /// background music, on another voice. Needs a player with at least two voices.
+    Duck(Gain),
+}
+
+/// Where a voice is within its clip sequence.
+///
+/// Returned by [`AudioVoice::position`]. Positions are updated once per output
+/// buffer (256 frames), so they run slightly ahead of what is heard.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PlaybackPosition {
+    /// Index of the playing clip in the sequence passed to `play`.
+    pub clip_index: usize,
+    /// Number of frames of the clip played so far.
+    pub sample_offset: usize,
+    /// Length of the clip in frames, or `None` for an [`AudioStream`].
+    pub sample_count: Option<usize>,
+}
+
+/// A change in what a voice is playing, from [`AudioVoice::wait_for_event`].
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaybackEvent {
+    /// The clip at `clip_index` of the sequence started, including on each pass of
+    /// an [`AtEnd::Loop`] sequence.
+    ClipStarted {
+        /// Index of the clip in the sequence passed to `play`.
+        clip_index: usize,
+    },
+    /// The clip at `clip_index` played to its end. Not sent for a clip cut off by a stop
+    /// or by another `play`.
+    ClipEnded {
+        /// Index of the clip in the sequence passed to `play`.
+        clip_index: usize,
+    },
+    /// The sequence played to its end or was stopped.
+    SequenceEnded,
+}
+
+/// Result of a [`AudioVoice::play_with_priority`] request.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlayOutcome {
+    /// The sequence started playing.
+    Played,
+    /// The sequence will play after the current sequence ends.
+    Queued,
+    /// The sequence was rejected and will not play.
+    Dropped,
+}
+
 /// Unsized view of static compressed (ADPCM) clip data.
 ///
 /// For fixed-size, const-friendly storage, see [`AdpcmClipBuf`].
@@ -877,15 +1637,17 @@ impl<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> AdpcmClip<SAMPLE_RATE_HZ,
 pub struct ParsedAdpcmWavHeader {
     /// WAV sample rate.
     pub sample_rate_hz: u32,
+    /// Channel count (`1` for mono, `2` for stereo).
+    pub channels: usize,
     /// ADPCM block size in bytes.
     pub block_align: usize,
-    /// Decoded samples per ADPCM block.
+    /// Decoded samples per channel per ADPCM block.
     pub samples_per_block: usize,
     /// Byte offset of the `data` chunk payload.
     pub data_chunk_start: usize,
     /// Byte length of the `data` chunk payload.
     pub data_chunk_len: usize,
-    /// Total decoded sample count from all ADPCM blocks.
+    /// Total decoded sample count per channel from all ADPCM blocks.
     pub sample_count: usize,
 }
 
@@ -905,6 +1667,7 @@ pub const fn __parse_adpcm_wav_header(wav_bytes: &[u8]) -> ParsedAdpcmWavHeader
 
     let mut chunk_offset = 12usize;
     let mut sample_rate_hz = 0u32;
+    let mut channels = 0usize;
     let mut block_align = 0usize;
     let mut samples_per_block = 0usize;
     let mut fmt_found = false;
@@ -927,7 +1690,7 @@ pub const fn __parse_adpcm_wav_header(wav_bytes: &[u8]) -> ParsedAdpcmWavHeader
             }
 
             let audio_format = read_u16_le_const(wav_bytes, chunk_data_start);
-            let channels = read_u16_le_const(wav_bytes, chunk_data_start + 2);
+            channels = read_u16_le_const(wav_bytes, chunk_data_start + 2) as usize;
             sample_rate_hz = read_u32_le_const(wav_bytes, chunk_data_start + 4);
             block_align = read_u16_le_const(wav_bytes, chunk_data_start + 12) as usize;
             let bits_per_sample = read_u16_le_const(wav_bytes, chunk_data_start + 14);
@@ -935,8 +1698,8 @@ pub const fn __parse_adpcm_wav_header(wav_bytes: &[u8]) -> ParsedAdpcmWavHeader
             if audio_format != 0x0011 {
                 panic!("Expected ADPCM WAV format");
             }
-            if channels != 1 {
-                panic!("Expected mono ADPCM WAV");
+            if channels != 1 && channels != 2 {
+                panic!("Expected mono or stereo ADPCM WAV");
             }
             if bits_per_sample != 4 {
                 panic!("Expected 4-bit ADPCM");
@@ -945,7 +1708,11 @@ pub const fn __parse_adpcm_wav_header(wav_bytes: &[u8]) -> ParsedAdpcmWavHeader
                 panic!("ADPCM block_align too small");
             }
 
-            let derived_samples_per_block = derive_samples_per_block_const(block_align);
+            let derived_samples_per_block = if channels == 1 {
+                derive_samples_per_block_const(block_align)
+            } else {
+                __stereo_adpcm_samples_per_block(block_align)
+            };
             samples_per_block = if chunk_size >= 22 {
                 read_u16_le_const(wav_bytes, chunk_data_start + 18) as usize
             } else {
@@ -981,6 +1748,7 @@ pub const fn __parse_adpcm_wav_header(wav_bytes: &[u8]) -> ParsedAdpcmWavHeader
 
     ParsedAdpcmWavHeader {
         sample_rate_hz,
+        channels,
         block_align,
         samples_per_block,
         data_chunk_start,
@@ -1080,7 +1848,47 @@ const fn read_u32_le_const(bytes: &[u8], byte_offset: usize) -> u32 {
 pub(crate) enum PlaybackClip<const SAMPLE_RATE_HZ: u32> {
     Pcm(&'static PcmClip<SAMPLE_RATE_HZ>),
     Adpcm(&'static AdpcmClip<SAMPLE_RATE_HZ>),
+    StereoPcm(&'static StereoPcmClip<SAMPLE_RATE_HZ>),
+    StereoAdpcm(&'static StereoAdpcmClip<SAMPLE_RATE_HZ>),
+    Mulaw(&'static MulawClip<SAMPLE_RATE_HZ>),
+    Alaw(&'static AlawClip<SAMPLE_RATE_HZ>),
+    Qoa(&'static QoaClip<SAMPLE_RATE_HZ>),
+    StereoQoa(&'static StereoQoaClip<SAMPLE_RATE_HZ>),
     Silence(Duration),
+    Synth(&'static Melody<SAMPLE_RATE_HZ>),
+    Stream(&'static dyn AudioStream<SAMPLE_RATE_HZ>),
+}
+
+impl<const SAMPLE_RATE_HZ: u32> PlaybackClip<SAMPLE_RATE_HZ> {
+    /// Returns the clip length in frames, or `None` for streams, whose length isn't known.
+    fn sample_count(&self) -> Option<usize> {
+        match self {
+            Self::Pcm(pcm_clip) => Some(pcm_clip.samples.len()),
+            Self::Adpcm(adpcm_clip) => Some(
+                (adpcm_clip.data.len() / adpcm_clip.block_align as usize)
+                    * adpcm_clip.samples_per_block as usize,
+            ),
+            Self::StereoPcm(stereo_pcm_clip) => Some(stereo_pcm_clip.frames.len()),
+            Self::StereoAdpcm(stereo_adpcm_clip) => Some(
+                (stereo_adpcm_clip.data.len() / stereo_adpcm_clip.block_align as usize)
+                    * stereo_adpcm_clip.samples_per_block as usize,
+            ),
+            Self::Mulaw(mulaw_clip) => Some(mulaw_clip.data.len()),
+            Self::Alaw(alaw_clip) => Some(alaw_clip.data.len()),
+            Self::Qoa(qoa_clip) => Some(qoa_clip.sample_count as usize),
+            Self::StereoQoa(stereo_qoa_clip) => Some(stereo_qoa_clip.frame_count as usize),
+            Self::Silence(duration) => Some(__samples_for_duration(*duration, SAMPLE_RATE_HZ)),
+            Self::Synth(melody) => Some(melody.sample_count()),
+            Self::Stream(_) => None,
+        }
+    }
+}
+
+/// One entry of a play sequence: a clip plus its fade envelope.
+pub(crate) struct SequenceClip<const SAMPLE_RATE_HZ: u32> {
+    playback_clip: PlaybackClip<SAMPLE_RATE_HZ>,
+    fade_in: Duration,
+    fade_out: Duration,
 }
 
 /// An audio clip of silence for a specific duration. Memory-efficient because it stores no audio sample data.
@@ -1111,9 +1919,74 @@ impl SilenceClip {
     }
 }
 
+/// An audio clip with a fade-in and/or fade-out envelope. Stores no audio sample data
+/// of its own.
+///
+/// Wrap any [`Playable`] to ramp it up from silence at its start, or down to silence at
+/// its end, so cuts between clips don't click. The envelope is applied at playback time.
+///
+/// Fade-outs need to know where the clip ends, so they have no effect on an
+/// [`AudioStream`]. To fade between every pair of clips in a sequence instead, see
+/// [`AudioPlayer::play_with_crossfade`].
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy)]
+pub struct FadedClip<const SAMPLE_RATE_HZ: u32> {
+    audio_clip: &'static dyn Playable<SAMPLE_RATE_HZ>,
+    fade_in: Duration,
+    fade_out: Duration,
+}
+
+impl<const SAMPLE_RATE_HZ: u32> FadedClip<SAMPLE_RATE_HZ> {
+    /// Wraps `audio_clip` with no fades. Add them with [`Self::with_fade_in`] and
+    /// [`Self::with_fade_out`].
+    #[must_use]
+    pub const fn new(audio_clip: &'static dyn Playable<SAMPLE_RATE_HZ>) -> Self {
+        Self {
+            audio_clip,
+            fade_in: Duration::ZERO,
+            fade_out: Duration::ZERO,
+        }
+    }
+
+    /// Returns this clip with a fade-in from silence over `duration` at its start.
+    /// This uses [`core::time::Duration`].
+    #[must_use]
+    pub const fn with_fade_in(self, duration: core::time::Duration) -> Self {
+        Self {
+            fade_in: duration,
+            ..self
+        }
+    }
+
+    /// Returns this clip with a fade-out to silence over `duration` at its end.
+    /// This uses [`core::time::Duration`].
+    #[must_use]
+    pub const fn with_fade_out(self, duration: core::time::Duration) -> Self {
+        Self {
+            fade_out: duration,
+            ..self
+        }
+    }
+
+    /// Returns the fade-in duration.
+    #[must_use]
+    pub const fn fade_in(self) -> core::time::Duration {
+        self.fade_in
+    }
+
+    /// Returns the fade-out duration.
+    #[must_use]
+    pub const fn fade_out(self) -> core::time::Duration {
+        self.fade_out
+    }
+}
+
 /// A clip source trait for [`AudioPlayer::play`](crate::audio_player::AudioPlayer::play).
 ///
-/// This trait let's us pass audio clips of different types (PCM, ADPCM, or silence) in a single heterogeneous sequence to `play`.
+/// This trait let's us pass audio clips of different types (mono or stereo PCM, ADPCM, and
+/// QOA, μ-law/A-law, silence, or an [`AudioStream`]) in a single heterogeneous sequence to `play`.
 ///
 /// This trait is object-safe, so mixed clips are passed as:
 /// `&'static dyn Playable<SAMPLE_RATE_HZ>`.
@@ -1126,37 +1999,135 @@ impl<const SAMPLE_RATE_HZ: u32, T: ?Sized> Playable<SAMPLE_RATE_HZ> for T where
 }
 
 mod sealed {
-    use super::{AdpcmClip, PcmClip, PlaybackClip, SilenceClip};
+    use core::time::Duration;
+
+    use super::{
+        AdpcmClip, AlawClip, AudioStream, FadedClip, Melody, MulawClip, PcmClip, PlaybackClip,
+        QoaClip, SequenceClip, SilenceClip, StereoAdpcmClip, StereoPcmClip, StereoQoaClip,
+    };
+
+    // `Sync` so `&'static dyn Playable` can be stored in statics, such as in a `FadedClip`.
+    pub(crate) trait PlayableSealed<const SAMPLE_RATE_HZ: u32>: Sync {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ>;
+
+        fn sequence_clip(&'static self) -> SequenceClip<SAMPLE_RATE_HZ> {
+            SequenceClip {
+                playback_clip: self.playback_clip(),
+                fade_in: Duration::ZERO,
+                fade_out: Duration::ZERO,
+            }
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for PcmClip<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Pcm(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize> PlayableSealed<SAMPLE_RATE_HZ>
+        for PcmClip<SAMPLE_RATE_HZ, [i16; SAMPLE_COUNT]>
+    {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Pcm(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for AdpcmClip<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Adpcm(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> PlayableSealed<SAMPLE_RATE_HZ>
+        for AdpcmClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>
+    {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Adpcm(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for StereoPcmClip<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::StereoPcm(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32, const FRAME_COUNT: usize> PlayableSealed<SAMPLE_RATE_HZ>
+        for StereoPcmClip<SAMPLE_RATE_HZ, [[i16; 2]; FRAME_COUNT]>
+    {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::StereoPcm(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for StereoAdpcmClip<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::StereoAdpcm(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> PlayableSealed<SAMPLE_RATE_HZ>
+        for StereoAdpcmClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>
+    {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::StereoAdpcm(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for MulawClip<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Mulaw(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize> PlayableSealed<SAMPLE_RATE_HZ>
+        for MulawClip<SAMPLE_RATE_HZ, [u8; SAMPLE_COUNT]>
+    {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Mulaw(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for AlawClip<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Alaw(self)
+        }
+    }
 
-    pub(crate) trait PlayableSealed<const SAMPLE_RATE_HZ: u32> {
-        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ>;
+    impl<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize> PlayableSealed<SAMPLE_RATE_HZ>
+        for AlawClip<SAMPLE_RATE_HZ, [u8; SAMPLE_COUNT]>
+    {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Alaw(self)
+        }
     }
 
-    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for PcmClip<SAMPLE_RATE_HZ> {
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for QoaClip<SAMPLE_RATE_HZ> {
         fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
-            PlaybackClip::Pcm(self)
+            PlaybackClip::Qoa(self)
         }
     }
 
-    impl<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize> PlayableSealed<SAMPLE_RATE_HZ>
-        for PcmClip<SAMPLE_RATE_HZ, [i16; SAMPLE_COUNT]>
+    impl<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> PlayableSealed<SAMPLE_RATE_HZ>
+        for QoaClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>
     {
         fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
-            PlaybackClip::Pcm(self)
+            PlaybackClip::Qoa(self)
         }
     }
 
-    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for AdpcmClip<SAMPLE_RATE_HZ> {
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for StereoQoaClip<SAMPLE_RATE_HZ> {
         fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
-            PlaybackClip::Adpcm(self)
+            PlaybackClip::StereoQoa(self)
         }
     }
 
     impl<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> PlayableSealed<SAMPLE_RATE_HZ>
-        for AdpcmClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>
+        for StereoQoaClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>
     {
         fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
-            PlaybackClip::Adpcm(self)
+            PlaybackClip::StereoQoa(self)
         }
     }
 
@@ -1165,6 +2136,41 @@ mod sealed {
             PlaybackClip::Silence(self.duration())
         }
     }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for Melody<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Synth(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for FadedClip<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            self.audio_clip.playback_clip()
+        }
+
+        fn sequence_clip(&'static self) -> SequenceClip<SAMPLE_RATE_HZ> {
+            let sequence_clip = self.audio_clip.sequence_clip();
+            SequenceClip {
+                fade_in: sequence_clip.fade_in.max(self.fade_in),
+                fade_out: sequence_clip.fade_out.max(self.fade_out),
+                ..sequence_clip
+            }
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32, T: AudioStream<SAMPLE_RATE_HZ>> PlayableSealed<SAMPLE_RATE_HZ>
+        for T
+    {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Stream(self)
+        }
+    }
+
+    impl<const SAMPLE_RATE_HZ: u32> PlayableSealed<SAMPLE_RATE_HZ> for dyn AudioStream<SAMPLE_RATE_HZ> {
+        fn playback_clip(&'static self) -> PlaybackClip<SAMPLE_RATE_HZ> {
+            PlaybackClip::Stream(self)
+        }
+    }
 }
 
 /// Unsized view of static uncompressed (PCM) audio clip data.
@@ -1538,68 +2544,62 @@ pub const fn __resample_pcm_clip<
 
 enum AudioCommand<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> {
     Play {
-        audio_clips: Vec<PlaybackClip<SAMPLE_RATE_HZ>, MAX_CLIPS>,
+        audio_clips: Vec<SequenceClip<SAMPLE_RATE_HZ>, MAX_CLIPS>,
         at_end: AtEnd,
+        crossfade: Duration,
     },
     Stop,
+    StopWithFade(Duration),
 }
 
-/// Static resources for [`AudioPlayer`].
-// Must be `pub` so `audio_player!` expansions in downstream crates can reference this type.
-#[doc(hidden)]
-pub struct AudioPlayerStatic<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> {
+/// A [`AudioVoice::play_with_priority`] request, waiting for the mixer to decide on it.
+struct PriorityPlay<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> {
+    request_id: u32,
+    audio_clips: Vec<SequenceClip<SAMPLE_RATE_HZ>, MAX_CLIPS>,
+    at_end: AtEnd,
+    priority: Priority,
+    interrupt_policy: InterruptPolicy,
+}
+
+/// Static resources for one voice of an [`AudioPlayer`].
+struct VoiceStatic<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> {
     command_signal: Signal<CriticalSectionRawMutex, AudioCommand<MAX_CLIPS, SAMPLE_RATE_HZ>>,
+    priority_play_signal: Signal<CriticalSectionRawMutex, PriorityPlay<MAX_CLIPS, SAMPLE_RATE_HZ>>,
+    play_outcome_signal: Signal<CriticalSectionRawMutex, (u32, PlayOutcome)>,
+    /// Keeps one priority request in flight per voice, so each outcome has one waiter.
+    /// Holds the next request ID.
+    priority_play_mutex: Mutex<CriticalSectionRawMutex, u32>,
     stopped_signal: Signal<CriticalSectionRawMutex, ()>,
+    position: BlockingMutex<CriticalSectionRawMutex, Cell<Option<PlaybackPosition>>>,
+    event_channel:
+        channel::Channel<CriticalSectionRawMutex, PlaybackEvent, PLAYBACK_EVENT_CAPACITY>,
     is_playing: AtomicBool,
     has_pending_play: AtomicBool,
-    max_volume_linear: i32,
-    runtime_volume_relative_linear: AtomicI32,
+    gain_linear: AtomicI32,
+    pan_percent: AtomicI8,
 }
 
-impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32>
-    AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ>
-{
-    /// Creates static resources for a player.
-    #[must_use]
-    pub const fn new_static() -> Self {
-        Self::new_static_with_max_volume_and_initial_volume(Volume::MAX, Volume::MAX)
-    }
-
-    /// Creates static resources for a player with a runtime volume ceiling.
-    #[must_use]
-    pub const fn new_static_with_max_volume(max_volume: Volume) -> Self {
-        Self::new_static_with_max_volume_and_initial_volume(max_volume, Volume::MAX)
-    }
-
-    /// Creates static resources for a player with a runtime volume ceiling
-    /// and an initial runtime volume relative to that ceiling.
-    #[must_use]
-    pub const fn new_static_with_max_volume_and_initial_volume(
-        max_volume: Volume,
-        initial_volume: Volume,
-    ) -> Self {
+impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> VoiceStatic<MAX_CLIPS, SAMPLE_RATE_HZ> {
+    const fn new() -> Self {
         Self {
             command_signal: Signal::new(),
+            priority_play_signal: Signal::new(),
+            play_outcome_signal: Signal::new(),
+            priority_play_mutex: Mutex::new(0),
             stopped_signal: Signal::new(),
+            position: BlockingMutex::new(Cell::new(None)),
+            event_channel: channel::Channel::new(),
             is_playing: AtomicBool::new(false),
             has_pending_play: AtomicBool::new(false),
-            max_volume_linear: max_volume.to_i16() as i32,
-            runtime_volume_relative_linear: AtomicI32::new(initial_volume.to_i16() as i32),
+            gain_linear: AtomicI32::new(Gain::percent(100).linear()),
+            pan_percent: AtomicI8::new(Pan::CENTER.to_percent()),
         }
     }
 
-    fn signal(&self, audio_command: AudioCommand<MAX_CLIPS, SAMPLE_RATE_HZ>) {
-        self.command_signal.signal(audio_command);
-    }
-
     fn mark_pending_play(&self) {
         self.has_pending_play.store(true, AtomicOrdering::Relaxed);
     }
 
-    async fn wait(&self) -> AudioCommand<MAX_CLIPS, SAMPLE_RATE_HZ> {
-        self.command_signal.wait().await
-    }
-
     fn mark_playing(&self) {
         self.has_pending_play.store(false, AtomicOrdering::Relaxed);
         self.is_playing.store(true, AtomicOrdering::Relaxed);
@@ -1608,9 +2608,29 @@ impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32>
     fn mark_stopped(&self) {
         self.has_pending_play.store(false, AtomicOrdering::Relaxed);
         self.is_playing.store(false, AtomicOrdering::Relaxed);
+        self.set_position(None);
         self.stopped_signal.signal(());
     }
 
+    fn set_position(&self, position: Option<PlaybackPosition>) {
+        self.position
+            .lock(|position_cell| position_cell.set(position));
+    }
+
+    fn position(&self) -> Option<PlaybackPosition> {
+        self.position.lock(Cell::get)
+    }
+
+    /// Sends `playback_event`, dropping the oldest unread events to make room.
+    fn publish_event(&self, mut playback_event: PlaybackEvent) {
+        while let Err(channel::TrySendError::Full(unsent_event)) =
+            self.event_channel.try_send(playback_event)
+        {
+            let _ = self.event_channel.try_receive();
+            playback_event = unsent_event;
+        }
+    }
+
     fn is_idle(&self) -> bool {
         !self.has_pending_play.load(AtomicOrdering::Relaxed)
             && !self.is_playing.load(AtomicOrdering::Relaxed)
@@ -1622,6 +2642,84 @@ impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32>
         }
     }
 
+    fn set_gain(&self, gain: Gain) {
+        self.gain_linear.store(gain.linear(), Ordering::Relaxed);
+    }
+
+    fn gain(&self) -> Gain {
+        Gain::from_linear(self.gain_linear.load(Ordering::Relaxed))
+    }
+
+    fn set_pan(&self, pan: Pan) {
+        self.pan_percent.store(pan.to_percent(), Ordering::Relaxed);
+    }
+
+    fn pan(&self) -> Pan {
+        Pan::percent(self.pan_percent.load(Ordering::Relaxed))
+    }
+}
+
+/// Static resources for [`AudioPlayer`].
+// Must be `pub` so `audio_player!` expansions in downstream crates can reference this type.
+#[doc(hidden)]
+pub struct AudioPlayerStatic<
+    const MAX_CLIPS: usize,
+    const SAMPLE_RATE_HZ: u32,
+    const VOICES: usize = 1,
+> {
+    voices: [VoiceStatic<MAX_CLIPS, SAMPLE_RATE_HZ>; VOICES],
+    command_pending_signal: Signal<CriticalSectionRawMutex, ()>,
+    dsp_signal: Signal<CriticalSectionRawMutex, Dsp>,
+    max_volume_linear: i32,
+    runtime_volume_relative_linear: AtomicI32,
+}
+
+impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32, const VOICES: usize>
+    AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>
+{
+    /// Creates static resources for a player.
+    #[must_use]
+    pub const fn new_static() -> Self {
+        Self::new_static_with_max_volume_and_initial_volume(Volume::MAX, Volume::MAX)
+    }
+
+    /// Creates static resources for a player with a runtime volume ceiling.
+    #[must_use]
+    pub const fn new_static_with_max_volume(max_volume: Volume) -> Self {
+        Self::new_static_with_max_volume_and_initial_volume(max_volume, Volume::MAX)
+    }
+
+    /// Creates static resources for a player with a runtime volume ceiling
+    /// and an initial runtime volume relative to that ceiling.
+    #[must_use]
+    pub const fn new_static_with_max_volume_and_initial_volume(
+        max_volume: Volume,
+        initial_volume: Volume,
+    ) -> Self {
+        assert!(VOICES > 0, "voices must be > 0");
+        Self {
+            voices: [const { VoiceStatic::new() }; VOICES],
+            command_pending_signal: Signal::new(),
+            dsp_signal: Signal::new(),
+            max_volume_linear: max_volume.to_i16() as i32,
+            runtime_volume_relative_linear: AtomicI32::new(initial_volume.to_i16() as i32),
+        }
+    }
+
+    fn voice(&'static self, voice_index: usize) -> AudioVoice<MAX_CLIPS, SAMPLE_RATE_HZ> {
+        assert!(voice_index < VOICES, "voice_index must be < voices");
+        AudioVoice {
+            voice_static: &self.voices[voice_index],
+            command_pending_signal: &self.command_pending_signal,
+            has_other_voices: VOICES > 1,
+        }
+    }
+
+    #[cfg(target_os = "none")]
+    async fn wait_for_command(&self) {
+        self.command_pending_signal.wait().await;
+    }
+
     fn set_runtime_volume(&self, volume: Volume) {
         self.runtime_volume_relative_linear
             .store(volume.to_i16() as i32, Ordering::Relaxed);
@@ -1631,6 +2729,11 @@ impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32>
         Volume::from_i16(self.runtime_volume_relative_linear.load(Ordering::Relaxed) as i16)
     }
 
+    fn set_dsp(&self, dsp: Dsp) {
+        self.dsp_signal.signal(dsp);
+        self.command_pending_signal.signal(());
+    }
+
     fn effective_runtime_volume(&self) -> Volume {
         let runtime_volume_relative = self.runtime_volume();
         Volume::from_i16(scale_linear(self.max_volume_linear, runtime_volume_relative) as i16)
@@ -1640,16 +2743,22 @@ impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32>
 /// Plays static audio clips with preemptive command handling in the background device task.
 ///
 /// See the [`audio_player!`] macro for the normal construction pattern.
+///
+/// A player mixes `VOICES` independent voices (set by the `voices` field of
+/// [`audio_player!`], default `1`). [`Self::play`], [`Self::stop`], and
+/// [`Self::wait_until_stopped`] control voice `0`; use [`Self::voice`] for the others.
 // Must be `pub` so `audio_player!` expansions in downstream crates can reference this type.
 #[doc(hidden)]
-pub struct AudioPlayer<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> {
-    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ>,
+pub struct AudioPlayer<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32, const VOICES: usize = 1> {
+    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
 }
 
-impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> AudioPlayer<MAX_CLIPS, SAMPLE_RATE_HZ> {
+impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32, const VOICES: usize>
+    AudioPlayer<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>
+{
     /// Creates static resources for a player.
     #[must_use]
-    pub const fn new_static() -> AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ> {
+    pub const fn new_static() -> AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES> {
         AudioPlayerStatic::new_static()
     }
 
@@ -1657,7 +2766,7 @@ impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> AudioPlayer<MAX_CLIPS, S
     #[must_use]
     pub const fn new_static_with_max_volume(
         max_volume: Volume,
-    ) -> AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ> {
+    ) -> AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES> {
         AudioPlayerStatic::new_static_with_max_volume(max_volume)
     }
 
@@ -1667,40 +2776,299 @@ impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> AudioPlayer<MAX_CLIPS, S
     pub const fn new_static_with_max_volume_and_initial_volume(
         max_volume: Volume,
         initial_volume: Volume,
-    ) -> AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ> {
+    ) -> AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES> {
         AudioPlayerStatic::new_static_with_max_volume_and_initial_volume(max_volume, initial_volume)
     }
 
     /// Creates a player handle. The device task must already be running.
     #[must_use]
     pub const fn new(
-        audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ>,
+        audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
     ) -> Self {
         Self {
             audio_player_static,
         }
     }
 
-    /// Starts playback of one or more static audio clips.
+    /// Returns a handle to one voice of the mixer.
+    ///
+    /// Each voice has its own clip queue, [`AtEnd`] behavior, and [`Gain`]. Voices
+    /// play at the same time and are summed, with saturation, into the output.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `voice_index` is not less than the `voices` field of
+    /// [`audio_player!`](macro@crate::audio_player::audio_player) (default: `1`).
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    #[must_use]
+    pub fn voice(&self, voice_index: usize) -> AudioVoice<MAX_CLIPS, SAMPLE_RATE_HZ> {
+        self.audio_player_static.voice(voice_index)
+    }
+
+    /// Starts playback of one or more static audio clips on voice `0`.
     /// Playback runs in the background. If you need to know when playback is
     /// finished, use [`Self::wait_until_stopped`].
     ///
     /// Accepts any array-like or iterator input. The maximum number of clips
     /// is set by the `max_clips` field of
     /// [`audio_player!`](macro@crate::audio_player::audio_player) (default: `16`).
-    ///
-    /// See the [audio_player module documentation](mod@crate::audio_player) for
-    /// usage examples.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    pub fn play<I>(&self, audio_clips: I, at_end: AtEnd)
+    where
+        I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
+    {
+        self.voice(0).play(audio_clips, at_end);
+    }
+
+    /// Starts playback from a generic iterator of static clip sources.
+    ///
+    /// This allows runtime-selected sequencing while still requiring static
+    /// clip sample storage.
+    pub fn play_iter<I>(&self, audio_clips: I, at_end: AtEnd)
+    where
+        I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
+    {
+        self.play(audio_clips, at_end);
+    }
+
+    /// Like [`Self::play`], but overlaps consecutive clips by `crossfade`, fading the
+    /// ending clip out while the next clip fades in.
+    ///
+    /// See [`AudioVoice::play_with_crossfade`] for details.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    pub fn play_with_crossfade<I>(&self, audio_clips: I, at_end: AtEnd, crossfade: Duration)
+    where
+        I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
+    {
+        self.voice(0)
+            .play_with_crossfade(audio_clips, at_end, crossfade);
+    }
+
+    /// Plays on voice `0` according to `priority` and `interrupt_policy`, instead of
+    /// always replacing the current sequence.
+    ///
+    /// See [`AudioVoice::play_with_priority`] for details.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    pub async fn play_with_priority<I>(
+        &self,
+        audio_clips: I,
+        at_end: AtEnd,
+        priority: Priority,
+        interrupt_policy: InterruptPolicy,
+    ) -> PlayOutcome
+    where
+        I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
+    {
+        self.voice(0)
+            .play_with_priority(audio_clips, at_end, priority, interrupt_policy)
+            .await
+    }
+
+    /// Stops current playback on voice `0` as soon as possible.
+    ///
+    /// If playback is active, it is interrupted at the next DMA chunk boundary.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    pub fn stop(&self) {
+        self.voice(0).stop();
+    }
+
+    /// Fades playback on voice `0` out over `duration`, then stops it.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    pub fn stop_with_fade(&self, duration: Duration) {
+        self.voice(0).stop_with_fade(duration);
+    }
+
+    /// Waits until playback on voice `0` is stopped.
+    ///
+    /// If playback is currently stopped, this returns immediately.
+    /// If playback is active, this waits until the player reaches the stopped
+    /// state (natural end with [`AtEnd::Stop`] or a processed [`Self::stop`]).
+    pub async fn wait_until_stopped(&self) {
+        self.voice(0).wait_until_stopped().await;
+    }
+
+    /// Returns where voice `0` is within its clip sequence, or `None` if it is stopped.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    #[must_use]
+    pub fn position(&self) -> Option<PlaybackPosition> {
+        self.voice(0).position()
+    }
+
+    /// Waits for the next clip-started, clip-ended, or sequence-ended event on voice `0`.
+    ///
+    /// See [`AudioVoice::wait_for_event`] for details.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    pub async fn wait_for_event(&self) -> PlaybackEvent {
+        self.voice(0).wait_for_event().await
+    }
+
+    /// Sets runtime playback volume relative to [`Self::MAX_VOLUME`].
+    /// This can take effect while a sequence of clips is already playing.
+    ///
+    /// - `Volume::percent(100)` plays at exactly `max_volume`.
+    /// - `Volume::percent(50)` plays at half of `max_volume`.
+    ///
+    /// This relative scale composes multiplicatively with any per-clip gain
+    /// pre-applied via [`PcmClipBuf::with_gain`] and with each voice's
+    /// [`AudioVoice::set_gain`].
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    pub fn set_volume(&self, volume: Volume) {
+        self.audio_player_static.set_runtime_volume(volume);
+    }
+
+    /// Returns the current runtime playback volume relative to [`Self::MAX_VOLUME`].
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    #[must_use]
+    pub fn volume(&self) -> Volume {
+        self.audio_player_static.runtime_volume()
+    }
+
+    /// Replaces the effects chain applied to the mixed output of all voices.
+    ///
+    /// The new chain starts with cleared filter and echo history. Pass
+    /// [`Dsp::BYPASS`] to turn processing off.
+    ///
+    /// See the [audio_player module documentation](mod@crate::audio_player) for
+    /// usage examples.
+    pub fn set_dsp(&self, dsp: Dsp) {
+        self.audio_player_static.set_dsp(dsp);
+    }
+}
+
+/// Handle to one voice of a generated audio player.
+///
+/// Get one from the generated player's `voice(voice_index)` method. Each voice has its
+/// own clip queue, [`AtEnd`] behavior, and [`Gain`], so, for example, sound effects can
+/// play over looping background music.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy)]
+pub struct AudioVoice<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> {
+    voice_static: &'static VoiceStatic<MAX_CLIPS, SAMPLE_RATE_HZ>,
+    command_pending_signal: &'static Signal<CriticalSectionRawMutex, ()>,
+    /// Whether the player has other voices for [`InterruptPolicy::Duck`] to lower.
+    has_other_voices: bool,
+}
+
+impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> AudioVoice<MAX_CLIPS, SAMPLE_RATE_HZ> {
+    fn signal(&self, audio_command: AudioCommand<MAX_CLIPS, SAMPLE_RATE_HZ>) {
+        self.voice_static.command_signal.signal(audio_command);
+        self.command_pending_signal.signal(());
+    }
+
+    /// Starts playback of one or more static audio clips on this voice, replacing
+    /// whatever the voice was playing. Other voices keep playing.
+    ///
+    /// Accepts any array-like or iterator input. The maximum number of clips
+    /// is set by the `max_clips` field of
+    /// [`audio_player!`](macro@crate::audio_player::audio_player) (default: `16`).
     pub fn play<I>(&self, audio_clips: I, at_end: AtEnd)
+    where
+        I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
+    {
+        self.play_with_crossfade(audio_clips, at_end, Duration::ZERO);
+    }
+
+    /// Like [`Self::play`], but overlaps consecutive clips by `crossfade`, fading the
+    /// ending clip out while the next clip fades in.
+    ///
+    /// With [`AtEnd::Loop`], the last clip also crossfades into the first. The crossfade
+    /// is shortened to fit clips shorter than `crossfade`, and is skipped after an
+    /// [`AudioStream`], whose end isn't known in advance.
+    /// This uses [`core::time::Duration`].
+    pub fn play_with_crossfade<I>(&self, audio_clips: I, at_end: AtEnd, crossfade: Duration)
+    where
+        I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
+    {
+        let audio_clips = Self::audio_clip_sequence(audio_clips);
+        self.voice_static.mark_pending_play();
+        self.signal(AudioCommand::Play {
+            audio_clips,
+            at_end,
+            crossfade,
+        });
+    }
+
+    /// Plays on this voice according to `priority` and `interrupt_policy`, instead of
+    /// always replacing the current sequence like [`Self::play`] does.
+    ///
+    /// If the voice is stopped, the sequence plays right away. Otherwise:
+    ///
+    /// - [`InterruptPolicy::Enqueue`] queues it to play after the current sequence.
+    /// - [`InterruptPolicy::Preempt`] replaces the current sequence if this request has
+    ///   higher priority.
+    /// - [`InterruptPolicy::Duck`] also lowers the other voices while it plays. It
+    ///   panics on a player with only one voice, which has nothing to duck.
+    ///
+    /// Returns, once the player has decided, whether the sequence was played, queued,
+    /// or dropped. [`Self::play`] and [`Self::stop`] discard a queued sequence.
+    pub async fn play_with_priority<I>(
+        &self,
+        audio_clips: I,
+        at_end: AtEnd,
+        priority: Priority,
+        interrupt_policy: InterruptPolicy,
+    ) -> PlayOutcome
+    where
+        I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
+    {
+        assert!(
+            self.has_other_voices || !matches!(interrupt_policy, InterruptPolicy::Duck(_)),
+            "InterruptPolicy::Duck needs a player with at least two voices"
+        );
+        let audio_clips = Self::audio_clip_sequence(audio_clips);
+        let mut next_request_id = self.voice_static.priority_play_mutex.lock().await;
+        let request_id = *next_request_id;
+        *next_request_id = request_id.wrapping_add(1);
+        self.voice_static.priority_play_signal.signal(PriorityPlay {
+            request_id,
+            audio_clips,
+            at_end,
+            priority,
+            interrupt_policy,
+        });
+        self.command_pending_signal.signal(());
+        loop {
+            // Skip any outcome left by a request whose caller stopped waiting.
+            let (outcome_request_id, play_outcome) =
+                self.voice_static.play_outcome_signal.wait().await;
+            if outcome_request_id == request_id {
+                return play_outcome;
+            }
+        }
+    }
+
+    fn audio_clip_sequence<I>(audio_clips: I) -> Vec<SequenceClip<SAMPLE_RATE_HZ>, MAX_CLIPS>
     where
         I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
     {
         assert!(MAX_CLIPS > 0, "play disabled: max_clips is 0");
-        let mut audio_clip_sequence: Vec<PlaybackClip<SAMPLE_RATE_HZ>, MAX_CLIPS> = Vec::new();
+        let mut audio_clip_sequence: Vec<SequenceClip<SAMPLE_RATE_HZ>, MAX_CLIPS> = Vec::new();
         for audio_clip in audio_clips {
             assert!(
                 audio_clip_sequence
-                    .push(sealed::PlayableSealed::playback_clip(audio_clip))
+                    .push(sealed::PlayableSealed::sequence_clip(audio_clip))
                     .is_ok(),
                 "play sequence fits within max_clips"
             );
@@ -1709,66 +3077,76 @@ impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> AudioPlayer<MAX_CLIPS, S
             !audio_clip_sequence.is_empty(),
             "play requires at least one clip"
         );
-
-        self.audio_player_static.mark_pending_play();
-        self.audio_player_static.signal(AudioCommand::Play {
-            audio_clips: audio_clip_sequence,
-            at_end,
-        });
+        audio_clip_sequence
     }
 
-    /// Starts playback from a generic iterator of static clip sources.
+    /// Stops playback on this voice as soon as possible.
     ///
-    /// This allows runtime-selected sequencing while still requiring static
-    /// clip sample storage.
-    pub fn play_iter<I>(&self, audio_clips: I, at_end: AtEnd)
-    where
-        I: IntoIterator<Item = &'static dyn Playable<SAMPLE_RATE_HZ>>,
-    {
-        self.play(audio_clips, at_end);
+    /// If playback is active, it is interrupted at the next DMA chunk boundary.
+    pub fn stop(&self) {
+        self.signal(AudioCommand::Stop);
     }
 
-    /// Stops current playback as soon as possible.
-    ///
-    /// If playback is active, it is interrupted at the next DMA chunk boundary.
+    /// Fades playback on this voice out over `duration`, then stops it.
     ///
-    /// See the [audio_player module documentation](mod@crate::audio_player) for
-    /// usage examples.
-    pub fn stop(&self) {
-        self.audio_player_static.signal(AudioCommand::Stop);
+    /// Unlike [`Self::stop`], this doesn't click when interrupting a clip.
+    /// [`Self::wait_until_stopped`] returns once the fade has finished.
+    /// This uses [`core::time::Duration`].
+    pub fn stop_with_fade(&self, duration: Duration) {
+        self.signal(AudioCommand::StopWithFade(duration));
     }
 
-    /// Waits until playback is stopped.
+    /// Waits until playback on this voice is stopped.
     ///
-    /// If playback is currently stopped, this returns immediately.
-    /// If playback is active, this waits until the player reaches the stopped
-    /// state (natural end with [`AtEnd::Stop`] or a processed [`Self::stop`]).
+    /// If the voice is currently stopped, this returns immediately.
     pub async fn wait_until_stopped(&self) {
-        self.audio_player_static.wait_until_stopped().await;
+        self.voice_static.wait_until_stopped().await;
     }
 
-    /// Sets runtime playback volume relative to [`Self::MAX_VOLUME`].
-    /// This can take effect while a sequence of clips is already playing.
-    ///
-    /// - `Volume::percent(100)` plays at exactly `max_volume`.
-    /// - `Volume::percent(50)` plays at half of `max_volume`.
+    /// Returns where this voice is within its clip sequence, or `None` if it is stopped.
+    #[must_use]
+    pub fn position(&self) -> Option<PlaybackPosition> {
+        self.voice_static.position()
+    }
+
+    /// Waits for the next clip-started, clip-ended, or sequence-ended event on this voice.
     ///
-    /// This relative scale composes multiplicatively with any per-clip gain
-    /// pre-applied via [`PcmClipBuf::with_gain`].
+    /// Events are sent after each output buffer (256 frames) is written, so they can
+    /// start LED animations or servo moves in step with the sound.
+    /// The voice holds the 8 most recent unread events; older ones are dropped
+    /// to make room, so a late reader still sees how the sequence ended.
+    /// Each event goes to one waiter.
+    pub async fn wait_for_event(&self) -> PlaybackEvent {
+        self.voice_static.event_channel.receive().await
+    }
+
+    /// Sets this voice's gain relative to the player's volume.
+    /// This can take effect while the voice is playing.
     ///
-    /// See the [audio_player module documentation](mod@crate::audio_player) for
-    /// usage examples.
-    pub fn set_volume(&self, volume: Volume) {
-        self.audio_player_static.set_runtime_volume(volume);
+    /// The default is `Gain::percent(100)`. Gains above `100%` can make the
+    /// voice louder; the mixed output saturates rather than wrapping.
+    pub fn set_gain(&self, gain: Gain) {
+        self.voice_static.set_gain(gain);
     }
 
-    /// Returns the current runtime playback volume relative to [`Self::MAX_VOLUME`].
+    /// Returns this voice's current gain.
+    #[must_use]
+    pub fn gain(&self) -> Gain {
+        self.voice_static.gain()
+    }
+
+    /// Sets this voice's left/right position in the stereo output.
+    /// This can take effect while the voice is playing.
     ///
-    /// See the [audio_player module documentation](mod@crate::audio_player) for
-    /// usage examples.
+    /// The default is [`Pan::CENTER`]. For stereo clips, pan acts as a balance control.
+    pub fn set_pan(&self, pan: Pan) {
+        self.voice_static.set_pan(pan);
+    }
+
+    /// Returns this voice's current pan position.
     #[must_use]
-    pub fn volume(&self) -> Volume {
-        self.audio_player_static.runtime_volume()
+    pub fn pan(&self) -> Pan {
+        self.voice_static.pan()
     }
 }
 
@@ -1778,13 +3156,15 @@ impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> AudioPlayer<MAX_CLIPS, S
 pub async fn device_loop<
     const MAX_CLIPS: usize,
     const SAMPLE_RATE_HZ: u32,
+    const VOICES: usize,
+    const ECHO_FRAMES: usize,
     PIO: PioIrqMap,
     DMA: Channel,
     DinPin: Pin + PioPin,
     BclkPin: Pin + PioPin,
     LrcPin: Pin + PioPin,
 >(
-    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ>,
+    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
     pio: Peri<'static, PIO>,
     dma: Peri<'static, DMA>,
     data_pin: Peri<'static, DinPin>,
@@ -1807,275 +3187,19 @@ pub async fn device_loop<
 
     let _pio_i2s_out_program = pio_i2s_out_program;
     let mut sample_buffer = [0_u32; SAMPLE_BUFFER_LEN];
+    let mut mixer = Mixer::<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES, ECHO_FRAMES>::new();
 
     loop {
-        let mut audio_command = audio_player_static.wait().await;
-
-        loop {
-            match audio_command {
-                AudioCommand::Play {
-                    audio_clips,
-                    at_end,
-                } => {
-                    audio_player_static.mark_playing();
-                    let next_audio_command = match at_end {
-                        AtEnd::Loop => loop {
-                            if let Some(next_audio_command) = play_clip_sequence_once(
-                                &mut pio_i2s_out,
-                                &audio_clips,
-                                &mut sample_buffer,
-                                audio_player_static,
-                            )
-                            .await
-                            {
-                                break Some(next_audio_command);
-                            }
-                        },
-                        AtEnd::Stop => {
-                            play_clip_sequence_once(
-                                &mut pio_i2s_out,
-                                &audio_clips,
-                                &mut sample_buffer,
-                                audio_player_static,
-                            )
-                            .await
-                        }
-                    };
-
-                    if let Some(next_audio_command) = next_audio_command {
-                        audio_command = next_audio_command;
-                        continue;
-                    }
-
-                    audio_player_static.mark_stopped();
-                }
-                AudioCommand::Stop => audio_player_static.mark_stopped(),
-            }
-
-            break;
-        }
-    }
-}
-
-#[cfg(target_os = "none")]
-async fn play_clip_sequence_once<
-    PIO: Instance,
-    const MAX_CLIPS: usize,
-    const SAMPLE_RATE_HZ: u32,
->(
-    pio_i2s_out: &mut PioI2sOut<'static, PIO, 0>,
-    audio_clips: &[PlaybackClip<SAMPLE_RATE_HZ>],
-    sample_buffer: &mut [u32; SAMPLE_BUFFER_LEN],
-    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ>,
-) -> Option<AudioCommand<MAX_CLIPS, SAMPLE_RATE_HZ>> {
-    for audio_clip in audio_clips {
-        match audio_clip {
-            PlaybackClip::Pcm(audio_clip) => {
-                if let ControlFlow::Break(next_audio_command) = play_full_pcm_clip_once(
-                    pio_i2s_out,
-                    audio_clip,
-                    sample_buffer,
-                    audio_player_static,
-                )
-                .await
-                {
-                    return Some(next_audio_command);
-                }
-            }
-            PlaybackClip::Adpcm(adpcm_clip) => {
-                if let ControlFlow::Break(next_audio_command) = play_full_adpcm_clip_once(
-                    pio_i2s_out,
-                    adpcm_clip,
-                    sample_buffer,
-                    audio_player_static,
-                )
-                .await
-                {
-                    return Some(next_audio_command);
-                }
-            }
-            PlaybackClip::Silence(duration) => {
-                if let ControlFlow::Break(next_audio_command) = play_silence_duration_once(
-                    pio_i2s_out,
-                    *duration,
-                    sample_buffer,
-                    audio_player_static,
-                )
-                .await
-                {
-                    return Some(next_audio_command);
-                }
-            }
-        }
-    }
-    None
-}
-
-#[cfg(target_os = "none")]
-async fn play_full_pcm_clip_once<
-    PIO: Instance,
-    const MAX_CLIPS: usize,
-    const SAMPLE_RATE_HZ: u32,
->(
-    pio_i2s_out: &mut PioI2sOut<'static, PIO, 0>,
-    audio_clip: &PcmClip<SAMPLE_RATE_HZ>,
-    sample_buffer: &mut [u32; SAMPLE_BUFFER_LEN],
-    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ>,
-) -> ControlFlow<AudioCommand<MAX_CLIPS, SAMPLE_RATE_HZ>, ()> {
-    for audio_sample_chunk in audio_clip.samples.chunks(SAMPLE_BUFFER_LEN) {
-        let runtime_volume = audio_player_static.effective_runtime_volume();
-        for (sample_buffer_slot, sample_value_ref) in
-            sample_buffer.iter_mut().zip(audio_sample_chunk.iter())
-        {
-            let sample_value = *sample_value_ref;
-            let scaled_sample_value =
-                scale_sample_with_linear(sample_value, runtime_volume.to_i16() as i32);
-            *sample_buffer_slot = stereo_sample(scaled_sample_value);
-        }
-
-        sample_buffer[audio_sample_chunk.len()..].fill(stereo_sample(0));
-        pio_i2s_out.write(sample_buffer).await;
-
-        if let Some(next_audio_command) = audio_player_static.command_signal.try_take() {
-            return ControlFlow::Break(next_audio_command);
-        }
-    }
-
-    ControlFlow::Continue(())
-}
-
-#[cfg(target_os = "none")]
-async fn play_full_adpcm_clip_once<
-    PIO: Instance,
-    const MAX_CLIPS: usize,
-    const SAMPLE_RATE_HZ: u32,
->(
-    pio_i2s_out: &mut PioI2sOut<'static, PIO, 0>,
-    adpcm_clip: &AdpcmClip<SAMPLE_RATE_HZ>,
-    sample_buffer: &mut [u32; SAMPLE_BUFFER_LEN],
-    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ>,
-) -> ControlFlow<AudioCommand<MAX_CLIPS, SAMPLE_RATE_HZ>, ()> {
-    let mut sample_buffer_len = 0usize;
-
-    let block_align = adpcm_clip.block_align as usize;
-    for adpcm_block in adpcm_clip.data.chunks_exact(block_align) {
-        if adpcm_block.len() < 4 {
-            return ControlFlow::Continue(());
-        }
-
-        let runtime_volume = audio_player_static.effective_runtime_volume();
-        let mut predictor_i32 = match read_i16_le(adpcm_block, 0) {
-            Ok(value) => value as i32,
-            Err(_) => return ControlFlow::Continue(()),
-        };
-        let mut step_index_i32 = adpcm_block[2] as i32;
-        if !(0..=88).contains(&step_index_i32) {
-            return ControlFlow::Continue(());
-        }
-
-        sample_buffer[sample_buffer_len] = stereo_sample(scale_sample_with_linear(
-            predictor_i32 as i16,
-            runtime_volume.to_i16() as i32,
-        ));
-        sample_buffer_len += 1;
-        if sample_buffer_len == SAMPLE_BUFFER_LEN {
-            pio_i2s_out.write(sample_buffer).await;
-            sample_buffer_len = 0;
-            if let Some(next_audio_command) = audio_player_static.command_signal.try_take() {
-                return ControlFlow::Break(next_audio_command);
-            }
+        mixer.apply_commands(audio_player_static);
+        if mixer.is_idle() {
+            audio_player_static.wait_for_command().await;
+            continue;
         }
 
-        let mut samples_decoded_in_block = 1usize;
-        let samples_per_block = adpcm_clip.samples_per_block as usize;
-
-        for adpcm_byte in &adpcm_block[4..] {
-            for adpcm_nibble in [adpcm_byte & 0x0F, adpcm_byte >> 4] {
-                if samples_decoded_in_block >= samples_per_block {
-                    break;
-                }
-
-                let decoded_sample_i16 =
-                    decode_adpcm_nibble(adpcm_nibble, &mut predictor_i32, &mut step_index_i32);
-                sample_buffer[sample_buffer_len] = stereo_sample(scale_sample_with_linear(
-                    decoded_sample_i16,
-                    runtime_volume.to_i16() as i32,
-                ));
-                sample_buffer_len += 1;
-                samples_decoded_in_block += 1;
-
-                if sample_buffer_len == SAMPLE_BUFFER_LEN {
-                    pio_i2s_out.write(sample_buffer).await;
-                    sample_buffer_len = 0;
-                    if let Some(next_audio_command) = audio_player_static.command_signal.try_take()
-                    {
-                        return ControlFlow::Break(next_audio_command);
-                    }
-                }
-            }
-        }
-
-        if let Some(next_audio_command) = audio_player_static.command_signal.try_take() {
-            return ControlFlow::Break(next_audio_command);
-        }
-    }
-
-    if sample_buffer_len != 0 {
-        sample_buffer[sample_buffer_len..].fill(stereo_sample(0));
-        pio_i2s_out.write(sample_buffer).await;
-        if let Some(next_audio_command) = audio_player_static.command_signal.try_take() {
-            return ControlFlow::Break(next_audio_command);
-        }
-    }
-
-    ControlFlow::Continue(())
-}
-
-#[cfg(target_os = "none")]
-async fn play_silence_duration_once<
-    PIO: Instance,
-    const MAX_CLIPS: usize,
-    const SAMPLE_RATE_HZ: u32,
->(
-    pio_i2s_out: &mut PioI2sOut<'static, PIO, 0>,
-    duration: Duration,
-    sample_buffer: &mut [u32; SAMPLE_BUFFER_LEN],
-    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ>,
-) -> ControlFlow<AudioCommand<MAX_CLIPS, SAMPLE_RATE_HZ>, ()> {
-    let silence_sample_count = __samples_for_duration(duration, SAMPLE_RATE_HZ);
-    let mut remaining_sample_count = silence_sample_count;
-    let zero_stereo_sample = stereo_sample(0);
-    sample_buffer.fill(zero_stereo_sample);
-
-    while remaining_sample_count > 0 {
-        let chunk_sample_count = remaining_sample_count.min(SAMPLE_BUFFER_LEN);
-        pio_i2s_out.write(sample_buffer).await;
-        remaining_sample_count -= chunk_sample_count;
-        if let Some(next_audio_command) = audio_player_static.command_signal.try_take() {
-            return ControlFlow::Break(next_audio_command);
-        }
+        mixer.fill(audio_player_static, &mut sample_buffer);
+        pio_i2s_out.write(&sample_buffer).await;
+        mixer.publish_progress(audio_player_static);
     }
-
-    ControlFlow::Continue(())
-}
-
-#[cfg(target_os = "none")]
-fn read_i16_le(bytes: &[u8], byte_offset: usize) -> crate::Result<i16> {
-    let Some(end_offset) = byte_offset.checked_add(2) else {
-        return Err(crate::Error::FormatError);
-    };
-    if end_offset > bytes.len() {
-        return Err(crate::Error::FormatError);
-    }
-    Ok(i16::from_le_bytes([
-        bytes[byte_offset],
-        bytes[byte_offset + 1],
-    ]))
-}
-
-#[cfg(target_os = "none")]
-fn decode_adpcm_nibble(adpcm_nibble: u8, predictor_i32: &mut i32, step_index_i32: &mut i32) -> i16 {
-    decode_adpcm_nibble_const(adpcm_nibble, predictor_i32, step_index_i32)
 }
 
 const fn decode_adpcm_nibble_const(
@@ -2166,11 +3290,10 @@ const fn encode_adpcm_nibble(
     adpcm_nibble
 }
 
+/// Packs one I²S frame with the left sample in the high half-word.
 #[inline]
-#[cfg(target_os = "none")]
-const fn stereo_sample(sample: i16) -> u32 {
-    let sample_bits = sample as u16 as u32;
-    (sample_bits << 16) | sample_bits
+const fn stereo_frame(left_sample: i16, right_sample: i16) -> u32 {
+    ((left_sample as u16 as u32) << 16) | right_sample as u16 as u32
 }
 
 // Must be `pub` so macro expansion works in downstream crates.
@@ -2310,23 +3433,122 @@ macro_rules! __audio_clip_parse {
             vis: $vis,
             name: $name,
             file: $file,
-            source_sample_rate_hz: $sample_rate_hz,
-            target_sample_rate_hz: $sample_rate_hz,
+            source_sample_rate_hz: $sample_rate_hz,
+            target_sample_rate_hz: $sample_rate_hz,
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            source_sample_rate_hz: $source_sample_rate_hz:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr,
+            channels: $channels:tt $(,)?
+        }
+    ) => {
+        $crate::__audio_clip_dispatch! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            source_sample_rate_hz: $source_sample_rate_hz,
+            target_sample_rate_hz: $target_sample_rate_hz,
+            channels: $channels,
+        }
+    };
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            source_sample_rate_hz: $source_sample_rate_hz:expr,
+            channels: $channels:tt $(,)?
+        }
+    ) => {
+        $crate::__audio_clip_dispatch! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            source_sample_rate_hz: $source_sample_rate_hz,
+            target_sample_rate_hz: $source_sample_rate_hz,
+            channels: $channels,
+        }
+    };
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            sample_rate_hz: $sample_rate_hz:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr,
+            channels: $channels:tt $(,)?
+        }
+    ) => {
+        $crate::__audio_clip_dispatch! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            source_sample_rate_hz: $sample_rate_hz,
+            target_sample_rate_hz: $target_sample_rate_hz,
+            channels: $channels,
+        }
+    };
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            sample_rate_hz: $sample_rate_hz:expr,
+            channels: $channels:tt $(,)?
+        }
+    ) => {
+        $crate::__audio_clip_dispatch! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            source_sample_rate_hz: $sample_rate_hz,
+            target_sample_rate_hz: $sample_rate_hz,
+            channels: $channels,
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __audio_clip_dispatch {
+    (
+        vis: $vis:vis,
+        name: $name:ident,
+        file: $file:expr,
+        source_sample_rate_hz: $source_sample_rate_hz:expr,
+        target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
+    ) => {
+        $crate::__audio_clip_impl! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            source_sample_rate_hz: $source_sample_rate_hz,
+            target_sample_rate_hz: $target_sample_rate_hz,
+        }
+    };
+    (
+        vis: $vis:vis,
+        name: $name:ident,
+        file: $file:expr,
+        source_sample_rate_hz: $source_sample_rate_hz:expr,
+        target_sample_rate_hz: $target_sample_rate_hz:expr,
+        channels: 1 $(,)?
+    ) => {
+        $crate::__audio_clip_impl! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            source_sample_rate_hz: $source_sample_rate_hz,
+            target_sample_rate_hz: $target_sample_rate_hz,
         }
     };
-}
-
-#[doc(hidden)]
-#[macro_export]
-macro_rules! __audio_clip_dispatch {
     (
         vis: $vis:vis,
         name: $name:ident,
         file: $file:expr,
         source_sample_rate_hz: $source_sample_rate_hz:expr,
-        target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
+        target_sample_rate_hz: $target_sample_rate_hz:expr,
+        channels: 2 $(,)?
     ) => {
-        $crate::__audio_clip_impl! {
+        $crate::__stereo_audio_clip_impl! {
             vis: $vis,
             name: $name,
             file: $file,
@@ -2334,6 +3556,16 @@ macro_rules! __audio_clip_dispatch {
             target_sample_rate_hz: $target_sample_rate_hz,
         }
     };
+    (
+        vis: $vis:vis,
+        name: $name:ident,
+        file: $file:expr,
+        source_sample_rate_hz: $source_sample_rate_hz:expr,
+        target_sample_rate_hz: $target_sample_rate_hz:expr,
+        channels: $channels:tt $(,)?
+    ) => {
+        compile_error!("pcm_clip! channels must be 1 (mono) or 2 (stereo)");
+    };
 }
 
 #[doc(hidden)]
@@ -2429,29 +3661,307 @@ macro_rules! __audio_clip_impl {
             }
         }
     };
-}
-
-#[doc = "Macro to \"compile in\" a compressed (ADPCM) WAV clip from an external file (includes syntax details)."]
-#[doc = include_str!("audio_player/adpcm_clip_docs.md")]
-#[doc = include_str!("audio_player/audio_prep_steps_1_2.md")]
-#[doc = include_str!("audio_player/adpcm_clip_step_3.md")]
-#[doc(inline)]
-pub use crate::adpcm_clip;
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stereo_audio_clip_impl {
+    (
+        vis: $vis:vis,
+        name: $name:ident,
+        file: $file:expr,
+        source_sample_rate_hz: $source_sample_rate_hz:expr,
+        target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
+    ) => {
+        $crate::audio_player::paste::paste! {
+            const [<$name:upper _SOURCE_SAMPLE_RATE_HZ>]: u32 = $source_sample_rate_hz;
+            const [<$name:upper _TARGET_SAMPLE_RATE_HZ>]: u32 = $target_sample_rate_hz;
+
+            #[allow(non_snake_case)]
+            #[doc = concat!(
+                "Stereo audio clip module generated by [`pcm_clip!`](macro@crate::audio_player::pcm_clip).\n\n",
+                "[`SAMPLE_RATE_HZ`](Self::SAMPLE_RATE_HZ), ",
+                "[`PCM_SAMPLE_COUNT`](Self::PCM_SAMPLE_COUNT), ",
+                "[`ADPCM_DATA_LEN`](Self::ADPCM_DATA_LEN), ",
+                "[`pcm_clip`](Self::pcm_clip), ",
+                "and [`adpcm_clip`](Self::adpcm_clip)."
+            )]
+            $vis mod $name {
+                const SOURCE_SAMPLE_RATE_HZ: u32 = super::[<$name:upper _SOURCE_SAMPLE_RATE_HZ>];
+                const TARGET_SAMPLE_RATE_HZ: u32 = super::[<$name:upper _TARGET_SAMPLE_RATE_HZ>];
+                #[doc = "Sample rate in hertz for this generated clip output."]
+                pub const SAMPLE_RATE_HZ: u32 = TARGET_SAMPLE_RATE_HZ;
+                const AUDIO_SAMPLE_BYTES_LEN: usize = include_bytes!($file).len();
+                const SOURCE_FRAME_COUNT: usize = AUDIO_SAMPLE_BYTES_LEN / 4;
+                #[doc = "Number of `[left, right]` frames for uncompressed (PCM) version of this clip."]
+                pub const PCM_SAMPLE_COUNT: usize = $crate::audio_player::__resampled_sample_count(
+                    SOURCE_FRAME_COUNT,
+                    SOURCE_SAMPLE_RATE_HZ,
+                    TARGET_SAMPLE_RATE_HZ,
+                );
+                #[doc = "Byte length for compressed (ADPCM) encoding this clip."]
+                pub const ADPCM_DATA_LEN: usize =
+                    $crate::audio_player::__stereo_adpcm_data_len_for_pcm_frames(PCM_SAMPLE_COUNT);
+
+                #[doc = "`const` function that returns the uncompressed (PCM) version of this clip."]
+                #[must_use]
+                pub const fn pcm_clip() -> $crate::audio_player::StereoPcmClipBuf<
+                    { SAMPLE_RATE_HZ },
+                    { PCM_SAMPLE_COUNT },
+                > {
+                    assert!(
+                        AUDIO_SAMPLE_BYTES_LEN % 4 == 0,
+                        "audio byte length must be a multiple of 4 for 2-channel s16le"
+                    );
+
+                    let audio_sample_s16le: &[u8; AUDIO_SAMPLE_BYTES_LEN] = include_bytes!($file);
+                    let mut frames = [[0_i16; 2]; SOURCE_FRAME_COUNT];
+                    let mut frame_index = 0_usize;
+                    while frame_index < SOURCE_FRAME_COUNT {
+                        let byte_index = frame_index * 4;
+                        frames[frame_index] = [
+                            i16::from_le_bytes([
+                                audio_sample_s16le[byte_index],
+                                audio_sample_s16le[byte_index + 1],
+                            ]),
+                            i16::from_le_bytes([
+                                audio_sample_s16le[byte_index + 2],
+                                audio_sample_s16le[byte_index + 3],
+                            ]),
+                        ];
+                        frame_index += 1;
+                    }
+                    $crate::audio_player::__resample_stereo_pcm_clip::<
+                        SOURCE_SAMPLE_RATE_HZ,
+                        SOURCE_FRAME_COUNT,
+                        TARGET_SAMPLE_RATE_HZ,
+                        PCM_SAMPLE_COUNT,
+                    >($crate::audio_player::__stereo_pcm_clip_from_frames::<
+                        SOURCE_SAMPLE_RATE_HZ,
+                        SOURCE_FRAME_COUNT,
+                    >(frames))
+                }
+
+                #[doc = "`const` function that returns the compressed (ADPCM) encoding for this clip."]
+                #[must_use]
+                pub const fn adpcm_clip() -> $crate::audio_player::StereoAdpcmClipBuf<
+                    { SAMPLE_RATE_HZ },
+                    { ADPCM_DATA_LEN },
+                > {
+                    pcm_clip().with_adpcm::<ADPCM_DATA_LEN>()
+                }
+
+            }
+        }
+    };
+}
+
+#[doc = "Macro to \"compile in\" a compressed (ADPCM) WAV clip from an external file (includes syntax details)."]
+#[doc = include_str!("audio_player/adpcm_clip_docs.md")]
+#[doc = include_str!("audio_player/audio_prep_steps_1_2.md")]
+#[doc = include_str!("audio_player/adpcm_clip_step_3.md")]
+#[doc(inline)]
+pub use crate::adpcm_clip;
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! adpcm_clip {
+    ($($tt:tt)*) => { $crate::__adpcm_clip_parse! { $($tt)* } };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __adpcm_clip_parse {
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
+        }
+    ) => {
+        $crate::audio_player::paste::paste! {
+            const [<$name:upper _TARGET_SAMPLE_RATE_HZ>]: u32 = $target_sample_rate_hz;
+
+            #[allow(non_snake_case)]
+            #[allow(missing_docs)]
+            $vis mod $name {
+                const PARSED_WAV: $crate::audio_player::ParsedAdpcmWavHeader =
+                    $crate::audio_player::__parse_adpcm_wav_header(include_bytes!($file));
+                const _: () = assert!(
+                    PARSED_WAV.channels == 1,
+                    "Expected mono ADPCM WAV (use `channels: 2` for stereo)"
+                );
+                const SOURCE_SAMPLE_RATE_HZ: u32 = PARSED_WAV.sample_rate_hz;
+                const TARGET_SAMPLE_RATE_HZ: u32 = super::[<$name:upper _TARGET_SAMPLE_RATE_HZ>];
+                pub const SAMPLE_RATE_HZ: u32 = TARGET_SAMPLE_RATE_HZ;
+
+                const SOURCE_SAMPLE_COUNT: usize = PARSED_WAV.sample_count;
+                #[doc = "Number of samples for uncompressed (PCM) version of this clip."]
+                pub const PCM_SAMPLE_COUNT: usize = $crate::audio_player::__resampled_sample_count(
+                    SOURCE_SAMPLE_COUNT,
+                    SOURCE_SAMPLE_RATE_HZ,
+                    TARGET_SAMPLE_RATE_HZ,
+                );
+                const BLOCK_ALIGN: usize = PARSED_WAV.block_align;
+                const SOURCE_DATA_LEN: usize = PARSED_WAV.data_chunk_len;
+                #[doc = "Byte length for compressed (ADPCM) encoding this clip."]
+                pub const ADPCM_DATA_LEN: usize = if TARGET_SAMPLE_RATE_HZ == SOURCE_SAMPLE_RATE_HZ {
+                    SOURCE_DATA_LEN
+                } else {
+                    $crate::audio_player::__adpcm_data_len_for_pcm_samples_with_block_align(
+                        PCM_SAMPLE_COUNT,
+                        BLOCK_ALIGN,
+                    )
+                };
+                type SourceAdpcmClip = $crate::audio_player::AdpcmClipBuf<SOURCE_SAMPLE_RATE_HZ, SOURCE_DATA_LEN>;
+
+                #[must_use]
+                const fn source_adpcm_clip() -> SourceAdpcmClip {
+                    let wav_bytes = include_bytes!($file);
+                    let parsed_wav = $crate::audio_player::__parse_adpcm_wav_header(wav_bytes);
+                    assert!(parsed_wav.block_align <= u16::MAX as usize, "block_align too large");
+                    assert!(
+                        parsed_wav.samples_per_block <= u16::MAX as usize,
+                        "samples_per_block too large"
+                    );
+
+                    let mut adpcm_data = [0_u8; SOURCE_DATA_LEN];
+                    let mut data_index = 0usize;
+                    while data_index < SOURCE_DATA_LEN {
+                        adpcm_data[data_index] = wav_bytes[parsed_wav.data_chunk_start + data_index];
+                        data_index += 1;
+                    }
+
+                    $crate::audio_player::__adpcm_clip_from_parts(
+                        parsed_wav.block_align as u16,
+                        parsed_wav.samples_per_block as u16,
+                        adpcm_data,
+                    )
+                }
+
+                #[doc = "`const` function that returns the uncompressed (PCM) version of this clip."]
+                #[must_use]
+                pub const fn pcm_clip() -> $crate::audio_player::PcmClipBuf<SAMPLE_RATE_HZ, PCM_SAMPLE_COUNT> {
+                    $crate::audio_player::__resample_pcm_clip::<
+                        SOURCE_SAMPLE_RATE_HZ,
+                        SOURCE_SAMPLE_COUNT,
+                        TARGET_SAMPLE_RATE_HZ,
+                        PCM_SAMPLE_COUNT,
+                    >(source_adpcm_clip().with_pcm::<SOURCE_SAMPLE_COUNT>())
+                }
+
+                #[doc = "`const` function that returns the compressed (ADPCM) encoding for this clip."]
+                #[must_use]
+                pub const fn adpcm_clip() -> $crate::audio_player::AdpcmClipBuf<SAMPLE_RATE_HZ, ADPCM_DATA_LEN> {
+                    if TARGET_SAMPLE_RATE_HZ == SOURCE_SAMPLE_RATE_HZ {
+                        let wav_bytes = include_bytes!($file);
+                        let parsed_wav = $crate::audio_player::__parse_adpcm_wav_header(wav_bytes);
+                        assert!(parsed_wav.block_align <= u16::MAX as usize, "block_align too large");
+                        assert!(
+                            parsed_wav.samples_per_block <= u16::MAX as usize,
+                            "samples_per_block too large"
+                        );
+                        let mut adpcm_data = [0_u8; ADPCM_DATA_LEN];
+                        let mut data_index = 0usize;
+                        while data_index < ADPCM_DATA_LEN {
+                            adpcm_data[data_index] =
+                                wav_bytes[parsed_wav.data_chunk_start + data_index];
+                            data_index += 1;
+                        }
+                        $crate::audio_player::__adpcm_clip_from_parts(
+                            parsed_wav.block_align as u16,
+                            parsed_wav.samples_per_block as u16,
+                            adpcm_data,
+                        )
+                    } else {
+                        $crate::audio_player::__pcm_with_adpcm_block_align::<
+                            SAMPLE_RATE_HZ,
+                            PCM_SAMPLE_COUNT,
+                            ADPCM_DATA_LEN,
+                        >(&pcm_clip(), BLOCK_ALIGN)
+                    }
+                }
+
+            }
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr,
+            channels: 1 $(,)?
+        }
+    ) => {
+        $crate::__adpcm_clip_parse! {
+            $vis $name {
+                file: $file,
+                target_sample_rate_hz: $target_sample_rate_hz,
+            }
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr,
+            channels: 2 $(,)?
+        }
+    ) => {
+        $crate::__stereo_adpcm_clip_impl! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            target_sample_rate_hz: $target_sample_rate_hz,
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr,
+            channels: $channels:tt $(,)?
+        }
+    ) => {
+        compile_error!("adpcm_clip! channels must be 1 (mono) or 2 (stereo)");
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr $(,)?
+        }
+    ) => {
+        $crate::__adpcm_clip_parse! {
+            $vis $name {
+                file: $file,
+                target_sample_rate_hz: $crate::audio_player::__parse_adpcm_wav_header(include_bytes!($file)).sample_rate_hz,
+            }
+        }
+    };
 
-#[doc(hidden)]
-#[macro_export]
-macro_rules! adpcm_clip {
-    ($($tt:tt)*) => { $crate::__adpcm_clip_parse! { $($tt)* } };
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            channels: $channels:tt $(,)?
+        }
+    ) => {
+        $crate::__adpcm_clip_parse! {
+            $vis $name {
+                file: $file,
+                target_sample_rate_hz: $crate::audio_player::__parse_adpcm_wav_header(include_bytes!($file)).sample_rate_hz,
+                channels: $channels,
+            }
+        }
+    };
 }
 
 #[doc(hidden)]
 #[macro_export]
-macro_rules! __adpcm_clip_parse {
+macro_rules! __stereo_adpcm_clip_impl {
     (
-        $vis:vis $name:ident {
-            file: $file:expr,
-            target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
-        }
+        vis: $vis:vis,
+        name: $name:ident,
+        file: $file:expr,
+        target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
     ) => {
         $crate::audio_player::paste::paste! {
             const [<$name:upper _TARGET_SAMPLE_RATE_HZ>]: u32 = $target_sample_rate_hz;
@@ -2461,14 +3971,18 @@ macro_rules! __adpcm_clip_parse {
             $vis mod $name {
                 const PARSED_WAV: $crate::audio_player::ParsedAdpcmWavHeader =
                     $crate::audio_player::__parse_adpcm_wav_header(include_bytes!($file));
+                const _: () = assert!(
+                    PARSED_WAV.channels == 2,
+                    "Expected stereo ADPCM WAV (omit `channels` or use `channels: 1` for mono)"
+                );
                 const SOURCE_SAMPLE_RATE_HZ: u32 = PARSED_WAV.sample_rate_hz;
                 const TARGET_SAMPLE_RATE_HZ: u32 = super::[<$name:upper _TARGET_SAMPLE_RATE_HZ>];
                 pub const SAMPLE_RATE_HZ: u32 = TARGET_SAMPLE_RATE_HZ;
 
-                const SOURCE_SAMPLE_COUNT: usize = PARSED_WAV.sample_count;
-                #[doc = "Number of samples for uncompressed (PCM) version of this clip."]
+                const SOURCE_FRAME_COUNT: usize = PARSED_WAV.sample_count;
+                #[doc = "Number of `[left, right]` frames for uncompressed (PCM) version of this clip."]
                 pub const PCM_SAMPLE_COUNT: usize = $crate::audio_player::__resampled_sample_count(
-                    SOURCE_SAMPLE_COUNT,
+                    SOURCE_FRAME_COUNT,
                     SOURCE_SAMPLE_RATE_HZ,
                     TARGET_SAMPLE_RATE_HZ,
                 );
@@ -2478,73 +3992,63 @@ macro_rules! __adpcm_clip_parse {
                 pub const ADPCM_DATA_LEN: usize = if TARGET_SAMPLE_RATE_HZ == SOURCE_SAMPLE_RATE_HZ {
                     SOURCE_DATA_LEN
                 } else {
-                    $crate::audio_player::__adpcm_data_len_for_pcm_samples_with_block_align(
+                    $crate::audio_player::__stereo_adpcm_data_len_for_pcm_frames_with_block_align(
                         PCM_SAMPLE_COUNT,
                         BLOCK_ALIGN,
                     )
                 };
-                type SourceAdpcmClip = $crate::audio_player::AdpcmClipBuf<SOURCE_SAMPLE_RATE_HZ, SOURCE_DATA_LEN>;
+                type SourceAdpcmClip =
+                    $crate::audio_player::StereoAdpcmClipBuf<SOURCE_SAMPLE_RATE_HZ, SOURCE_DATA_LEN>;
 
                 #[must_use]
                 const fn source_adpcm_clip() -> SourceAdpcmClip {
                     let wav_bytes = include_bytes!($file);
-                    let parsed_wav = $crate::audio_player::__parse_adpcm_wav_header(wav_bytes);
-                    assert!(parsed_wav.block_align <= u16::MAX as usize, "block_align too large");
-                    assert!(
-                        parsed_wav.samples_per_block <= u16::MAX as usize,
-                        "samples_per_block too large"
-                    );
+                    assert!(PARSED_WAV.block_align <= u16::MAX as usize, "block_align too large");
 
                     let mut adpcm_data = [0_u8; SOURCE_DATA_LEN];
                     let mut data_index = 0usize;
                     while data_index < SOURCE_DATA_LEN {
-                        adpcm_data[data_index] = wav_bytes[parsed_wav.data_chunk_start + data_index];
+                        adpcm_data[data_index] = wav_bytes[PARSED_WAV.data_chunk_start + data_index];
                         data_index += 1;
                     }
 
-                    $crate::audio_player::__adpcm_clip_from_parts(
-                        parsed_wav.block_align as u16,
-                        parsed_wav.samples_per_block as u16,
+                    $crate::audio_player::__stereo_adpcm_clip_from_parts(
+                        PARSED_WAV.block_align as u16,
+                        PARSED_WAV.samples_per_block as u16,
                         adpcm_data,
                     )
                 }
 
                 #[doc = "`const` function that returns the uncompressed (PCM) version of this clip."]
                 #[must_use]
-                pub const fn pcm_clip() -> $crate::audio_player::PcmClipBuf<SAMPLE_RATE_HZ, PCM_SAMPLE_COUNT> {
-                    $crate::audio_player::__resample_pcm_clip::<
+                pub const fn pcm_clip() -> $crate::audio_player::StereoPcmClipBuf<SAMPLE_RATE_HZ, PCM_SAMPLE_COUNT> {
+                    $crate::audio_player::__resample_stereo_pcm_clip::<
                         SOURCE_SAMPLE_RATE_HZ,
-                        SOURCE_SAMPLE_COUNT,
+                        SOURCE_FRAME_COUNT,
                         TARGET_SAMPLE_RATE_HZ,
                         PCM_SAMPLE_COUNT,
-                    >(source_adpcm_clip().with_pcm::<SOURCE_SAMPLE_COUNT>())
+                    >(source_adpcm_clip().with_pcm::<SOURCE_FRAME_COUNT>())
                 }
 
                 #[doc = "`const` function that returns the compressed (ADPCM) encoding for this clip."]
                 #[must_use]
-                pub const fn adpcm_clip() -> $crate::audio_player::AdpcmClipBuf<SAMPLE_RATE_HZ, ADPCM_DATA_LEN> {
+                pub const fn adpcm_clip() -> $crate::audio_player::StereoAdpcmClipBuf<SAMPLE_RATE_HZ, ADPCM_DATA_LEN> {
                     if TARGET_SAMPLE_RATE_HZ == SOURCE_SAMPLE_RATE_HZ {
                         let wav_bytes = include_bytes!($file);
-                        let parsed_wav = $crate::audio_player::__parse_adpcm_wav_header(wav_bytes);
-                        assert!(parsed_wav.block_align <= u16::MAX as usize, "block_align too large");
-                        assert!(
-                            parsed_wav.samples_per_block <= u16::MAX as usize,
-                            "samples_per_block too large"
-                        );
                         let mut adpcm_data = [0_u8; ADPCM_DATA_LEN];
                         let mut data_index = 0usize;
                         while data_index < ADPCM_DATA_LEN {
                             adpcm_data[data_index] =
-                                wav_bytes[parsed_wav.data_chunk_start + data_index];
+                                wav_bytes[PARSED_WAV.data_chunk_start + data_index];
                             data_index += 1;
                         }
-                        $crate::audio_player::__adpcm_clip_from_parts(
-                            parsed_wav.block_align as u16,
-                            parsed_wav.samples_per_block as u16,
+                        $crate::audio_player::__stereo_adpcm_clip_from_parts(
+                            PARSED_WAV.block_align as u16,
+                            PARSED_WAV.samples_per_block as u16,
                             adpcm_data,
                         )
                     } else {
-                        $crate::audio_player::__pcm_with_adpcm_block_align::<
+                        $crate::audio_player::__stereo_pcm_with_adpcm_block_align::<
                             SAMPLE_RATE_HZ,
                             PCM_SAMPLE_COUNT,
                             ADPCM_DATA_LEN,
@@ -2555,21 +4059,29 @@ macro_rules! __adpcm_clip_parse {
             }
         }
     };
-
-    (
-        $vis:vis $name:ident {
-            file: $file:expr $(,)?
-        }
-    ) => {
-        $crate::__adpcm_clip_parse! {
-            $vis $name {
-                file: $file,
-                target_sample_rate_hz: $crate::audio_player::__parse_adpcm_wav_header(include_bytes!($file)).sample_rate_hz,
-            }
-        }
-    };
 }
 
+#[doc = "Macro to \"compile in\" a compressed (G.711 μ-law) WAV clip from an external file (includes syntax details)."]
+#[doc = include_str!("audio_player/mulaw_clip_docs.md")]
+#[doc = include_str!("audio_player/audio_prep_steps_1_2.md")]
+#[doc = include_str!("audio_player/mulaw_clip_step_3.md")]
+#[doc(inline)]
+pub use crate::mulaw_clip;
+
+#[doc = "Macro to \"compile in\" a compressed (G.711 A-law) WAV clip from an external file (includes syntax details)."]
+#[doc = include_str!("audio_player/alaw_clip_docs.md")]
+#[doc = include_str!("audio_player/audio_prep_steps_1_2.md")]
+#[doc = include_str!("audio_player/alaw_clip_step_3.md")]
+#[doc(inline)]
+pub use crate::alaw_clip;
+
+#[doc = "Macro to \"compile in\" a compressed (QOA) clip from an external `.qoa` file (includes syntax details)."]
+#[doc = include_str!("audio_player/qoa_clip_docs.md")]
+#[doc = include_str!("audio_player/audio_prep_steps_1_2.md")]
+#[doc = include_str!("audio_player/qoa_clip_step_3.md")]
+#[doc(inline)]
+pub use crate::qoa_clip;
+
 /// Macro to create an audio clip of a musical tone.
 ///
 /// Examples:
@@ -2592,6 +4104,36 @@ macro_rules! tone {
     };
 }
 
+/// Macro to create a list of [`Note`](crate::audio_player::Note)s for a
+/// [`Melody`](crate::audio_player::Melody) from text notation.
+///
+/// Examples:
+/// - `notes!("E4:8 D4:8 C4:4")`
+/// - `notes!("C#5:8. Bb4:16 R:4 G4:2")`
+///
+/// **Syntax:** whitespace-separated notes, each `<name><octave>[:<length>[.]]`.
+///
+/// - `<name>` — `C D E F G A B`, optionally followed by `#` (sharp) or `b` (flat),
+///   or `R` for a rest (which takes no octave).
+/// - `<octave>` — `0` through `9`; `A4` is 440 Hz.
+/// - `<length>` — `1` (whole), `2`, `4` (quarter, the default), `8`, `16`, `32`, or `64`.
+///   A trailing `.` makes a dotted note, 1.5 times as long.
+///
+/// The result is a `&'static [Note; N]`. Invalid notation is a compile-time error.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! notes {
+    ($notation:expr) => {{
+        const NOTATION: &str = $notation;
+        const NOTES: [$crate::audio_player::Note; $crate::audio_player::__note_count(NOTATION)] =
+            $crate::audio_player::__parse_notes(NOTATION);
+        &NOTES
+    }};
+}
+
 /// Macro to generate an audio player struct type (includes syntax details).
 ///
 /// See [`AudioPlayerGenerated`](crate::audio_player::audio_player_generated::AudioPlayerGenerated)
@@ -2605,15 +4147,18 @@ macro_rules! tone {
 /// ```text
 /// audio_player! {
 ///     [<visibility>] <Name> {
-///         data_pin: <pin_ident>,
+///         data_pin: <pin_ident>,            // I²S output, or...
 ///         bit_clock_pin: <pin_ident>,
 ///         word_select_pin: <pin_ident>,
+///         pwm_pin: <pin_ident>,             // ...PWM output on one pin
 ///         sample_rate_hz: <sample_rate_expr>,
 ///         pio: <pio_ident>,                 // optional
 ///         dma: <dma_ident>,                 // optional
 ///         max_clips: <usize_expr>,          // optional
+///         voices: <usize_expr>,             // optional
 ///         max_volume: <Volume_expr>,        // optional
 ///         initial_volume: <Volume_expr>,    // optional
+///         max_echo_delay: <Duration_expr>,  // optional
 ///     }
 /// }
 /// ```
@@ -2626,9 +4171,13 @@ macro_rules! tone {
 ///
 /// **Required fields:**
 ///
-/// - `data_pin` - GPIO pin carrying I²S data (`DIN`)
-/// - `bit_clock_pin` - GPIO pin carrying I²S bit clock (`BCLK`)
-/// - `word_select_pin` - GPIO pin carrying I²S word-select / LR clock (`LRC` / `LRCLK`)
+/// - Either the three I²S pins, for an external I²S DAC/amp:
+///   - `data_pin` - GPIO pin carrying I²S data (`DIN`)
+///   - `bit_clock_pin` - GPIO pin carrying I²S bit clock (`BCLK`)
+///   - `word_select_pin` - GPIO pin carrying I²S word-select / LR clock (`LRC` / `LRCLK`)
+/// - Or `pwm_pin` - one GPIO pin driving a piezo or transistor-driven speaker with PWM.
+///   The generated `new(...)` then takes this one pin in place of the three I²S pins,
+///   and stereo audio is mixed to mono.
 /// - `sample_rate_hz` - Playback sample rate in hertz (for example:
 ///   [`VOICE_22050_HZ`](crate::audio_player::VOICE_22050_HZ))
 ///
@@ -2637,17 +4186,21 @@ macro_rules! tone {
 /// - `pio` - PIO resource (default: `PIO0`)
 /// - `dma` - DMA channel (default: `DMA_CH0`)
 /// - `max_clips` - Maximum clips per queued play request (default: `16`)
+/// - `voices` - Number of mixer voices that can play at the same time, each with its
+///   own clip queue and gain (default: `1`)
 /// - `max_volume` - Runtime volume ceiling (default: [`Volume::MAX`])
 /// - `initial_volume` - Initial runtime volume relative to `max_volume`
 ///   (default: [`Volume::MAX`])
+/// - `max_echo_delay` - Longest [`Echo`] delay, a [`core::time::Duration`]; reserves
+///   4 bytes of RAM per sample of delay (default: `Duration::ZERO`, no echo)
 ///
 /// **Generated items:**
 ///
 /// - `<Name>` - generated player struct type
 /// - `<Name>Playable` - trait-object clip source alias at this player's sample rate
 /// - associated constants and methods on `<Name>` (for example:
-///   `SAMPLE_RATE_HZ`, `new(...)`, `play(...)`,
-///   `wait_until_stopped(...)`, and runtime volume controls)
+///   `SAMPLE_RATE_HZ`, `new(...)`, `play(...)`, `voice(...)`,
+///   `wait_until_stopped(...)`, runtime volume controls, and `set_dsp(...)`)
 ///
 /// The generated type contains static resources and spawns its background device
 /// task from `new(...)`.
@@ -2680,8 +4233,11 @@ macro_rules! __audio_player_impl {
             pio: PIO0,
             dma: DMA_CH0,
             max_clips: 16,
+            voices: 1,
             max_volume: $crate::audio_player::Volume::MAX,
             initial_volume: $crate::audio_player::Volume::MAX,
+            max_echo_delay: ::core::time::Duration::ZERO,
+            pwm_pin: _UNSET_,
             fields: [ $($fields)* ]
         }
     };
@@ -2702,8 +4258,11 @@ macro_rules! __audio_player_impl {
             pio: PIO0,
             dma: DMA_CH0,
             max_clips: 16,
+            voices: 1,
             max_volume: $crate::audio_player::Volume::MAX,
             initial_volume: $crate::audio_player::Volume::MAX,
+            max_echo_delay: ::core::time::Duration::ZERO,
+            pwm_pin: _UNSET_,
             fields: [ $($fields)* ]
         }
     };
@@ -2718,8 +4277,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ data_pin: $din_pin_value:ident $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2733,8 +4295,11 @@ macro_rules! __audio_player_impl {
             pio: $pio,
             dma: $dma,
             max_clips: $max_clips,
+            voices: $voices,
             max_volume: $max_volume,
             initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2749,8 +4314,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ sample_rate_hz: $sample_rate_hz_value:expr $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2764,8 +4332,11 @@ macro_rules! __audio_player_impl {
             pio: $pio,
             dma: $dma,
             max_clips: $max_clips,
+            voices: $voices,
             max_volume: $max_volume,
             initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2780,8 +4351,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ bit_clock_pin: $bclk_pin_value:ident $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2795,8 +4369,11 @@ macro_rules! __audio_player_impl {
             pio: $pio,
             dma: $dma,
             max_clips: $max_clips,
+            voices: $voices,
             max_volume: $max_volume,
             initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2811,8 +4388,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ word_select_pin: $lrc_pin_value:ident $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2826,8 +4406,11 @@ macro_rules! __audio_player_impl {
             pio: $pio,
             dma: $dma,
             max_clips: $max_clips,
+            voices: $voices,
             max_volume: $max_volume,
             initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2842,8 +4425,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ pio: $pio_value:ident $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2857,8 +4443,11 @@ macro_rules! __audio_player_impl {
             pio: $pio_value,
             dma: $dma,
             max_clips: $max_clips,
+            voices: $voices,
             max_volume: $max_volume,
             initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2873,8 +4462,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ dma: $dma_value:ident $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2888,8 +4480,11 @@ macro_rules! __audio_player_impl {
             pio: $pio,
             dma: $dma_value,
             max_clips: $max_clips,
+            voices: $voices,
             max_volume: $max_volume,
             initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2904,8 +4499,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ max_clips: $max_clips_value:expr $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2919,8 +4517,48 @@ macro_rules! __audio_player_impl {
             pio: $pio,
             dma: $dma,
             max_clips: $max_clips_value,
+            voices: $voices,
+            max_volume: $max_volume,
+            initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        max_clips: $max_clips:expr,
+        voices: $voices:expr,
+        max_volume: $max_volume:expr,
+        initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
+        fields: [ voices: $voices_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_player_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            pio: $pio,
+            dma: $dma,
+            max_clips: $max_clips,
+            voices: $voices_value,
             max_volume: $max_volume,
             initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2935,8 +4573,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ max_volume: $max_volume_value:expr $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2950,8 +4591,11 @@ macro_rules! __audio_player_impl {
             pio: $pio,
             dma: $dma,
             max_clips: $max_clips,
+            voices: $voices,
             max_volume: $max_volume_value,
             initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2966,8 +4610,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
         fields: [ initial_volume: $initial_volume_value:expr $(, $($rest:tt)* )? ]
     ) => {
         $crate::__audio_player_impl! {
@@ -2981,8 +4628,85 @@ macro_rules! __audio_player_impl {
             pio: $pio,
             dma: $dma,
             max_clips: $max_clips,
+            voices: $voices,
             max_volume: $max_volume,
             initial_volume: $initial_volume_value,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        max_clips: $max_clips:expr,
+        voices: $voices:expr,
+        max_volume: $max_volume:expr,
+        initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
+        fields: [ max_echo_delay: $max_echo_delay_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_player_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            pio: $pio,
+            dma: $dma,
+            max_clips: $max_clips,
+            voices: $voices,
+            max_volume: $max_volume,
+            initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay_value,
+            pwm_pin: $pwm_pin,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        max_clips: $max_clips:expr,
+        voices: $voices:expr,
+        max_volume: $max_volume:expr,
+        initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:tt,
+        fields: [ pwm_pin: $pwm_pin_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_player_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            pio: $pio,
+            dma: $dma,
+            max_clips: $max_clips,
+            voices: $voices,
+            max_volume: $max_volume,
+            initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            pwm_pin: $pwm_pin_value,
             fields: [ $($($rest)*)? ]
         }
     };
@@ -2997,11 +4721,14 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: _UNSET_,
         fields: [ ]
     ) => {
-        compile_error!("audio_player! requires data_pin");
+        compile_error!("audio_player! requires data_pin (or pwm_pin for PWM output)");
     };
 
     (@__fill_defaults
@@ -3014,8 +4741,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: _UNSET_,
         fields: [ ]
     ) => {
         compile_error!("audio_player! requires bit_clock_pin");
@@ -3031,8 +4761,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: _UNSET_,
         fields: [ ]
     ) => {
         compile_error!("audio_player! requires word_select_pin");
@@ -3048,8 +4781,11 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: _UNSET_,
         fields: [ ]
     ) => {
         compile_error!("audio_player! requires sample_rate_hz");
@@ -3065,14 +4801,110 @@ macro_rules! __audio_player_impl {
         pio: $pio:ident,
         dma: $dma:ident,
         max_clips: $max_clips:expr,
+        voices: $voices:expr,
+        max_volume: $max_volume:expr,
+        initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: _UNSET_,
+        fields: [ ]
+    ) => {
+        $crate::__audio_player_impl! {
+            @__generate
+            vis: $vis,
+            name: $name,
+            sample_rate_hz: $sample_rate_hz,
+            pio: $pio,
+            dma: $dma,
+            max_clips: $max_clips,
+            voices: $voices,
+            max_volume: $max_volume,
+            initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            device_loop: device_loop,
+            pins: [
+                data_pin: $data_pin,
+                bit_clock_pin: $bit_clock_pin,
+                word_select_pin: $word_select_pin,
+            ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: _UNSET_,
+        bit_clock_pin: _UNSET_,
+        word_select_pin: _UNSET_,
+        sample_rate_hz: $sample_rate_hz:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        max_clips: $max_clips:expr,
+        voices: $voices:expr,
+        max_volume: $max_volume:expr,
+        initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:ident,
+        fields: [ ]
+    ) => {
+        $crate::__audio_player_impl! {
+            @__generate
+            vis: $vis,
+            name: $name,
+            sample_rate_hz: $sample_rate_hz,
+            pio: $pio,
+            dma: $dma,
+            max_clips: $max_clips,
+            voices: $voices,
+            max_volume: $max_volume,
+            initial_volume: $initial_volume,
+            max_echo_delay: $max_echo_delay,
+            device_loop: pwm_device_loop,
+            pins: [
+                pwm_pin: $pwm_pin,
+            ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        max_clips: $max_clips:expr,
+        voices: $voices:expr,
         max_volume: $max_volume:expr,
         initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        pwm_pin: $pwm_pin:ident,
         fields: [ ]
+    ) => {
+        compile_error!(
+            "audio_player! takes either pwm_pin or the I²S pins (data_pin, bit_clock_pin, word_select_pin), not both"
+        );
+    };
+
+    (@__generate
+        vis: $vis:vis,
+        name: $name:ident,
+        sample_rate_hz: $sample_rate_hz:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        max_clips: $max_clips:expr,
+        voices: $voices:expr,
+        max_volume: $max_volume:expr,
+        initial_volume: $initial_volume:expr,
+        max_echo_delay: $max_echo_delay:expr,
+        device_loop: $device_loop:ident,
+        pins: [ $($pin_name:ident: $pin:ident,)+ ]
     ) => {
         $crate::audio_player::paste::paste! {
             static [<$name:upper _AUDIO_PLAYER_STATIC>]:
-                $crate::audio_player::AudioPlayerStatic<$max_clips, { $sample_rate_hz }> =
-                $crate::audio_player::AudioPlayer::<$max_clips, { $sample_rate_hz }>::new_static_with_max_volume_and_initial_volume(
+                $crate::audio_player::AudioPlayerStatic<$max_clips, { $sample_rate_hz }, { $voices }> =
+                $crate::audio_player::AudioPlayer::<$max_clips, { $sample_rate_hz }, { $voices }>::new_static_with_max_volume_and_initial_volume(
                     $max_volume,
                     $initial_volume,
                 );
@@ -3084,7 +4916,7 @@ macro_rules! __audio_player_impl {
                 "See the [audio_player module documentation](mod@crate::audio_player) for usage and examples."
             )]
             $vis struct $name {
-                player: $crate::audio_player::AudioPlayer<$max_clips, { $sample_rate_hz }>,
+                player: $crate::audio_player::AudioPlayer<$max_clips, { $sample_rate_hz }, { $voices }>,
             }
 
             #[doc = concat!(
@@ -3109,15 +4941,17 @@ macro_rules! __audio_player_impl {
                 pub const INITIAL_VOLUME: $crate::audio_player::Volume = $initial_volume;
                 /// Runtime volume ceiling for this generated player type.
                 pub const MAX_VOLUME: $crate::audio_player::Volume = $max_volume;
+                /// Number of mixer voices that can play at the same time.
+                pub const VOICES: usize = $voices;
+                /// Longest [`Echo`](crate::audio_player::Echo) delay this generated player type supports.
+                pub const MAX_ECHO_DELAY: ::core::time::Duration = $max_echo_delay;
 
                 /// Creates and spawns the generated audio player instance.
                 ///
                 /// See the [audio_player module documentation](mod@crate::audio_player)
                 /// for example usage.
                 pub fn new(
-                    data_pin: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$data_pin>>,
-                    bit_clock_pin: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$bit_clock_pin>>,
-                    word_select_pin: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$word_select_pin>>,
+                    $($pin_name: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$pin>>,)+
                     pio: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$pio>>,
                     dma: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$dma>>,
                     spawner: ::embassy_executor::Spawner,
@@ -3126,9 +4960,7 @@ macro_rules! __audio_player_impl {
                         &[<$name:upper _AUDIO_PLAYER_STATIC>],
                         pio.into(),
                         dma.into(),
-                        data_pin.into(),
-                        bit_clock_pin.into(),
-                        word_select_pin.into(),
+                        $($pin_name.into(),)+
                     );
                     spawner.spawn(token)?;
                     let player =
@@ -3136,7 +4968,7 @@ macro_rules! __audio_player_impl {
                     Ok([<$name:upper _AUDIO_PLAYER_CELL>].init(Self { player }))
                 }
 
-                /// Waits until current playback has fully stopped.
+                /// Waits until playback on voice `0` has fully stopped.
                 ///
                 /// See the [audio_player module documentation](mod@crate::audio_player)
                 /// for example usage.
@@ -3146,7 +4978,7 @@ macro_rules! __audio_player_impl {
             }
 
             impl ::core::ops::Deref for $name {
-                type Target = $crate::audio_player::AudioPlayer<$max_clips, { $sample_rate_hz }>;
+                type Target = $crate::audio_player::AudioPlayer<$max_clips, { $sample_rate_hz }, { $voices }>;
 
                 fn deref(&self) -> &Self::Target {
                     &self.player
@@ -3155,22 +4987,25 @@ macro_rules! __audio_player_impl {
 
             #[::embassy_executor::task]
             async fn [<$name:snake _audio_player_task>](
-                audio_player_static: &'static $crate::audio_player::AudioPlayerStatic<$max_clips, { $sample_rate_hz }>,
+                audio_player_static: &'static $crate::audio_player::AudioPlayerStatic<$max_clips, { $sample_rate_hz }, { $voices }>,
                 pio: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$pio>,
                 dma: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$dma>,
-                data_pin: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$data_pin>,
-                bit_clock_pin: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$bit_clock_pin>,
-                word_select_pin: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$word_select_pin>,
+                $($pin_name: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$pin>,)+
             ) -> ! {
-                $crate::audio_player::device_loop::<
+                $crate::audio_player::$device_loop::<
                     $max_clips,
                     { $sample_rate_hz },
+                    { $voices },
+                    {
+                        $crate::audio_player::__samples_for_duration(
+                            $max_echo_delay,
+                            $sample_rate_hz,
+                        )
+                    },
                     ::embassy_rp::peripherals::$pio,
                     ::embassy_rp::peripherals::$dma,
-                    ::embassy_rp::peripherals::$data_pin,
-                    ::embassy_rp::peripherals::$bit_clock_pin,
-                    ::embassy_rp::peripherals::$word_select_pin,
-                >(audio_player_static, pio, dma, data_pin, bit_clock_pin, word_select_pin).await
+                    $(::embassy_rp::peripherals::$pin,)+
+                >(audio_player_static, pio, dma, $($pin_name,)+).await
             }
         }
     };
@@ -3179,4 +5014,6 @@ macro_rules! __audio_player_impl {
 #[doc(inline)]
 pub use audio_player;
 #[doc(inline)]
+pub use notes;
+#[doc(inline)]
 pub use tone;
@@ -0,0 +1,483 @@
+//! Runtime effects applied to the mixed output: equalizer, echo, and limiter.
+//!
+//! See [`Dsp`].
+
+use core::time::Duration;
+
+use super::{__samples_for_duration, Gain, Volume, clamp_i64_to_i16, sine_sample_from_phase};
+
+/// Maximum number of [`EqBand`]s in one [`Dsp`] chain.
+const MAX_EQ_BANDS: usize = 4;
+
+/// Fraction bits of the biquad coefficients used while mixing.
+const COEFFICIENT_SHIFT: u32 = 28;
+
+/// Fraction bits used while designing biquad coefficients.
+const DESIGN_SHIFT: u32 = 30;
+
+/// Unity on the [`Gain`] and [`Volume`] linear scale.
+const UNITY_LINEAR: i32 = i16::MAX as i32;
+
+/// Shape of one [`EqBand`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum EqBandKind {
+    LowShelf,
+    HighShelf,
+    Peaking { q_percent: u16 },
+}
+
+/// One equalizer band of a [`Dsp`] chain.
+///
+/// Shelves boost or cut everything below ([`EqBand::low_shelf`]) or above
+/// ([`EqBand::high_shelf`]) a corner frequency; [`EqBand::peaking`] boosts or cuts
+/// around a center frequency. Gains clamp to the same range as [`Gain::db`], and
+/// a `0` dB band passes audio through unchanged.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EqBand {
+    kind: EqBandKind,
+    frequency_hz: u32,
+    gain_db: i8,
+}
+
+impl EqBand {
+    /// Boosts (positive `gain_db`) or cuts (negative) frequencies below `frequency_hz`.
+    #[must_use]
+    pub const fn low_shelf(frequency_hz: u32, gain_db: i8) -> Self {
+        Self {
+            kind: EqBandKind::LowShelf,
+            frequency_hz,
+            gain_db,
+        }
+    }
+
+    /// Boosts (positive `gain_db`) or cuts (negative) frequencies above `frequency_hz`.
+    #[must_use]
+    pub const fn high_shelf(frequency_hz: u32, gain_db: i8) -> Self {
+        Self {
+            kind: EqBandKind::HighShelf,
+            frequency_hz,
+            gain_db,
+        }
+    }
+
+    /// Boosts (positive `gain_db`) or cuts (negative) frequencies around `frequency_hz`.
+    ///
+    /// `q_percent` sets the width: `71` (Q = 0.71) is broad, and higher values are
+    /// narrower. Values below `10` clamp to `10`.
+    #[must_use]
+    pub const fn peaking(frequency_hz: u32, gain_db: i8, q_percent: u16) -> Self {
+        let q_percent = if q_percent < 10 { 10 } else { q_percent };
+        Self {
+            kind: EqBandKind::Peaking { q_percent },
+            frequency_hz,
+            gain_db,
+        }
+    }
+}
+
+/// Peak limiter at the end of a [`Dsp`] chain.
+///
+/// When the output would rise above `threshold`, the limiter turns it down at once,
+/// and then eases back to full level over `release`. This keeps EQ boosts, echoes,
+/// and overlapping voices (for example, quiet clips boosted with [`Gain::db`]) from
+/// clipping.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Limiter {
+    threshold: Volume,
+    release: Duration,
+}
+
+impl Limiter {
+    /// Limits peaks to 90% of full scale, recovering over 100 ms.
+    pub const DEFAULT: Self = Self::new(Volume::percent(90), Duration::from_millis(100));
+
+    /// Creates a limiter. `threshold` is the highest output level, relative to full scale.
+    /// This uses [`core::time::Duration`].
+    #[must_use]
+    pub const fn new(threshold: Volume, release: core::time::Duration) -> Self {
+        Self { threshold, release }
+    }
+}
+
+/// Echo (feedback delay) in a [`Dsp`] chain.
+///
+/// Each repeat plays `delay` after the previous one, `feedback` as loud. `mix` sets
+/// the level of the repeats against the original sound. Short delays (under about
+/// 50 ms) with some feedback sound like a small room; longer delays sound like
+/// distinct echoes.
+///
+/// The delay is limited by the `max_echo_delay` field of
+/// [`audio_player!`](macro@crate::audio_player), which reserves the delay memory.
+/// With no `max_echo_delay` the echo is silently skipped.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Echo {
+    delay: Duration,
+    feedback: Volume,
+    mix: Volume,
+}
+
+impl Echo {
+    /// Creates an echo. This uses [`core::time::Duration`].
+    #[must_use]
+    pub const fn new(delay: core::time::Duration, feedback: Volume, mix: Volume) -> Self {
+        Self {
+            delay,
+            feedback,
+            mix,
+        }
+    }
+}
+
+/// Effects chain applied to a player's mixed output, set with
+/// [`set_dsp`](audio_player_generated::AudioPlayerGenerated::set_dsp).
+///
+/// Audio runs through up to four [`EqBand`]s, then the [`Echo`], then the [`Limiter`].
+/// Each stage is optional, and [`Dsp::BYPASS`] (the default) skips the whole chain,
+/// costing nothing while mixing.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Dsp {
+    eq_bands: [Option<EqBand>; MAX_EQ_BANDS],
+    limiter: Option<Limiter>,
+    echo: Option<Echo>,
+}
+
+impl Dsp {
+    /// No processing: output is exactly the mix of the voices.
+    pub const BYPASS: Self = Self {
+        eq_bands: [None; MAX_EQ_BANDS],
+        limiter: None,
+        echo: None,
+    };
+
+    /// Creates an empty chain, the same as [`Dsp::BYPASS`]. Add stages with the
+    /// `with_*` methods.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self::BYPASS
+    }
+
+    /// Adds an equalizer band. Panics if the chain already has four bands.
+    #[must_use]
+    pub const fn with_eq_band(mut self, eq_band: EqBand) -> Self {
+        let mut band_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while band_index < MAX_EQ_BANDS {
+            if self.eq_bands[band_index].is_none() {
+                self.eq_bands[band_index] = Some(eq_band);
+                return self;
+            }
+            band_index += 1;
+        }
+        panic!("Dsp supports at most 4 EQ bands");
+    }
+
+    /// Sets the limiter at the end of the chain.
+    #[must_use]
+    pub const fn with_limiter(mut self, limiter: Limiter) -> Self {
+        self.limiter = Some(limiter);
+        self
+    }
+
+    /// Sets the echo.
+    #[must_use]
+    pub const fn with_echo(mut self, echo: Echo) -> Self {
+        self.echo = Some(echo);
+        self
+    }
+
+    /// Returns `true` if the chain has no stages.
+    #[must_use]
+    pub const fn is_bypass(&self) -> bool {
+        let mut band_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while band_index < MAX_EQ_BANDS {
+            if self.eq_bands[band_index].is_some() {
+                return false;
+            }
+            band_index += 1;
+        }
+        self.limiter.is_none() && self.echo.is_none()
+    }
+}
+
+impl Default for Dsp {
+    fn default() -> Self {
+        Self::BYPASS
+    }
+}
+
+/// Normalized biquad coefficients, with [`COEFFICIENT_SHIFT`] fraction bits.
+#[derive(Clone, Copy)]
+struct BiquadCoefficients {
+    b0: i64,
+    b1: i64,
+    b2: i64,
+    a1: i64,
+    a2: i64,
+}
+
+impl BiquadCoefficients {
+    /// Designs `eq_band` at `sample_rate_hz` with the Audio EQ Cookbook formulas
+    /// (shelf slope 1), or returns `None` if the band leaves audio unchanged.
+    const fn design(eq_band: EqBand, sample_rate_hz: u32) -> Option<Self> {
+        let nyquist_hz = sample_rate_hz / 2;
+        if eq_band.gain_db == 0 || eq_band.frequency_hz == 0 || eq_band.frequency_hz >= nyquist_hz {
+            return None;
+        }
+        let one = 1_i128 << DESIGN_SHIFT;
+        let phase_u32 = ((eq_band.frequency_hz as u64) << 32) / sample_rate_hz as u64;
+        let sin_w0 = sine_sample_from_phase(phase_u32 as u32) as i128 * one / UNITY_LINEAR as i128;
+        let cos_w0 = sine_sample_from_phase((phase_u32 as u32).wrapping_add(1 << 30)) as i128 * one
+            / UNITY_LINEAR as i128;
+        // A = 10^(dB / 40), the square root of the band's amplitude gain.
+        let gain_linear = Gain::db(eq_band.gain_db).linear() as u128;
+        let a = ((gain_linear << (2 * DESIGN_SHIFT)) / UNITY_LINEAR as u128).isqrt() as i128;
+
+        let (b0, b1, b2, a0, a1, a2) = match eq_band.kind {
+            EqBandKind::Peaking { q_percent } => {
+                let alpha = sin_w0 * 100 / (2 * q_percent as i128);
+                let alpha_times_a = alpha * a / one;
+                let alpha_over_a = alpha * one / a;
+                (
+                    one + alpha_times_a,
+                    -2 * cos_w0,
+                    one - alpha_times_a,
+                    one + alpha_over_a,
+                    -2 * cos_w0,
+                    one - alpha_over_a,
+                )
+            }
+            EqBandKind::LowShelf | EqBandKind::HighShelf => {
+                // With slope 1, alpha = sin(w0) / sqrt(2).
+                let alpha = sin_w0 * 46_341 / 65_536;
+                let sqrt_a = ((a as u128) << DESIGN_SHIFT).isqrt() as i128;
+                let two_sqrt_a_alpha = 2 * sqrt_a * alpha / one;
+                let a_plus_one = a + one;
+                let a_minus_one = a - one;
+                let a_plus_one_cos = a_plus_one * cos_w0 / one;
+                let a_minus_one_cos = a_minus_one * cos_w0 / one;
+                if matches!(eq_band.kind, EqBandKind::LowShelf) {
+                    (
+                        a * (a_plus_one - a_minus_one_cos + two_sqrt_a_alpha) / one,
+                        2 * a * (a_minus_one - a_plus_one_cos) / one,
+                        a * (a_plus_one - a_minus_one_cos - two_sqrt_a_alpha) / one,
+                        a_plus_one + a_minus_one_cos + two_sqrt_a_alpha,
+                        -2 * (a_minus_one + a_plus_one_cos),
+                        a_plus_one + a_minus_one_cos - two_sqrt_a_alpha,
+                    )
+                } else {
+                    (
+                        a * (a_plus_one + a_minus_one_cos + two_sqrt_a_alpha) / one,
+                        -2 * a * (a_minus_one + a_plus_one_cos) / one,
+                        a * (a_plus_one + a_minus_one_cos - two_sqrt_a_alpha) / one,
+                        a_plus_one - a_minus_one_cos + two_sqrt_a_alpha,
+                        2 * (a_minus_one - a_plus_one_cos),
+                        a_plus_one - a_minus_one_cos - two_sqrt_a_alpha,
+                    )
+                }
+            }
+        };
+        Some(Self {
+            b0: normalize_coefficient(b0, a0),
+            b1: normalize_coefficient(b1, a0),
+            b2: normalize_coefficient(b2, a0),
+            a1: normalize_coefficient(a1, a0),
+            a2: normalize_coefficient(a2, a0),
+        })
+    }
+}
+
+/// Direct form I history of one biquad on one channel.
+#[derive(Clone, Copy)]
+struct BiquadHistory {
+    x1: i32,
+    x2: i32,
+    y1: i32,
+    y2: i32,
+}
+
+impl BiquadHistory {
+    const ZERO: Self = Self {
+        x1: 0,
+        x2: 0,
+        y1: 0,
+        y2: 0,
+    };
+
+    #[inline]
+    fn process(&mut self, coefficients: &BiquadCoefficients, x0: i32) -> i32 {
+        let accumulator_i64 = coefficients.b0 * x0 as i64
+            + coefficients.b1 * self.x1 as i64
+            + coefficients.b2 * self.x2 as i64
+            - coefficients.a1 * self.y1 as i64
+            - coefficients.a2 * self.y2 as i64;
+        let y0 = (accumulator_i64 >> COEFFICIENT_SHIFT)
+            .clamp(i32::MIN as i64 / 4, i32::MAX as i64 / 4) as i32;
+        self.x2 = self.x1;
+        self.x1 = x0;
+        self.y2 = self.y1;
+        self.y1 = y0;
+        y0
+    }
+}
+
+/// One designed EQ band with left and right history.
+#[derive(Clone, Copy)]
+struct BiquadStage {
+    coefficients: BiquadCoefficients,
+    left: BiquadHistory,
+    right: BiquadHistory,
+}
+
+/// Running state of a [`Dsp`] chain inside the mixer.
+///
+/// `ECHO_FRAMES` is the echo delay memory, in stereo frames.
+pub(crate) struct DspState<const SAMPLE_RATE_HZ: u32, const ECHO_FRAMES: usize> {
+    is_bypass: bool,
+    biquad_stages: [Option<BiquadStage>; MAX_EQ_BANDS],
+    echo_delay_frames: usize,
+    echo_feedback_linear: i32,
+    echo_mix_linear: i32,
+    echo_index: usize,
+    echo_frames: [(i16, i16); ECHO_FRAMES],
+    echo_silent_frame_count: usize,
+    limiter_threshold: i32,
+    limiter_release_step: i32,
+    limiter_linear: i32,
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const ECHO_FRAMES: usize> DspState<SAMPLE_RATE_HZ, ECHO_FRAMES> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            is_bypass: true,
+            biquad_stages: [None; MAX_EQ_BANDS],
+            echo_delay_frames: 0,
+            echo_feedback_linear: 0,
+            echo_mix_linear: 0,
+            echo_index: 0,
+            echo_frames: [(0, 0); ECHO_FRAMES],
+            echo_silent_frame_count: 0,
+            limiter_threshold: 0,
+            limiter_release_step: 0,
+            limiter_linear: UNITY_LINEAR,
+        }
+    }
+
+    pub(crate) const fn is_bypass(&self) -> bool {
+        self.is_bypass
+    }
+
+    /// Returns `true` while the echo memory still holds sound to play back.
+    pub(crate) const fn is_ringing(&self) -> bool {
+        self.echo_silent_frame_count < self.echo_delay_frames
+    }
+
+    /// Replaces the chain, clearing filter history and echo memory.
+    pub(crate) fn configure(&mut self, dsp: Dsp) {
+        self.is_bypass = dsp.is_bypass();
+        for (biquad_stage, eq_band) in self.biquad_stages.iter_mut().zip(dsp.eq_bands) {
+            *biquad_stage = eq_band
+                .and_then(|eq_band| BiquadCoefficients::design(eq_band, SAMPLE_RATE_HZ))
+                .map(|coefficients| BiquadStage {
+                    coefficients,
+                    left: BiquadHistory::ZERO,
+                    right: BiquadHistory::ZERO,
+                });
+        }
+
+        self.echo_delay_frames = match dsp.echo {
+            Some(echo) => __samples_for_duration(echo.delay, SAMPLE_RATE_HZ).min(ECHO_FRAMES),
+            None => 0,
+        };
+        if let Some(echo) = dsp.echo {
+            self.echo_feedback_linear = echo.feedback.to_i16() as i32;
+            self.echo_mix_linear = echo.mix.to_i16() as i32;
+        }
+        self.echo_index = 0;
+        self.echo_frames.fill((0, 0));
+        self.echo_silent_frame_count = self.echo_delay_frames;
+
+        match dsp.limiter {
+            Some(limiter) => {
+                self.limiter_threshold = limiter.threshold.to_i16() as i32;
+                let release_len = __samples_for_duration(limiter.release, SAMPLE_RATE_HZ).max(1);
+                self.limiter_release_step = (UNITY_LINEAR / release_len as i32).max(1);
+            }
+            None => self.limiter_threshold = 0,
+        }
+        self.limiter_linear = UNITY_LINEAR;
+    }
+
+    /// Runs one mixed stereo frame through the chain.
+    pub(crate) fn process(&mut self, mut left_i32: i32, mut right_i32: i32) -> (i16, i16) {
+        for biquad_stage in self.biquad_stages.iter_mut().flatten() {
+            left_i32 = biquad_stage
+                .left
+                .process(&biquad_stage.coefficients, left_i32);
+            right_i32 = biquad_stage
+                .right
+                .process(&biquad_stage.coefficients, right_i32);
+        }
+
+        if self.echo_delay_frames > 0 {
+            let (delayed_left, delayed_right) = self.echo_frames[self.echo_index];
+            let echo_frame = (
+                clamp_i64_to_i16(
+                    left_i32 as i64 + scale_i32(delayed_left as i32, self.echo_feedback_linear),
+                ),
+                clamp_i64_to_i16(
+                    right_i32 as i64 + scale_i32(delayed_right as i32, self.echo_feedback_linear),
+                ),
+            );
+            self.echo_frames[self.echo_index] = echo_frame;
+            self.echo_silent_frame_count = if echo_frame == (0, 0) {
+                self.echo_silent_frame_count.saturating_add(1)
+            } else {
+                0
+            };
+            self.echo_index = (self.echo_index + 1) % self.echo_delay_frames;
+            left_i32 += scale_i32(delayed_left as i32, self.echo_mix_linear) as i32;
+            right_i32 += scale_i32(delayed_right as i32, self.echo_mix_linear) as i32;
+        }
+
+        if self.limiter_threshold > 0 {
+            let peak_i64 = scale_i32(left_i32.abs().max(right_i32.abs()), self.limiter_linear);
+            if peak_i64 > self.limiter_threshold as i64 {
+                self.limiter_linear =
+                    (self.limiter_threshold as i64 * self.limiter_linear as i64 / peak_i64) as i32;
+            }
+            left_i32 = scale_i32(left_i32, self.limiter_linear) as i32;
+            right_i32 = scale_i32(right_i32, self.limiter_linear) as i32;
+            self.limiter_linear =
+                (self.limiter_linear + self.limiter_release_step).min(UNITY_LINEAR);
+        }
+
+        (
+            clamp_i64_to_i16(left_i32 as i64),
+            clamp_i64_to_i16(right_i32 as i64),
+        )
+    }
+}
+
+/// Scales `value_i32` by `linear_i32`, where [`UNITY_LINEAR`] leaves it unchanged.
+#[inline]
+const fn scale_i32(value_i32: i32, linear_i32: i32) -> i64 {
+    value_i32 as i64 * linear_i32 as i64 / UNITY_LINEAR as i64
+}
+
+/// Converts a design coefficient to [`COEFFICIENT_SHIFT`] fraction bits, normalized by `a0`.
+const fn normalize_coefficient(coefficient: i128, a0: i128) -> i64 {
+    ((coefficient << COEFFICIENT_SHIFT) / a0) as i64
+}
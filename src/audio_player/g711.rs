@@ -0,0 +1,499 @@
+//! G.711 μ-law and A-law clip types: 8 bits per sample (50% the size of PCM), decoded
+//! one sample at a time with a few shifts.
+//!
+//! See [`MulawClip`] and [`AlawClip`].
+
+use super::{
+    Gain, PcmClip, PcmClipBuf, read_u16_le_const, read_u32_le_const, scale_sample_with_linear,
+    wav_tag_eq,
+};
+
+/// Bias added to magnitudes before μ-law segment lookup.
+const MULAW_BIAS: i32 = 0x84;
+/// WAV `fmt ` format code for G.711 A-law.
+const WAV_FORMAT_ALAW: u16 = 0x0006;
+/// WAV `fmt ` format code for G.711 μ-law.
+const WAV_FORMAT_MULAW: u16 = 0x0007;
+
+/// Unsized view of static compressed (G.711 μ-law) clip data.
+///
+/// Each byte is one mono sample. For fixed-size, const-friendly storage, see
+/// [`MulawClipBuf`]. Use [`mulaw_clip!`](macro@crate::audio_player::mulaw_clip) to read
+/// one from a μ-law WAV file.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+pub struct MulawClip<const SAMPLE_RATE_HZ: u32, T: ?Sized = [u8]> {
+    pub(super) data: T,
+}
+
+/// Sized, const-friendly storage for compressed (G.711 μ-law) clip data.
+///
+/// `SAMPLE_COUNT` is both the number of samples and the data length in bytes.
+pub type MulawClipBuf<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize> =
+    MulawClip<SAMPLE_RATE_HZ, [u8; SAMPLE_COUNT]>;
+
+/// Unsized view of static compressed (G.711 A-law) clip data.
+///
+/// Each byte is one mono sample. For fixed-size, const-friendly storage, see
+/// [`AlawClipBuf`]. Use [`alaw_clip!`](macro@crate::audio_player::alaw_clip) to read
+/// one from an A-law WAV file.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+pub struct AlawClip<const SAMPLE_RATE_HZ: u32, T: ?Sized = [u8]> {
+    pub(super) data: T,
+}
+
+/// Sized, const-friendly storage for compressed (G.711 A-law) clip data.
+///
+/// `SAMPLE_COUNT` is both the number of samples and the data length in bytes.
+pub type AlawClipBuf<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize> =
+    AlawClip<SAMPLE_RATE_HZ, [u8; SAMPLE_COUNT]>;
+
+/// **Implementation for fixed-size clips (`MulawClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize>
+    MulawClip<SAMPLE_RATE_HZ, [u8; SAMPLE_COUNT]>
+{
+    /// Returns the uncompressed (PCM) version of this clip.
+    #[must_use]
+    pub const fn with_pcm(&self) -> PcmClipBuf<SAMPLE_RATE_HZ, SAMPLE_COUNT> {
+        assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+        let mut samples = [0_i16; SAMPLE_COUNT];
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            samples[sample_index] = mulaw_decode(self.data[sample_index]);
+            sample_index += 1;
+        }
+        PcmClip { samples }
+    }
+
+    /// Returns this clip with linear sample gain applied.
+    ///
+    /// This decodes to PCM, applies gain, then re-encodes.
+    #[must_use]
+    pub const fn with_gain(self, gain: Gain) -> Self {
+        let mut data = self.data;
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            data[sample_index] = mulaw_encode(scale_sample_with_linear(
+                mulaw_decode(data[sample_index]),
+                gain.linear(),
+            ));
+            sample_index += 1;
+        }
+        Self { data }
+    }
+}
+
+/// **Implementation for fixed-size clips (`AlawClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize>
+    AlawClip<SAMPLE_RATE_HZ, [u8; SAMPLE_COUNT]>
+{
+    /// Returns the uncompressed (PCM) version of this clip.
+    #[must_use]
+    pub const fn with_pcm(&self) -> PcmClipBuf<SAMPLE_RATE_HZ, SAMPLE_COUNT> {
+        assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+        let mut samples = [0_i16; SAMPLE_COUNT];
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            samples[sample_index] = alaw_decode(self.data[sample_index]);
+            sample_index += 1;
+        }
+        PcmClip { samples }
+    }
+
+    /// Returns this clip with linear sample gain applied.
+    ///
+    /// This decodes to PCM, applies gain, then re-encodes.
+    #[must_use]
+    pub const fn with_gain(self, gain: Gain) -> Self {
+        let mut data = self.data;
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            data[sample_index] = alaw_encode(scale_sample_with_linear(
+                alaw_decode(data[sample_index]),
+                gain.linear(),
+            ));
+            sample_index += 1;
+        }
+        Self { data }
+    }
+}
+
+/// **G.711 encoding for fixed-size clips (`PcmClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize>
+    PcmClip<SAMPLE_RATE_HZ, [i16; SAMPLE_COUNT]>
+{
+    /// Returns the compressed (G.711 μ-law) encoding for this clip.
+    #[must_use]
+    pub const fn with_mulaw(&self) -> MulawClipBuf<SAMPLE_RATE_HZ, SAMPLE_COUNT> {
+        let mut data = [0_u8; SAMPLE_COUNT];
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            data[sample_index] = mulaw_encode(self.samples[sample_index]);
+            sample_index += 1;
+        }
+        MulawClip { data }
+    }
+
+    /// Returns the compressed (G.711 A-law) encoding for this clip.
+    #[must_use]
+    pub const fn with_alaw(&self) -> AlawClipBuf<SAMPLE_RATE_HZ, SAMPLE_COUNT> {
+        let mut data = [0_u8; SAMPLE_COUNT];
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            data[sample_index] = alaw_encode(self.samples[sample_index]);
+            sample_index += 1;
+        }
+        AlawClip { data }
+    }
+}
+
+/// Returns the G.711 segment (0..=8) of a non-negative magnitude.
+const fn g711_segment(magnitude_i32: i32) -> i32 {
+    // The highest set bit of `magnitude | 0xFF` is at least bit 7 (segment 0).
+    (31 - (magnitude_i32 | 0xFF).leading_zeros() as i32) - 7
+}
+
+/// Encodes one sample as G.711 μ-law.
+pub(crate) const fn mulaw_encode(sample_i16: i16) -> u8 {
+    let sample_i32 = sample_i16 as i32;
+    let (magnitude_i32, mask) = if sample_i32 < 0 {
+        (MULAW_BIAS - sample_i32 - 1, 0x7F)
+    } else {
+        (MULAW_BIAS + sample_i32, 0xFF)
+    };
+    let segment = g711_segment(magnitude_i32);
+    if segment >= 8 {
+        return 0x7F ^ mask;
+    }
+    (((segment << 4) | ((magnitude_i32 >> (segment + 3)) & 0x0F)) as u8) ^ mask
+}
+
+/// Decodes one G.711 μ-law byte.
+pub(crate) const fn mulaw_decode(mulaw_byte: u8) -> i16 {
+    let inverted_byte = !mulaw_byte;
+    let segment = (inverted_byte >> 4) & 0x07;
+    let magnitude_i32 = ((((inverted_byte & 0x0F) as i32) << 3) + MULAW_BIAS) << segment;
+    if inverted_byte & 0x80 != 0 {
+        (MULAW_BIAS - magnitude_i32) as i16
+    } else {
+        (magnitude_i32 - MULAW_BIAS) as i16
+    }
+}
+
+/// Encodes one sample as G.711 A-law.
+pub(crate) const fn alaw_encode(sample_i16: i16) -> u8 {
+    let sample_i32 = sample_i16 as i32;
+    let (magnitude_i32, mask) = if sample_i32 >= 0 {
+        (sample_i32, 0xD5)
+    } else {
+        (-sample_i32 - 1, 0x55)
+    };
+    let segment = g711_segment(magnitude_i32);
+    let shift = if segment == 0 { 4 } else { segment + 3 };
+    (((segment << 4) | ((magnitude_i32 >> shift) & 0x0F)) as u8) ^ mask
+}
+
+/// Decodes one G.711 A-law byte.
+pub(crate) const fn alaw_decode(alaw_byte: u8) -> i16 {
+    let toggled_byte = alaw_byte ^ 0x55;
+    let segment = (toggled_byte >> 4) & 0x07;
+    let mut magnitude_i32 = ((toggled_byte & 0x0F) as i32) << 4;
+    magnitude_i32 = if segment == 0 {
+        magnitude_i32 + 8
+    } else {
+        (magnitude_i32 + 0x108) << (segment - 1)
+    };
+    if toggled_byte & 0x80 != 0 {
+        magnitude_i32 as i16
+    } else {
+        -magnitude_i32 as i16
+    }
+}
+
+/// Parsed G.711 WAV metadata used by
+/// [`mulaw_clip!`](macro@crate::audio_player::mulaw_clip) and
+/// [`alaw_clip!`](macro@crate::audio_player::alaw_clip).
+#[derive(Clone, Copy)]
+#[doc(hidden)]
+pub struct ParsedG711WavHeader {
+    /// WAV sample rate.
+    pub sample_rate_hz: u32,
+    /// Byte offset of the `data` chunk payload.
+    pub data_chunk_start: usize,
+    /// Byte length of the `data` chunk payload, which is also the sample count.
+    pub data_chunk_len: usize,
+}
+
+/// Parses μ-law WAV header metadata in a `const` context.
+#[must_use]
+#[doc(hidden)]
+pub const fn __parse_mulaw_wav_header(wav_bytes: &[u8]) -> ParsedG711WavHeader {
+    parse_g711_wav_header(wav_bytes, WAV_FORMAT_MULAW)
+}
+
+/// Parses A-law WAV header metadata in a `const` context.
+#[must_use]
+#[doc(hidden)]
+pub const fn __parse_alaw_wav_header(wav_bytes: &[u8]) -> ParsedG711WavHeader {
+    parse_g711_wav_header(wav_bytes, WAV_FORMAT_ALAW)
+}
+
+const fn parse_g711_wav_header(
+    wav_bytes: &[u8],
+    expected_audio_format: u16,
+) -> ParsedG711WavHeader {
+    if wav_bytes.len() < 12 {
+        panic!("WAV file too small");
+    }
+    if !wav_tag_eq(wav_bytes, 0, *b"RIFF") {
+        panic!("Missing RIFF header");
+    }
+    if !wav_tag_eq(wav_bytes, 8, *b"WAVE") {
+        panic!("Missing WAVE header");
+    }
+
+    let mut chunk_offset = 12usize;
+    let mut sample_rate_hz = 0u32;
+    let mut fmt_found = false;
+    let mut data_chunk_start = 0usize;
+    let mut data_chunk_end = 0usize;
+    let mut data_found = false;
+
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while chunk_offset + 8 <= wav_bytes.len() {
+        let chunk_size = read_u32_le_const(wav_bytes, chunk_offset + 4) as usize;
+        let chunk_data_start = chunk_offset + 8;
+        if chunk_data_start > wav_bytes.len() || chunk_size > wav_bytes.len() - chunk_data_start {
+            panic!("WAV chunk overruns file");
+        }
+        let chunk_data_end = chunk_data_start + chunk_size;
+
+        if wav_tag_eq(wav_bytes, chunk_offset, *b"fmt ") {
+            if chunk_size < 16 {
+                panic!("fmt chunk too small");
+            }
+
+            let audio_format = read_u16_le_const(wav_bytes, chunk_data_start);
+            let channels = read_u16_le_const(wav_bytes, chunk_data_start + 2);
+            sample_rate_hz = read_u32_le_const(wav_bytes, chunk_data_start + 4);
+            let bits_per_sample = read_u16_le_const(wav_bytes, chunk_data_start + 14);
+
+            if audio_format != expected_audio_format {
+                if expected_audio_format == WAV_FORMAT_MULAW {
+                    panic!("Expected mu-law WAV format");
+                }
+                panic!("Expected A-law WAV format");
+            }
+            if channels != 1 {
+                panic!("Expected mono G.711 WAV");
+            }
+            if bits_per_sample != 8 {
+                panic!("Expected 8-bit G.711");
+            }
+            fmt_found = true;
+        } else if wav_tag_eq(wav_bytes, chunk_offset, *b"data") {
+            data_chunk_start = chunk_data_start;
+            data_chunk_end = chunk_data_end;
+            data_found = true;
+        }
+
+        let padded_chunk_size = chunk_size + (chunk_size & 1);
+        if chunk_data_start > usize::MAX - padded_chunk_size {
+            panic!("WAV chunk traversal overflow");
+        }
+        chunk_offset = chunk_data_start + padded_chunk_size;
+    }
+
+    if !fmt_found {
+        panic!("Missing fmt chunk");
+    }
+    if !data_found {
+        panic!("Missing data chunk");
+    }
+
+    ParsedG711WavHeader {
+        sample_rate_hz,
+        data_chunk_start,
+        data_chunk_len: data_chunk_end - data_chunk_start,
+    }
+}
+
+/// Const backend helper that builds a fixed-size μ-law clip from its bytes.
+#[must_use]
+#[doc(hidden)]
+pub const fn __mulaw_clip_from_bytes<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize>(
+    data: [u8; SAMPLE_COUNT],
+) -> MulawClipBuf<SAMPLE_RATE_HZ, SAMPLE_COUNT> {
+    assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+    MulawClip { data }
+}
+
+/// Const backend helper that builds a fixed-size A-law clip from its bytes.
+#[must_use]
+#[doc(hidden)]
+pub const fn __alaw_clip_from_bytes<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize>(
+    data: [u8; SAMPLE_COUNT],
+) -> AlawClipBuf<SAMPLE_RATE_HZ, SAMPLE_COUNT> {
+    assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+    AlawClip { data }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! mulaw_clip {
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
+        }
+    ) => {
+        $crate::__g711_clip_impl! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            target_sample_rate_hz: $target_sample_rate_hz,
+            codec: mulaw,
+            codec_name: "μ-law",
+        }
+    };
+    (
+        $vis:vis $name:ident {
+            file: $file:expr $(,)?
+        }
+    ) => {
+        $crate::__g711_clip_impl! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            target_sample_rate_hz: $crate::audio_player::__parse_mulaw_wav_header(include_bytes!($file)).sample_rate_hz,
+            codec: mulaw,
+            codec_name: "μ-law",
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! alaw_clip {
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
+        }
+    ) => {
+        $crate::__g711_clip_impl! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            target_sample_rate_hz: $target_sample_rate_hz,
+            codec: alaw,
+            codec_name: "A-law",
+        }
+    };
+    (
+        $vis:vis $name:ident {
+            file: $file:expr $(,)?
+        }
+    ) => {
+        $crate::__g711_clip_impl! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            target_sample_rate_hz: $crate::audio_player::__parse_alaw_wav_header(include_bytes!($file)).sample_rate_hz,
+            codec: alaw,
+            codec_name: "A-law",
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __g711_clip_impl {
+    (
+        vis: $vis:vis,
+        name: $name:ident,
+        file: $file:expr,
+        target_sample_rate_hz: $target_sample_rate_hz:expr,
+        codec: $codec:ident,
+        codec_name: $codec_name:literal $(,)?
+    ) => {
+        $crate::audio_player::paste::paste! {
+            const [<$name:upper _TARGET_SAMPLE_RATE_HZ>]: u32 = $target_sample_rate_hz;
+
+            #[allow(non_snake_case)]
+            #[allow(missing_docs)]
+            $vis mod $name {
+                const PARSED_WAV: $crate::audio_player::ParsedG711WavHeader =
+                    $crate::audio_player::[<__parse_ $codec _wav_header>](include_bytes!($file));
+                const SOURCE_SAMPLE_RATE_HZ: u32 = PARSED_WAV.sample_rate_hz;
+                const TARGET_SAMPLE_RATE_HZ: u32 = super::[<$name:upper _TARGET_SAMPLE_RATE_HZ>];
+                pub const SAMPLE_RATE_HZ: u32 = TARGET_SAMPLE_RATE_HZ;
+
+                const SOURCE_SAMPLE_COUNT: usize = PARSED_WAV.data_chunk_len;
+                #[doc = "Number of samples for uncompressed (PCM) version of this clip."]
+                pub const PCM_SAMPLE_COUNT: usize = $crate::audio_player::__resampled_sample_count(
+                    SOURCE_SAMPLE_COUNT,
+                    SOURCE_SAMPLE_RATE_HZ,
+                    TARGET_SAMPLE_RATE_HZ,
+                );
+                #[doc = concat!("Byte length for compressed (", $codec_name, ") encoding this clip.")]
+                pub const [<$codec:upper _DATA_LEN>]: usize = PCM_SAMPLE_COUNT;
+
+                #[must_use]
+                const fn [<source_ $codec _clip>]() -> $crate::audio_player::[<$codec:camel ClipBuf>]<
+                    SOURCE_SAMPLE_RATE_HZ,
+                    SOURCE_SAMPLE_COUNT,
+                > {
+                    let wav_bytes = include_bytes!($file);
+                    let mut data = [0_u8; SOURCE_SAMPLE_COUNT];
+                    let mut data_index = 0usize;
+                    while data_index < SOURCE_SAMPLE_COUNT {
+                        data[data_index] = wav_bytes[PARSED_WAV.data_chunk_start + data_index];
+                        data_index += 1;
+                    }
+                    $crate::audio_player::[<__ $codec _clip_from_bytes>](data)
+                }
+
+                #[doc = "`const` function that returns the uncompressed (PCM) version of this clip."]
+                #[must_use]
+                pub const fn pcm_clip() -> $crate::audio_player::PcmClipBuf<SAMPLE_RATE_HZ, PCM_SAMPLE_COUNT> {
+                    $crate::audio_player::__resample_pcm_clip::<
+                        SOURCE_SAMPLE_RATE_HZ,
+                        SOURCE_SAMPLE_COUNT,
+                        TARGET_SAMPLE_RATE_HZ,
+                        PCM_SAMPLE_COUNT,
+                    >([<source_ $codec _clip>]().with_pcm())
+                }
+
+                #[doc = concat!("`const` function that returns the compressed (", $codec_name, ") encoding for this clip.")]
+                #[must_use]
+                pub const fn [<$codec _clip>]() -> $crate::audio_player::[<$codec:camel ClipBuf>]<
+                    SAMPLE_RATE_HZ,
+                    [<$codec:upper _DATA_LEN>],
+                > {
+                    if TARGET_SAMPLE_RATE_HZ == SOURCE_SAMPLE_RATE_HZ {
+                        let wav_bytes = include_bytes!($file);
+                        let mut data = [0_u8; [<$codec:upper _DATA_LEN>]];
+                        let mut data_index = 0usize;
+                        while data_index < [<$codec:upper _DATA_LEN>] {
+                            data[data_index] = wav_bytes[PARSED_WAV.data_chunk_start + data_index];
+                            data_index += 1;
+                        }
+                        $crate::audio_player::[<__ $codec _clip_from_bytes>](data)
+                    } else {
+                        pcm_clip().[<with_ $codec>]()
+                    }
+                }
+            }
+        }
+    };
+}
@@ -1,12 +1,23 @@
 #![allow(missing_docs)]
 
+use super::g711::{alaw_decode, alaw_encode, mulaw_decode, mulaw_encode};
+use super::mixer::Mixer;
+use super::pwm::{pwm_period_cycles, pwm_periods_per_sample, pwm_word};
 use super::{
-    __adpcm_data_len_for_pcm_samples, AdpcmClipBuf, AtEnd, AudioPlayer, Gain, PcmClip, PcmClipBuf,
-    Playable, SilenceClip, VOICE_22050_HZ,
+    __adpcm_data_len_for_pcm_samples, __parse_adpcm_wav_header, __qoa_data_len_for_pcm_samples,
+    __stereo_adpcm_data_len_for_pcm_frames, __stereo_qoa_data_len_for_pcm_frames, AdpcmClipBuf,
+    Adsr, AtEnd, AudioPlayer, AudioPlayerStatic, AudioRenderer, AudioRingBuffer, AudioStream, Dsp,
+    Echo, EqBand, FadedClip, Gain, InterruptPolicy, Limiter, Melody, Note, PRO_48000_HZ, Pan,
+    PcmClip, PcmClipBuf, PlayOutcome, Playable, PlaybackEvent, PlaybackPosition, Priority,
+    QoaClipBuf, SilenceClip, StereoAdpcmClipBuf, StereoPcmClipBuf, StereoQoaClipBuf,
+    VOICE_16000_HZ, VOICE_22050_HZ, Volume, Waveform, stereo_frame,
 };
 use std::error::Error;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::pin::pin;
+use std::sync::atomic::{AtomicI16, Ordering};
+use std::task::{Context, Poll, Waker};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 const TONE_SAMPLE_COUNT: usize = 32;
@@ -149,15 +160,1484 @@ fn play_accepts_iterator_inputs() {
     audio_player4.play(audio_clip_iterator, AtEnd::Stop);
 }
 
+type AudioPlayer4x2 = AudioPlayer<4, VOICE_22050_HZ, 2>;
+type Mixer4x2 = Mixer<4, VOICE_22050_HZ, 2>;
+
+#[test]
+fn mixer_plays_sequence_then_silence_and_marks_stopped() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static RAMP: PcmClipBuf<VOICE_22050_HZ, 3> = super::__pcm_clip_from_samples([100, 200, 300]);
+    static GAP: SilenceClip = SilenceClip::new(std::time::Duration::from_micros(91));
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&RAMP as &'static dyn Playable<VOICE_22050_HZ>, &GAP, &RAMP],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert!(!mixer.is_idle(), "play command must start voice 0");
+
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 10),
+        [100, 200, 300, 0, 0, 100, 200, 300, 0, 0],
+        "clips must play back to back, then silence"
+    );
+    assert!(
+        mixer.is_idle(),
+        "voice must stop after AtEnd::Stop sequence"
+    );
+    assert!(!AUDIO_PLAYER_STATIC.voices[0].is_idle());
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert!(
+        AUDIO_PLAYER_STATIC.voices[0].is_idle(),
+        "finished voice must be marked stopped"
+    );
+}
+
+#[test]
+fn mixer_sums_voices_with_saturation_and_per_voice_gain() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static LOUD: PcmClipBuf<VOICE_22050_HZ, 4> =
+        super::__pcm_clip_from_samples([20_000, -20_000, 1_000, 0]);
+    static STEADY: PcmClipBuf<VOICE_22050_HZ, 2> =
+        super::__pcm_clip_from_samples([20_000, -20_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&LOUD as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    audio_player.voice(1).play(
+        [&STEADY as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 4),
+        [i16::MAX, i16::MIN, 21_000, -20_000],
+        "overlapping voices must saturate instead of wrapping"
+    );
+
+    audio_player.voice(1).set_gain(Gain::percent(50));
+    assert_eq!(audio_player.voice(1).gain(), Gain::percent(50));
+    let half_gain_samples = mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 2);
+    assert!(
+        half_gain_samples
+            .iter()
+            .zip([10_000_i16, -10_000])
+            .all(|(actual, expected)| (actual - expected).abs() <= 1),
+        "50% voice gain must halve that voice: {half_gain_samples:?}"
+    );
+}
+
+#[test]
+fn mixer_stop_on_one_voice_leaves_others_playing() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static ONES: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([1]);
+    static TENS: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([10]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&ONES as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    audio_player.voice(1).play(
+        [&TENS as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 3),
+        [11, 11, 11]
+    );
+
+    audio_player.stop();
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 3),
+        [10, 10, 10]
+    );
+    assert!(AUDIO_PLAYER_STATIC.voices[0].is_idle());
+    assert!(!AUDIO_PLAYER_STATIC.voices[1].is_idle());
+}
+
+#[test]
+fn mixer_decodes_adpcm_like_with_pcm() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    type ToneAdpcm =
+        AdpcmClipBuf<VOICE_22050_HZ, { __adpcm_data_len_for_pcm_samples(TONE_SAMPLE_COUNT) }>;
+    static TONE_ADPCM: ToneAdpcm =
+        super::__tone_pcm_clip::<VOICE_22050_HZ, TONE_SAMPLE_COUNT>(TONE_FREQUENCY_HZ).with_adpcm();
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&TONE_ADPCM as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+
+    const DECODED_SAMPLE_COUNT: usize = (TONE_ADPCM.data.len() / TONE_ADPCM.block_align as usize)
+        * TONE_ADPCM.samples_per_block as usize;
+    let mixed = mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, DECODED_SAMPLE_COUNT + 4);
+    let decoded: PcmClipBuf<VOICE_22050_HZ, DECODED_SAMPLE_COUNT> = TONE_ADPCM.with_pcm();
+    assert_eq!(
+        mixed[..DECODED_SAMPLE_COUNT],
+        decoded.samples,
+        "streamed ADPCM decoding must match the const decoder"
+    );
+    assert!(
+        mixed[DECODED_SAMPLE_COUNT..]
+            .iter()
+            .all(|sample| *sample == 0),
+        "ADPCM playback must end after the last block"
+    );
+}
+
+#[test]
+fn ring_buffer_stream_plays_written_samples_then_silence_until_finished() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static STREAM: AudioRingBuffer<VOICE_22050_HZ, 4> = AudioRingBuffer::new();
+    static TAIL: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([-7]);
+
+    assert_eq!(
+        STREAM.try_write(&[1, 2, 3, 4, 5]),
+        4,
+        "write must stop at capacity"
+    );
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&STREAM as &'static dyn Playable<VOICE_22050_HZ>, &TAIL],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 6),
+        [1, 2, 3, 4, 0, 0],
+        "an underrun must play silence, not end the stream"
+    );
+
+    embassy_futures::block_on(STREAM.write(&[8, 9]));
+    STREAM.finish();
+    // The player reads ahead by one block, so the new samples follow that block's silence.
+    let after_finish = mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 64);
+    let tail_index = after_finish
+        .iter()
+        .position(|sample| *sample == -7)
+        .expect("sequence must continue after the stream ends");
+    assert_eq!(after_finish[tail_index - 2..tail_index], [8, 9]);
+    assert!(mixer.is_idle(), "voice must stop after the last clip");
+    assert!(STREAM.is_empty());
+}
+
+#[test]
+fn custom_stream_is_playable_and_ends_on_zero() {
+    struct Countdown(AtomicI16);
+
+    impl AudioStream<VOICE_22050_HZ> for Countdown {
+        fn fill(&self, samples: &mut [i16]) -> usize {
+            let mut filled_count = 0;
+            for sample in samples {
+                let value = self.0.load(Ordering::Relaxed);
+                if value == 0 {
+                    break;
+                }
+                *sample = value;
+                self.0.store(value - 1, Ordering::Relaxed);
+                filled_count += 1;
+            }
+            filled_count
+        }
+    }
+
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static COUNTDOWN: Countdown = Countdown(AtomicI16::new(40));
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.voice(1).play(
+        [&COUNTDOWN as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    let expected: Vec<i16> = (1..=40).rev().chain([0, 0]).collect();
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 42),
+        expected
+    );
+    assert!(mixer.is_idle(), "stream returning 0 must end playback");
+}
+
+crate::pcm_clip! {
+    ToneAsStereo {
+        file: "../../tests/data/audio_with_gain/tone_440hz_32.s16",
+        source_sample_rate_hz: VOICE_22050_HZ,
+        channels: 2,
+    }
+}
+
+#[test]
+fn pcm_clip_macro_reads_interleaved_stereo_frames() {
+    const TONE_AS_STEREO: StereoPcmClipBuf<VOICE_22050_HZ, { ToneAsStereo::PCM_SAMPLE_COUNT }> =
+        ToneAsStereo::pcm_clip();
+    const TONE: AudioClipTone = super::__tone_pcm_clip(TONE_FREQUENCY_HZ);
+
+    assert_eq!(ToneAsStereo::PCM_SAMPLE_COUNT, TONE_SAMPLE_COUNT / 2);
+    for (frame, samples) in TONE_AS_STEREO.frames.iter().zip(TONE.samples.chunks(2)) {
+        assert_eq!(
+            frame[..],
+            *samples,
+            "s16le input must interleave left, right"
+        );
+    }
+    assert_eq!(
+        ToneAsStereo::ADPCM_DATA_LEN,
+        __stereo_adpcm_data_len_for_pcm_frames(TONE_SAMPLE_COUNT / 2)
+    );
+}
+
+#[test]
+fn parse_adpcm_wav_header_accepts_stereo() {
+    const BLOCK_ALIGN: usize = 64;
+    let mut wav_bytes = Vec::new();
+    wav_bytes.extend_from_slice(b"RIFF");
+    wav_bytes.extend_from_slice(&0_u32.to_le_bytes());
+    wav_bytes.extend_from_slice(b"WAVEfmt ");
+    wav_bytes.extend_from_slice(&16_u32.to_le_bytes());
+    wav_bytes.extend_from_slice(&0x0011_u16.to_le_bytes());
+    wav_bytes.extend_from_slice(&2_u16.to_le_bytes());
+    wav_bytes.extend_from_slice(&VOICE_22050_HZ.to_le_bytes());
+    wav_bytes.extend_from_slice(&0_u32.to_le_bytes());
+    wav_bytes.extend_from_slice(&(BLOCK_ALIGN as u16).to_le_bytes());
+    wav_bytes.extend_from_slice(&4_u16.to_le_bytes());
+    wav_bytes.extend_from_slice(b"data");
+    wav_bytes.extend_from_slice(&(BLOCK_ALIGN as u32 * 3).to_le_bytes());
+    wav_bytes.resize(wav_bytes.len() + BLOCK_ALIGN * 3, 0);
+
+    let parsed_wav = __parse_adpcm_wav_header(&wav_bytes);
+    assert_eq!(parsed_wav.channels, 2);
+    assert_eq!(parsed_wav.samples_per_block, BLOCK_ALIGN - 7);
+    assert_eq!(parsed_wav.sample_count, (BLOCK_ALIGN - 7) * 3);
+}
+
+#[test]
+fn stereo_adpcm_round_trips_and_mixer_decodes_like_with_pcm() {
+    const FRAME_COUNT: usize = 600;
+    const DATA_LEN: usize = __stereo_adpcm_data_len_for_pcm_frames(FRAME_COUNT);
+    const DECODED_FRAME_COUNT: usize = 2 * (512 - 7);
+    const TONE: PcmClipBuf<VOICE_22050_HZ, FRAME_COUNT> = super::__tone_pcm_clip(TONE_FREQUENCY_HZ);
+    const STEREO_TONE: StereoPcmClipBuf<VOICE_22050_HZ, FRAME_COUNT> = {
+        let mut frames = [[0_i16; 2]; FRAME_COUNT];
+        let mut frame_index = 0;
+        while frame_index < FRAME_COUNT {
+            frames[frame_index] = [TONE.samples[frame_index], TONE.samples[frame_index] / -2];
+            frame_index += 1;
+        }
+        super::__stereo_pcm_clip_from_frames(frames)
+    };
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static STEREO_TONE_ADPCM: StereoAdpcmClipBuf<VOICE_22050_HZ, DATA_LEN> =
+        STEREO_TONE.with_adpcm();
+
+    // Stereo blocks hold as many frames per channel as mono blocks of half the size,
+    // so each decoded channel must match the mono encoding of that channel.
+    let decoded: StereoPcmClipBuf<VOICE_22050_HZ, DECODED_FRAME_COUNT> =
+        STEREO_TONE_ADPCM.with_pcm();
+    let left_adpcm: AdpcmClipBuf<
+        VOICE_22050_HZ,
+        { __adpcm_data_len_for_pcm_samples(FRAME_COUNT) },
+    > = STEREO_TONE.left().with_adpcm();
+    let right_adpcm: AdpcmClipBuf<
+        VOICE_22050_HZ,
+        { __adpcm_data_len_for_pcm_samples(FRAME_COUNT) },
+    > = STEREO_TONE.right().with_adpcm();
+    assert_eq!(
+        decoded.left().samples,
+        left_adpcm.with_pcm::<DECODED_FRAME_COUNT>().samples
+    );
+    assert_eq!(
+        decoded.right().samples,
+        right_adpcm.with_pcm::<DECODED_FRAME_COUNT>().samples
+    );
+    let gained: StereoPcmClipBuf<VOICE_22050_HZ, DECODED_FRAME_COUNT> = STEREO_TONE
+        .with_adpcm::<DATA_LEN>()
+        .with_gain(Gain::percent(50))
+        .with_pcm();
+    assert_ne!(gained.frames, decoded.frames, "gain must change the data");
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&STEREO_TONE_ADPCM as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    let mixed = mixed_frames(&mut mixer, &AUDIO_PLAYER_STATIC, DECODED_FRAME_COUNT + 2);
+    assert!(
+        mixed[..DECODED_FRAME_COUNT].iter().zip(decoded.frames).all(
+            |(mixed_frame, decoded_frame)| *mixed_frame == (decoded_frame[0], decoded_frame[1])
+        ),
+        "streamed stereo ADPCM decoding must match the const decoder"
+    );
+    assert_eq!(mixed[DECODED_FRAME_COUNT..], [(0, 0), (0, 0)]);
+}
+
+#[test]
+fn mixer_keeps_stereo_channels_and_pans_mono_voices() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static STEREO: StereoPcmClipBuf<VOICE_22050_HZ, 2> =
+        super::__stereo_pcm_clip_from_frames([[1_000, -1_000], [2_000, 0]]);
+    static MONO: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([10_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&STEREO as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    audio_player.voice(1).play(
+        [&MONO as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(audio_player.voice(1).pan(), Pan::CENTER);
+    assert_eq!(
+        mixed_frames(&mut mixer, &AUDIO_PLAYER_STATIC, 2),
+        [(11_000, 9_000), (12_000, 10_000)]
+    );
+
+    audio_player.voice(1).set_pan(Pan::RIGHT);
+    assert_eq!(
+        mixed_frames(&mut mixer, &AUDIO_PLAYER_STATIC, 2),
+        [(1_000, 9_000), (2_000, 10_000)],
+        "full right pan must silence the left channel of a mono voice"
+    );
+
+    audio_player.voice(1).set_pan(Pan::percent(-50));
+    audio_player.voice(0).set_pan(Pan::LEFT);
+    assert_eq!(
+        mixed_frames(&mut mixer, &AUDIO_PLAYER_STATIC, 2),
+        [(11_000, 5_000), (12_000, 5_000)],
+        "pan must act as balance on stereo clips"
+    );
+    assert_eq!(Pan::percent(120), Pan::RIGHT);
+}
+
+#[test]
+fn faded_clip_ramps_in_and_out() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static STEADY: PcmClipBuf<VOICE_22050_HZ, 8> = super::__pcm_clip_from_samples([16_000; 8]);
+    static STEADY_FADED: FadedClip<VOICE_22050_HZ> = FadedClip::new(&STEADY)
+        .with_fade_in(duration_for_samples(4))
+        .with_fade_out(duration_for_samples(4));
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&STEADY_FADED as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 9),
+        [0, 4_000, 8_000, 12_000, 16_000, 12_000, 8_000, 4_000, 0]
+    );
+}
+
+#[test]
+fn play_with_crossfade_overlaps_consecutive_clips() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static FIRST: PcmClipBuf<VOICE_22050_HZ, 6> = super::__pcm_clip_from_samples([16_000; 6]);
+    static SECOND: PcmClipBuf<VOICE_22050_HZ, 6> = super::__pcm_clip_from_samples([-8_000; 6]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play_with_crossfade(
+        [&FIRST as &'static dyn Playable<VOICE_22050_HZ>, &SECOND],
+        AtEnd::Stop,
+        duration_for_samples(4),
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 9),
+        [
+            16_000, 16_000, 16_000, 10_000, 4_000, -2_000, -8_000, -8_000, 0
+        ],
+        "the last 4 samples of the first clip must overlap the first 4 of the second"
+    );
+    assert!(mixer.is_idle());
+}
+
+#[test]
+fn stop_with_fade_ramps_down_then_stops() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static STEADY: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([16_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&STEADY as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 2),
+        [16_000, 16_000]
+    );
+
+    audio_player.stop_with_fade(duration_for_samples(4));
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 6),
+        [16_000, 12_000, 8_000, 4_000, 0, 0]
+    );
+    assert!(!AUDIO_PLAYER_STATIC.voices[0].is_idle());
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert!(
+        AUDIO_PLAYER_STATIC.voices[0].is_idle(),
+        "voice must be marked stopped once the fade ends"
+    );
+
+    audio_player
+        .voice(1)
+        .stop_with_fade(duration_for_samples(4));
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert!(
+        AUDIO_PLAYER_STATIC.voices[1].is_idle(),
+        "fading out a stopped voice must leave it stopped"
+    );
+}
+
+#[test]
+fn notes_macro_parses_pitch_length_and_rests() {
+    let notes = crate::notes!(" E4:8 D4:8.  C#5 R:2 Bb3:16 A0:1 ");
+    let parsed: Vec<(u32, u16)> = notes
+        .iter()
+        .map(|note| (note.frequency_millihertz(), note.length_64ths()))
+        .collect();
+    assert_eq!(
+        parsed,
+        [
+            (329_628, 8),
+            (293_665, 12),
+            (554_366, 16),
+            (0, 32),
+            (233_082, 4),
+            (27_500, 64),
+        ]
+    );
+    assert!(notes[3].is_rest());
+    assert_eq!(Note::from_notation("Cb5"), Note::from_notation("B4"));
+}
+
+#[test]
+fn melody_waveforms_start_at_expected_levels() {
+    // At 1 kHz and 60 BPM, a quarter note is 1000 samples.
+    const NOTES: &[Note] = crate::notes!("A4");
+    const fn rendered(waveform: Waveform) -> PcmClipBuf<1_000, 1_000> {
+        Melody::<1_000>::new(NOTES)
+            .with_tempo(60)
+            .with_waveform(waveform)
+            .with_adsr(Adsr::NONE)
+            .with_pcm()
+    }
+    const SQUARE: PcmClipBuf<1_000, 1_000> = rendered(Waveform::Square);
+    const SAW: PcmClipBuf<1_000, 1_000> = rendered(Waveform::Saw);
+    const TRIANGLE: PcmClipBuf<1_000, 1_000> = rendered(Waveform::Triangle);
+    const NOISE: PcmClipBuf<1_000, 1_000> = rendered(Waveform::Noise);
+
+    assert!(
+        SQUARE
+            .samples
+            .iter()
+            .all(|sample| sample.unsigned_abs() == i16::MAX as u16)
+    );
+    // 440 Hz at 1 kHz advances 0.44 cycles per sample.
+    assert_eq!(SQUARE.samples[..3], [i16::MAX, i16::MAX, -i16::MAX]);
+    assert_eq!(SAW.samples[0], i16::MIN);
+    assert_eq!(TRIANGLE.samples[0], i16::MIN);
+    assert!(
+        NOISE.samples.windows(2).any(|pair| pair[0] != pair[1]),
+        "noise must vary"
+    );
+}
+
+#[test]
+fn melody_adsr_shapes_each_note() {
+    // At 1 kHz, each millisecond is one sample.
+    const MELODY: Melody<1_000> = Melody::new(crate::notes!("A4 R:4"))
+        .with_tempo(60)
+        .with_waveform(Waveform::Square)
+        .with_adsr(Adsr::new(
+            std::time::Duration::from_millis(10),
+            std::time::Duration::from_millis(10),
+            50,
+            std::time::Duration::from_millis(10),
+        ));
+    const PCM: PcmClipBuf<1_000, 2_000> = MELODY.with_pcm();
+    let levels: Vec<u16> = PCM
+        .samples
+        .iter()
+        .map(|sample| sample.unsigned_abs())
+        .collect();
+    let sustain_level = super::scale_sample_with_linear(i16::MAX, Gain::percent(50).linear());
+
+    assert_eq!(MELODY.sample_count(), 2_000);
+    assert_eq!(levels[0], 0, "attack must start from silence");
+    assert!(levels[..10].windows(2).all(|pair| pair[0] < pair[1]));
+    assert_eq!(levels[10], i16::MAX as u16, "attack must reach full level");
+    assert!(levels[10..20].windows(2).all(|pair| pair[0] > pair[1]));
+    assert!(
+        levels[20..990]
+            .iter()
+            .all(|level| *level == sustain_level as u16)
+    );
+    assert!(levels[990..1_000].windows(2).all(|pair| pair[0] > pair[1]));
+    assert!(levels[999] < 2_000, "release must end near silence");
+    assert!(levels[1_000..].iter().all(|level| *level == 0));
+}
+
+#[test]
+fn mixer_plays_melody_like_with_pcm() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    const MELODY: Melody<VOICE_22050_HZ> = Melody::new(crate::notes!("E4:32 D4:32 R:64 C4:16"))
+        .with_tempo(600)
+        .with_gain(Gain::percent(50));
+    const SAMPLE_COUNT: usize = MELODY.sample_count();
+    static MELODY_STATIC: Melody<VOICE_22050_HZ> = MELODY;
+    static MELODY_PCM: PcmClipBuf<VOICE_22050_HZ, SAMPLE_COUNT> = MELODY.with_pcm();
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&MELODY_STATIC as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+
+    let mixed = mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, SAMPLE_COUNT + 4);
+    assert_eq!(
+        mixed[..SAMPLE_COUNT],
+        MELODY_PCM.samples,
+        "runtime synthesis must match the const renderer"
+    );
+    assert!(mixed[SAMPLE_COUNT..].iter().all(|sample| *sample == 0));
+}
+
+#[test]
+fn play_with_priority_preempts_only_lower_priority_sequences() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static ANNOUNCEMENT: PcmClipBuf<VOICE_22050_HZ, 3> =
+        super::__pcm_clip_from_samples([1_000, 1_000, 1_000]);
+    static TICK: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([50]);
+    static ALARM: PcmClipBuf<VOICE_22050_HZ, 2> = super::__pcm_clip_from_samples([9_000, 9_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    let mut preempt = |audio_clip: &'static dyn Playable<VOICE_22050_HZ>, priority| {
+        play_outcome_after_apply(
+            audio_player.play_with_priority(
+                [audio_clip],
+                AtEnd::Stop,
+                priority,
+                InterruptPolicy::Preempt,
+            ),
+            &mut mixer,
+            &AUDIO_PLAYER_STATIC,
+        )
+    };
+
+    assert_eq!(
+        preempt(&ANNOUNCEMENT, Priority::High),
+        PlayOutcome::Played,
+        "a stopped voice must play any request"
+    );
+    assert_eq!(preempt(&TICK, Priority::Low), PlayOutcome::Dropped);
+    assert_eq!(
+        preempt(&TICK, Priority::High),
+        PlayOutcome::Dropped,
+        "equal priority must not preempt"
+    );
+    drop(preempt);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 2),
+        [1_000, 1_000]
+    );
+
+    assert_eq!(
+        play_outcome_after_apply(
+            audio_player.play_with_priority(
+                [&ALARM as &'static dyn Playable<VOICE_22050_HZ>],
+                AtEnd::Stop,
+                Priority::Critical,
+                InterruptPolicy::Preempt,
+            ),
+            &mut mixer,
+            &AUDIO_PLAYER_STATIC,
+        ),
+        PlayOutcome::Played
+    );
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 3),
+        [9_000, 9_000, 0]
+    );
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert!(AUDIO_PLAYER_STATIC.voices[0].is_idle());
+}
+
+#[test]
+fn play_with_priority_enqueues_after_current_sequence() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static FIRST: PcmClipBuf<VOICE_22050_HZ, 2> = super::__pcm_clip_from_samples([100, 100]);
+    static LOW: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([200]);
+    static HIGH: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([300]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    let enqueue =
+        |audio_clip: &'static dyn Playable<VOICE_22050_HZ>, priority, mixer: &mut Mixer4x2| {
+            play_outcome_after_apply(
+                audio_player.play_with_priority(
+                    [audio_clip],
+                    AtEnd::Stop,
+                    priority,
+                    InterruptPolicy::Enqueue,
+                ),
+                mixer,
+                &AUDIO_PLAYER_STATIC,
+            )
+        };
+
+    assert_eq!(
+        enqueue(&FIRST, Priority::Normal, &mut mixer),
+        PlayOutcome::Played
+    );
+    assert_eq!(
+        enqueue(&LOW, Priority::Low, &mut mixer),
+        PlayOutcome::Queued
+    );
+    assert_eq!(
+        enqueue(&LOW, Priority::Low, &mut mixer),
+        PlayOutcome::Dropped,
+        "a full queue must keep its equal-priority sequence"
+    );
+    assert_eq!(
+        enqueue(&HIGH, Priority::High, &mut mixer),
+        PlayOutcome::Queued,
+        "a higher priority request must replace the queued sequence"
+    );
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 5),
+        [100, 100, 0, 300, 0]
+    );
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert!(AUDIO_PLAYER_STATIC.voices[0].is_idle());
+
+    assert_eq!(
+        enqueue(&FIRST, Priority::Normal, &mut mixer),
+        PlayOutcome::Played
+    );
+    assert_eq!(
+        enqueue(&LOW, Priority::Low, &mut mixer),
+        PlayOutcome::Queued
+    );
+    audio_player.play(
+        [&HIGH as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 3),
+        [300, 0, 0],
+        "plain play must discard the queued sequence"
+    );
+}
+
+#[test]
+fn play_with_duck_lowers_other_voices_while_playing() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static MUSIC: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([8_000]);
+    static ANNOUNCEMENT: PcmClipBuf<VOICE_22050_HZ, 2> =
+        super::__pcm_clip_from_samples([1_000, 1_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.voice(1).play(
+        [&MUSIC as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 1), [8_000]);
+
+    assert_eq!(
+        play_outcome_after_apply(
+            audio_player.play_with_priority(
+                [&ANNOUNCEMENT as &'static dyn Playable<VOICE_22050_HZ>],
+                AtEnd::Stop,
+                Priority::High,
+                InterruptPolicy::Duck(Gain::percent(25)),
+            ),
+            &mut mixer,
+            &AUDIO_PLAYER_STATIC,
+        ),
+        PlayOutcome::Played
+    );
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 2),
+        [3_000, 3_000],
+        "music must be ducked to 25% under the announcement"
+    );
+    // Ducking is set per buffer, so it lasts until the buffer in which the announcement ends.
+    assert_eq!(mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 1), [2_000]);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 2),
+        [8_000, 8_000],
+        "music must return to full level once the announcement ends"
+    );
+}
+
+#[test]
+#[should_panic(expected = "InterruptPolicy::Duck needs a player with at least two voices")]
+fn play_with_duck_rejects_one_voice_player() {
+    type AudioPlayer4x1 = AudioPlayer<4, VOICE_22050_HZ, 1>;
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 1> =
+        AudioPlayer4x1::new_static();
+    static ANNOUNCEMENT: PcmClipBuf<VOICE_22050_HZ, 2> =
+        super::__pcm_clip_from_samples([1_000, 1_000]);
+
+    // With one voice there is nothing to duck; the announcement would just cut off
+    // whatever was playing.
+    let audio_player = AudioPlayer4x1::new(&AUDIO_PLAYER_STATIC);
+    let play_future = pin!(audio_player.play_with_priority(
+        [&ANNOUNCEMENT as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+        Priority::High,
+        InterruptPolicy::Duck(Gain::percent(25)),
+    ));
+    let _ = play_future.poll(&mut Context::from_waker(Waker::noop()));
+}
+
+#[test]
+fn full_event_channel_keeps_newest_events() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static BEAT: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([9]);
+    static LAST_BEAT: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([5]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+
+    // Fill the channel with a first sequence's 7 events, leaving them unread.
+    audio_player.play(
+        [&BEAT as &'static dyn Playable<VOICE_22050_HZ>; 3],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 4),
+        [9, 9, 9, 0]
+    );
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+
+    // A second sequence's 5 events then push out the 4 oldest.
+    audio_player.play(
+        [&BEAT as &'static dyn Playable<VOICE_22050_HZ>, &LAST_BEAT],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 3),
+        [9, 5, 0]
+    );
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        received_events(&AUDIO_PLAYER_STATIC, 0),
+        [
+            PlaybackEvent::ClipStarted { clip_index: 2 },
+            PlaybackEvent::ClipEnded { clip_index: 2 },
+            PlaybackEvent::SequenceEnded,
+            PlaybackEvent::ClipStarted { clip_index: 0 },
+            PlaybackEvent::ClipEnded { clip_index: 0 },
+            PlaybackEvent::ClipStarted { clip_index: 1 },
+            PlaybackEvent::ClipEnded { clip_index: 1 },
+            PlaybackEvent::SequenceEnded,
+        ],
+        "a full channel must drop its oldest events, not the latest sequence's end"
+    );
+}
+
+#[test]
+fn full_event_buffer_keeps_newest_events() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static BEAT: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([9]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&BEAT as &'static dyn Playable<VOICE_22050_HZ>; 4],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+
+    // Four one-sample clips make 9 events in one buffer, one more than fits.
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 5),
+        [9, 9, 9, 9, 0]
+    );
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    let events = received_events(&AUDIO_PLAYER_STATIC, 0);
+    assert_eq!(events.len(), 8);
+    assert_eq!(events[0], PlaybackEvent::ClipEnded { clip_index: 0 });
+    assert_eq!(events[7], PlaybackEvent::SequenceEnded);
+}
+
+#[test]
+fn position_and_events_track_clip_sequence() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static FIRST: PcmClipBuf<VOICE_22050_HZ, 3> = super::__pcm_clip_from_samples([1, 2, 3]);
+    static SECOND: PcmClipBuf<VOICE_22050_HZ, 2> = super::__pcm_clip_from_samples([4, 5]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    assert_eq!(audio_player.position(), None);
+    audio_player.play(
+        [&FIRST as &'static dyn Playable<VOICE_22050_HZ>, &SECOND],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 4),
+        [1, 2, 3, 4]
+    );
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        audio_player.position(),
+        Some(PlaybackPosition {
+            clip_index: 1,
+            sample_offset: 1,
+            sample_count: Some(2),
+        })
+    );
+    assert_eq!(
+        received_events(&AUDIO_PLAYER_STATIC, 0),
+        [
+            PlaybackEvent::ClipStarted { clip_index: 0 },
+            PlaybackEvent::ClipEnded { clip_index: 0 },
+            PlaybackEvent::ClipStarted { clip_index: 1 },
+        ]
+    );
+
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 4),
+        [5, 0, 0, 0]
+    );
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert_eq!(audio_player.position(), None);
+    assert_eq!(
+        received_events(&AUDIO_PLAYER_STATIC, 0),
+        [
+            PlaybackEvent::ClipEnded { clip_index: 1 },
+            PlaybackEvent::SequenceEnded,
+        ]
+    );
+}
+
+#[test]
+fn events_report_loop_passes_and_stop() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static BEAT: PcmClipBuf<VOICE_22050_HZ, 2> = super::__pcm_clip_from_samples([7, 8]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let voice = audio_player.voice(1);
+    let mut mixer = Mixer4x2::new();
+    voice.play(
+        [&BEAT as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 3),
+        [7, 8, 7]
+    );
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        voice.position().map(|position| position.sample_offset),
+        Some(1)
+    );
+
+    voice.stop();
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(voice.position(), None);
+    mixer.publish_progress(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        received_events(&AUDIO_PLAYER_STATIC, 1),
+        [
+            PlaybackEvent::ClipStarted { clip_index: 0 },
+            PlaybackEvent::ClipEnded { clip_index: 0 },
+            PlaybackEvent::ClipStarted { clip_index: 0 },
+            PlaybackEvent::SequenceEnded,
+        ],
+        "a stop must end the sequence without ending the interrupted clip"
+    );
+    assert!(
+        received_events(&AUDIO_PLAYER_STATIC, 0).is_empty(),
+        "events must stay on their own voice"
+    );
+}
+
+#[test]
+fn dsp_bypass_and_zero_db_eq_leave_mix_unchanged() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static CLIP: PcmClipBuf<VOICE_22050_HZ, 4> =
+        super::__pcm_clip_from_samples([1_000, -20_000, 32_767, -32_768]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.set_dsp(
+        Dsp::new()
+            .with_eq_band(EqBand::low_shelf(200, 0))
+            .with_eq_band(EqBand::peaking(1_000, 0, 71)),
+    );
+    audio_player.play(
+        [&CLIP as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 4),
+        [1_000, -20_000, 32_767, -32_768],
+        "0 dB bands must pass audio through unchanged"
+    );
+
+    audio_player.set_dsp(Dsp::BYPASS);
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 4),
+        [1_000, -20_000, 32_767, -32_768]
+    );
+    assert!(Dsp::default().is_bypass());
+}
+
+#[test]
+fn eq_shelves_and_peaks_shape_low_and_high_frequencies() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    // A constant level is 0 Hz; alternating signs are the highest frequency (Nyquist).
+    static CONSTANT: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([4_000]);
+    static ALTERNATING: PcmClipBuf<VOICE_22050_HZ, 2> =
+        super::__pcm_clip_from_samples([4_000, -4_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    // Each case: EQ band, clip, expected settled peak level.
+    let cases: [(EqBand, &'static dyn Playable<VOICE_22050_HZ>, i16); 6] = [
+        (EqBand::low_shelf(1_000, 6), &CONSTANT, 7_981),
+        (EqBand::low_shelf(1_000, 6), &ALTERNATING, 4_000),
+        (EqBand::high_shelf(1_000, -6), &CONSTANT, 4_000),
+        (EqBand::high_shelf(1_000, -6), &ALTERNATING, 2_005),
+        (EqBand::peaking(1_000, 6, 71), &CONSTANT, 4_000),
+        (EqBand::peaking(1_000, 6, 71), &ALTERNATING, 4_000),
+    ];
+    for (eq_band, clip, expected_peak) in cases {
+        audio_player.set_dsp(Dsp::new().with_eq_band(eq_band));
+        audio_player.play([clip], AtEnd::Loop);
+        mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+        let samples = mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 4_000);
+        let settled_peak = samples[3_000..]
+            .iter()
+            .map(|sample| sample.unsigned_abs() as i16)
+            .max()
+            .unwrap();
+        assert!(
+            (settled_peak - expected_peak).abs() <= expected_peak / 50,
+            "{eq_band:?}: settled peak {settled_peak} must be near {expected_peak}"
+        );
+    }
+}
+
+#[test]
+fn limiter_keeps_summed_voices_below_threshold() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static LOUD: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([30_000]);
+    static QUIET: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([8_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    for voice_index in 0..2 {
+        audio_player.voice(voice_index).play(
+            [&LOUD as &'static dyn Playable<VOICE_22050_HZ>],
+            AtEnd::Loop,
+        );
+    }
+    audio_player.set_dsp(
+        Dsp::new().with_limiter(Limiter::new(Volume::percent(50), duration_for_samples(100))),
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    let limited_samples = mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 50);
+    assert!(
+        limited_samples
+            .iter()
+            .all(|sample| (16_000..=16_383).contains(sample)),
+        "two 30_000 voices must be held at the 50% threshold, got {limited_samples:?}"
+    );
+
+    // Once the peaks stop, the limiter eases back to unity over its release time.
+    for voice_index in 0..2 {
+        audio_player.voice(voice_index).play(
+            [&QUIET as &'static dyn Playable<VOICE_22050_HZ>],
+            AtEnd::Loop,
+        );
+    }
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    let released_samples = mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 200);
+    assert!(released_samples[0] < 10_000);
+    assert_eq!(released_samples[199], 16_000);
+}
+
+#[test]
+fn echo_repeats_after_delay_and_keeps_mixer_busy_until_it_fades() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static IMPULSE: PcmClipBuf<VOICE_22050_HZ, 1> = super::__pcm_clip_from_samples([10_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    // Room for 8 frames of delay; the echo asks for 4.
+    let mut mixer = Mixer::<4, VOICE_22050_HZ, 2, 8>::new();
+    audio_player.set_dsp(Dsp::new().with_echo(Echo::new(
+        duration_for_samples(4),
+        Volume::percent(50),
+        Volume::MAX,
+    )));
+    audio_player.play(
+        [&IMPULSE as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    assert_eq!(
+        mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 13),
+        [10_000, 0, 0, 0, 10_000, 0, 0, 0, 4_999, 0, 0, 0, 2_499]
+    );
+    assert!(
+        !mixer.is_idle(),
+        "the mixer must keep running while the echo fades out"
+    );
+    mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, 100);
+    assert!(
+        mixer.is_idle(),
+        "the mixer must go idle once the echo is silent"
+    );
+}
+
+#[test]
+fn pwm_words_keep_period_and_track_sample_level() {
+    // 125 MHz at 22.05 kHz: two PWM periods per sample, for a 44.1 kHz carrier.
+    assert_eq!(pwm_periods_per_sample(VOICE_22050_HZ), 2);
+    assert_eq!(pwm_periods_per_sample(PRO_48000_HZ), 1);
+    let period_cycles = pwm_period_cycles(125_000_000, VOICE_22050_HZ);
+    assert_eq!(period_cycles, 2834);
+
+    let high_and_low_counts = |left_sample: i16, right_sample: i16| {
+        let word = pwm_word(stereo_frame(left_sample, right_sample), period_cycles);
+        (word & 0xFFFF, word >> 16)
+    };
+    let count_total = period_cycles - 6;
+    for (left_sample, right_sample) in [(i16::MIN, i16::MIN), (0, 0), (i16::MAX, i16::MAX)] {
+        let (high_count, low_count) = high_and_low_counts(left_sample, right_sample);
+        assert_eq!(
+            high_count + low_count,
+            count_total,
+            "every period must be the same length"
+        );
+    }
+    assert_eq!(high_and_low_counts(i16::MIN, i16::MIN).0, 0);
+    assert_eq!(high_and_low_counts(0, 0).0, count_total / 2);
+    assert_eq!(high_and_low_counts(i16::MAX, i16::MAX).0, count_total - 1);
+    assert_eq!(
+        high_and_low_counts(16_000, -16_000),
+        high_and_low_counts(0, 0),
+        "left and right must average to mono"
+    );
+}
+
+#[test]
+fn g711_codecs_match_reference_values_and_round_trip() {
+    assert_eq!(mulaw_decode(0xFF), 0);
+    assert_eq!(mulaw_decode(0x80), 32_124);
+    assert_eq!(mulaw_decode(0x00), -32_124);
+    assert_eq!(mulaw_encode(0), 0xFF);
+    assert_eq!(mulaw_encode(i16::MAX), 0x80);
+    assert_eq!(mulaw_encode(i16::MIN), 0x00);
+    assert_eq!(alaw_decode(0xD5), 8);
+    assert_eq!(alaw_decode(0x55), -8);
+    assert_eq!(alaw_decode(0xAA), 32_256);
+    assert_eq!(alaw_decode(0x2A), -32_256);
+    assert_eq!(alaw_encode(0), 0xD5);
+    assert_eq!(alaw_encode(i16::MIN), 0x2A);
+
+    for byte in 0..=u8::MAX {
+        // 0x7F is μ-law's negative zero, which decodes to the same 0 as 0xFF.
+        if byte != 0x7F {
+            assert_eq!(
+                mulaw_encode(mulaw_decode(byte)),
+                byte,
+                "mu-law byte {byte:#04x}"
+            );
+        }
+        assert_eq!(
+            alaw_encode(alaw_decode(byte)),
+            byte,
+            "A-law byte {byte:#04x}"
+        );
+    }
+}
+
+crate::mulaw_clip! {
+    ToneMulaw {
+        file: "../../tests/data/audio_codecs/tone_440hz_32_mulaw.wav",
+    }
+}
+
+crate::alaw_clip! {
+    ToneAlaw {
+        file: "../../tests/data/audio_codecs/tone_440hz_32_alaw.wav",
+        target_sample_rate_hz: VOICE_16000_HZ,
+    }
+}
+
+#[test]
+fn g711_clip_macros_read_wav_data_and_mixer_decodes_like_with_pcm() {
+    const TONE: AudioClipTone = super::__tone_pcm_clip(TONE_FREQUENCY_HZ);
+    static TONE_MULAW: super::MulawClipBuf<VOICE_22050_HZ, { ToneMulaw::MULAW_DATA_LEN }> =
+        ToneMulaw::mulaw_clip();
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+
+    assert_eq!(ToneMulaw::SAMPLE_RATE_HZ, VOICE_22050_HZ);
+    assert_eq!(ToneMulaw::MULAW_DATA_LEN, TONE_SAMPLE_COUNT);
+    let decoded = ToneMulaw::pcm_clip();
+    for (decoded_sample, tone_sample) in decoded.samples.iter().zip(TONE.samples) {
+        let tolerance = tone_sample.unsigned_abs() / 16 + 16;
+        assert!(
+            decoded_sample.abs_diff(tone_sample) <= tolerance,
+            "mu-law sample {decoded_sample} is too far from {tone_sample}"
+        );
+    }
+    assert_eq!(
+        decoded.with_mulaw().data,
+        TONE_MULAW.data,
+        "re-encoding decoded mu-law must reproduce the file data"
+    );
+
+    // The A-law clip is resampled at compile time, like other clip macros.
+    assert_eq!(ToneAlaw::SAMPLE_RATE_HZ, VOICE_16000_HZ);
+    assert_eq!(ToneAlaw::ALAW_DATA_LEN, 23);
+    assert_eq!(
+        ToneAlaw::alaw_clip().with_pcm().samples,
+        ToneAlaw::pcm_clip().with_alaw().with_pcm().samples
+    );
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [&TONE_MULAW as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    let mixed = mixed_samples(&mut mixer, &AUDIO_PLAYER_STATIC, TONE_SAMPLE_COUNT + 2);
+    assert_eq!(mixed[..TONE_SAMPLE_COUNT], decoded.samples);
+    assert_eq!(mixed[TONE_SAMPLE_COUNT..], [0, 0]);
+}
+
+crate::qoa_clip! {
+    ToneQoa {
+        file: "../../tests/data/audio_codecs/tone_440hz_32.qoa",
+    }
+}
+
+#[test]
+fn qoa_clip_macro_matches_const_encoder_and_decodes_close_to_source() {
+    const TONE: AudioClipTone = super::__tone_pcm_clip(TONE_FREQUENCY_HZ);
+    const TONE_QOA: QoaClipBuf<VOICE_22050_HZ, { ToneQoa::QOA_DATA_LEN }> = TONE.with_qoa();
+
+    assert_eq!(ToneQoa::SAMPLE_RATE_HZ, VOICE_22050_HZ);
+    assert_eq!(ToneQoa::PCM_SAMPLE_COUNT, TONE_SAMPLE_COUNT);
+    assert_eq!(
+        ToneQoa::QOA_DATA_LEN,
+        __qoa_data_len_for_pcm_samples(TONE_SAMPLE_COUNT)
+    );
+    assert_eq!(
+        TONE_QOA.data,
+        ToneQoa::qoa_clip().data,
+        "const encoder must reproduce the .qoa file"
+    );
+    let decoded = ToneQoa::pcm_clip();
+    assert_eq!(decoded.samples, TONE_QOA.with_pcm().samples);
+    // The predictor needs a few samples to lock on, so a 32-sample clip is loose.
+    for (decoded_sample, tone_sample) in decoded.samples.iter().zip(TONE.samples) {
+        assert!(
+            decoded_sample.abs_diff(tone_sample) <= 1_000,
+            "QOA sample {decoded_sample} is too far from {tone_sample}"
+        );
+    }
+}
+
+#[test]
+fn stereo_qoa_spans_frames_and_mixer_decodes_like_with_pcm() {
+    // More than one 5,120-frame QOA frame, ending with a partial slice.
+    const FRAME_COUNT: usize = 5_130;
+    const DATA_LEN: usize = __stereo_qoa_data_len_for_pcm_frames(FRAME_COUNT);
+    const TONE: PcmClipBuf<VOICE_22050_HZ, FRAME_COUNT> = super::__tone_pcm_clip(TONE_FREQUENCY_HZ);
+    const STEREO_TONE: StereoPcmClipBuf<VOICE_22050_HZ, FRAME_COUNT> = {
+        let mut frames = [[0_i16; 2]; FRAME_COUNT];
+        let mut frame_index = 0;
+        while frame_index < FRAME_COUNT {
+            frames[frame_index] = [TONE.samples[frame_index], TONE.samples[frame_index] / -2];
+            frame_index += 1;
+        }
+        super::__stereo_pcm_clip_from_frames(frames)
+    };
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    // Encoding at run time keeps the test build fast.
+    let stereo_tone_qoa: &'static StereoQoaClipBuf<VOICE_22050_HZ, DATA_LEN> =
+        Box::leak(Box::new(STEREO_TONE.with_qoa()));
+
+    let decoded: StereoPcmClipBuf<VOICE_22050_HZ, FRAME_COUNT> = stereo_tone_qoa.with_pcm();
+    for (decoded_frame, tone_frame) in decoded.frames.iter().zip(STEREO_TONE.frames) {
+        assert!(
+            decoded_frame[0].abs_diff(tone_frame[0]) <= 64
+                && decoded_frame[1].abs_diff(tone_frame[1]) <= 64,
+            "QOA frame {decoded_frame:?} is too far from {tone_frame:?}"
+        );
+    }
+    let gained: StereoPcmClipBuf<VOICE_22050_HZ, FRAME_COUNT> =
+        StereoQoaClipBuf::<VOICE_22050_HZ, DATA_LEN>::with_gain(
+            STEREO_TONE.with_qoa(),
+            Gain::percent(50),
+        )
+        .with_pcm();
+    assert!(
+        gained.frames[1_000][0].abs_diff(decoded.frames[1_000][0] / 2) <= 64,
+        "gain must scale the decoded samples"
+    );
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut mixer = Mixer4x2::new();
+    audio_player.play(
+        [stereo_tone_qoa as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    mixer.apply_commands(&AUDIO_PLAYER_STATIC);
+    let mixed = mixed_frames(&mut mixer, &AUDIO_PLAYER_STATIC, FRAME_COUNT + 2);
+    assert!(
+        mixed[..FRAME_COUNT]
+            .iter()
+            .zip(decoded.frames)
+            .all(
+                |(mixed_frame, decoded_frame)| *mixed_frame == (decoded_frame[0], decoded_frame[1])
+            ),
+        "streamed QOA decoding must match the const decoder"
+    );
+    assert_eq!(mixed[FRAME_COUNT..], [(0, 0), (0, 0)]);
+}
+
+#[test]
+fn renderer_matches_clock_chime_golden_wav() -> Result<(), Box<dyn Error>> {
+    // The "WiFi connected" chime from `examples/clock_audio.rs`.
+    type AudioPlayer10 = AudioPlayer<8, VOICE_22050_HZ>;
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<8, VOICE_22050_HZ> =
+        AudioPlayer10::new_static_with_max_volume(Volume::percent(10));
+    const WIFI_CONNECTED_TONE: &dyn Playable<VOICE_22050_HZ> =
+        &crate::tone!(880, VOICE_22050_HZ, std::time::Duration::from_millis(140))
+            .with_gain(Gain::percent(20));
+    const SILENCE_40MS: &dyn Playable<VOICE_22050_HZ> =
+        &SilenceClip::new(std::time::Duration::from_millis(40));
+
+    let audio_player10 = AudioPlayer10::new(&AUDIO_PLAYER_STATIC);
+    let mut audio_renderer = AudioRenderer::<8, VOICE_22050_HZ>::new(&AUDIO_PLAYER_STATIC);
+    audio_player10.play(
+        [WIFI_CONNECTED_TONE, SILENCE_40MS, WIFI_CONNECTED_TONE],
+        AtEnd::Stop,
+    );
+    assert!(audio_renderer.render_until_idle(std::time::Duration::from_secs(1)));
+    assert!(
+        audio_renderer.duration() >= std::time::Duration::from_millis(320),
+        "rendering must cover the whole sequence"
+    );
+    assert!(AUDIO_PLAYER_STATIC.voices[0].is_idle());
+
+    assert_file_matches_expected(
+        &audio_render_path("clock_wifi_connected.wav"),
+        &audio_renderer.wav_bytes(),
+    )
+}
+
+#[test]
+fn renderer_applies_volume_and_records_idle_time_as_silence() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ, 2> =
+        AudioPlayer4x2::new_static();
+    static RAMP: PcmClipBuf<VOICE_22050_HZ, 3> =
+        super::__pcm_clip_from_samples([1_000, 2_000, 3_000]);
+
+    let audio_player = AudioPlayer4x2::new(&AUDIO_PLAYER_STATIC);
+    let mut audio_renderer = AudioRenderer::<4, VOICE_22050_HZ, 2>::new(&AUDIO_PLAYER_STATIC);
+    assert!(
+        !audio_renderer.render_buffer(),
+        "idle player must render nothing"
+    );
+    audio_renderer.render_for(std::time::Duration::from_millis(1));
+    assert_eq!(
+        audio_renderer.frames(),
+        [[0, 0]; 256],
+        "idle time must be whole silent buffers"
+    );
+
+    audio_renderer.clear();
+    audio_player.set_volume(Volume::percent(50));
+    audio_player.voice(1).set_pan(Pan::percent(100));
+    audio_player.voice(1).play(
+        [&RAMP as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Stop,
+    );
+    assert!(audio_renderer.render_until_idle(std::time::Duration::from_millis(100)));
+    assert_eq!(audio_renderer.frames().len(), 256);
+    assert_eq!(
+        audio_renderer.frames()[..4],
+        [[0, 500], [0, 1_000], [0, 1_500], [0, 0]],
+        "volume and pan must apply to the rendered mix"
+    );
+
+    audio_renderer.clear();
+    audio_player.play(
+        [&RAMP as &'static dyn Playable<VOICE_22050_HZ>],
+        AtEnd::Loop,
+    );
+    assert!(
+        !audio_renderer.render_until_idle(std::time::Duration::from_millis(30)),
+        "a looping sequence must never go idle"
+    );
+    assert_eq!(audio_renderer.frames().len(), 768);
+}
+
+fn received_events(
+    audio_player_static: &AudioPlayerStatic<4, VOICE_22050_HZ, 2>,
+    voice_index: usize,
+) -> Vec<PlaybackEvent> {
+    std::iter::from_fn(|| {
+        audio_player_static.voices[voice_index]
+            .event_channel
+            .try_receive()
+            .ok()
+    })
+    .collect()
+}
+
+/// Polls `play_future` once to send its request, applies it, and returns its outcome.
+fn play_outcome_after_apply(
+    play_future: impl Future<Output = PlayOutcome>,
+    mixer: &mut Mixer4x2,
+    audio_player_static: &AudioPlayerStatic<4, VOICE_22050_HZ, 2>,
+) -> PlayOutcome {
+    let mut play_future = pin!(play_future);
+    let mut context = Context::from_waker(Waker::noop());
+    assert!(
+        play_future.as_mut().poll(&mut context).is_pending(),
+        "outcome must wait for the mixer"
+    );
+    mixer.apply_commands(audio_player_static);
+    match play_future.as_mut().poll(&mut context) {
+        Poll::Ready(play_outcome) => play_outcome,
+        Poll::Pending => panic!("mixer must report an outcome"),
+    }
+}
+
+/// Returns a duration that converts back to exactly `sample_count` samples.
+const fn duration_for_samples(sample_count: u64) -> std::time::Duration {
+    std::time::Duration::from_nanos((sample_count * 1_000_000_000).div_ceil(VOICE_22050_HZ as u64))
+}
+
+fn mixed_frames<const ECHO_FRAMES: usize>(
+    mixer: &mut Mixer<4, VOICE_22050_HZ, 2, ECHO_FRAMES>,
+    audio_player_static: &AudioPlayerStatic<4, VOICE_22050_HZ, 2>,
+    frame_count: usize,
+) -> Vec<(i16, i16)> {
+    let mut sample_buffer = vec![0_u32; frame_count];
+    mixer.fill(audio_player_static, &mut sample_buffer);
+    sample_buffer
+        .iter()
+        .map(|stereo_frame| {
+            (
+                (stereo_frame >> 16) as u16 as i16,
+                *stereo_frame as u16 as i16,
+            )
+        })
+        .collect()
+}
+
+fn mixed_samples<const ECHO_FRAMES: usize>(
+    mixer: &mut Mixer<4, VOICE_22050_HZ, 2, ECHO_FRAMES>,
+    audio_player_static: &AudioPlayerStatic<4, VOICE_22050_HZ, 2>,
+    sample_count: usize,
+) -> Vec<i16> {
+    let mut sample_buffer = vec![0_u32; sample_count];
+    mixer.fill(audio_player_static, &mut sample_buffer);
+    sample_buffer
+        .iter()
+        .map(|stereo_sample| {
+            let left = (stereo_sample >> 16) as u16 as i16;
+            let right = *stereo_sample as u16 as i16;
+            assert_eq!(left, right, "mono mix must be duplicated to both channels");
+            left
+        })
+        .collect()
+}
+
 fn assert_clip_file_matches_expected<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize>(
     filename: &str,
     audio_clip: &PcmClip<SAMPLE_RATE_HZ, [i16; SAMPLE_COUNT]>,
 ) -> Result<(), Box<dyn Error>> {
-    let expected_path = audio_with_gain_path(filename);
-    let actual_bytes = clip_to_s16le_bytes(audio_clip);
+    assert_file_matches_expected(
+        &audio_with_gain_path(filename),
+        &clip_to_s16le_bytes(audio_clip),
+    )
+}
+
+fn assert_file_matches_expected(
+    expected_path: &Path,
+    actual_bytes: &[u8],
+) -> Result<(), Box<dyn Error>> {
+    let filename = expected_path
+        .file_name()
+        .and_then(|filename| filename.to_str())
+        .ok_or("expected path must name a file")?;
 
     if std::env::var_os("DEVICE_KIT_UPDATE_AUDIO").is_some() {
-        fs::write(&expected_path, &actual_bytes)?;
+        fs::write(expected_path, actual_bytes)?;
         println!("updated audio at {}", expected_path.display());
         return Ok(());
     }
@@ -167,9 +1647,9 @@ fn assert_clip_file_matches_expected<const SAMPLE_RATE_HZ: u32, const SAMPLE_COU
     }
 
     let output_path = temp_output_path(filename);
-    fs::write(&output_path, &actual_bytes)?;
+    fs::write(&output_path, actual_bytes)?;
 
-    let expected_bytes = fs::read(&expected_path)?;
+    let expected_bytes = fs::read(expected_path)?;
     let actual_file_bytes = fs::read(&output_path)?;
     assert_eq!(
         expected_bytes, actual_file_bytes,
@@ -208,3 +1688,11 @@ fn audio_with_gain_path(filename: &str) -> PathBuf {
     path.push(filename);
     path
 }
+
+fn audio_render_path(filename: &str) -> PathBuf {
+    let mut path = PathBuf::from("tests");
+    path.push("data");
+    path.push("audio_render");
+    path.push(filename);
+    path
+}
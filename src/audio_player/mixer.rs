@@ -0,0 +1,919 @@
+//! Pull-based rendering of queued clips into output sample buffers.
+//!
+//! Each voice walks its own clip sequence one frame at a time. The [`Mixer`] sums
+//! all voices, with per-voice gain and pan, into each buffer the device task sends to I²S.
+
+use core::time::Duration;
+
+use heapless::Vec;
+
+use super::dsp::DspState;
+use super::g711::{alaw_decode, mulaw_decode};
+use super::qoa::QoaDecoder;
+use super::stereo::stereo_adpcm_nibble;
+use super::synth::SynthCursor;
+use super::{
+    __samples_for_duration, AdpcmClip, AtEnd, AudioCommand, AudioPlayerStatic, Gain,
+    InterruptPolicy, PLAYBACK_EVENT_CAPACITY, PlayOutcome, PlaybackClip, PlaybackEvent,
+    PlaybackPosition, Priority, PriorityPlay, SequenceClip, StereoAdpcmClip, clamp_i64_to_i16,
+    decode_adpcm_nibble_const, scale_linear, scale_sample_with_linear, stereo_frame,
+};
+
+/// Number of samples pulled from an [`AudioStream`](super::AudioStream) at a time.
+const STREAM_BLOCK_LEN: usize = 32;
+
+/// One output frame from a clip.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Frame {
+    Mono(i16),
+    Stereo(i16, i16),
+}
+
+/// Read position within one clip.
+enum ClipCursor {
+    Pcm {
+        sample_index: usize,
+    },
+    Adpcm(AdpcmCursor),
+    StereoPcm {
+        frame_index: usize,
+    },
+    StereoAdpcm(StereoAdpcmCursor),
+    Mulaw {
+        sample_index: usize,
+    },
+    Alaw {
+        sample_index: usize,
+    },
+    Qoa(QoaDecoder),
+    Silence {
+        remaining_sample_count: usize,
+    },
+    Synth(SynthCursor),
+    Stream {
+        block: [i16; STREAM_BLOCK_LEN],
+        block_len: usize,
+        block_index: usize,
+    },
+}
+
+impl ClipCursor {
+    fn new<const SAMPLE_RATE_HZ: u32>(audio_clip: &PlaybackClip<SAMPLE_RATE_HZ>) -> Self {
+        match audio_clip {
+            PlaybackClip::Pcm(_) => Self::Pcm { sample_index: 0 },
+            PlaybackClip::Adpcm(_) => Self::Adpcm(AdpcmCursor::new()),
+            PlaybackClip::StereoPcm(_) => Self::StereoPcm { frame_index: 0 },
+            PlaybackClip::StereoAdpcm(_) => Self::StereoAdpcm(StereoAdpcmCursor::new()),
+            PlaybackClip::Mulaw(_) => Self::Mulaw { sample_index: 0 },
+            PlaybackClip::Alaw(_) => Self::Alaw { sample_index: 0 },
+            PlaybackClip::Qoa(_) => Self::Qoa(QoaDecoder::new(1)),
+            PlaybackClip::StereoQoa(_) => Self::Qoa(QoaDecoder::new(2)),
+            PlaybackClip::Silence(duration) => Self::Silence {
+                remaining_sample_count: __samples_for_duration(*duration, SAMPLE_RATE_HZ),
+            },
+            PlaybackClip::Synth(melody) => Self::Synth(SynthCursor::new(*melody)),
+            PlaybackClip::Stream(_) => Self::Stream {
+                block: [0; STREAM_BLOCK_LEN],
+                block_len: 0,
+                block_index: 0,
+            },
+        }
+    }
+
+    /// Returns the next frame of `audio_clip`, or `None` at the end of the clip.
+    fn next_frame<const SAMPLE_RATE_HZ: u32>(
+        &mut self,
+        audio_clip: &PlaybackClip<SAMPLE_RATE_HZ>,
+    ) -> Option<Frame> {
+        match (self, audio_clip) {
+            (Self::Pcm { sample_index }, PlaybackClip::Pcm(pcm_clip)) => {
+                let sample = *pcm_clip.samples.get(*sample_index)?;
+                *sample_index += 1;
+                Some(Frame::Mono(sample))
+            }
+            (Self::Adpcm(adpcm_cursor), PlaybackClip::Adpcm(adpcm_clip)) => {
+                adpcm_cursor.next_sample(adpcm_clip).map(Frame::Mono)
+            }
+            (Self::StereoPcm { frame_index }, PlaybackClip::StereoPcm(stereo_pcm_clip)) => {
+                let [left_sample, right_sample] = *stereo_pcm_clip.frames.get(*frame_index)?;
+                *frame_index += 1;
+                Some(Frame::Stereo(left_sample, right_sample))
+            }
+            (
+                Self::StereoAdpcm(stereo_adpcm_cursor),
+                PlaybackClip::StereoAdpcm(stereo_adpcm_clip),
+            ) => stereo_adpcm_cursor.next_frame(stereo_adpcm_clip),
+            (Self::Mulaw { sample_index }, PlaybackClip::Mulaw(mulaw_clip)) => {
+                let mulaw_byte = *mulaw_clip.data.get(*sample_index)?;
+                *sample_index += 1;
+                Some(Frame::Mono(mulaw_decode(mulaw_byte)))
+            }
+            (Self::Alaw { sample_index }, PlaybackClip::Alaw(alaw_clip)) => {
+                let alaw_byte = *alaw_clip.data.get(*sample_index)?;
+                *sample_index += 1;
+                Some(Frame::Mono(alaw_decode(alaw_byte)))
+            }
+            (Self::Qoa(qoa_decoder), PlaybackClip::Qoa(qoa_clip)) => qoa_decoder
+                .next_frame(&qoa_clip.data)
+                .map(|[sample, _]| Frame::Mono(sample)),
+            (Self::Qoa(qoa_decoder), PlaybackClip::StereoQoa(stereo_qoa_clip)) => qoa_decoder
+                .next_frame(&stereo_qoa_clip.data)
+                .map(|[left_sample, right_sample]| Frame::Stereo(left_sample, right_sample)),
+            (
+                Self::Silence {
+                    remaining_sample_count,
+                },
+                PlaybackClip::Silence(_),
+            ) => {
+                if *remaining_sample_count == 0 {
+                    return None;
+                }
+                *remaining_sample_count -= 1;
+                Some(Frame::Mono(0))
+            }
+            (Self::Synth(synth_cursor), PlaybackClip::Synth(melody)) => {
+                synth_cursor.next_sample(*melody).map(Frame::Mono)
+            }
+            (
+                Self::Stream {
+                    block,
+                    block_len,
+                    block_index,
+                },
+                PlaybackClip::Stream(audio_stream),
+            ) => {
+                if *block_index == *block_len {
+                    *block_len = audio_stream.fill(block).min(STREAM_BLOCK_LEN);
+                    *block_index = 0;
+                    if *block_len == 0 {
+                        return None;
+                    }
+                }
+                let sample = block[*block_index];
+                *block_index += 1;
+                Some(Frame::Mono(sample))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Decoder state for one ADPCM clip.
+struct AdpcmCursor {
+    block_offset: usize,
+    nibble_index: usize,
+    samples_decoded_in_block: usize,
+    predictor_i32: i32,
+    step_index_i32: i32,
+}
+
+impl AdpcmCursor {
+    const fn new() -> Self {
+        Self {
+            block_offset: 0,
+            nibble_index: 0,
+            samples_decoded_in_block: 0,
+            predictor_i32: 0,
+            step_index_i32: 0,
+        }
+    }
+
+    /// Decodes the next sample. Stops at the end of the data or at a malformed block.
+    fn next_sample<const SAMPLE_RATE_HZ: u32>(
+        &mut self,
+        adpcm_clip: &AdpcmClip<SAMPLE_RATE_HZ>,
+    ) -> Option<i16> {
+        let block_align = adpcm_clip.block_align as usize;
+        let samples_per_block = adpcm_clip.samples_per_block as usize;
+        loop {
+            let block_end = self.block_offset.checked_add(block_align)?;
+            let adpcm_block = adpcm_clip.data.get(self.block_offset..block_end)?;
+            if adpcm_block.len() < 4 {
+                return None;
+            }
+
+            if self.samples_decoded_in_block == 0 {
+                let predictor_i16 = i16::from_le_bytes([adpcm_block[0], adpcm_block[1]]);
+                let step_index_i32 = adpcm_block[2] as i32;
+                if !(0..=88).contains(&step_index_i32) {
+                    return None;
+                }
+                self.predictor_i32 = predictor_i16 as i32;
+                self.step_index_i32 = step_index_i32;
+                self.nibble_index = 0;
+                self.samples_decoded_in_block = 1;
+                return Some(predictor_i16);
+            }
+
+            let nibble_data = &adpcm_block[4..];
+            if self.samples_decoded_in_block < samples_per_block
+                && self.nibble_index < nibble_data.len() * 2
+            {
+                let adpcm_byte = nibble_data[self.nibble_index / 2];
+                let adpcm_nibble = if self.nibble_index % 2 == 0 {
+                    adpcm_byte & 0x0F
+                } else {
+                    adpcm_byte >> 4
+                };
+                self.nibble_index += 1;
+                self.samples_decoded_in_block += 1;
+                return Some(decode_adpcm_nibble_const(
+                    adpcm_nibble,
+                    &mut self.predictor_i32,
+                    &mut self.step_index_i32,
+                ));
+            }
+
+            self.block_offset = block_end;
+            self.samples_decoded_in_block = 0;
+        }
+    }
+}
+
+/// Decoder state for one stereo ADPCM clip.
+struct StereoAdpcmCursor {
+    block_offset: usize,
+    frames_decoded_in_block: usize,
+    predictor_i32: [i32; 2],
+    step_index_i32: [i32; 2],
+}
+
+impl StereoAdpcmCursor {
+    const fn new() -> Self {
+        Self {
+            block_offset: 0,
+            frames_decoded_in_block: 0,
+            predictor_i32: [0; 2],
+            step_index_i32: [0; 2],
+        }
+    }
+
+    /// Decodes the next frame. Stops at the end of the data or at a malformed block.
+    fn next_frame<const SAMPLE_RATE_HZ: u32>(
+        &mut self,
+        stereo_adpcm_clip: &StereoAdpcmClip<SAMPLE_RATE_HZ>,
+    ) -> Option<Frame> {
+        let block_align = stereo_adpcm_clip.block_align as usize;
+        let samples_per_block = stereo_adpcm_clip.samples_per_block as usize;
+        let data = &stereo_adpcm_clip.data;
+        if self.frames_decoded_in_block == samples_per_block {
+            self.block_offset += block_align;
+            self.frames_decoded_in_block = 0;
+        }
+        let block_end = self.block_offset.checked_add(block_align)?;
+        if block_end > data.len() {
+            return None;
+        }
+
+        let mut samples = [0_i16; 2];
+        for (channel_index, sample) in samples.iter_mut().enumerate() {
+            if self.frames_decoded_in_block == 0 {
+                let header_start = self.block_offset + channel_index * 4;
+                let predictor_i16 =
+                    i16::from_le_bytes([data[header_start], data[header_start + 1]]);
+                let step_index_i32 = data[header_start + 2] as i32;
+                if !(0..=88).contains(&step_index_i32) {
+                    return None;
+                }
+                self.predictor_i32[channel_index] = predictor_i16 as i32;
+                self.step_index_i32[channel_index] = step_index_i32;
+                *sample = predictor_i16;
+            } else {
+                let adpcm_nibble = stereo_adpcm_nibble(
+                    data,
+                    self.block_offset,
+                    channel_index,
+                    self.frames_decoded_in_block - 1,
+                );
+                *sample = decode_adpcm_nibble_const(
+                    adpcm_nibble,
+                    &mut self.predictor_i32[channel_index],
+                    &mut self.step_index_i32[channel_index],
+                );
+            }
+        }
+        self.frames_decoded_in_block += 1;
+        Some(Frame::Stereo(samples[0], samples[1]))
+    }
+}
+
+/// Envelope level for full (unfaded) playback, on the [`Gain`](super::Gain) linear scale.
+const UNITY_LINEAR: i32 = i16::MAX as i32;
+
+/// Returns the envelope level `numerator / denominator` of the way up from silence.
+fn ramp_linear(numerator: usize, denominator: usize) -> i32 {
+    (UNITY_LINEAR as i64 * numerator as i64 / denominator as i64) as i32
+}
+
+impl Frame {
+    fn scaled(self, linear_i32: i32) -> Self {
+        match self {
+            Self::Mono(sample) => Self::Mono(scale_sample_with_linear(sample, linear_i32)),
+            Self::Stereo(left_sample, right_sample) => Self::Stereo(
+                scale_sample_with_linear(left_sample, linear_i32),
+                scale_sample_with_linear(right_sample, linear_i32),
+            ),
+        }
+    }
+
+    /// Sums two frames with saturation.
+    fn mixed(self, other: Self) -> Self {
+        match (self, other) {
+            (Self::Mono(sample), Self::Mono(other_sample)) => {
+                Self::Mono(sample.saturating_add(other_sample))
+            }
+            _ => {
+                let (left_sample, right_sample) = self.channels();
+                let (other_left_sample, other_right_sample) = other.channels();
+                Self::Stereo(
+                    left_sample.saturating_add(other_left_sample),
+                    right_sample.saturating_add(other_right_sample),
+                )
+            }
+        }
+    }
+
+    const fn channels(self) -> (i16, i16) {
+        match self {
+            Self::Mono(sample) => (sample, sample),
+            Self::Stereo(left_sample, right_sample) => (left_sample, right_sample),
+        }
+    }
+}
+
+/// Read position within one clip of a sequence, plus that clip's fade envelope.
+struct ClipPlayhead {
+    clip_index: usize,
+    clip_cursor: ClipCursor,
+    position: usize,
+    sample_count: Option<usize>,
+    fade_in_len: usize,
+    fade_out_len: usize,
+}
+
+impl ClipPlayhead {
+    const fn idle() -> Self {
+        Self {
+            clip_index: 0,
+            clip_cursor: ClipCursor::Silence {
+                remaining_sample_count: 0,
+            },
+            position: 0,
+            sample_count: Some(0),
+            fade_in_len: 0,
+            fade_out_len: 0,
+        }
+    }
+
+    /// Starts `sequence_clip`, fading it in over at least `crossfade_len` frames.
+    fn new<const SAMPLE_RATE_HZ: u32>(
+        clip_index: usize,
+        sequence_clip: &SequenceClip<SAMPLE_RATE_HZ>,
+        crossfade_len: usize,
+    ) -> Self {
+        Self {
+            clip_index,
+            clip_cursor: ClipCursor::new(&sequence_clip.playback_clip),
+            position: 0,
+            sample_count: sequence_clip.playback_clip.sample_count(),
+            fade_in_len: __samples_for_duration(sequence_clip.fade_in, SAMPLE_RATE_HZ)
+                .max(crossfade_len),
+            fade_out_len: __samples_for_duration(sequence_clip.fade_out, SAMPLE_RATE_HZ),
+        }
+    }
+
+    /// Returns the frames left in the clip, or `None` if its length isn't known.
+    fn remaining_len(&self) -> Option<usize> {
+        self.sample_count
+            .map(|sample_count| sample_count.saturating_sub(self.position))
+    }
+
+    fn envelope_linear(&self) -> i32 {
+        let mut linear_i32 = UNITY_LINEAR;
+        if self.position < self.fade_in_len {
+            linear_i32 = ramp_linear(self.position, self.fade_in_len);
+        }
+        if let Some(remaining_len) = self.remaining_len()
+            && remaining_len <= self.fade_out_len
+        {
+            linear_i32 = linear_i32.min(ramp_linear(remaining_len, self.fade_out_len));
+        }
+        linear_i32
+    }
+
+    fn next_frame<const SAMPLE_RATE_HZ: u32>(
+        &mut self,
+        sequence_clip: &SequenceClip<SAMPLE_RATE_HZ>,
+    ) -> Option<Frame> {
+        let frame = self.clip_cursor.next_frame(&sequence_clip.playback_clip)?;
+        let linear_i32 = self.envelope_linear();
+        self.position += 1;
+        Some(if linear_i32 == UNITY_LINEAR {
+            frame
+        } else {
+            frame.scaled(linear_i32)
+        })
+    }
+}
+
+/// A fade to silence in progress, after which the voice stops.
+#[derive(Clone, Copy)]
+struct StopFade {
+    remaining_len: usize,
+    fade_len: usize,
+}
+
+/// One voice's clip sequence and read position.
+pub(crate) struct Voice<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> {
+    audio_clips: Vec<SequenceClip<SAMPLE_RATE_HZ>, MAX_CLIPS>,
+    at_end: AtEnd,
+    crossfade_len: usize,
+    playhead: ClipPlayhead,
+    /// The next clip, while it crossfades in over the end of [`Self::playhead`].
+    incoming_playhead: Option<ClipPlayhead>,
+    stop_fade: Option<StopFade>,
+    priority: Priority,
+    /// Gain applied to the other voices while this sequence plays.
+    duck_gain: Option<Gain>,
+    /// The sequence to play after this one ends.
+    queued_play: Option<PriorityPlay<MAX_CLIPS, SAMPLE_RATE_HZ>>,
+    /// Events since the last [`Mixer::publish_progress`].
+    playback_events: Vec<PlaybackEvent, PLAYBACK_EVENT_CAPACITY>,
+    is_playing: bool,
+    is_pass_silent: bool,
+    has_finished: bool,
+}
+
+impl<const MAX_CLIPS: usize, const SAMPLE_RATE_HZ: u32> Voice<MAX_CLIPS, SAMPLE_RATE_HZ> {
+    pub(crate) const fn new() -> Self {
+        Self {
+            audio_clips: Vec::new(),
+            at_end: AtEnd::Stop,
+            crossfade_len: 0,
+            playhead: ClipPlayhead::idle(),
+            incoming_playhead: None,
+            stop_fade: None,
+            priority: Priority::Normal,
+            duck_gain: None,
+            queued_play: None,
+            playback_events: Vec::new(),
+            is_playing: false,
+            is_pass_silent: true,
+            has_finished: false,
+        }
+    }
+
+    pub(crate) const fn is_playing(&self) -> bool {
+        self.is_playing
+    }
+
+    /// Returns the current clip and offset, or `None` if the voice is not playing.
+    const fn position(&self) -> Option<PlaybackPosition> {
+        if !self.is_playing {
+            return None;
+        }
+        Some(PlaybackPosition {
+            clip_index: self.playhead.clip_index,
+            sample_offset: self.playhead.position,
+            sample_count: self.playhead.sample_count,
+        })
+    }
+
+    /// Records `playback_event`, dropping the oldest unpublished event if the buffer is full.
+    fn push_event(&mut self, playback_event: PlaybackEvent) {
+        if self.playback_events.is_full() {
+            self.playback_events.remove(0);
+        }
+        let _ = self.playback_events.push(playback_event);
+    }
+
+    /// Returns the gain this voice applies to the other voices, if it is ducking them.
+    const fn duck_gain(&self) -> Option<Gain> {
+        if self.is_playing {
+            self.duck_gain
+        } else {
+            None
+        }
+    }
+
+    /// Replaces whatever this voice was playing, or had queued, with `audio_clips`.
+    pub(crate) fn play(
+        &mut self,
+        audio_clips: Vec<SequenceClip<SAMPLE_RATE_HZ>, MAX_CLIPS>,
+        at_end: AtEnd,
+        crossfade: Duration,
+    ) {
+        self.queued_play = None;
+        self.start(audio_clips, at_end, crossfade, Priority::Normal, None);
+    }
+
+    /// Plays, queues, or drops `priority_play`, depending on what this voice is playing.
+    pub(crate) fn play_with_priority(
+        &mut self,
+        priority_play: PriorityPlay<MAX_CLIPS, SAMPLE_RATE_HZ>,
+    ) -> PlayOutcome {
+        let duck_gain = match priority_play.interrupt_policy {
+            InterruptPolicy::Enqueue if self.is_playing => {
+                if self
+                    .queued_play
+                    .as_ref()
+                    .is_some_and(|queued_play| queued_play.priority >= priority_play.priority)
+                {
+                    return PlayOutcome::Dropped;
+                }
+                self.queued_play = Some(priority_play);
+                return PlayOutcome::Queued;
+            }
+            InterruptPolicy::Enqueue => None,
+            InterruptPolicy::Preempt | InterruptPolicy::Duck(_)
+                if self.is_playing && priority_play.priority <= self.priority =>
+            {
+                return PlayOutcome::Dropped;
+            }
+            InterruptPolicy::Preempt => None,
+            InterruptPolicy::Duck(duck_gain) => Some(duck_gain),
+        };
+        self.start(
+            priority_play.audio_clips,
+            priority_play.at_end,
+            Duration::ZERO,
+            priority_play.priority,
+            duck_gain,
+        );
+        PlayOutcome::Played
+    }
+
+    fn start(
+        &mut self,
+        audio_clips: Vec<SequenceClip<SAMPLE_RATE_HZ>, MAX_CLIPS>,
+        at_end: AtEnd,
+        crossfade: Duration,
+        priority: Priority,
+        duck_gain: Option<Gain>,
+    ) {
+        self.priority = priority;
+        self.duck_gain = duck_gain;
+        self.audio_clips = audio_clips;
+        self.at_end = at_end;
+        self.crossfade_len = __samples_for_duration(crossfade, SAMPLE_RATE_HZ);
+        self.incoming_playhead = None;
+        self.stop_fade = None;
+        self.is_pass_silent = true;
+        self.has_finished = false;
+        self.is_playing = !self.audio_clips.is_empty();
+        if let Some(sequence_clip) = self.audio_clips.first() {
+            self.playhead = ClipPlayhead::new(0, sequence_clip, 0);
+            self.push_event(PlaybackEvent::ClipStarted { clip_index: 0 });
+        }
+    }
+
+    pub(crate) fn stop(&mut self) {
+        if self.is_playing {
+            self.push_event(PlaybackEvent::SequenceEnded);
+        }
+        self.queued_play = None;
+        self.audio_clips.clear();
+        self.incoming_playhead = None;
+        self.stop_fade = None;
+        self.is_playing = false;
+        self.has_finished = false;
+    }
+
+    /// Fades this voice out over `fade_len` frames, then stops it as if its sequence ended.
+    ///
+    /// A fade already in progress continues from its current level, and is only ever
+    /// shortened.
+    pub(crate) fn stop_with_fade(&mut self, fade_len: usize) {
+        self.queued_play = None;
+        self.stop_fade = match self.stop_fade {
+            Some(stop_fade) if stop_fade.remaining_len <= fade_len => Some(stop_fade),
+            Some(stop_fade) => Some(StopFade {
+                remaining_len: fade_len,
+                fade_len: (fade_len as u64 * stop_fade.fade_len as u64
+                    / stop_fade.remaining_len as u64) as usize,
+            }),
+            None => Some(StopFade {
+                remaining_len: fade_len,
+                fade_len,
+            }),
+        };
+    }
+
+    /// Returns `true` once after the clip sequence ends on its own.
+    pub(crate) fn take_finished(&mut self) -> bool {
+        core::mem::take(&mut self.has_finished)
+    }
+
+    /// Returns the next frame, or `None` if the voice is not playing.
+    pub(crate) fn next_frame(&mut self) -> Option<Frame> {
+        if !self.is_playing {
+            return None;
+        }
+        let frame = self.next_sequence_frame()?;
+        let Some(stop_fade) = &mut self.stop_fade else {
+            return Some(frame);
+        };
+        if stop_fade.remaining_len == 0 {
+            self.finish();
+            return None;
+        }
+        let linear_i32 = ramp_linear(stop_fade.remaining_len, stop_fade.fade_len);
+        stop_fade.remaining_len -= 1;
+        Some(frame.scaled(linear_i32))
+    }
+
+    fn next_sequence_frame(&mut self) -> Option<Frame> {
+        loop {
+            self.start_crossfade_if_due();
+            if let Some(frame) = self
+                .playhead
+                .next_frame(&self.audio_clips[self.playhead.clip_index])
+            {
+                self.is_pass_silent = false;
+                let Some(incoming_playhead) = &mut self.incoming_playhead else {
+                    return Some(frame);
+                };
+                return Some(
+                    match incoming_playhead
+                        .next_frame(&self.audio_clips[incoming_playhead.clip_index])
+                    {
+                        Some(incoming_frame) => frame.mixed(incoming_frame),
+                        None => frame,
+                    },
+                );
+            }
+            self.push_event(PlaybackEvent::ClipEnded {
+                clip_index: self.playhead.clip_index,
+            });
+
+            if let Some(incoming_playhead) = self.incoming_playhead.take() {
+                if incoming_playhead.clip_index == 0 {
+                    self.is_pass_silent = true;
+                }
+                self.playhead = incoming_playhead;
+                continue;
+            }
+
+            let Some(clip_index) = self.next_clip_index() else {
+                self.finish();
+                return None;
+            };
+            if clip_index == 0 {
+                // A looping sequence with no samples at all would spin forever; end it instead.
+                if self.is_pass_silent {
+                    self.finish();
+                    return None;
+                }
+                self.is_pass_silent = true;
+            }
+            self.playhead = ClipPlayhead::new(clip_index, &self.audio_clips[clip_index], 0);
+            self.push_event(PlaybackEvent::ClipStarted { clip_index });
+        }
+    }
+
+    /// Returns the index of the clip after the current one, if the sequence continues.
+    fn next_clip_index(&self) -> Option<usize> {
+        let clip_index = self.playhead.clip_index + 1;
+        if clip_index < self.audio_clips.len() {
+            Some(clip_index)
+        } else if matches!(self.at_end, AtEnd::Loop) {
+            Some(0)
+        } else {
+            None
+        }
+    }
+
+    /// Starts the next clip once the current one is within the crossfade of its end.
+    fn start_crossfade_if_due(&mut self) {
+        if self.crossfade_len == 0 || self.incoming_playhead.is_some() {
+            return;
+        }
+        let (Some(sample_count), Some(remaining_len), Some(clip_index)) = (
+            self.playhead.sample_count,
+            self.playhead.remaining_len(),
+            self.next_clip_index(),
+        ) else {
+            return;
+        };
+        let sequence_clip = &self.audio_clips[clip_index];
+        let crossfade_len = self.crossfade_len.min(sample_count).min(
+            sequence_clip
+                .playback_clip
+                .sample_count()
+                .unwrap_or(usize::MAX),
+        );
+        if crossfade_len == 0 || remaining_len != crossfade_len {
+            return;
+        }
+        self.playhead.fade_out_len = self.playhead.fade_out_len.max(crossfade_len);
+        self.incoming_playhead = Some(ClipPlayhead::new(clip_index, sequence_clip, crossfade_len));
+        self.push_event(PlaybackEvent::ClipStarted { clip_index });
+    }
+
+    /// Ends the current sequence, starting the queued one, if any.
+    fn finish(&mut self) {
+        self.push_event(PlaybackEvent::SequenceEnded);
+        if let Some(queued_play) = self.queued_play.take() {
+            let duck_gain = match queued_play.interrupt_policy {
+                InterruptPolicy::Duck(duck_gain) => Some(duck_gain),
+                InterruptPolicy::Enqueue | InterruptPolicy::Preempt => None,
+            };
+            self.start(
+                queued_play.audio_clips,
+                queued_play.at_end,
+                Duration::ZERO,
+                queued_play.priority,
+                duck_gain,
+            );
+            return;
+        }
+        self.audio_clips.clear();
+        self.incoming_playhead = None;
+        self.stop_fade = None;
+        self.is_playing = false;
+        self.has_finished = true;
+    }
+}
+
+/// Sums all voices of a player into output sample buffers.
+///
+/// `ECHO_FRAMES` sizes the echo memory of the [`Dsp`](super::Dsp) chain, in stereo frames.
+pub(crate) struct Mixer<
+    const MAX_CLIPS: usize,
+    const SAMPLE_RATE_HZ: u32,
+    const VOICES: usize,
+    const ECHO_FRAMES: usize = 0,
+> {
+    voices: [Voice<MAX_CLIPS, SAMPLE_RATE_HZ>; VOICES],
+    dsp_state: DspState<SAMPLE_RATE_HZ, ECHO_FRAMES>,
+}
+
+impl<
+    const MAX_CLIPS: usize,
+    const SAMPLE_RATE_HZ: u32,
+    const VOICES: usize,
+    const ECHO_FRAMES: usize,
+> Mixer<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES, ECHO_FRAMES>
+{
+    pub(crate) const fn new() -> Self {
+        Self {
+            voices: [const { Voice::new() }; VOICES],
+            dsp_state: DspState::new(),
+        }
+    }
+
+    /// Returns `true` when no voice is playing and no echo is still fading out.
+    pub(crate) fn is_idle(&self) -> bool {
+        !self.voices.iter().any(Voice::is_playing) && !self.dsp_state.is_ringing()
+    }
+
+    /// Applies any commands sent to the player's voices since the last call.
+    pub(crate) fn apply_commands(
+        &mut self,
+        audio_player_static: &AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
+    ) {
+        if let Some(dsp) = audio_player_static.dsp_signal.try_take() {
+            self.dsp_state.configure(dsp);
+        }
+        for (voice, voice_static) in self.voices.iter_mut().zip(&audio_player_static.voices) {
+            match voice_static.command_signal.try_take() {
+                Some(AudioCommand::Play {
+                    audio_clips,
+                    at_end,
+                    crossfade,
+                }) => {
+                    voice.play(audio_clips, at_end, crossfade);
+                    voice_static.mark_playing();
+                }
+                Some(AudioCommand::Stop) => {
+                    voice.stop();
+                    voice_static.mark_stopped();
+                }
+                Some(AudioCommand::StopWithFade(duration)) => {
+                    let fade_len = __samples_for_duration(duration, SAMPLE_RATE_HZ);
+                    if voice.is_playing() && fade_len > 0 {
+                        voice.stop_with_fade(fade_len);
+                    } else {
+                        voice.stop();
+                        voice_static.mark_stopped();
+                    }
+                }
+                None => {}
+            }
+            if let Some(priority_play) = voice_static.priority_play_signal.try_take() {
+                let request_id = priority_play.request_id;
+                let play_outcome = voice.play_with_priority(priority_play);
+                if play_outcome == PlayOutcome::Played {
+                    voice_static.mark_playing();
+                }
+                voice_static
+                    .play_outcome_signal
+                    .signal((request_id, play_outcome));
+            }
+        }
+    }
+
+    /// Fills `sample_buffer` with the next stereo frames, summing the voices with saturation.
+    ///
+    /// Mono clips play on both channels, placed by the voice's pan. Stereo clips keep their
+    /// channels, with pan acting as balance.
+    ///
+    /// Voices that are not playing contribute silence. A voice playing an
+    /// [`InterruptPolicy::Duck`] sequence lowers all other voices, updated once per buffer.
+    /// The sum then runs through the [`Dsp`](super::Dsp) chain, unless it is bypassed.
+    pub(crate) fn fill(
+        &mut self,
+        audio_player_static: &AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
+        sample_buffer: &mut [u32],
+    ) {
+        let runtime_volume = audio_player_static.effective_runtime_volume();
+        let linear_by_voice: [(i32, i32); VOICES] = core::array::from_fn(|voice_index| {
+            let voice_static = &audio_player_static.voices[voice_index];
+            let duck_linear_i32 = self
+                .voices
+                .iter()
+                .enumerate()
+                .filter(|(other_voice_index, _)| *other_voice_index != voice_index)
+                .filter_map(|(_, other_voice)| other_voice.duck_gain())
+                .map(Gain::linear)
+                .min()
+                .unwrap_or(UNITY_LINEAR);
+            let gain_linear_i32 = (voice_static.gain().linear() as i64 * duck_linear_i32 as i64
+                / UNITY_LINEAR as i64) as i32;
+            voice_static
+                .pan()
+                .split_linear(scale_linear(gain_linear_i32, runtime_volume))
+        });
+
+        // Choose the loop once per buffer, so a bypassed chain costs nothing per frame.
+        if self.dsp_state.is_bypass() {
+            for sample_buffer_slot in sample_buffer {
+                let (mixed_left_i32, mixed_right_i32) = self.mix_frame(&linear_by_voice);
+                *sample_buffer_slot = stereo_frame(
+                    clamp_i64_to_i16(mixed_left_i32 as i64),
+                    clamp_i64_to_i16(mixed_right_i32 as i64),
+                );
+            }
+        } else {
+            for sample_buffer_slot in sample_buffer {
+                let (mixed_left_i32, mixed_right_i32) = self.mix_frame(&linear_by_voice);
+                let (left_sample, right_sample) =
+                    self.dsp_state.process(mixed_left_i32, mixed_right_i32);
+                *sample_buffer_slot = stereo_frame(left_sample, right_sample);
+            }
+        }
+    }
+
+    /// Sums the next frame of every voice, scaled by its `(left, right)` linear gain.
+    #[inline]
+    fn mix_frame(&mut self, linear_by_voice: &[(i32, i32); VOICES]) -> (i32, i32) {
+        let mut mixed_left_i32 = 0_i32;
+        let mut mixed_right_i32 = 0_i32;
+        for (voice, &(left_linear_i32, right_linear_i32)) in
+            self.voices.iter_mut().zip(linear_by_voice)
+        {
+            let (left_sample, right_sample) = match voice.next_frame() {
+                None => continue,
+                Some(Frame::Mono(sample)) => {
+                    let left_sample = scale_sample_with_linear(sample, left_linear_i32);
+                    // Centered mono voices (the common case) scale once.
+                    let right_sample = if left_linear_i32 == right_linear_i32 {
+                        left_sample
+                    } else {
+                        scale_sample_with_linear(sample, right_linear_i32)
+                    };
+                    (left_sample, right_sample)
+                }
+                Some(Frame::Stereo(left_sample, right_sample)) => (
+                    scale_sample_with_linear(left_sample, left_linear_i32),
+                    scale_sample_with_linear(right_sample, right_linear_i32),
+                ),
+            };
+            mixed_left_i32 += left_sample as i32;
+            mixed_right_i32 += right_sample as i32;
+        }
+        (mixed_left_i32, mixed_right_i32)
+    }
+
+    /// Publishes each voice's position and events from the last [`Self::fill`], and marks
+    /// voices whose sequences ended as stopped.
+    ///
+    /// Call this after the filled buffer has been written, so waiters wake once the
+    /// samples are out.
+    pub(crate) fn publish_progress(
+        &mut self,
+        audio_player_static: &AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
+    ) {
+        for (voice, voice_static) in self.voices.iter_mut().zip(&audio_player_static.voices) {
+            for playback_event in &voice.playback_events {
+                voice_static.publish_event(*playback_event);
+            }
+            voice.playback_events.clear();
+            if voice.take_finished() {
+                voice_static.mark_stopped();
+            } else if voice.is_playing() {
+                voice_static.set_position(voice.position());
+            }
+        }
+    }
+}
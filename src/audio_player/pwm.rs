@@ -0,0 +1,151 @@
+//! PWM output: plays through a speaker or piezo on one GPIO pin instead of an I²S amp.
+
+#[cfg(target_os = "none")]
+use super::mixer::Mixer;
+#[cfg(target_os = "none")]
+use super::{AudioPlayerStatic, SAMPLE_BUFFER_LEN};
+#[cfg(target_os = "none")]
+use crate::pio_irqs::PioIrqMap;
+#[cfg(target_os = "none")]
+use embassy_rp::Peri;
+#[cfg(target_os = "none")]
+use embassy_rp::dma::Channel;
+#[cfg(target_os = "none")]
+use embassy_rp::gpio::Pin;
+#[cfg(target_os = "none")]
+use embassy_rp::pio::{Config, Direction, FifoJoin, Pio, PioPin, ShiftConfig, ShiftDirection};
+#[cfg(target_os = "none")]
+use fixed::traits::ToFixed;
+
+/// Lowest PWM carrier frequency; each sample repeats until the carrier is above hearing.
+const PWM_CARRIER_MIN_HZ: u32 = 40_000;
+/// PIO cycles in each PWM period spent outside the high and low counting loops.
+const PWM_OVERHEAD_CYCLES: u32 = 6;
+/// PWM periods sent to the PIO per DMA transfer.
+#[cfg(target_os = "none")]
+const PWM_BUFFER_LEN: usize = 256;
+
+/// Returns how many PWM periods play each sample at `sample_rate_hz`.
+pub(crate) const fn pwm_periods_per_sample(sample_rate_hz: u32) -> u32 {
+    assert!(sample_rate_hz > 0, "sample_rate_hz must be > 0");
+    PWM_CARRIER_MIN_HZ.div_ceil(sample_rate_hz)
+}
+
+/// Returns the PIO cycles in each PWM period, given the PIO clock.
+pub(crate) const fn pwm_period_cycles(clock_hz: u32, sample_rate_hz: u32) -> u32 {
+    let period_cycles = clock_hz / (sample_rate_hz * pwm_periods_per_sample(sample_rate_hz));
+    assert!(
+        period_cycles > PWM_OVERHEAD_CYCLES && period_cycles - PWM_OVERHEAD_CYCLES <= 0xFFFF,
+        "sample rate is out of range for PWM output"
+    );
+    period_cycles
+}
+
+/// Packs one PWM period for the PIO program: the high count in the low half-word and
+/// the low count in the high half-word.
+///
+/// The left and right channels of `frame` (as packed for I²S) are averaged to mono.
+#[inline]
+pub(crate) const fn pwm_word(frame: u32, period_cycles: u32) -> u32 {
+    let count_total = period_cycles - PWM_OVERHEAD_CYCLES;
+    let left_sample_i32 = (frame >> 16) as u16 as i16 as i32;
+    let right_sample_i32 = frame as u16 as i16 as i32;
+    let level_u32 = ((left_sample_i32 + right_sample_i32) / 2 - i16::MIN as i32) as u32;
+    let high_count = (level_u32 * count_total) >> 16;
+    ((count_total - high_count) << 16) | high_count
+}
+
+// Called by macro-generated code in downstream crates; must be public.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub async fn pwm_device_loop<
+    const MAX_CLIPS: usize,
+    const SAMPLE_RATE_HZ: u32,
+    const VOICES: usize,
+    const ECHO_FRAMES: usize,
+    PIO: PioIrqMap,
+    DMA: Channel,
+    PwmPin: Pin + PioPin,
+>(
+    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
+    pio: Peri<'static, PIO>,
+    mut dma: Peri<'static, DMA>,
+    pwm_pin: Peri<'static, PwmPin>,
+) -> ! {
+    let mut pio = Pio::new(pio, <PIO as PioIrqMap>::irqs());
+    // Each word holds one PWM period: the pin is high for `high + 1` cycles, then low
+    // for `low + 5` cycles (including the other four instructions). When the FIFO is
+    // empty, `pull noblock` reloads the last word from `x`, so the pin keeps repeating
+    // it instead of stalling.
+    let program = pio::pio_asm!(
+        ".side_set 1",
+        ".wrap_target",
+        "    pull noblock side 0",
+        "    mov x, osr   side 0",
+        "    out y, 16    side 0",
+        "high:",
+        "    jmp y-- high side 1",
+        "    out y, 16    side 0",
+        "low:",
+        "    jmp y-- low  side 0",
+        ".wrap",
+    );
+    let loaded_program = pio.common.load_program(&program.program);
+    let pwm_pin = pio.common.make_pio_pin(pwm_pin);
+
+    let clock_hz = embassy_rp::clocks::clk_sys_freq();
+    let periods_per_sample = pwm_periods_per_sample(SAMPLE_RATE_HZ);
+    let period_cycles = pwm_period_cycles(clock_hz, SAMPLE_RATE_HZ);
+    let mut config = Config::default();
+    config.use_program(&loaded_program, &[&pwm_pin]);
+    // Stretch each cycle slightly so whole-cycle periods land exactly on the sample rate.
+    config.clock_divider = (clock_hz as f64
+        / (SAMPLE_RATE_HZ as f64 * periods_per_sample as f64 * period_cycles as f64))
+        .to_fixed();
+    config.shift_out = ShiftConfig {
+        threshold: 32,
+        direction: ShiftDirection::Right,
+        auto_fill: false,
+    };
+    config.fifo_join = FifoJoin::TxOnly;
+    let mut state_machine = pio.sm0;
+    state_machine.set_config(&config);
+    state_machine.set_pin_dirs(Direction::Out, &[&pwm_pin]);
+    state_machine.set_enable(true);
+
+    // Silence is a 50% duty cycle. Idling there rather than low keeps playback from
+    // clicking as it starts and stops.
+    let silence_word = pwm_word(0, period_cycles);
+    state_machine.tx().wait_push(silence_word).await;
+
+    let periods_per_sample = periods_per_sample as usize;
+    let samples_per_transfer = PWM_BUFFER_LEN / periods_per_sample;
+    let mut sample_buffer = [0_u32; SAMPLE_BUFFER_LEN];
+    let mut pwm_buffer = [0_u32; PWM_BUFFER_LEN];
+    let mut mixer = Mixer::<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES, ECHO_FRAMES>::new();
+
+    loop {
+        mixer.apply_commands(audio_player_static);
+        if mixer.is_idle() {
+            // The PIO repeats the last word it pulled, so end on silence.
+            state_machine.tx().wait_push(silence_word).await;
+            audio_player_static.wait_for_command().await;
+            continue;
+        }
+
+        mixer.fill(audio_player_static, &mut sample_buffer);
+        for frames in sample_buffer.chunks(samples_per_transfer) {
+            let mut pwm_len = 0;
+            for &frame in frames {
+                let word = pwm_word(frame, period_cycles);
+                pwm_buffer[pwm_len..pwm_len + periods_per_sample].fill(word);
+                pwm_len += periods_per_sample;
+            }
+            state_machine
+                .tx()
+                .dma_push(dma.reborrow(), &pwm_buffer[..pwm_len], false)
+                .await;
+        }
+        mixer.publish_progress(audio_player_static);
+    }
+}
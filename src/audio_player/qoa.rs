@@ -0,0 +1,974 @@
+//! QOA ("Quite OK Audio") clip types: about 3.2 bits per sample (20% the size of PCM), with
+//! quality close to the original even for music.
+//!
+//! See [`QoaClip`] and [`StereoQoaClip`]. The data is a complete `.qoa` file, as written by
+//! the reference `qoaconv` tool (<https://qoaformat.org>).
+
+use super::{Gain, PcmClip, PcmClipBuf, StereoPcmClip, StereoPcmClipBuf, scale_sample_with_linear};
+
+const QOA_MAGIC: u32 = 0x716F_6166; // 'qoaf'
+const QOA_FILE_HEADER_LEN: usize = 8;
+const QOA_FRAME_HEADER_LEN: usize = 8;
+/// Bytes of LMS history and weights per channel at the start of each frame.
+const QOA_LMS_STATE_LEN: usize = 16;
+const QOA_SLICE_LEN: usize = 20;
+const QOA_SLICES_PER_FRAME: usize = 256;
+const QOA_FRAME_LEN: usize = QOA_SLICE_LEN * QOA_SLICES_PER_FRAME;
+
+const QOA_SCALEFACTOR_COUNT: usize = 16;
+/// Quantized residual (index `-8..=8` offset by 8) to 3-bit code.
+const QOA_QUANT_TABLE: [u8; 17] = [7, 7, 7, 5, 5, 3, 3, 1, 0, 0, 2, 2, 4, 4, 6, 6, 6];
+/// `65536 / scalefactor`, rounded up, for each scalefactor.
+const QOA_RECIPROCAL_TABLE: [i64; QOA_SCALEFACTOR_COUNT] = [
+    65536, 9363, 3121, 1457, 781, 475, 311, 216, 156, 117, 90, 71, 57, 47, 39, 32,
+];
+/// Residual for each scalefactor and 3-bit code.
+const QOA_DEQUANT_TABLE: [[i32; 8]; QOA_SCALEFACTOR_COUNT] = [
+    [1, -1, 3, -3, 5, -5, 7, -7],
+    [5, -5, 18, -18, 32, -32, 49, -49],
+    [16, -16, 53, -53, 95, -95, 147, -147],
+    [34, -34, 113, -113, 203, -203, 315, -315],
+    [63, -63, 210, -210, 378, -378, 588, -588],
+    [104, -104, 345, -345, 621, -621, 966, -966],
+    [158, -158, 528, -528, 950, -950, 1477, -1477],
+    [228, -228, 760, -760, 1368, -1368, 2128, -2128],
+    [316, -316, 1053, -1053, 1895, -1895, 2947, -2947],
+    [422, -422, 1405, -1405, 2529, -2529, 3934, -3934],
+    [548, -548, 1828, -1828, 3290, -3290, 5117, -5117],
+    [696, -696, 2320, -2320, 4176, -4176, 6496, -6496],
+    [868, -868, 2893, -2893, 5207, -5207, 8099, -8099],
+    [1064, -1064, 3548, -3548, 6386, -6386, 9933, -9933],
+    [1286, -1286, 4288, -4288, 7718, -7718, 12005, -12005],
+    [1536, -1536, 5120, -5120, 9216, -9216, 14336, -14336],
+];
+
+/// Unsized view of static compressed (QOA) mono clip data.
+///
+/// For fixed-size, const-friendly storage, see [`QoaClipBuf`]. Use
+/// [`qoa_clip!`](macro@crate::audio_player::qoa_clip) to read one from a `.qoa` file.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+pub struct QoaClip<const SAMPLE_RATE_HZ: u32, T: ?Sized = [u8]> {
+    pub(super) sample_count: u32,
+    pub(super) data: T,
+}
+
+/// Sized, const-friendly storage for compressed (QOA) mono clip data.
+pub type QoaClipBuf<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> =
+    QoaClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>;
+
+/// Unsized view of static compressed (QOA) stereo clip data.
+///
+/// For fixed-size, const-friendly storage, see [`StereoQoaClipBuf`]. Use
+/// [`qoa_clip!`](macro@crate::audio_player::qoa_clip) with `channels: 2` to read one
+/// from a file.
+pub struct StereoQoaClip<const SAMPLE_RATE_HZ: u32, T: ?Sized = [u8]> {
+    pub(super) frame_count: u32,
+    pub(super) data: T,
+}
+
+/// Sized, const-friendly storage for compressed (QOA) stereo clip data.
+pub type StereoQoaClipBuf<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> =
+    StereoQoaClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>;
+
+/// **Implementation for fixed-size clips (`QoaClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> QoaClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]> {
+    /// Returns the uncompressed (PCM) version of this clip.
+    ///
+    /// `SAMPLE_COUNT` is the number of samples in the resulting PCM clip.
+    /// Typically, use the generated clip-module constant `PCM_SAMPLE_COUNT`.
+    #[must_use]
+    pub const fn with_pcm<const SAMPLE_COUNT: usize>(
+        &self,
+    ) -> PcmClipBuf<SAMPLE_RATE_HZ, SAMPLE_COUNT> {
+        assert!(
+            SAMPLE_COUNT == self.sample_count as usize,
+            "sample count must match decoded QOA length"
+        );
+        let mut samples = [0_i16; SAMPLE_COUNT];
+        let mut qoa_decoder = QoaDecoder::new(1);
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            let Some([sample, _]) = qoa_decoder.next_frame(&self.data) else {
+                panic!("QOA data ended early");
+            };
+            samples[sample_index] = sample;
+            sample_index += 1;
+        }
+        assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+        PcmClip { samples }
+    }
+
+    /// Returns this fixed-size QOA clip with linear sample gain applied.
+    ///
+    /// This decodes QOA to PCM, applies gain, then re-encodes QOA. Encoding QOA
+    /// searches 16 scale factors for every 20 samples, so this is much slower to
+    /// compile than gain on PCM or ADPCM.
+    #[must_use]
+    pub const fn with_gain(self, gain: Gain) -> Self {
+        let sample_count = self.sample_count as usize;
+        let mut gained_data = [0_u8; DATA_LEN];
+        let mut qoa_decoder = QoaDecoder::new(1);
+        let mut qoa_encoder = QoaEncoder::new(1, sample_count, SAMPLE_RATE_HZ, &mut gained_data);
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < sample_count {
+            let Some([sample, _]) = qoa_decoder.next_frame(&self.data) else {
+                panic!("QOA data ended early");
+            };
+            let gained_sample = scale_sample_with_linear(sample, gain.linear());
+            qoa_encoder.push_frame([gained_sample, gained_sample], &mut gained_data);
+            sample_index += 1;
+        }
+        Self {
+            sample_count: self.sample_count,
+            data: gained_data,
+        }
+    }
+}
+
+/// **Implementation for fixed-size clips (`StereoQoaClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize>
+    StereoQoaClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>
+{
+    /// Returns the uncompressed (PCM) version of this clip.
+    ///
+    /// `FRAME_COUNT` is the number of `[left, right]` frames in the resulting PCM clip.
+    /// Typically, use the generated clip-module constant `PCM_SAMPLE_COUNT`.
+    #[must_use]
+    pub const fn with_pcm<const FRAME_COUNT: usize>(
+        &self,
+    ) -> StereoPcmClipBuf<SAMPLE_RATE_HZ, FRAME_COUNT> {
+        assert!(
+            FRAME_COUNT == self.frame_count as usize,
+            "frame count must match decoded QOA length"
+        );
+        let mut frames = [[0_i16; 2]; FRAME_COUNT];
+        let mut qoa_decoder = QoaDecoder::new(2);
+        let mut frame_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while frame_index < FRAME_COUNT {
+            let Some(frame) = qoa_decoder.next_frame(&self.data) else {
+                panic!("QOA data ended early");
+            };
+            frames[frame_index] = frame;
+            frame_index += 1;
+        }
+        assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+        StereoPcmClip { frames }
+    }
+
+    /// Returns this fixed-size stereo QOA clip with linear sample gain applied to both
+    /// channels.
+    ///
+    /// Like [`QoaClipBuf::with_gain`], this decodes, applies gain, and re-encodes.
+    #[must_use]
+    pub const fn with_gain(self, gain: Gain) -> Self {
+        let frame_count = self.frame_count as usize;
+        let mut gained_data = [0_u8; DATA_LEN];
+        let mut qoa_decoder = QoaDecoder::new(2);
+        let mut qoa_encoder = QoaEncoder::new(2, frame_count, SAMPLE_RATE_HZ, &mut gained_data);
+        let mut frame_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while frame_index < frame_count {
+            let Some([left_sample, right_sample]) = qoa_decoder.next_frame(&self.data) else {
+                panic!("QOA data ended early");
+            };
+            qoa_encoder.push_frame(
+                [
+                    scale_sample_with_linear(left_sample, gain.linear()),
+                    scale_sample_with_linear(right_sample, gain.linear()),
+                ],
+                &mut gained_data,
+            );
+            frame_index += 1;
+        }
+        Self {
+            frame_count: self.frame_count,
+            data: gained_data,
+        }
+    }
+}
+
+/// **QOA encoding for fixed-size clips (`PcmClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const SAMPLE_COUNT: usize>
+    PcmClip<SAMPLE_RATE_HZ, [i16; SAMPLE_COUNT]>
+{
+    /// Returns the compressed (QOA) encoding for this clip.
+    ///
+    /// Use the generated clip-module constant `QOA_DATA_LEN` (or
+    /// `__qoa_data_len_for_pcm_samples`) for `DATA_LEN`. Encoding searches 16 scale
+    /// factors for every 20 samples, so long clips are slow to compile; for those,
+    /// prefer converting once with `qoaconv` and reading the file with
+    /// [`qoa_clip!`](macro@crate::audio_player::qoa_clip).
+    #[must_use]
+    pub const fn with_qoa<const DATA_LEN: usize>(&self) -> QoaClipBuf<SAMPLE_RATE_HZ, DATA_LEN> {
+        assert!(
+            DATA_LEN == __qoa_data_len_for_pcm_samples(SAMPLE_COUNT),
+            "qoa data length must match sample count"
+        );
+        let mut qoa_data = [0_u8; DATA_LEN];
+        let mut qoa_encoder = QoaEncoder::new(1, SAMPLE_COUNT, SAMPLE_RATE_HZ, &mut qoa_data);
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            let sample = self.samples[sample_index];
+            qoa_encoder.push_frame([sample, sample], &mut qoa_data);
+            sample_index += 1;
+        }
+        QoaClip {
+            sample_count: SAMPLE_COUNT as u32,
+            data: qoa_data,
+        }
+    }
+}
+
+/// **QOA encoding for fixed-size clips (`StereoPcmClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const FRAME_COUNT: usize>
+    StereoPcmClip<SAMPLE_RATE_HZ, [[i16; 2]; FRAME_COUNT]>
+{
+    /// Returns the compressed (QOA) encoding for this clip.
+    ///
+    /// Use the generated clip-module constant `QOA_DATA_LEN` (or
+    /// `__stereo_qoa_data_len_for_pcm_frames`) for `DATA_LEN`. See
+    /// [`PcmClipBuf::with_qoa`] about compile time.
+    #[must_use]
+    pub const fn with_qoa<const DATA_LEN: usize>(
+        &self,
+    ) -> StereoQoaClipBuf<SAMPLE_RATE_HZ, DATA_LEN> {
+        assert!(
+            DATA_LEN == __stereo_qoa_data_len_for_pcm_frames(FRAME_COUNT),
+            "qoa data length must match frame count"
+        );
+        let mut qoa_data = [0_u8; DATA_LEN];
+        let mut qoa_encoder = QoaEncoder::new(2, FRAME_COUNT, SAMPLE_RATE_HZ, &mut qoa_data);
+        let mut frame_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while frame_index < FRAME_COUNT {
+            qoa_encoder.push_frame(self.frames[frame_index], &mut qoa_data);
+            frame_index += 1;
+        }
+        StereoQoaClip {
+            frame_count: FRAME_COUNT as u32,
+            data: qoa_data,
+        }
+    }
+}
+
+/// Sign-sign LMS predictor state for one channel.
+#[derive(Clone, Copy)]
+struct QoaLms {
+    history: [i32; 4],
+    weights: [i32; 4],
+}
+
+impl QoaLms {
+    /// Starting state for encoding; the weights favor continuing the recent slope.
+    const INITIAL: Self = Self {
+        history: [0; 4],
+        weights: [0, 0, -(1 << 13), 1 << 14],
+    };
+
+    const fn predict(&self) -> i32 {
+        let mut prediction_i64 = 0_i64;
+        let mut index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while index < 4 {
+            prediction_i64 += self.weights[index] as i64 * self.history[index] as i64;
+            index += 1;
+        }
+        (prediction_i64 >> 13) as i32
+    }
+
+    const fn update(&mut self, sample_i32: i32, residual_i32: i32) {
+        let delta_i32 = residual_i32 >> 4;
+        let mut index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while index < 4 {
+            self.weights[index] += if self.history[index] < 0 {
+                -delta_i32
+            } else {
+                delta_i32
+            };
+            index += 1;
+        }
+        self.history = [
+            self.history[1],
+            self.history[2],
+            self.history[3],
+            sample_i32,
+        ];
+    }
+
+    /// Returns the reconstructed sample for 3-bit `code` at `scalefactor`, and updates
+    /// the predictor with it.
+    const fn decode(&mut self, scalefactor: usize, code: usize) -> i16 {
+        let dequantized_i32 = QOA_DEQUANT_TABLE[scalefactor][code];
+        let reconstructed_i16 = clamp_i32_to_i16(self.predict() + dequantized_i32);
+        self.update(reconstructed_i16 as i32, dequantized_i32);
+        reconstructed_i16
+    }
+}
+
+/// Streaming QOA decoder, shared by the const `with_pcm` functions and the mixer.
+///
+/// It keeps one 64-bit slice per channel, so decoding needs no sample buffer.
+pub(super) struct QoaDecoder {
+    channels: usize,
+    byte_offset: usize,
+    frame_sample_count: usize,
+    frame_sample_index: usize,
+    lms: [QoaLms; 2],
+    scalefactors: [usize; 2],
+    slices: [u64; 2],
+}
+
+impl QoaDecoder {
+    pub(super) const fn new(channels: usize) -> Self {
+        assert!(
+            channels == 1 || channels == 2,
+            "QOA channels must be 1 or 2"
+        );
+        Self {
+            channels,
+            byte_offset: QOA_FILE_HEADER_LEN,
+            frame_sample_count: 0,
+            frame_sample_index: 0,
+            lms: [QoaLms::INITIAL; 2],
+            scalefactors: [0; 2],
+            slices: [0; 2],
+        }
+    }
+
+    /// Decodes the next `[left, right]` frame (mono repeats the sample). Stops at the end
+    /// of the data or at a malformed frame.
+    pub(super) const fn next_frame(&mut self, data: &[u8]) -> Option<[i16; 2]> {
+        if self.frame_sample_index == self.frame_sample_count {
+            let Some(frame_header) = read_u64_be(data, self.byte_offset) else {
+                return None;
+            };
+            let frame_sample_count = ((frame_header >> 16) & 0xFFFF) as usize;
+            if (frame_header >> 56) as usize != self.channels || frame_sample_count == 0 {
+                return None;
+            }
+            self.byte_offset += QOA_FRAME_HEADER_LEN;
+            let mut channel_index = 0_usize;
+            // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+            while channel_index < self.channels {
+                let Some(mut history) = read_u64_be(data, self.byte_offset) else {
+                    return None;
+                };
+                let Some(mut weights) = read_u64_be(data, self.byte_offset + 8) else {
+                    return None;
+                };
+                let mut index = 0_usize;
+                while index < 4 {
+                    self.lms[channel_index].history[index] = (history >> 48) as i16 as i32;
+                    self.lms[channel_index].weights[index] = (weights >> 48) as i16 as i32;
+                    history <<= 16;
+                    weights <<= 16;
+                    index += 1;
+                }
+                self.byte_offset += QOA_LMS_STATE_LEN;
+                channel_index += 1;
+            }
+            self.frame_sample_count = frame_sample_count;
+            self.frame_sample_index = 0;
+        }
+
+        if self.frame_sample_index % QOA_SLICE_LEN == 0 {
+            let mut channel_index = 0_usize;
+            // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+            while channel_index < self.channels {
+                let Some(slice) = read_u64_be(data, self.byte_offset) else {
+                    return None;
+                };
+                self.scalefactors[channel_index] = (slice >> 60) as usize;
+                self.slices[channel_index] = slice << 4;
+                self.byte_offset += 8;
+                channel_index += 1;
+            }
+        }
+
+        let mut frame = [0_i16; 2];
+        let mut channel_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while channel_index < self.channels {
+            let code = (self.slices[channel_index] >> 61) as usize;
+            self.slices[channel_index] <<= 3;
+            frame[channel_index] =
+                self.lms[channel_index].decode(self.scalefactors[channel_index], code);
+            channel_index += 1;
+        }
+        if self.channels == 1 {
+            frame[1] = frame[0];
+        }
+        self.frame_sample_index += 1;
+        Some(frame)
+    }
+}
+
+/// Streaming QOA encoder that writes a complete `.qoa` file, one frame at a time.
+struct QoaEncoder {
+    channels: usize,
+    sample_rate_hz: u32,
+    sample_count: usize,
+    sample_index: usize,
+    byte_offset: usize,
+    lms: [QoaLms; 2],
+    previous_scalefactors: [usize; 2],
+    slice_frames: [[i16; 2]; QOA_SLICE_LEN],
+}
+
+impl QoaEncoder {
+    /// Starts encoding `sample_count` samples per channel, writing the file header.
+    const fn new(
+        channels: usize,
+        sample_count: usize,
+        sample_rate_hz: u32,
+        data: &mut [u8],
+    ) -> Self {
+        assert!(
+            channels == 1 || channels == 2,
+            "QOA channels must be 1 or 2"
+        );
+        assert!(sample_rate_hz > 0, "sample_rate_hz must be > 0");
+        assert!(
+            sample_rate_hz <= 0xFF_FFFF,
+            "QOA sample_rate_hz must fit in 24 bits"
+        );
+        assert!(
+            sample_count <= u32::MAX as usize,
+            "QOA sample count must fit in u32"
+        );
+        assert!(
+            data.len() == qoa_data_len(sample_count, channels),
+            "qoa data length must match sample count and channels"
+        );
+        write_u64_be(data, 0, ((QOA_MAGIC as u64) << 32) | sample_count as u64);
+        Self {
+            channels,
+            sample_rate_hz,
+            sample_count,
+            sample_index: 0,
+            byte_offset: QOA_FILE_HEADER_LEN,
+            lms: [QoaLms::INITIAL; 2],
+            previous_scalefactors: [0; 2],
+            slice_frames: [[0; 2]; QOA_SLICE_LEN],
+        }
+    }
+
+    /// Adds one `[left, right]` frame (mono uses the left sample).
+    const fn push_frame(&mut self, frame: [i16; 2], data: &mut [u8]) {
+        assert!(
+            self.sample_index < self.sample_count,
+            "more QOA frames than declared"
+        );
+        if self.sample_index % QOA_FRAME_LEN == 0 {
+            self.write_frame_header(data);
+        }
+        self.slice_frames[self.sample_index % QOA_SLICE_LEN] = frame;
+        self.sample_index += 1;
+        if self.sample_index % QOA_SLICE_LEN == 0 || self.sample_index == self.sample_count {
+            let slice_len = (self.sample_index - 1) % QOA_SLICE_LEN + 1;
+            let mut channel_index = 0_usize;
+            // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+            while channel_index < self.channels {
+                let slice = self.encode_slice(channel_index, slice_len);
+                write_u64_be(data, self.byte_offset, slice);
+                self.byte_offset += 8;
+                channel_index += 1;
+            }
+        }
+    }
+
+    const fn write_frame_header(&mut self, data: &mut [u8]) {
+        let remaining_sample_count = self.sample_count - self.sample_index;
+        let frame_len = if remaining_sample_count < QOA_FRAME_LEN {
+            remaining_sample_count
+        } else {
+            QOA_FRAME_LEN
+        };
+        let frame_size = qoa_frame_size(self.channels, frame_len.div_ceil(QOA_SLICE_LEN));
+        write_u64_be(
+            data,
+            self.byte_offset,
+            ((self.channels as u64) << 56)
+                | ((self.sample_rate_hz as u64) << 32)
+                | ((frame_len as u64) << 16)
+                | frame_size as u64,
+        );
+        self.byte_offset += QOA_FRAME_HEADER_LEN;
+
+        let mut channel_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while channel_index < self.channels {
+            let mut history = 0_u64;
+            let mut weights = 0_u64;
+            let mut index = 0_usize;
+            while index < 4 {
+                history =
+                    (history << 16) | (self.lms[channel_index].history[index] as u64 & 0xFFFF);
+                weights =
+                    (weights << 16) | (self.lms[channel_index].weights[index] as u64 & 0xFFFF);
+                index += 1;
+            }
+            write_u64_be(data, self.byte_offset, history);
+            write_u64_be(data, self.byte_offset + 8, weights);
+            self.byte_offset += QOA_LMS_STATE_LEN;
+            channel_index += 1;
+        }
+    }
+
+    /// Encodes the buffered samples of one channel, trying every scalefactor and keeping
+    /// the one with the least error.
+    const fn encode_slice(&mut self, channel_index: usize, slice_len: usize) -> u64 {
+        let mut best_rank = u64::MAX;
+        let mut best_slice = 0_u64;
+        let mut best_lms = self.lms[channel_index];
+        let mut best_scalefactor = 0_usize;
+
+        let mut scalefactor_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while scalefactor_index < QOA_SCALEFACTOR_COUNT {
+            // Neighboring slices tend to share a scalefactor, so try the last one first;
+            // that lets worse candidates stop early.
+            let scalefactor = (scalefactor_index + self.previous_scalefactors[channel_index])
+                % QOA_SCALEFACTOR_COUNT;
+            let mut lms = self.lms[channel_index];
+            let mut slice = scalefactor as u64;
+            let mut rank = 0_u64;
+            let mut sample_index = 0_usize;
+            while sample_index < slice_len {
+                let sample_i32 = self.slice_frames[sample_index][channel_index] as i32;
+                let predicted_i32 = lms.predict();
+                let scaled_i32 = qoa_div(sample_i32 - predicted_i32, scalefactor);
+                let clamped_i32 = if scaled_i32 < -8 {
+                    -8
+                } else if scaled_i32 > 8 {
+                    8
+                } else {
+                    scaled_i32
+                };
+                let code = QOA_QUANT_TABLE[(clamped_i32 + 8) as usize] as usize;
+                let dequantized_i32 = QOA_DEQUANT_TABLE[scalefactor][code];
+                let reconstructed_i32 = clamp_i32_to_i16(predicted_i32 + dequantized_i32) as i32;
+
+                // Penalize large weights, which can make the predictor ring.
+                let weights_penalty_i64 = ((lms.weights[0] as i64 * lms.weights[0] as i64
+                    + lms.weights[1] as i64 * lms.weights[1] as i64
+                    + lms.weights[2] as i64 * lms.weights[2] as i64
+                    + lms.weights[3] as i64 * lms.weights[3] as i64)
+                    >> 18)
+                    - 0x8FF;
+                let weights_penalty_u64 = if weights_penalty_i64 < 0 {
+                    0
+                } else {
+                    weights_penalty_i64 as u64
+                };
+                let error_i64 = (sample_i32 - reconstructed_i32) as i64;
+                rank += (error_i64 * error_i64) as u64 + weights_penalty_u64 * weights_penalty_u64;
+                if rank > best_rank {
+                    break;
+                }
+
+                lms.update(reconstructed_i32, dequantized_i32);
+                slice = (slice << 3) | code as u64;
+                sample_index += 1;
+            }
+
+            if rank < best_rank {
+                best_rank = rank;
+                best_slice = slice;
+                best_lms = lms;
+                best_scalefactor = scalefactor;
+            }
+            scalefactor_index += 1;
+        }
+
+        self.previous_scalefactors[channel_index] = best_scalefactor;
+        self.lms[channel_index] = best_lms;
+        // A short final slice leaves its unused (low) bits zero.
+        best_slice << ((QOA_SLICE_LEN - slice_len) * 3)
+    }
+}
+
+/// Divides a residual by a scalefactor, rounding away from zero.
+const fn qoa_div(value_i32: i32, scalefactor: usize) -> i32 {
+    let reciprocal_i64 = QOA_RECIPROCAL_TABLE[scalefactor];
+    let quotient_i32 = ((value_i32 as i64 * reciprocal_i64 + (1 << 15)) >> 16) as i32;
+    quotient_i32 + value_i32.signum() - quotient_i32.signum()
+}
+
+const fn clamp_i32_to_i16(value_i32: i32) -> i16 {
+    if value_i32 < i16::MIN as i32 {
+        i16::MIN
+    } else if value_i32 > i16::MAX as i32 {
+        i16::MAX
+    } else {
+        value_i32 as i16
+    }
+}
+
+const fn qoa_frame_size(channels: usize, slice_count: usize) -> usize {
+    QOA_FRAME_HEADER_LEN + QOA_LMS_STATE_LEN * channels + 8 * slice_count * channels
+}
+
+const fn qoa_data_len(sample_count: usize, channels: usize) -> usize {
+    let frame_count = sample_count.div_ceil(QOA_FRAME_LEN);
+    let slice_count = sample_count.div_ceil(QOA_SLICE_LEN);
+    QOA_FILE_HEADER_LEN
+        + frame_count * (QOA_FRAME_HEADER_LEN + QOA_LMS_STATE_LEN * channels)
+        + slice_count * 8 * channels
+}
+
+const fn read_u64_be(bytes: &[u8], byte_offset: usize) -> Option<u64> {
+    if byte_offset > bytes.len().saturating_sub(8) {
+        return None;
+    }
+    Some(u64::from_be_bytes([
+        bytes[byte_offset],
+        bytes[byte_offset + 1],
+        bytes[byte_offset + 2],
+        bytes[byte_offset + 3],
+        bytes[byte_offset + 4],
+        bytes[byte_offset + 5],
+        bytes[byte_offset + 6],
+        bytes[byte_offset + 7],
+    ]))
+}
+
+const fn write_u64_be(bytes: &mut [u8], byte_offset: usize, value: u64) {
+    let value_bytes = value.to_be_bytes();
+    let mut index = 0_usize;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while index < 8 {
+        bytes[byte_offset + index] = value_bytes[index];
+        index += 1;
+    }
+}
+
+/// Returns QOA byte length (including the file header) needed to encode
+/// `sample_count` mono PCM samples.
+#[doc(hidden)]
+#[must_use]
+pub const fn __qoa_data_len_for_pcm_samples(sample_count: usize) -> usize {
+    qoa_data_len(sample_count, 1)
+}
+
+/// Returns QOA byte length (including the file header) needed to encode
+/// `frame_count` stereo PCM frames.
+#[doc(hidden)]
+#[must_use]
+pub const fn __stereo_qoa_data_len_for_pcm_frames(frame_count: usize) -> usize {
+    qoa_data_len(frame_count, 2)
+}
+
+/// Parsed QOA file metadata used by [`qoa_clip!`](macro@crate::audio_player::qoa_clip).
+#[derive(Clone, Copy)]
+#[doc(hidden)]
+pub struct ParsedQoaHeader {
+    /// Sample rate from the first frame header.
+    pub sample_rate_hz: u32,
+    /// Channel count (`1` for mono, `2` for stereo).
+    pub channels: usize,
+    /// Samples per channel.
+    pub sample_count: usize,
+}
+
+/// Parses QOA file metadata in a `const` context.
+#[must_use]
+#[doc(hidden)]
+pub const fn __parse_qoa_header(qoa_bytes: &[u8]) -> ParsedQoaHeader {
+    let Some(file_header) = read_u64_be(qoa_bytes, 0) else {
+        panic!("QOA file too small");
+    };
+    if (file_header >> 32) as u32 != QOA_MAGIC {
+        panic!("Missing qoaf header");
+    }
+    let sample_count = (file_header & 0xFFFF_FFFF) as usize;
+    if sample_count == 0 {
+        panic!("QOA file has no samples (streaming QOA files are not supported)");
+    }
+    let Some(frame_header) = read_u64_be(qoa_bytes, QOA_FILE_HEADER_LEN) else {
+        panic!("QOA file has no frames");
+    };
+    let channels = (frame_header >> 56) as usize;
+    if channels != 1 && channels != 2 {
+        panic!("Expected mono or stereo QOA");
+    }
+    if qoa_bytes.len() != qoa_data_len(sample_count, channels) {
+        panic!("QOA file length does not match its sample count");
+    }
+    ParsedQoaHeader {
+        sample_rate_hz: ((frame_header >> 32) & 0xFF_FFFF) as u32,
+        channels,
+        sample_count,
+    }
+}
+
+/// Const backend helper that builds a fixed-size mono QOA clip from a `.qoa` file's bytes.
+#[must_use]
+#[doc(hidden)]
+pub const fn __qoa_clip_from_bytes<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize>(
+    data: [u8; DATA_LEN],
+) -> QoaClipBuf<SAMPLE_RATE_HZ, DATA_LEN> {
+    let parsed_qoa = __parse_qoa_header(&data);
+    assert!(parsed_qoa.channels == 1, "Expected mono QOA");
+    assert!(
+        parsed_qoa.sample_rate_hz == SAMPLE_RATE_HZ,
+        "QOA sample rate must match clip sample rate"
+    );
+    QoaClip {
+        sample_count: parsed_qoa.sample_count as u32,
+        data,
+    }
+}
+
+/// Const backend helper that builds a fixed-size stereo QOA clip from a `.qoa` file's bytes.
+#[must_use]
+#[doc(hidden)]
+pub const fn __stereo_qoa_clip_from_bytes<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize>(
+    data: [u8; DATA_LEN],
+) -> StereoQoaClipBuf<SAMPLE_RATE_HZ, DATA_LEN> {
+    let parsed_qoa = __parse_qoa_header(&data);
+    assert!(parsed_qoa.channels == 2, "Expected stereo QOA");
+    assert!(
+        parsed_qoa.sample_rate_hz == SAMPLE_RATE_HZ,
+        "QOA sample rate must match clip sample rate"
+    );
+    StereoQoaClip {
+        frame_count: parsed_qoa.sample_count as u32,
+        data,
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! qoa_clip {
+    ($($tt:tt)*) => { $crate::__qoa_clip_parse! { $($tt)* } };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __qoa_clip_parse {
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
+        }
+    ) => {
+        $crate::audio_player::paste::paste! {
+            const [<$name:upper _TARGET_SAMPLE_RATE_HZ>]: u32 = $target_sample_rate_hz;
+
+            #[allow(non_snake_case)]
+            #[allow(missing_docs)]
+            $vis mod $name {
+                const PARSED_QOA: $crate::audio_player::ParsedQoaHeader =
+                    $crate::audio_player::__parse_qoa_header(include_bytes!($file));
+                const _: () = assert!(
+                    PARSED_QOA.channels == 1,
+                    "Expected mono QOA (use `channels: 2` for stereo)"
+                );
+                const SOURCE_SAMPLE_RATE_HZ: u32 = PARSED_QOA.sample_rate_hz;
+                const TARGET_SAMPLE_RATE_HZ: u32 = super::[<$name:upper _TARGET_SAMPLE_RATE_HZ>];
+                pub const SAMPLE_RATE_HZ: u32 = TARGET_SAMPLE_RATE_HZ;
+
+                const SOURCE_SAMPLE_COUNT: usize = PARSED_QOA.sample_count;
+                #[doc = "Number of samples for uncompressed (PCM) version of this clip."]
+                pub const PCM_SAMPLE_COUNT: usize = $crate::audio_player::__resampled_sample_count(
+                    SOURCE_SAMPLE_COUNT,
+                    SOURCE_SAMPLE_RATE_HZ,
+                    TARGET_SAMPLE_RATE_HZ,
+                );
+                const SOURCE_DATA_LEN: usize = include_bytes!($file).len();
+                #[doc = "Byte length for compressed (QOA) encoding this clip."]
+                pub const QOA_DATA_LEN: usize =
+                    $crate::audio_player::__qoa_data_len_for_pcm_samples(PCM_SAMPLE_COUNT);
+
+                #[must_use]
+                const fn source_qoa_clip() -> $crate::audio_player::QoaClipBuf<
+                    SOURCE_SAMPLE_RATE_HZ,
+                    SOURCE_DATA_LEN,
+                > {
+                    $crate::audio_player::__qoa_clip_from_bytes(*include_bytes!($file))
+                }
+
+                #[doc = "`const` function that returns the uncompressed (PCM) version of this clip."]
+                #[must_use]
+                pub const fn pcm_clip() -> $crate::audio_player::PcmClipBuf<SAMPLE_RATE_HZ, PCM_SAMPLE_COUNT> {
+                    $crate::audio_player::__resample_pcm_clip::<
+                        SOURCE_SAMPLE_RATE_HZ,
+                        SOURCE_SAMPLE_COUNT,
+                        TARGET_SAMPLE_RATE_HZ,
+                        PCM_SAMPLE_COUNT,
+                    >(source_qoa_clip().with_pcm::<SOURCE_SAMPLE_COUNT>())
+                }
+
+                #[doc = "`const` function that returns the compressed (QOA) encoding for this clip."]
+                #[must_use]
+                pub const fn qoa_clip() -> $crate::audio_player::QoaClipBuf<SAMPLE_RATE_HZ, QOA_DATA_LEN> {
+                    if TARGET_SAMPLE_RATE_HZ == SOURCE_SAMPLE_RATE_HZ {
+                        let qoa_bytes = include_bytes!($file);
+                        let mut qoa_data = [0_u8; QOA_DATA_LEN];
+                        let mut data_index = 0usize;
+                        while data_index < QOA_DATA_LEN {
+                            qoa_data[data_index] = qoa_bytes[data_index];
+                            data_index += 1;
+                        }
+                        $crate::audio_player::__qoa_clip_from_bytes(qoa_data)
+                    } else {
+                        pcm_clip().with_qoa::<QOA_DATA_LEN>()
+                    }
+                }
+            }
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr,
+            channels: 1 $(,)?
+        }
+    ) => {
+        $crate::__qoa_clip_parse! {
+            $vis $name {
+                file: $file,
+                target_sample_rate_hz: $target_sample_rate_hz,
+            }
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr,
+            channels: 2 $(,)?
+        }
+    ) => {
+        $crate::__stereo_qoa_clip_impl! {
+            vis: $vis,
+            name: $name,
+            file: $file,
+            target_sample_rate_hz: $target_sample_rate_hz,
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            target_sample_rate_hz: $target_sample_rate_hz:expr,
+            channels: $channels:tt $(,)?
+        }
+    ) => {
+        compile_error!("qoa_clip! channels must be 1 (mono) or 2 (stereo)");
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr $(,)?
+        }
+    ) => {
+        $crate::__qoa_clip_parse! {
+            $vis $name {
+                file: $file,
+                target_sample_rate_hz: $crate::audio_player::__parse_qoa_header(include_bytes!($file)).sample_rate_hz,
+            }
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            file: $file:expr,
+            channels: $channels:tt $(,)?
+        }
+    ) => {
+        $crate::__qoa_clip_parse! {
+            $vis $name {
+                file: $file,
+                target_sample_rate_hz: $crate::audio_player::__parse_qoa_header(include_bytes!($file)).sample_rate_hz,
+                channels: $channels,
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __stereo_qoa_clip_impl {
+    (
+        vis: $vis:vis,
+        name: $name:ident,
+        file: $file:expr,
+        target_sample_rate_hz: $target_sample_rate_hz:expr $(,)?
+    ) => {
+        $crate::audio_player::paste::paste! {
+            const [<$name:upper _TARGET_SAMPLE_RATE_HZ>]: u32 = $target_sample_rate_hz;
+
+            #[allow(non_snake_case)]
+            #[allow(missing_docs)]
+            $vis mod $name {
+                const PARSED_QOA: $crate::audio_player::ParsedQoaHeader =
+                    $crate::audio_player::__parse_qoa_header(include_bytes!($file));
+                const _: () = assert!(
+                    PARSED_QOA.channels == 2,
+                    "Expected stereo QOA (omit `channels` or use `channels: 1` for mono)"
+                );
+                const SOURCE_SAMPLE_RATE_HZ: u32 = PARSED_QOA.sample_rate_hz;
+                const TARGET_SAMPLE_RATE_HZ: u32 = super::[<$name:upper _TARGET_SAMPLE_RATE_HZ>];
+                pub const SAMPLE_RATE_HZ: u32 = TARGET_SAMPLE_RATE_HZ;
+
+                const SOURCE_FRAME_COUNT: usize = PARSED_QOA.sample_count;
+                #[doc = "Number of `[left, right]` frames for uncompressed (PCM) version of this clip."]
+                pub const PCM_SAMPLE_COUNT: usize = $crate::audio_player::__resampled_sample_count(
+                    SOURCE_FRAME_COUNT,
+                    SOURCE_SAMPLE_RATE_HZ,
+                    TARGET_SAMPLE_RATE_HZ,
+                );
+                const SOURCE_DATA_LEN: usize = include_bytes!($file).len();
+                #[doc = "Byte length for compressed (QOA) encoding this clip."]
+                pub const QOA_DATA_LEN: usize =
+                    $crate::audio_player::__stereo_qoa_data_len_for_pcm_frames(PCM_SAMPLE_COUNT);
+
+                #[must_use]
+                const fn source_qoa_clip() -> $crate::audio_player::StereoQoaClipBuf<
+                    SOURCE_SAMPLE_RATE_HZ,
+                    SOURCE_DATA_LEN,
+                > {
+                    $crate::audio_player::__stereo_qoa_clip_from_bytes(*include_bytes!($file))
+                }
+
+                #[doc = "`const` function that returns the uncompressed (PCM) version of this clip."]
+                #[must_use]
+                pub const fn pcm_clip() -> $crate::audio_player::StereoPcmClipBuf<SAMPLE_RATE_HZ, PCM_SAMPLE_COUNT> {
+                    $crate::audio_player::__resample_stereo_pcm_clip::<
+                        SOURCE_SAMPLE_RATE_HZ,
+                        SOURCE_FRAME_COUNT,
+                        TARGET_SAMPLE_RATE_HZ,
+                        PCM_SAMPLE_COUNT,
+                    >(source_qoa_clip().with_pcm::<SOURCE_FRAME_COUNT>())
+                }
+
+                #[doc = "`const` function that returns the compressed (QOA) encoding for this clip."]
+                #[must_use]
+                pub const fn qoa_clip() -> $crate::audio_player::StereoQoaClipBuf<SAMPLE_RATE_HZ, QOA_DATA_LEN> {
+                    if TARGET_SAMPLE_RATE_HZ == SOURCE_SAMPLE_RATE_HZ {
+                        let qoa_bytes = include_bytes!($file);
+                        let mut qoa_data = [0_u8; QOA_DATA_LEN];
+                        let mut data_index = 0usize;
+                        while data_index < QOA_DATA_LEN {
+                            qoa_data[data_index] = qoa_bytes[data_index];
+                            data_index += 1;
+                        }
+                        $crate::audio_player::__stereo_qoa_clip_from_bytes(qoa_data)
+                    } else {
+                        pcm_clip().with_qoa::<QOA_DATA_LEN>()
+                    }
+                }
+            }
+        }
+    };
+}
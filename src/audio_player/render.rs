@@ -0,0 +1,188 @@
+//! Host rendering: runs the mixer the way the device loop does and records what it would
+//! send to I²S, as stereo frames or a WAV file.
+//!
+//! See [`AudioRenderer`].
+
+use core::time::Duration;
+use std::error::Error;
+use std::path::Path;
+
+use super::mixer::Mixer;
+use super::{__samples_for_duration, AudioPlayerStatic, SAMPLE_BUFFER_LEN};
+use crate::to_png::create_parent_dir;
+
+/// Bytes in a WAV header for 16-bit PCM: the `RIFF`, `fmt ` and `data` chunk headers.
+const WAV_HEADER_LEN: usize = 44;
+
+/// Host stand-in for an `audio_player!` device task, for listening to or golden-testing the
+/// final mix.
+///
+/// Create an [`AudioPlayerStatic`] and an [`AudioPlayer`](super::AudioPlayer) handle as
+/// usual, and give the renderer the same static instead of spawning a device task. Calls
+/// on the player (`play`, `set_volume`, `voice(..).set_gain`, ...) are applied when the
+/// renderer next renders, so the output includes every [`Volume`](super::Volume),
+/// [`Gain`](super::Gain), pan, and [`Dsp`](super::Dsp) setting.
+///
+/// The renderer works in the same 256-frame buffers as the device loop, so commands take
+/// effect on the same buffer boundaries as on hardware. While the device loop is idle it
+/// sends nothing to I²S; [`render_for`](Self::render_for) records that time as silence so
+/// the recording keeps real time.
+///
+/// ```rust,no_run
+/// # use core::time::Duration;
+/// # use device_envoy::audio_player::{
+/// #     AtEnd, AudioPlayer, AudioPlayerStatic, AudioRenderer, Playable, SilenceClip,
+/// #     VOICE_22050_HZ, Volume, tone,
+/// # };
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// const CHIME: &dyn Playable<VOICE_22050_HZ> =
+///     &tone!(880, VOICE_22050_HZ, Duration::from_millis(140));
+/// const SILENCE_40MS: &dyn Playable<VOICE_22050_HZ> =
+///     &SilenceClip::new(Duration::from_millis(40));
+/// static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ> =
+///     AudioPlayer::new_static_with_max_volume(Volume::percent(10));
+/// let audio_player = AudioPlayer::new(&AUDIO_PLAYER_STATIC);
+/// let mut audio_renderer = AudioRenderer::<4, VOICE_22050_HZ>::new(&AUDIO_PLAYER_STATIC);
+///
+/// audio_player.play([CHIME, SILENCE_40MS, CHIME], AtEnd::Stop);
+/// assert!(audio_renderer.render_until_idle(Duration::from_secs(1)));
+/// audio_renderer.write_wav("target/audio/chime.wav")?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct AudioRenderer<
+    const MAX_CLIPS: usize,
+    const SAMPLE_RATE_HZ: u32,
+    const VOICES: usize = 1,
+    const ECHO_FRAMES: usize = 0,
+> {
+    audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
+    mixer: Mixer<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES, ECHO_FRAMES>,
+    frames: Vec<[i16; 2]>,
+}
+
+impl<
+    const MAX_CLIPS: usize,
+    const SAMPLE_RATE_HZ: u32,
+    const VOICES: usize,
+    const ECHO_FRAMES: usize,
+> AudioRenderer<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES, ECHO_FRAMES>
+{
+    /// Creates a renderer that plays whatever is sent to `audio_player_static`.
+    ///
+    /// `ECHO_FRAMES` is the echo buffer's length in frames: the `max_echo_delay` field of
+    /// `audio_player!` times `SAMPLE_RATE_HZ`, rounded down. For example, a 200 ms
+    /// `max_echo_delay` at 22,050 Hz is `4_410`. The default, `0`, leaves no room for echo.
+    #[must_use]
+    pub const fn new(
+        audio_player_static: &'static AudioPlayerStatic<MAX_CLIPS, SAMPLE_RATE_HZ, VOICES>,
+    ) -> Self {
+        Self {
+            audio_player_static,
+            mixer: Mixer::new(),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Runs one pass of the device loop: applies pending commands and, unless the mixer is
+    /// idle, records one buffer of frames.
+    ///
+    /// Returns `false`, recording nothing, when the mixer is idle.
+    pub fn render_buffer(&mut self) -> bool {
+        self.mixer.apply_commands(self.audio_player_static);
+        if self.mixer.is_idle() {
+            return false;
+        }
+
+        let mut sample_buffer = [0_u32; SAMPLE_BUFFER_LEN];
+        self.mixer
+            .fill(self.audio_player_static, &mut sample_buffer);
+        self.frames
+            .extend(sample_buffer.iter().map(|&frame| unpack_frame(frame)));
+        self.mixer.publish_progress(self.audio_player_static);
+        true
+    }
+
+    /// Renders buffers until every voice has stopped and any echo has faded.
+    ///
+    /// Returns `false` if playback was still going after `max_duration`, for example
+    /// because a sequence uses [`AtEnd::Loop`](super::AtEnd::Loop).
+    pub fn render_until_idle(&mut self, max_duration: Duration) -> bool {
+        let max_frame_count =
+            self.frames.len() + __samples_for_duration(max_duration, SAMPLE_RATE_HZ);
+        while self.frames.len() < max_frame_count {
+            if !self.render_buffer() {
+                return true;
+            }
+        }
+        self.mixer.is_idle()
+    }
+
+    /// Renders at least `duration` of output, in whole buffers, recording silence for
+    /// buffers where the mixer is idle.
+    pub fn render_for(&mut self, duration: Duration) {
+        let end_frame_count = self.frames.len() + __samples_for_duration(duration, SAMPLE_RATE_HZ);
+        while self.frames.len() < end_frame_count {
+            if !self.render_buffer() {
+                self.frames
+                    .resize(self.frames.len() + SAMPLE_BUFFER_LEN, [0, 0]);
+            }
+        }
+    }
+
+    /// The `[left, right]` frames recorded so far.
+    #[must_use]
+    pub fn frames(&self) -> &[[i16; 2]] {
+        &self.frames
+    }
+
+    /// The playing time of the frames recorded so far.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        Duration::from_nanos(self.frames.len() as u64 * 1_000_000_000 / u64::from(SAMPLE_RATE_HZ))
+    }
+
+    /// Discards the recorded frames, keeping the mixer's state.
+    pub fn clear(&mut self) {
+        self.frames.clear();
+    }
+
+    /// Returns the recorded frames as a 16-bit stereo PCM WAV file.
+    #[must_use]
+    pub fn wav_bytes(&self) -> Vec<u8> {
+        let data_len = u32::try_from(self.frames.len() * 4).expect("WAV data must be < 4 GiB");
+        let mut wav_bytes = Vec::with_capacity(WAV_HEADER_LEN + data_len as usize);
+        wav_bytes.extend_from_slice(b"RIFF");
+        wav_bytes.extend_from_slice(&(WAV_HEADER_LEN as u32 - 8 + data_len).to_le_bytes());
+        wav_bytes.extend_from_slice(b"WAVE");
+        wav_bytes.extend_from_slice(b"fmt ");
+        wav_bytes.extend_from_slice(&16_u32.to_le_bytes());
+        wav_bytes.extend_from_slice(&1_u16.to_le_bytes()); // PCM
+        wav_bytes.extend_from_slice(&2_u16.to_le_bytes()); // channels
+        wav_bytes.extend_from_slice(&SAMPLE_RATE_HZ.to_le_bytes());
+        wav_bytes.extend_from_slice(&(SAMPLE_RATE_HZ * 4).to_le_bytes()); // byte rate
+        wav_bytes.extend_from_slice(&4_u16.to_le_bytes()); // block align
+        wav_bytes.extend_from_slice(&16_u16.to_le_bytes()); // bits per sample
+        wav_bytes.extend_from_slice(b"data");
+        wav_bytes.extend_from_slice(&data_len.to_le_bytes());
+        for [left_sample, right_sample] in &self.frames {
+            wav_bytes.extend_from_slice(&left_sample.to_le_bytes());
+            wav_bytes.extend_from_slice(&right_sample.to_le_bytes());
+        }
+        wav_bytes
+    }
+
+    /// Writes the recorded frames to `output_path` as a 16-bit stereo PCM WAV file,
+    /// creating parent directories as needed.
+    pub fn write_wav(&self, output_path: impl AsRef<Path>) -> Result<(), Box<dyn Error>> {
+        let output_path = output_path.as_ref();
+        create_parent_dir(output_path)?;
+        std::fs::write(output_path, self.wav_bytes())?;
+        Ok(())
+    }
+}
+
+/// Splits an I²S frame, packed with the left sample in the high half-word.
+const fn unpack_frame(frame: u32) -> [i16; 2] {
+    [(frame >> 16) as u16 as i16, frame as u16 as i16]
+}
@@ -0,0 +1,433 @@
+//! Stereo (two-channel) PCM and ADPCM clip types.
+//!
+//! See [`StereoPcmClip`] and [`StereoAdpcmClip`].
+
+use super::{
+    __resample_pcm_clip, Gain, PcmClip, PcmClipBuf, decode_adpcm_nibble_const, encode_adpcm_nibble,
+    read_i16_le_const, scale_sample_with_linear,
+};
+
+/// ADPCM block size used when encoding stereo clips (256 bytes per channel).
+const STEREO_ADPCM_ENCODE_BLOCK_ALIGN: usize = 512;
+
+/// Unsized view of static uncompressed (PCM) stereo clip data.
+///
+/// Each frame is a `[left, right]` pair of samples. For fixed-size, const-friendly
+/// storage, see [`StereoPcmClipBuf`]. Use
+/// [`pcm_clip!`](macro@crate::audio_player::pcm_clip) with `channels: 2` to read one
+/// from a file.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+pub struct StereoPcmClip<const SAMPLE_RATE_HZ: u32, T: ?Sized = [[i16; 2]]> {
+    pub(super) frames: T,
+}
+
+/// Sized, const-friendly storage for uncompressed (PCM) stereo clip data.
+///
+/// `FRAME_COUNT` is the number of `[left, right]` frames, that is, samples per channel.
+pub type StereoPcmClipBuf<const SAMPLE_RATE_HZ: u32, const FRAME_COUNT: usize> =
+    StereoPcmClip<SAMPLE_RATE_HZ, [[i16; 2]; FRAME_COUNT]>;
+
+/// Unsized view of static compressed (ADPCM) stereo clip data.
+///
+/// The data uses the IMA ADPCM stereo WAV layout, so a 2-channel ADPCM WAV file's
+/// `data` chunk can be used as-is. For fixed-size, const-friendly storage, see
+/// [`StereoAdpcmClipBuf`]. Use [`adpcm_clip!`](macro@crate::audio_player::adpcm_clip)
+/// with `channels: 2` to read one from a file.
+pub struct StereoAdpcmClip<const SAMPLE_RATE_HZ: u32, T: ?Sized = [u8]> {
+    pub(super) block_align: u16,
+    pub(super) samples_per_block: u16,
+    pub(super) data: T,
+}
+
+/// Sized, const-friendly storage for compressed (ADPCM) stereo clip data.
+pub type StereoAdpcmClipBuf<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize> =
+    StereoAdpcmClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>;
+
+/// **Implementation for fixed-size clips (`StereoPcmClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const FRAME_COUNT: usize>
+    StereoPcmClip<SAMPLE_RATE_HZ, [[i16; 2]; FRAME_COUNT]>
+{
+    /// Returns a new clip with linear sample gain applied to both channels.
+    ///
+    /// Like [`PcmClipBuf::with_gain`], this is intended for const clip definitions
+    /// and saturates to i16 sample bounds.
+    #[must_use]
+    pub const fn with_gain(self, gain: Gain) -> Self {
+        let mut frames = self.frames;
+        let mut frame_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while frame_index < FRAME_COUNT {
+            frames[frame_index][0] =
+                scale_sample_with_linear(frames[frame_index][0], gain.linear());
+            frames[frame_index][1] =
+                scale_sample_with_linear(frames[frame_index][1], gain.linear());
+            frame_index += 1;
+        }
+        Self { frames }
+    }
+
+    /// Returns the left channel as a mono clip.
+    #[must_use]
+    pub const fn left(&self) -> PcmClipBuf<SAMPLE_RATE_HZ, FRAME_COUNT> {
+        self.channel(0)
+    }
+
+    /// Returns the right channel as a mono clip.
+    #[must_use]
+    pub const fn right(&self) -> PcmClipBuf<SAMPLE_RATE_HZ, FRAME_COUNT> {
+        self.channel(1)
+    }
+
+    const fn channel(&self, channel_index: usize) -> PcmClipBuf<SAMPLE_RATE_HZ, FRAME_COUNT> {
+        let mut samples = [0_i16; FRAME_COUNT];
+        let mut frame_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while frame_index < FRAME_COUNT {
+            samples[frame_index] = self.frames[frame_index][channel_index];
+            frame_index += 1;
+        }
+        PcmClip { samples }
+    }
+
+    /// Returns the compressed (ADPCM) encoding for this clip.
+    ///
+    /// Use the generated clip-module constant `ADPCM_DATA_LEN` (or
+    /// `__stereo_adpcm_data_len_for_pcm_frames`) for `DATA_LEN`.
+    #[must_use]
+    pub const fn with_adpcm<const DATA_LEN: usize>(
+        &self,
+    ) -> StereoAdpcmClipBuf<SAMPLE_RATE_HZ, DATA_LEN> {
+        self.with_adpcm_block_align::<DATA_LEN>(STEREO_ADPCM_ENCODE_BLOCK_ALIGN)
+    }
+
+    #[must_use]
+    pub(crate) const fn with_adpcm_block_align<const DATA_LEN: usize>(
+        &self,
+        block_align: usize,
+    ) -> StereoAdpcmClipBuf<SAMPLE_RATE_HZ, DATA_LEN> {
+        let samples_per_block = __stereo_adpcm_samples_per_block(block_align);
+        assert!(
+            block_align <= u16::MAX as usize,
+            "block_align must fit in u16"
+        );
+        assert!(
+            DATA_LEN
+                == __stereo_adpcm_data_len_for_pcm_frames_with_block_align(
+                    FRAME_COUNT,
+                    block_align
+                ),
+            "adpcm data length must match frame count and block_align"
+        );
+
+        let mut adpcm_data = [0_u8; DATA_LEN];
+        let mut block_start = 0_usize;
+        let mut frame_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while frame_index < FRAME_COUNT {
+            let mut channel_index = 0_usize;
+            while channel_index < 2 {
+                let mut predictor_i32 = self.frames[frame_index][channel_index] as i32;
+                let mut step_index_i32 = 0_i32;
+                let header_start = block_start + channel_index * 4;
+                let predictor_bytes = (predictor_i32 as i16).to_le_bytes();
+                adpcm_data[header_start] = predictor_bytes[0];
+                adpcm_data[header_start + 1] = predictor_bytes[1];
+                adpcm_data[header_start + 2] = step_index_i32 as u8;
+                adpcm_data[header_start + 3] = 0;
+
+                let mut sample_in_block = 1_usize;
+                while sample_in_block < samples_per_block {
+                    let source_frame_index = frame_index + sample_in_block;
+                    let target_sample_i32 = if source_frame_index < FRAME_COUNT {
+                        self.frames[source_frame_index][channel_index] as i32
+                    } else {
+                        predictor_i32
+                    };
+                    let adpcm_nibble = encode_adpcm_nibble(
+                        target_sample_i32,
+                        &mut predictor_i32,
+                        &mut step_index_i32,
+                    );
+                    let nibble_index = sample_in_block - 1;
+                    adpcm_data
+                        [stereo_adpcm_byte_offset(block_start, channel_index, nibble_index)] |=
+                        adpcm_nibble << ((nibble_index % 2) * 4);
+                    sample_in_block += 1;
+                }
+                channel_index += 1;
+            }
+
+            frame_index += samples_per_block;
+            block_start += block_align;
+        }
+
+        StereoAdpcmClip::new(block_align as u16, samples_per_block as u16, adpcm_data)
+    }
+}
+
+/// **Implementation for fixed-size clips (`StereoAdpcmClipBuf`).**
+impl<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize>
+    StereoAdpcmClip<SAMPLE_RATE_HZ, [u8; DATA_LEN]>
+{
+    /// Creates a fixed-size stereo ADPCM clip.
+    #[must_use]
+    pub(crate) const fn new(
+        block_align: u16,
+        samples_per_block: u16,
+        data: [u8; DATA_LEN],
+    ) -> Self {
+        assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+        assert!(
+            samples_per_block as usize == __stereo_adpcm_samples_per_block(block_align as usize),
+            "samples_per_block must match block_align"
+        );
+        assert!(
+            DATA_LEN % block_align as usize == 0,
+            "adpcm data length must be block aligned"
+        );
+        Self {
+            block_align,
+            samples_per_block,
+            data,
+        }
+    }
+
+    /// Returns the uncompressed (PCM) version of this clip.
+    ///
+    /// `FRAME_COUNT` is the number of frames in the resulting PCM clip. Typically,
+    /// use the generated clip-module constant `PCM_SAMPLE_COUNT`.
+    #[must_use]
+    pub const fn with_pcm<const FRAME_COUNT: usize>(
+        &self,
+    ) -> StereoPcmClipBuf<SAMPLE_RATE_HZ, FRAME_COUNT> {
+        let block_align = self.block_align as usize;
+        let samples_per_block = self.samples_per_block as usize;
+        assert!(
+            FRAME_COUNT == (DATA_LEN / block_align) * samples_per_block,
+            "frame count must match decoded ADPCM length"
+        );
+
+        let mut frames = [[0_i16; 2]; FRAME_COUNT];
+        let mut block_start = 0_usize;
+        let mut frame_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while block_start < DATA_LEN {
+            let mut channel_index = 0_usize;
+            while channel_index < 2 {
+                let header_start = block_start + channel_index * 4;
+                let mut predictor_i32 = read_i16_le_const(&self.data, header_start) as i32;
+                let mut step_index_i32 = self.data[header_start + 2] as i32;
+                assert!(
+                    step_index_i32 >= 0 && step_index_i32 <= 88,
+                    "ADPCM step_index must be in 0..=88"
+                );
+                frames[frame_index][channel_index] = predictor_i32 as i16;
+
+                let mut sample_in_block = 1_usize;
+                while sample_in_block < samples_per_block {
+                    let nibble_index = sample_in_block - 1;
+                    let adpcm_nibble =
+                        stereo_adpcm_nibble(&self.data, block_start, channel_index, nibble_index);
+                    frames[frame_index + sample_in_block][channel_index] =
+                        decode_adpcm_nibble_const(
+                            adpcm_nibble,
+                            &mut predictor_i32,
+                            &mut step_index_i32,
+                        );
+                    sample_in_block += 1;
+                }
+                channel_index += 1;
+            }
+
+            frame_index += samples_per_block;
+            block_start += block_align;
+        }
+
+        StereoPcmClip { frames }
+    }
+
+    /// Returns this fixed-size stereo ADPCM clip with linear sample gain applied.
+    ///
+    /// Like [`AdpcmClipBuf::with_gain`](crate::audio_player::AdpcmClipBuf), this
+    /// decodes, applies gain, and re-encodes, which can be more lossy than applying
+    /// gain to PCM before a single encode.
+    #[must_use]
+    pub const fn with_gain(self, gain: Gain) -> Self {
+        let block_align = self.block_align as usize;
+        let samples_per_block = self.samples_per_block as usize;
+
+        let mut gained_data = [0_u8; DATA_LEN];
+        let mut block_start = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while block_start < DATA_LEN {
+            let mut channel_index = 0_usize;
+            while channel_index < 2 {
+                let header_start = block_start + channel_index * 4;
+                let mut source_predictor_i32 = read_i16_le_const(&self.data, header_start) as i32;
+                let mut source_step_index_i32 = self.data[header_start + 2] as i32;
+                assert!(
+                    source_step_index_i32 >= 0 && source_step_index_i32 <= 88,
+                    "ADPCM step_index must be in 0..=88"
+                );
+
+                let scaled_first_sample_i16 =
+                    scale_sample_with_linear(source_predictor_i32 as i16, gain.linear());
+                let mut destination_predictor_i32 = scaled_first_sample_i16 as i32;
+                let mut destination_step_index_i32 = source_step_index_i32;
+                let scaled_first_sample_bytes = scaled_first_sample_i16.to_le_bytes();
+                gained_data[header_start] = scaled_first_sample_bytes[0];
+                gained_data[header_start + 1] = scaled_first_sample_bytes[1];
+                gained_data[header_start + 2] = destination_step_index_i32 as u8;
+                gained_data[header_start + 3] = 0;
+
+                let mut sample_in_block = 1_usize;
+                while sample_in_block < samples_per_block {
+                    let nibble_index = sample_in_block - 1;
+                    let source_nibble =
+                        stereo_adpcm_nibble(&self.data, block_start, channel_index, nibble_index);
+                    let decoded_sample_i16 = decode_adpcm_nibble_const(
+                        source_nibble,
+                        &mut source_predictor_i32,
+                        &mut source_step_index_i32,
+                    );
+                    let destination_nibble = encode_adpcm_nibble(
+                        scale_sample_with_linear(decoded_sample_i16, gain.linear()) as i32,
+                        &mut destination_predictor_i32,
+                        &mut destination_step_index_i32,
+                    );
+                    gained_data
+                        [stereo_adpcm_byte_offset(block_start, channel_index, nibble_index)] |=
+                        destination_nibble << ((nibble_index % 2) * 4);
+                    sample_in_block += 1;
+                }
+                channel_index += 1;
+            }
+            block_start += block_align;
+        }
+
+        Self::new(self.block_align, self.samples_per_block, gained_data)
+    }
+}
+
+/// Returns the byte holding nibble `nibble_index` (counting after the block header)
+/// of one channel in a stereo ADPCM block.
+///
+/// After both 4-byte channel headers, the channels alternate in 4-byte (8-sample) groups.
+pub(super) const fn stereo_adpcm_byte_offset(
+    block_start: usize,
+    channel_index: usize,
+    nibble_index: usize,
+) -> usize {
+    block_start + 8 + (nibble_index / 8) * 8 + channel_index * 4 + (nibble_index % 8) / 2
+}
+
+/// Returns nibble `nibble_index` of one channel in a stereo ADPCM block.
+pub(super) const fn stereo_adpcm_nibble(
+    data: &[u8],
+    block_start: usize,
+    channel_index: usize,
+    nibble_index: usize,
+) -> u8 {
+    let adpcm_byte = data[stereo_adpcm_byte_offset(block_start, channel_index, nibble_index)];
+    if nibble_index % 2 == 0 {
+        adpcm_byte & 0x0F
+    } else {
+        adpcm_byte >> 4
+    }
+}
+
+/// Returns frames (samples per channel) per stereo ADPCM block.
+#[doc(hidden)]
+#[must_use]
+pub const fn __stereo_adpcm_samples_per_block(block_align: usize) -> usize {
+    if block_align < 16 || block_align % 8 != 0 {
+        panic!("stereo ADPCM block_align must be a multiple of 8 and >= 16");
+    }
+    block_align - 7
+}
+
+/// Returns stereo ADPCM byte length needed to encode `frame_count` PCM frames.
+#[doc(hidden)]
+#[must_use]
+pub const fn __stereo_adpcm_data_len_for_pcm_frames(frame_count: usize) -> usize {
+    __stereo_adpcm_data_len_for_pcm_frames_with_block_align(
+        frame_count,
+        STEREO_ADPCM_ENCODE_BLOCK_ALIGN,
+    )
+}
+
+/// Returns stereo ADPCM byte length needed to encode `frame_count` PCM frames
+/// with a specific ADPCM `block_align`.
+#[doc(hidden)]
+#[must_use]
+pub const fn __stereo_adpcm_data_len_for_pcm_frames_with_block_align(
+    frame_count: usize,
+    block_align: usize,
+) -> usize {
+    let samples_per_block = __stereo_adpcm_samples_per_block(block_align);
+    frame_count.div_ceil(samples_per_block) * block_align
+}
+
+/// Builds a fixed-size stereo PCM clip from frames.
+#[must_use]
+#[doc(hidden)]
+pub const fn __stereo_pcm_clip_from_frames<const SAMPLE_RATE_HZ: u32, const FRAME_COUNT: usize>(
+    frames: [[i16; 2]; FRAME_COUNT],
+) -> StereoPcmClipBuf<SAMPLE_RATE_HZ, FRAME_COUNT> {
+    assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+    StereoPcmClip { frames }
+}
+
+/// Const backend helper that builds a fixed-size stereo ADPCM clip from parts.
+#[must_use]
+#[doc(hidden)]
+pub const fn __stereo_adpcm_clip_from_parts<const SAMPLE_RATE_HZ: u32, const DATA_LEN: usize>(
+    block_align: u16,
+    samples_per_block: u16,
+    data: [u8; DATA_LEN],
+) -> StereoAdpcmClipBuf<SAMPLE_RATE_HZ, DATA_LEN> {
+    StereoAdpcmClip::new(block_align, samples_per_block, data)
+}
+
+/// Const backend helper that encodes stereo PCM into ADPCM with an explicit block size.
+#[must_use]
+#[doc(hidden)]
+pub const fn __stereo_pcm_with_adpcm_block_align<
+    const SAMPLE_RATE_HZ: u32,
+    const FRAME_COUNT: usize,
+    const DATA_LEN: usize,
+>(
+    source_pcm_clip: &StereoPcmClipBuf<SAMPLE_RATE_HZ, FRAME_COUNT>,
+    block_align: usize,
+) -> StereoAdpcmClipBuf<SAMPLE_RATE_HZ, DATA_LEN> {
+    source_pcm_clip.with_adpcm_block_align::<DATA_LEN>(block_align)
+}
+
+/// Const backend helper that resamples each channel of a stereo PCM clip.
+#[must_use]
+#[doc(hidden)]
+pub const fn __resample_stereo_pcm_clip<
+    const SOURCE_HZ: u32,
+    const SOURCE_COUNT: usize,
+    const TARGET_HZ: u32,
+    const TARGET_COUNT: usize,
+>(
+    source_pcm_clip: StereoPcmClipBuf<SOURCE_HZ, SOURCE_COUNT>,
+) -> StereoPcmClipBuf<TARGET_HZ, TARGET_COUNT> {
+    let left = __resample_pcm_clip::<SOURCE_HZ, SOURCE_COUNT, TARGET_HZ, TARGET_COUNT>(
+        source_pcm_clip.left(),
+    );
+    let right = __resample_pcm_clip::<SOURCE_HZ, SOURCE_COUNT, TARGET_HZ, TARGET_COUNT>(
+        source_pcm_clip.right(),
+    );
+    let mut frames = [[0_i16; 2]; TARGET_COUNT];
+    let mut frame_index = 0_usize;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while frame_index < TARGET_COUNT {
+        frames[frame_index] = [left.samples[frame_index], right.samples[frame_index]];
+        frame_index += 1;
+    }
+    StereoPcmClip { frames }
+}
@@ -0,0 +1,171 @@
+//! Audio produced at runtime, pulled by the player in blocks.
+//!
+//! See [`AudioStream`] and [`AudioRingBuffer`].
+
+use core::cell::RefCell;
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use heapless::Deque;
+
+/// A source of audio samples produced at runtime, such as synthesized, recorded, or
+/// network audio.
+///
+/// Implement this trait (or use [`AudioRingBuffer`]) to play audio that isn't known
+/// at compile time. A `&'static` stream is a [`Playable`](crate::audio_player::Playable),
+/// so it can be passed to `play` on its own or mixed into a sequence with static clips.
+///
+/// The player's background task calls [`Self::fill`] whenever it needs more samples.
+/// Because that task also feeds I²S, `fill` must return quickly and must not block.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+pub trait AudioStream<const SAMPLE_RATE_HZ: u32>: Sync {
+    /// Writes the next mono samples to the start of `samples`, returning how many
+    /// were written.
+    ///
+    /// Returning `0` ends the stream, and playback moves on to the next clip in the
+    /// sequence. Returning fewer than `samples.len()` is fine; the player asks again.
+    /// To keep playing through a gap in the data, write silence rather than
+    /// returning `0`.
+    fn fill(&self, samples: &mut [i16]) -> usize;
+}
+
+/// A bounded [`AudioStream`] that one task writes samples into while the player
+/// plays them.
+///
+/// Declare it as a `static`, start playback with it, and write samples from another
+/// task with [`Self::write`]. If the writer falls behind, the player plays silence
+/// until more samples arrive. Call [`Self::finish`] to end the stream once the
+/// written samples have played.
+///
+/// `CAPACITY` is the number of samples buffered. Larger buffers ride out longer
+/// writer delays at the cost of RAM and latency.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+pub struct AudioRingBuffer<const SAMPLE_RATE_HZ: u32, const CAPACITY: usize> {
+    state: Mutex<CriticalSectionRawMutex, RefCell<RingBufferState<CAPACITY>>>,
+    space_available_signal: Signal<CriticalSectionRawMutex, ()>,
+}
+
+struct RingBufferState<const CAPACITY: usize> {
+    samples: Deque<i16, CAPACITY>,
+    is_finished: bool,
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const CAPACITY: usize> AudioRingBuffer<SAMPLE_RATE_HZ, CAPACITY> {
+    /// Number of samples the buffer holds.
+    pub const CAPACITY: usize = CAPACITY;
+
+    /// Creates an empty ring buffer.
+    #[must_use]
+    pub const fn new() -> Self {
+        assert!(CAPACITY > 0, "capacity must be > 0");
+        Self {
+            state: Mutex::new(RefCell::new(RingBufferState {
+                samples: Deque::new(),
+                is_finished: false,
+            })),
+            space_available_signal: Signal::new(),
+        }
+    }
+
+    /// Writes all of `samples`, waiting for the player to make room as needed.
+    pub async fn write(&self, samples: &[i16]) {
+        let mut remaining_samples = samples;
+        loop {
+            let written_count = self.try_write(remaining_samples);
+            remaining_samples = &remaining_samples[written_count..];
+            if remaining_samples.is_empty() {
+                return;
+            }
+            self.space_available_signal.wait().await;
+        }
+    }
+
+    /// Writes as many of `samples` as fit without waiting, returning how many were written.
+    pub fn try_write(&self, samples: &[i16]) -> usize {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            let mut written_count = 0;
+            for &sample in samples {
+                if state.samples.push_back(sample).is_err() {
+                    break;
+                }
+                written_count += 1;
+            }
+            written_count
+        })
+    }
+
+    /// Ends the stream after the samples already written have played.
+    ///
+    /// Once the player reaches the end, the buffer is ready to be written and played again.
+    pub fn finish(&self) {
+        self.state
+            .lock(|state| state.borrow_mut().is_finished = true);
+    }
+
+    /// Discards any buffered samples and any pending [`Self::finish`].
+    pub fn clear(&self) {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.samples.clear();
+            state.is_finished = false;
+        });
+        self.space_available_signal.signal(());
+    }
+
+    /// Returns the number of samples waiting to be played.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.state.lock(|state| state.borrow().samples.len())
+    }
+
+    /// Returns `true` if no samples are waiting to be played.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const CAPACITY: usize> Default
+    for AudioRingBuffer<SAMPLE_RATE_HZ, CAPACITY>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const CAPACITY: usize> AudioStream<SAMPLE_RATE_HZ>
+    for AudioRingBuffer<SAMPLE_RATE_HZ, CAPACITY>
+{
+    fn fill(&self, samples: &mut [i16]) -> usize {
+        let filled_count = self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            if state.samples.is_empty() && state.is_finished {
+                state.is_finished = false;
+                return 0;
+            }
+
+            let mut read_count = 0;
+            while read_count < samples.len() {
+                let Some(sample) = state.samples.pop_front() else {
+                    break;
+                };
+                samples[read_count] = sample;
+                read_count += 1;
+            }
+            if state.is_finished {
+                return read_count;
+            }
+            // Underrun: keep I²S fed with silence until the writer catches up.
+            samples[read_count..].fill(0);
+            samples.len()
+        });
+        self.space_available_signal.signal(());
+        filled_count
+    }
+}
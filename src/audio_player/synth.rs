@@ -0,0 +1,516 @@
+//! Synthesized melodies: waveforms, ADSR envelopes, and note sequences.
+//!
+//! See [`Melody`] and [`notes!`](macro@crate::notes).
+
+use core::time::Duration;
+
+use super::{
+    __samples_for_duration, Gain, PcmClip, PcmClipBuf, scale_sample_with_linear,
+    sine_sample_from_phase,
+};
+
+/// Envelope level for full (unshaped) amplitude, on the [`Gain`] linear scale.
+const UNITY_LINEAR: i32 = i16::MAX as i32;
+
+/// Beats per minute used by [`Melody::new`].
+const DEFAULT_TEMPO_BPM: u16 = 120;
+
+/// Frequencies of `C4` through `B4` in millihertz (A4 = 440 Hz, equal temperament).
+const OCTAVE_4_MILLIHERTZ: [u32; 12] = [
+    261_626, 277_183, 293_665, 311_127, 329_628, 349_228, 369_994, 391_995, 415_305, 440_000,
+    466_164, 493_883,
+];
+
+/// Oscillator shape for a [`Melody`].
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Waveform {
+    /// Pure tone, like [`tone!`](macro@crate::tone).
+    Sine,
+    /// Hollow, buzzy tone, like classic game consoles.
+    Square,
+    /// Soft tone, between sine and square.
+    Triangle,
+    /// Bright, brassy tone.
+    Saw,
+    /// Pitched noise (a new random level each cycle), for percussion and effects.
+    Noise,
+}
+
+/// Attack, decay, sustain, release envelope applied to each note of a [`Melody`].
+///
+/// Each note ramps up from silence over `attack`, falls to the `sustain` level over
+/// `decay`, holds, and then ramps back to silence over `release`. The release is
+/// taken from the end of the note, so notes never overlap and never click.
+/// Phases are shortened to fit notes that are too short for them.
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Adsr {
+    attack: Duration,
+    decay: Duration,
+    sustain_linear: i32,
+    release: Duration,
+}
+
+impl Adsr {
+    /// No envelope: every note plays at full level from start to end.
+    pub const NONE: Self = Self {
+        attack: Duration::ZERO,
+        decay: Duration::ZERO,
+        sustain_linear: UNITY_LINEAR,
+        release: Duration::ZERO,
+    };
+
+    /// A gentle envelope that avoids clicks at note boundaries. Used by [`Melody::new`].
+    pub const SOFT: Self = Self::new(
+        Duration::from_millis(10),
+        Duration::from_millis(80),
+        70,
+        Duration::from_millis(40),
+    );
+
+    /// Creates an envelope. `sustain_percent` is the held level, where `100` is full level.
+    /// Values above `100` clamp to `100`.
+    /// This uses [`core::time::Duration`].
+    #[must_use]
+    pub const fn new(
+        attack: core::time::Duration,
+        decay: core::time::Duration,
+        sustain_percent: u8,
+        release: core::time::Duration,
+    ) -> Self {
+        let sustain_percent = if sustain_percent > 100 {
+            100
+        } else {
+            sustain_percent
+        };
+        Self {
+            attack,
+            decay,
+            sustain_linear: Gain::percent(sustain_percent as u16).linear(),
+            release,
+        }
+    }
+}
+
+/// One note (or rest) of a [`Melody`].
+///
+/// Usually created with [`notes!`](macro@crate::notes).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Note {
+    frequency_millihertz: u32,
+    length_64ths: u16,
+}
+
+impl Note {
+    /// Parses one note in [`notes!`](macro@crate::notes) notation, for example `"E4:8"`.
+    ///
+    /// Panics (at compile time, in a `const`) if the notation is invalid.
+    #[must_use]
+    pub const fn from_notation(notation: &str) -> Self {
+        let notation_bytes = notation.as_bytes();
+        let (note, byte_index) = parse_note(notation_bytes, 0);
+        assert!(
+            byte_index == notation_bytes.len(),
+            "note notation must be a single note"
+        );
+        note
+    }
+
+    /// Returns the pitch in millihertz, or `0` for a rest.
+    #[must_use]
+    pub const fn frequency_millihertz(self) -> u32 {
+        self.frequency_millihertz
+    }
+
+    /// Returns the length in 64th notes; for example, a quarter note is `16`.
+    #[must_use]
+    pub const fn length_64ths(self) -> u16 {
+        self.length_64ths
+    }
+
+    /// Returns `true` for a rest.
+    #[must_use]
+    pub const fn is_rest(self) -> bool {
+        self.frequency_millihertz == 0
+    }
+
+    const fn sample_count(self, tempo_bpm: u16, sample_rate_hz: u32) -> usize {
+        // One beat is a quarter note (16 64ths).
+        (self.length_64ths as u64 * 15 * sample_rate_hz as u64 / (4 * tempo_bpm as u64)) as usize
+    }
+}
+
+/// A sequence of synthesized notes, played by rendering samples at playback time.
+///
+/// A `Melody` stores only its note list and settings, so even long tunes take
+/// almost no flash. Use it directly as a [`Playable`](crate::audio_player::Playable),
+/// or render it to a [`PcmClipBuf`] at compile time with [`Self::with_pcm`].
+///
+/// See the [audio_player module documentation](mod@crate::audio_player) for
+/// usage examples.
+#[derive(Clone, Copy, Debug)]
+pub struct Melody<const SAMPLE_RATE_HZ: u32> {
+    notes: &'static [Note],
+    tempo_bpm: u16,
+    waveform: Waveform,
+    adsr: Adsr,
+    gain: Gain,
+}
+
+impl<const SAMPLE_RATE_HZ: u32> Melody<SAMPLE_RATE_HZ> {
+    /// Creates a melody at 120 beats per minute, using [`Waveform::Triangle`] and
+    /// [`Adsr::SOFT`].
+    #[must_use]
+    pub const fn new(notes: &'static [Note]) -> Self {
+        assert!(SAMPLE_RATE_HZ > 0, "sample_rate_hz must be > 0");
+        Self {
+            notes,
+            tempo_bpm: DEFAULT_TEMPO_BPM,
+            waveform: Waveform::Triangle,
+            adsr: Adsr::SOFT,
+            gain: Gain::percent(100),
+        }
+    }
+
+    /// Returns this melody at `tempo_bpm` quarter notes per minute.
+    #[must_use]
+    pub const fn with_tempo(self, tempo_bpm: u16) -> Self {
+        assert!(tempo_bpm > 0, "tempo_bpm must be > 0");
+        Self { tempo_bpm, ..self }
+    }
+
+    /// Returns this melody played with `waveform`.
+    #[must_use]
+    pub const fn with_waveform(self, waveform: Waveform) -> Self {
+        Self { waveform, ..self }
+    }
+
+    /// Returns this melody with `adsr` applied to each note.
+    #[must_use]
+    pub const fn with_adsr(self, adsr: Adsr) -> Self {
+        Self { adsr, ..self }
+    }
+
+    /// Returns this melody with linear sample gain applied.
+    ///
+    /// Full-scale square and saw waves are loud; `Gain::percent(50)` or lower is
+    /// often a better fit when mixing with other clips.
+    #[must_use]
+    pub const fn with_gain(self, gain: Gain) -> Self {
+        Self { gain, ..self }
+    }
+
+    /// Returns the melody length in samples.
+    #[must_use]
+    pub const fn sample_count(&self) -> usize {
+        let mut sample_count = 0_usize;
+        let mut note_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while note_index < self.notes.len() {
+            sample_count += self.notes[note_index].sample_count(self.tempo_bpm, SAMPLE_RATE_HZ);
+            note_index += 1;
+        }
+        sample_count
+    }
+
+    /// Renders this melody to an uncompressed (PCM) clip at compile time.
+    ///
+    /// `SAMPLE_COUNT` must equal [`Self::sample_count`].
+    #[must_use]
+    pub const fn with_pcm<const SAMPLE_COUNT: usize>(
+        &self,
+    ) -> PcmClipBuf<SAMPLE_RATE_HZ, SAMPLE_COUNT> {
+        assert!(
+            SAMPLE_COUNT == self.sample_count(),
+            "sample count must match melody length"
+        );
+        let mut samples = [0_i16; SAMPLE_COUNT];
+        let mut synth_cursor = SynthCursor::new(self);
+        let mut sample_index = 0_usize;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < SAMPLE_COUNT {
+            samples[sample_index] = match synth_cursor.next_sample(self) {
+                Some(sample) => sample,
+                None => 0,
+            };
+            sample_index += 1;
+        }
+        PcmClip { samples }
+    }
+}
+
+/// Oscillator and envelope state while rendering a [`Melody`].
+pub(crate) struct SynthCursor {
+    next_note_index: usize,
+    sample_in_note: usize,
+    note_len: usize,
+    is_rest: bool,
+    phase_u32: u32,
+    phase_step_u32: u32,
+    noise_state_u32: u32,
+    noise_sample_i16: i16,
+    attack_len: usize,
+    decay_len: usize,
+    release_len: usize,
+}
+
+impl SynthCursor {
+    pub(crate) const fn new<const SAMPLE_RATE_HZ: u32>(melody: &Melody<SAMPLE_RATE_HZ>) -> Self {
+        Self {
+            next_note_index: 0,
+            sample_in_note: 0,
+            note_len: 0,
+            is_rest: true,
+            phase_u32: 0,
+            phase_step_u32: 0,
+            noise_state_u32: 0x9E37_79B9,
+            noise_sample_i16: 0,
+            attack_len: __samples_for_duration(melody.adsr.attack, SAMPLE_RATE_HZ),
+            decay_len: __samples_for_duration(melody.adsr.decay, SAMPLE_RATE_HZ),
+            release_len: __samples_for_duration(melody.adsr.release, SAMPLE_RATE_HZ),
+        }
+    }
+
+    /// Returns the next sample, or `None` after the last note.
+    pub(crate) const fn next_sample<const SAMPLE_RATE_HZ: u32>(
+        &mut self,
+        melody: &Melody<SAMPLE_RATE_HZ>,
+    ) -> Option<i16> {
+        while self.sample_in_note == self.note_len {
+            if self.next_note_index == melody.notes.len() {
+                return None;
+            }
+            let note = melody.notes[self.next_note_index];
+            self.next_note_index += 1;
+            self.sample_in_note = 0;
+            self.note_len = note.sample_count(melody.tempo_bpm, SAMPLE_RATE_HZ);
+            self.is_rest = note.is_rest();
+            self.phase_u32 = 0;
+            self.phase_step_u32 = (((note.frequency_millihertz as u64) << 32)
+                / (SAMPLE_RATE_HZ as u64 * 1_000)) as u32;
+        }
+
+        let sample = if self.is_rest {
+            0
+        } else {
+            let raw_sample = self.waveform_sample(melody.waveform);
+            scale_sample_with_linear(
+                scale_sample_with_linear(raw_sample, self.envelope_linear(melody.adsr)),
+                melody.gain.linear(),
+            )
+        };
+        self.phase_u32 = self.phase_u32.wrapping_add(self.phase_step_u32);
+        self.sample_in_note += 1;
+        Some(sample)
+    }
+
+    const fn waveform_sample(&mut self, waveform: Waveform) -> i16 {
+        let phase_u32 = self.phase_u32;
+        match waveform {
+            Waveform::Sine => sine_sample_from_phase(phase_u32),
+            Waveform::Square => {
+                if phase_u32 < 1 << 31 {
+                    i16::MAX
+                } else {
+                    -i16::MAX
+                }
+            }
+            Waveform::Triangle => {
+                let position_i32 = (phase_u32 >> 15) as i32;
+                if position_i32 < 1 << 16 {
+                    (position_i32 - (1 << 15)) as i16
+                } else {
+                    (3 * (1 << 15) - 1 - position_i32) as i16
+                }
+            }
+            Waveform::Saw => ((phase_u32 >> 16) as i32 - (1 << 15)) as i16,
+            Waveform::Noise => {
+                // Sample-and-hold: pick a new xorshift level at the start of each cycle.
+                if phase_u32 < self.phase_step_u32 || self.sample_in_note == 0 {
+                    let mut noise_state_u32 = self.noise_state_u32;
+                    noise_state_u32 ^= noise_state_u32 << 13;
+                    noise_state_u32 ^= noise_state_u32 >> 17;
+                    noise_state_u32 ^= noise_state_u32 << 5;
+                    self.noise_state_u32 = noise_state_u32;
+                    self.noise_sample_i16 = (noise_state_u32 >> 16) as u16 as i16;
+                }
+                self.noise_sample_i16
+            }
+        }
+    }
+
+    const fn envelope_linear(&self, adsr: Adsr) -> i32 {
+        let release_len = if self.release_len < self.note_len {
+            self.release_len
+        } else {
+            self.note_len
+        };
+        let release_start = self.note_len - release_len;
+        if self.sample_in_note < release_start {
+            return self.pre_release_linear(self.sample_in_note, adsr);
+        }
+        let release_start_linear = self.pre_release_linear(release_start, adsr);
+        (release_start_linear as i64 * (self.note_len - self.sample_in_note) as i64
+            / release_len as i64) as i32
+    }
+
+    /// Returns the attack/decay/sustain level at `sample_in_note`.
+    const fn pre_release_linear(&self, sample_in_note: usize, adsr: Adsr) -> i32 {
+        if sample_in_note < self.attack_len {
+            (UNITY_LINEAR as i64 * sample_in_note as i64 / self.attack_len as i64) as i32
+        } else if sample_in_note - self.attack_len < self.decay_len {
+            let decay_position = sample_in_note - self.attack_len;
+            UNITY_LINEAR
+                - ((UNITY_LINEAR - adsr.sustain_linear) as i64 * decay_position as i64
+                    / self.decay_len as i64) as i32
+        } else {
+            adsr.sustain_linear
+        }
+    }
+}
+
+/// Parses one note starting at `byte_index`, returning it and the index just past it.
+const fn parse_note(notation_bytes: &[u8], byte_index: usize) -> (Note, usize) {
+    let mut byte_index = byte_index;
+    assert!(byte_index < notation_bytes.len(), "expected a note");
+    let frequency_millihertz = match notation_bytes[byte_index] {
+        b'R' | b'r' => {
+            byte_index += 1;
+            0
+        }
+        note_letter => {
+            let semitone_i32 = match note_letter {
+                b'C' => 0,
+                b'D' => 2,
+                b'E' => 4,
+                b'F' => 5,
+                b'G' => 7,
+                b'A' => 9,
+                b'B' => 11,
+                _ => panic!("note name must be one of C D E F G A B, or R for a rest"),
+            };
+            byte_index += 1;
+            let accidental_i32 = if byte_index < notation_bytes.len() {
+                match notation_bytes[byte_index] {
+                    b'#' => {
+                        byte_index += 1;
+                        1
+                    }
+                    b'b' => {
+                        byte_index += 1;
+                        -1
+                    }
+                    _ => 0,
+                }
+            } else {
+                0
+            };
+            assert!(
+                byte_index < notation_bytes.len() && notation_bytes[byte_index].is_ascii_digit(),
+                "note name must be followed by an octave digit, for example E4"
+            );
+            let octave_i32 = (notation_bytes[byte_index] - b'0') as i32;
+            byte_index += 1;
+            frequency_millihertz_for_semitone(octave_i32 * 12 + semitone_i32 + accidental_i32)
+        }
+    };
+
+    // Length defaults to a quarter note.
+    let mut length_64ths = 16_u16;
+    if byte_index < notation_bytes.len() && notation_bytes[byte_index] == b':' {
+        byte_index += 1;
+        let mut division = 0_u16;
+        while byte_index < notation_bytes.len() && notation_bytes[byte_index].is_ascii_digit() {
+            division = division * 10 + (notation_bytes[byte_index] - b'0') as u16;
+            byte_index += 1;
+        }
+        length_64ths = match division {
+            1 => 64,
+            2 => 32,
+            4 => 16,
+            8 => 8,
+            16 => 4,
+            32 => 2,
+            64 => 1,
+            _ => panic!("note length must be one of 1 2 4 8 16 32 64"),
+        };
+        if byte_index < notation_bytes.len() && notation_bytes[byte_index] == b'.' {
+            assert!(length_64ths > 1, "a dotted 64th note is too short");
+            length_64ths += length_64ths / 2;
+            byte_index += 1;
+        }
+    }
+
+    (
+        Note {
+            frequency_millihertz,
+            length_64ths,
+        },
+        byte_index,
+    )
+}
+
+/// Returns the frequency of the note `semitone` semitones above `C0`.
+const fn frequency_millihertz_for_semitone(semitone: i32) -> u32 {
+    assert!(semitone >= 0, "note is below C0");
+    let octave = semitone / 12;
+    let octave_4_millihertz = OCTAVE_4_MILLIHERTZ[(semitone % 12) as usize];
+    if octave >= 4 {
+        octave_4_millihertz << (octave - 4)
+    } else {
+        octave_4_millihertz >> (4 - octave)
+    }
+}
+
+const fn skip_whitespace(notation_bytes: &[u8], byte_index: usize) -> usize {
+    let mut byte_index = byte_index;
+    while byte_index < notation_bytes.len() && notation_bytes[byte_index].is_ascii_whitespace() {
+        byte_index += 1;
+    }
+    byte_index
+}
+
+/// Returns the number of notes in whitespace-separated note notation.
+#[doc(hidden)]
+#[must_use]
+pub const fn __note_count(notation: &str) -> usize {
+    let notation_bytes = notation.as_bytes();
+    let mut note_count = 0_usize;
+    let mut byte_index = skip_whitespace(notation_bytes, 0);
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while byte_index < notation_bytes.len() {
+        byte_index = skip_whitespace(notation_bytes, parse_note(notation_bytes, byte_index).1);
+        note_count += 1;
+    }
+    note_count
+}
+
+/// Parses whitespace-separated note notation into notes.
+#[doc(hidden)]
+#[must_use]
+pub const fn __parse_notes<const NOTE_COUNT: usize>(notation: &str) -> [Note; NOTE_COUNT] {
+    let notation_bytes = notation.as_bytes();
+    let mut notes = [Note {
+        frequency_millihertz: 0,
+        length_64ths: 0,
+    }; NOTE_COUNT];
+    let mut byte_index = skip_whitespace(notation_bytes, 0);
+    let mut note_index = 0_usize;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while note_index < NOTE_COUNT {
+        let (note, next_byte_index) = parse_note(notation_bytes, byte_index);
+        notes[note_index] = note;
+        byte_index = skip_whitespace(notation_bytes, next_byte_index);
+        note_index += 1;
+    }
+    assert!(
+        byte_index == notation_bytes.len(),
+        "note count must match notation"
+    );
+    notes
+}
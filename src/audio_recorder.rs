@@ -0,0 +1,1060 @@
+//! A device abstraction for I²S microphones, such as the INMP441 and SPH0645.
+//!
+//! See [`audio_recorder!`] and [`AudioRecorderGenerated`](audio_recorder_generated::AudioRecorderGenerated)
+//! for the generated recorder type.
+//!
+//! **Features**
+//!
+//! - Any sample rate supported by your microphone
+//! - 16-bit mono samples, taken from the left or right I²S slot (set by the mic's `L/R` pin)
+//! - A ring buffer of recent samples, read with
+//!   [`read`](audio_recorder_generated::AudioRecorderGenerated::read)
+//! - A level meter ([`AudioLevel`]) updated every [`LEVEL_BLOCK_LEN`] samples, for VU meters,
+//!   and [`wait_for_level`](audio_recorder_generated::AudioRecorderGenerated::wait_for_level)
+//!   for sound-activated triggers
+//! - Recording into flash with [`FlashClip`], which [`audio_player`](mod@crate::audio_player)
+//!   can play back
+//!
+//! **Wiring**
+//!
+//! The recorder drives the bit clock (`SCK`) and word select (`WS`) pins, and reads the
+//! data (`SD`) pin. Tie the mic's `L/R` pin low for [`MicChannel::Left`] (the default) or
+//! high for [`MicChannel::Right`].
+//!
+//! The recorder defaults to `PIO1` and `DMA_CH1`, so it can run beside an
+//! [`audio_player!`](macro@crate::audio_player) with its default `PIO0` and `DMA_CH0`.
+//!
+//! # Example: Light an LED While It's Loud
+//!
+//! This example waits for a sound louder than 20% of full scale, then lights an LED
+//! until the level drops again.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{VOICE_16000_HZ, Volume},
+//!     audio_recorder::audio_recorder,
+//!     led::{Led, LedStatic, OnLevel},
+//! };
+//! use embassy_rp::gpio::Level;
+//!
+//! audio_recorder! {
+//!     Mic2 {
+//!         data_pin: PIN_2,
+//!         bit_clock_pin: PIN_3,
+//!         word_select_pin: PIN_4,
+//!         sample_rate_hz: VOICE_16000_HZ,
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     static LED_STATIC: LedStatic = Led::new_static();
+//!     let p = embassy_rp::init(Default::default());
+//!     let led = Led::new(&LED_STATIC, p.PIN_25, OnLevel::High, spawner)?;
+//!     let mic2 = Mic2::new(p.PIN_2, p.PIN_3, p.PIN_4, p.PIO1, p.DMA_CH1, spawner)?;
+//!
+//!     const THRESHOLD: Volume = Volume::percent(20);
+//!     loop {
+//!         mic2.wait_for_level(THRESHOLD).await;
+//!         led.set_level(Level::High);
+//!         while mic2.level().peak() >= THRESHOLD {
+//!             mic2.wait_for_level(Volume::MUTE).await;
+//!         }
+//!         led.set_level(Level::Low);
+//!     }
+//! }
+//! ```
+//!
+//! # Example: Record a Voice Memo and Play It Back
+//!
+//! This example records into flash while a button is held, then plays the recording.
+//! The memo stays in flash across resets.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     audio_player::{AtEnd, VOICE_16000_HZ, audio_player},
+//!     audio_recorder::{FlashClip, audio_recorder},
+//!     button::{Button, PressedTo},
+//!     flash_array::FlashArray,
+//! };
+//!
+//! audio_recorder! {
+//!     Mic2 {
+//!         data_pin: PIN_2,
+//!         bit_clock_pin: PIN_3,
+//!         word_select_pin: PIN_4,
+//!         sample_rate_hz: VOICE_16000_HZ,
+//!     }
+//! }
+//!
+//! audio_player! {
+//!     AudioPlayer8 {
+//!         data_pin: PIN_8,
+//!         bit_clock_pin: PIN_9,
+//!         word_select_pin: PIN_10,
+//!         sample_rate_hz: VOICE_16000_HZ,
+//!     }
+//! }
+//!
+//! // 16 flash blocks hold about 2 seconds at 16 kHz.
+//! static MEMO: FlashClip<VOICE_16000_HZ, 16> = FlashClip::new();
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     let p = embassy_rp::init(Default::default());
+//!     MEMO.init(FlashArray::<16>::new(p.FLASH)?)?;
+//!     let mut button = Button::new(p.PIN_13, PressedTo::Ground);
+//!     let mic2 = Mic2::new(p.PIN_2, p.PIN_3, p.PIN_4, p.PIO1, p.DMA_CH1, spawner)?;
+//!     let audio_player8 = AudioPlayer8::new(p.PIN_8, p.PIN_9, p.PIN_10, p.PIO0, p.DMA_CH0, spawner)?;
+//!
+//!     loop {
+//!         button.wait_for_press().await;
+//!         mic2.record_to_flash(&MEMO, button.wait_for_release()).await?;
+//!         let memo: &'static AudioPlayer8Playable = &MEMO;
+//!         audio_player8.play([memo], AtEnd::Stop);
+//!     }
+//! }
+//! ```
+#![cfg_attr(all(test, feature = "host"), allow(dead_code))]
+
+pub mod audio_recorder_generated;
+#[cfg(target_os = "none")]
+mod flash_clip;
+#[cfg(all(test, feature = "host"))]
+mod host_tests;
+
+#[cfg(target_os = "none")]
+pub use flash_clip::FlashClip;
+
+use core::cell::RefCell;
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use heapless::Deque;
+
+use crate::audio_player::Volume;
+
+#[cfg(target_os = "none")]
+use crate::Result;
+#[cfg(target_os = "none")]
+use crate::pio_irqs::PioIrqMap;
+#[cfg(target_os = "none")]
+use core::pin::pin;
+#[cfg(target_os = "none")]
+use embassy_futures::select::{Either, select};
+#[cfg(target_os = "none")]
+use embassy_rp::Peri;
+#[cfg(target_os = "none")]
+use embassy_rp::dma::Channel;
+#[cfg(target_os = "none")]
+use embassy_rp::pio::{Config, Direction, FifoJoin, Pio, PioPin, ShiftConfig, ShiftDirection};
+#[cfg(target_os = "none")]
+use fixed::traits::ToFixed;
+
+/// Number of samples in each block read from the microphone, and in each [`AudioLevel`]
+/// measurement.
+pub const LEVEL_BLOCK_LEN: usize = 256;
+
+/// Bit clock cycles per I²S frame: two 32-bit slots.
+#[cfg(target_os = "none")]
+const BIT_CLOCKS_PER_FRAME: u32 = 64;
+
+/// Which I²S slot the microphone sends on.
+///
+/// Set with the `channel` field of [`audio_recorder!`]. Mics like the INMP441 and
+/// SPH0645 choose their slot with an `L/R` pin.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MicChannel {
+    /// Left slot (`L/R` pin tied low).
+    Left,
+    /// Right slot (`L/R` pin tied high).
+    Right,
+}
+
+/// Loudness of one block of [`LEVEL_BLOCK_LEN`] recorded samples.
+///
+/// Both measures are relative to full scale, as a [`Volume`]: `peak` is the largest
+/// sample magnitude, and `rms` (root mean square) tracks perceived loudness.
+///
+/// See the [audio_recorder module documentation](mod@crate::audio_recorder) for
+/// usage examples.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AudioLevel {
+    peak: Volume,
+    rms: Volume,
+}
+
+impl AudioLevel {
+    /// The level of silence.
+    pub const SILENCE: Self = Self {
+        peak: Volume::MUTE,
+        rms: Volume::MUTE,
+    };
+
+    /// Measures the level of `samples`.
+    #[must_use]
+    pub const fn from_samples(samples: &[i16]) -> Self {
+        if samples.is_empty() {
+            return Self::SILENCE;
+        }
+        let mut peak_u16 = 0_u16;
+        let mut sum_of_squares_u64 = 0_u64;
+        let mut sample_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while sample_index < samples.len() {
+            let magnitude_u16 = samples[sample_index].unsigned_abs();
+            if magnitude_u16 > peak_u16 {
+                peak_u16 = magnitude_u16;
+            }
+            sum_of_squares_u64 += magnitude_u16 as u64 * magnitude_u16 as u64;
+            sample_index += 1;
+        }
+        let rms_u64 = (sum_of_squares_u64 / samples.len() as u64).isqrt();
+        Self {
+            peak: volume_from_magnitude(peak_u16 as u64),
+            rms: volume_from_magnitude(rms_u64),
+        }
+    }
+
+    /// Largest sample magnitude, relative to full scale.
+    #[must_use]
+    pub const fn peak(self) -> Volume {
+        self.peak
+    }
+
+    /// Root mean square level, relative to full scale.
+    #[must_use]
+    pub const fn rms(self) -> Volume {
+        self.rms
+    }
+
+    const fn to_u32(self) -> u32 {
+        ((self.peak.to_i16() as u16 as u32) << 16) | self.rms.to_i16() as u16 as u32
+    }
+
+    const fn from_u32(level_u32: u32) -> Self {
+        Self {
+            peak: Volume::from_i16((level_u32 >> 16) as u16 as i16),
+            rms: Volume::from_i16(level_u32 as u16 as i16),
+        }
+    }
+}
+
+/// Converts a sample magnitude (up to `32_768`) to a [`Volume`], saturating at full scale.
+const fn volume_from_magnitude(magnitude_u64: u64) -> Volume {
+    if magnitude_u64 > i16::MAX as u64 {
+        Volume::MAX
+    } else {
+        Volume::from_i16(magnitude_u64 as i16)
+    }
+}
+
+/// Converts one 32-bit I²S slot (left-justified, 16 to 24 significant bits) to a sample.
+#[inline]
+const fn sample_from_slot(slot_u32: u32) -> i16 {
+    (slot_u32 >> 16) as u16 as i16
+}
+
+/// Static resources for [`AudioRecorder`].
+// Must be `pub` so `audio_recorder!` expansions in downstream crates can reference this type.
+#[doc(hidden)]
+pub struct AudioRecorderStatic<const SAMPLE_RATE_HZ: u32, const CAPACITY: usize> {
+    state: Mutex<CriticalSectionRawMutex, RefCell<RecorderState<CAPACITY>>>,
+    samples_available_signal: Signal<CriticalSectionRawMutex, ()>,
+    level_signal: Signal<CriticalSectionRawMutex, AudioLevel>,
+    level_u32: AtomicU32,
+}
+
+struct RecorderState<const CAPACITY: usize> {
+    samples: Deque<i16, CAPACITY>,
+    overrun_count: u32,
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const CAPACITY: usize>
+    AudioRecorderStatic<SAMPLE_RATE_HZ, CAPACITY>
+{
+    /// Creates static resources for a recorder.
+    #[must_use]
+    pub const fn new_static() -> Self {
+        assert!(CAPACITY > 0, "capacity must be > 0");
+        Self {
+            state: Mutex::new(RefCell::new(RecorderState {
+                samples: Deque::new(),
+                overrun_count: 0,
+            })),
+            samples_available_signal: Signal::new(),
+            level_signal: Signal::new(),
+            level_u32: AtomicU32::new(AudioLevel::SILENCE.to_u32()),
+        }
+    }
+
+    /// Adds one block of recorded samples, dropping the oldest samples if the buffer is
+    /// full, and updates the level meter.
+    fn push_block(&self, samples: &[i16]) {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            for &sample in samples {
+                if state.samples.is_full() {
+                    state.samples.pop_front();
+                    state.overrun_count = state.overrun_count.wrapping_add(1);
+                }
+                // Cannot fail: a slot was just freed if needed.
+                let _ = state.samples.push_back(sample);
+            }
+        });
+        let audio_level = AudioLevel::from_samples(samples);
+        self.level_u32
+            .store(audio_level.to_u32(), Ordering::Relaxed);
+        self.level_signal.signal(audio_level);
+        self.samples_available_signal.signal(());
+    }
+}
+
+/// Reads samples and levels from a microphone in the background device task.
+///
+/// See the [`audio_recorder!`] macro for the normal construction pattern.
+// Must be `pub` so `audio_recorder!` expansions in downstream crates can reference this type.
+#[doc(hidden)]
+pub struct AudioRecorder<const SAMPLE_RATE_HZ: u32, const CAPACITY: usize> {
+    audio_recorder_static: &'static AudioRecorderStatic<SAMPLE_RATE_HZ, CAPACITY>,
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const CAPACITY: usize> AudioRecorder<SAMPLE_RATE_HZ, CAPACITY> {
+    /// Sample rate of recorded samples.
+    pub const SAMPLE_RATE_HZ: u32 = SAMPLE_RATE_HZ;
+    /// Number of recorded samples buffered for [`Self::read`].
+    pub const CAPACITY: usize = CAPACITY;
+
+    /// Creates static resources for a recorder.
+    #[must_use]
+    pub const fn new_static() -> AudioRecorderStatic<SAMPLE_RATE_HZ, CAPACITY> {
+        AudioRecorderStatic::new_static()
+    }
+
+    /// Creates a recorder handle from static resources.
+    #[must_use]
+    pub const fn new(
+        audio_recorder_static: &'static AudioRecorderStatic<SAMPLE_RATE_HZ, CAPACITY>,
+    ) -> Self {
+        Self {
+            audio_recorder_static,
+        }
+    }
+
+    /// Waits for recorded samples, then moves as many as fit into `samples`, oldest
+    /// first, returning how many were read.
+    ///
+    /// Samples not read within [`Self::CAPACITY`] samples of being recorded are dropped
+    /// (see [`Self::overrun_count`]).
+    ///
+    /// See the [audio_recorder module documentation](mod@crate::audio_recorder) for
+    /// usage examples.
+    pub async fn read(&self, samples: &mut [i16]) -> usize {
+        loop {
+            let read_count = self.try_read(samples);
+            if read_count > 0 || samples.is_empty() {
+                return read_count;
+            }
+            self.audio_recorder_static
+                .samples_available_signal
+                .wait()
+                .await;
+        }
+    }
+
+    /// Moves as many buffered samples as fit into `samples` without waiting, returning
+    /// how many were read.
+    pub fn try_read(&self, samples: &mut [i16]) -> usize {
+        self.audio_recorder_static.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            let mut read_count = 0;
+            for sample_slot in samples.iter_mut() {
+                let Some(sample) = state.samples.pop_front() else {
+                    break;
+                };
+                *sample_slot = sample;
+                read_count += 1;
+            }
+            read_count
+        })
+    }
+
+    /// Discards buffered samples, so the next [`Self::read`] returns only new audio.
+    pub fn clear(&self) {
+        self.audio_recorder_static
+            .state
+            .lock(|state| state.borrow_mut().samples.clear());
+    }
+
+    /// Returns the level of the most recent block of [`LEVEL_BLOCK_LEN`] samples.
+    ///
+    /// See the [audio_recorder module documentation](mod@crate::audio_recorder) for
+    /// usage examples.
+    #[must_use]
+    pub fn level(&self) -> AudioLevel {
+        AudioLevel::from_u32(self.audio_recorder_static.level_u32.load(Ordering::Relaxed))
+    }
+
+    /// Waits for a block whose peak reaches `threshold`, and returns its level.
+    ///
+    /// `Volume::MUTE` returns the next block's level, whatever it is.
+    ///
+    /// See the [audio_recorder module documentation](mod@crate::audio_recorder) for
+    /// usage examples.
+    pub async fn wait_for_level(&self, threshold: Volume) -> AudioLevel {
+        self.audio_recorder_static.level_signal.reset();
+        loop {
+            let audio_level = self.audio_recorder_static.level_signal.wait().await;
+            if audio_level.peak() >= threshold {
+                return audio_level;
+            }
+        }
+    }
+
+    /// Returns how many samples have been dropped because they weren't read in time.
+    #[must_use]
+    pub fn overrun_count(&self) -> u32 {
+        self.audio_recorder_static
+            .state
+            .lock(|state| state.borrow().overrun_count)
+    }
+
+    /// Records into `flash_clip` until `stop` completes or the clip is full, and
+    /// returns the number of samples recorded.
+    ///
+    /// The clip's blocks are erased first, which takes tens of milliseconds per block,
+    /// so recording starts a moment after this is called. Any previous recording in
+    /// the clip is replaced.
+    ///
+    /// See the [audio_recorder module documentation](mod@crate::audio_recorder) for
+    /// usage examples.
+    #[cfg(target_os = "none")]
+    pub async fn record_to_flash<const BLOCK_COUNT: usize>(
+        &self,
+        flash_clip: &FlashClip<SAMPLE_RATE_HZ, BLOCK_COUNT>,
+        stop: impl Future<Output = ()>,
+    ) -> Result<usize> {
+        flash_clip.begin_recording()?;
+        self.clear();
+        let mut stop = pin!(stop);
+        let mut samples = [0_i16; LEVEL_BLOCK_LEN];
+        loop {
+            let event = select(self.read(&mut samples), stop.as_mut()).await;
+            let Either::First(read_count) = event else {
+                break;
+            };
+            let append_count = flash_clip.append(&samples[..read_count])?;
+            if append_count < read_count {
+                break;
+            }
+        }
+        flash_clip.finish_recording()
+    }
+}
+
+// Called by macro-generated code in downstream crates; must be public.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+#[expect(
+    clippy::too_many_arguments,
+    reason = "one argument per pin and resource"
+)]
+pub async fn device_loop<
+    const SAMPLE_RATE_HZ: u32,
+    const CAPACITY: usize,
+    PIO: PioIrqMap,
+    DMA: Channel,
+    DataPin: PioPin,
+    BitClockPin: PioPin,
+    WordSelectPin: PioPin,
+>(
+    audio_recorder_static: &'static AudioRecorderStatic<SAMPLE_RATE_HZ, CAPACITY>,
+    pio: Peri<'static, PIO>,
+    mut dma: Peri<'static, DMA>,
+    data_pin: Peri<'static, DataPin>,
+    bit_clock_pin: Peri<'static, BitClockPin>,
+    word_select_pin: Peri<'static, WordSelectPin>,
+    mic_channel: MicChannel,
+) -> ! {
+    let mut pio = Pio::new(pio, <PIO as PioIrqMap>::irqs());
+    // Like `embassy_rp`'s I²S input program, but with the 32-bit slots that I²S mics
+    // expect. Side-set pins are bit clock (bit 0) and word select (bit 1).
+    let program = pio::pio_asm!(
+        ".side_set 2",
+        "    set x, 30          side 0b01",
+        "left_data:",
+        "    in pins, 1         side 0b00",
+        "    jmp x-- left_data  side 0b01",
+        "    in pins, 1         side 0b10", // word select changes one clock before the MSB
+        "    set x, 30          side 0b11",
+        "right_data:",
+        "    in pins, 1         side 0b10",
+        "    jmp x-- right_data side 0b11",
+        "    in pins, 1         side 0b00",
+    );
+    let loaded_program = pio.common.load_program(&program.program);
+    let data_pin = pio.common.make_pio_pin(data_pin);
+    let bit_clock_pin = pio.common.make_pio_pin(bit_clock_pin);
+    let word_select_pin = pio.common.make_pio_pin(word_select_pin);
+
+    let mut config = Config::default();
+    config.use_program(&loaded_program, &[&bit_clock_pin, &word_select_pin]);
+    config.set_in_pins(&[&data_pin]);
+    // Each bit clock cycle takes two instructions.
+    let bit_clock_hz = SAMPLE_RATE_HZ * BIT_CLOCKS_PER_FRAME;
+    config.clock_divider =
+        (embassy_rp::clocks::clk_sys_freq() as f64 / bit_clock_hz as f64 / 2.0).to_fixed();
+    config.shift_in = ShiftConfig {
+        threshold: 32,
+        direction: ShiftDirection::Left,
+        auto_fill: true,
+    };
+    config.fifo_join = FifoJoin::RxOnly;
+    let mut state_machine = pio.sm0;
+    state_machine.set_config(&config);
+    state_machine.set_pin_dirs(Direction::In, &[&data_pin]);
+    state_machine.set_pin_dirs(Direction::Out, &[&bit_clock_pin, &word_select_pin]);
+    state_machine.set_enable(true);
+
+    let slot_index = match mic_channel {
+        MicChannel::Left => 0,
+        MicChannel::Right => 1,
+    };
+    let mut slot_buffer = [0_u32; 2 * LEVEL_BLOCK_LEN];
+    let mut sample_buffer = [0_i16; LEVEL_BLOCK_LEN];
+    loop {
+        state_machine
+            .rx()
+            .dma_pull(dma.reborrow(), &mut slot_buffer, false)
+            .await;
+        for (sample, frame) in sample_buffer.iter_mut().zip(slot_buffer.chunks_exact(2)) {
+            *sample = sample_from_slot(frame[slot_index]);
+        }
+        audio_recorder_static.push_block(&sample_buffer);
+    }
+}
+
+/// Macro to generate an audio recorder struct type for an I²S microphone (includes
+/// syntax details).
+///
+/// See [`AudioRecorderGenerated`](crate::audio_recorder::audio_recorder_generated::AudioRecorderGenerated)
+/// for a sample of a generated type.
+///
+/// **See the [audio_recorder module documentation](mod@crate::audio_recorder) for
+/// usage examples.**
+///
+/// **Syntax:**
+///
+/// ```text
+/// audio_recorder! {
+///     [<visibility>] <Name> {
+///         data_pin: <pin_ident>,
+///         bit_clock_pin: <pin_ident>,
+///         word_select_pin: <pin_ident>,
+///         sample_rate_hz: <sample_rate_expr>,
+///         channel: <MicChannel_expr>,       // optional
+///         pio: <pio_ident>,                 // optional
+///         dma: <dma_ident>,                 // optional
+///         capacity: <usize_expr>,           // optional
+///     }
+/// }
+/// ```
+///
+/// **Inputs:**
+///
+/// - `$vis` - Optional generated type visibility (for example: `pub`,
+///   `pub(crate)`, `pub(self)`). Defaults to private visibility when omitted.
+/// - `$name` - Generated type name (for example: `Mic2`)
+///
+/// **Required fields:**
+///
+/// - `data_pin` - GPIO pin connected to the mic's I²S data (`SD`)
+/// - `bit_clock_pin` - GPIO pin connected to the mic's bit clock (`SCK`)
+/// - `word_select_pin` - GPIO pin connected to the mic's word select (`WS`)
+/// - `sample_rate_hz` - Recording sample rate in hertz (for example:
+///   [`VOICE_16000_HZ`](crate::audio_player::VOICE_16000_HZ))
+///
+/// **Optional fields:**
+///
+/// - `channel` - I²S slot the mic sends on (default: [`MicChannel::Left`])
+/// - `pio` - PIO resource (default: `PIO1`)
+/// - `dma` - DMA channel (default: `DMA_CH1`)
+/// - `capacity` - Number of recorded samples buffered for `read` (default: `2048`)
+///
+/// **Generated items:**
+///
+/// - `<Name>` - generated recorder struct type
+/// - associated constants and methods on `<Name>` (for example:
+///   `SAMPLE_RATE_HZ`, `new(...)`, `read(...)`, `level()`, `wait_for_level(...)`,
+///   and `record_to_flash(...)`)
+///
+/// The generated type contains static resources and spawns its background device
+/// task from `new(...)`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! audio_recorder {
+    ($($tt:tt)*) => { $crate::__audio_recorder_impl! { $($tt)* } };
+}
+
+/// Internal implementation macro for [`audio_recorder!`].
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __audio_recorder_impl {
+    (
+        $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: pub(self),
+            name: $name,
+            data_pin: _UNSET_,
+            bit_clock_pin: _UNSET_,
+            word_select_pin: _UNSET_,
+            sample_rate_hz: _UNSET_,
+            channel: $crate::audio_recorder::MicChannel::Left,
+            pio: PIO1,
+            dma: DMA_CH1,
+            capacity: 2048,
+            fields: [ $($fields)* ]
+        }
+    };
+
+    (
+        $vis:vis $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: _UNSET_,
+            bit_clock_pin: _UNSET_,
+            word_select_pin: _UNSET_,
+            sample_rate_hz: _UNSET_,
+            channel: $crate::audio_recorder::MicChannel::Left,
+            pio: PIO1,
+            dma: DMA_CH1,
+            capacity: 2048,
+            fields: [ $($fields)* ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ data_pin: $data_pin_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin_value,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            channel: $channel,
+            pio: $pio,
+            dma: $dma,
+            capacity: $capacity,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ bit_clock_pin: $bit_clock_pin_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin_value,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            channel: $channel,
+            pio: $pio,
+            dma: $dma,
+            capacity: $capacity,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ word_select_pin: $word_select_pin_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin_value,
+            sample_rate_hz: $sample_rate_hz,
+            channel: $channel,
+            pio: $pio,
+            dma: $dma,
+            capacity: $capacity,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ sample_rate_hz: $sample_rate_hz_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: ($sample_rate_hz_value),
+            channel: $channel,
+            pio: $pio,
+            dma: $dma,
+            capacity: $capacity,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ channel: $channel_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            channel: $channel_value,
+            pio: $pio,
+            dma: $dma,
+            capacity: $capacity,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ pio: $pio_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            channel: $channel,
+            pio: $pio_value,
+            dma: $dma,
+            capacity: $capacity,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ dma: $dma_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            channel: $channel,
+            pio: $pio,
+            dma: $dma_value,
+            capacity: $capacity,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:tt,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ capacity: $capacity_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__audio_recorder_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            data_pin: $data_pin,
+            bit_clock_pin: $bit_clock_pin,
+            word_select_pin: $word_select_pin,
+            sample_rate_hz: $sample_rate_hz,
+            channel: $channel,
+            pio: $pio,
+            dma: $dma,
+            capacity: $capacity_value,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: _UNSET_,
+        bit_clock_pin: $bit_clock_pin:tt,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("audio_recorder! requires data_pin");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:ident,
+        bit_clock_pin: _UNSET_,
+        word_select_pin: $word_select_pin:tt,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("audio_recorder! requires bit_clock_pin");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:ident,
+        bit_clock_pin: $bit_clock_pin:ident,
+        word_select_pin: _UNSET_,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("audio_recorder! requires word_select_pin");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:ident,
+        bit_clock_pin: $bit_clock_pin:ident,
+        word_select_pin: $word_select_pin:ident,
+        sample_rate_hz: _UNSET_,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("audio_recorder! requires sample_rate_hz");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        data_pin: $data_pin:ident,
+        bit_clock_pin: $bit_clock_pin:ident,
+        word_select_pin: $word_select_pin:ident,
+        sample_rate_hz: $sample_rate_hz:tt,
+        channel: $channel:expr,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        capacity: $capacity:expr,
+        fields: [ ]
+    ) => {
+        $crate::audio_player::paste::paste! {
+            static [<$name:upper _AUDIO_RECORDER_STATIC>]:
+                $crate::audio_recorder::AudioRecorderStatic<{ $sample_rate_hz }, { $capacity }> =
+                $crate::audio_recorder::AudioRecorder::<{ $sample_rate_hz }, { $capacity }>::new_static();
+            static [<$name:upper _AUDIO_RECORDER_CELL>]: ::static_cell::StaticCell<$name> =
+                ::static_cell::StaticCell::new();
+
+            #[doc = concat!(
+                "Audio recorder generated by [`audio_recorder!`](macro@crate::audio_recorder).\n\n",
+                "See the [audio_recorder module documentation](mod@crate::audio_recorder) for usage and examples."
+            )]
+            $vis struct $name {
+                recorder: $crate::audio_recorder::AudioRecorder<{ $sample_rate_hz }, { $capacity }>,
+            }
+
+            impl $name {
+                /// Creates and spawns the generated audio recorder instance.
+                ///
+                /// See the [audio_recorder module documentation](mod@crate::audio_recorder)
+                /// for example usage.
+                pub fn new(
+                    data_pin: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$data_pin>>,
+                    bit_clock_pin: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$bit_clock_pin>>,
+                    word_select_pin: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$word_select_pin>>,
+                    pio: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$pio>>,
+                    dma: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$dma>>,
+                    spawner: ::embassy_executor::Spawner,
+                ) -> $crate::Result<&'static Self> {
+                    let token = [<$name:snake _audio_recorder_task>](
+                        &[<$name:upper _AUDIO_RECORDER_STATIC>],
+                        pio.into(),
+                        dma.into(),
+                        data_pin.into(),
+                        bit_clock_pin.into(),
+                        word_select_pin.into(),
+                    );
+                    spawner.spawn(token)?;
+                    let recorder = $crate::audio_recorder::AudioRecorder::new(
+                        &[<$name:upper _AUDIO_RECORDER_STATIC>],
+                    );
+                    Ok([<$name:upper _AUDIO_RECORDER_CELL>].init(Self { recorder }))
+                }
+            }
+
+            impl ::core::ops::Deref for $name {
+                type Target = $crate::audio_recorder::AudioRecorder<{ $sample_rate_hz }, { $capacity }>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.recorder
+                }
+            }
+
+            #[::embassy_executor::task]
+            async fn [<$name:snake _audio_recorder_task>](
+                audio_recorder_static: &'static $crate::audio_recorder::AudioRecorderStatic<{ $sample_rate_hz }, { $capacity }>,
+                pio: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$pio>,
+                dma: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$dma>,
+                data_pin: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$data_pin>,
+                bit_clock_pin: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$bit_clock_pin>,
+                word_select_pin: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$word_select_pin>,
+            ) -> ! {
+                $crate::audio_recorder::device_loop::<
+                    { $sample_rate_hz },
+                    { $capacity },
+                    ::embassy_rp::peripherals::$pio,
+                    ::embassy_rp::peripherals::$dma,
+                    ::embassy_rp::peripherals::$data_pin,
+                    ::embassy_rp::peripherals::$bit_clock_pin,
+                    ::embassy_rp::peripherals::$word_select_pin,
+                >(
+                    audio_recorder_static,
+                    pio,
+                    dma,
+                    data_pin,
+                    bit_clock_pin,
+                    word_select_pin,
+                    $channel,
+                ).await
+            }
+        }
+    };
+}
+
+#[doc(inline)]
+pub use audio_recorder;
@@ -0,0 +1,130 @@
+// @generated by `cargo check-all`. Do not edit by hand.
+//! Module containing [`AudioRecorderGenerated`], the sample struct type generated
+//! by the [`audio_recorder!`](macro@crate::audio_recorder) macro.
+//!
+//! Auto-generated.
+
+#[cfg(all(not(doc), not(feature = "host")))]
+use crate::audio_recorder;
+
+#[cfg(all(not(doc), not(feature = "host")))]
+audio_recorder! {
+    pub AudioRecorderGenerated {
+        data_pin: PIN_2,
+        bit_clock_pin: PIN_3,
+        word_select_pin: PIN_4,
+        sample_rate_hz: crate::audio_player::VOICE_16000_HZ,
+    }
+}
+
+#[cfg(doc)]
+/// Sample struct type generated by the [`audio_recorder!`](macro@crate::audio_recorder)
+/// macro, showing methods and associated constants.
+///
+/// This page serves as the reference for what a generated audio recorder type
+/// provides. For first-time readers, start with the
+/// [`audio_recorder`](mod@crate::audio_recorder) module documentation, then return
+/// here for a complete list of available methods and associated constants.
+///
+/// Auto-generated.
+pub struct AudioRecorderGenerated;
+
+#[cfg(doc)]
+use crate::Result;
+#[cfg(doc)]
+use crate::audio_player::{VOICE_16000_HZ, Volume};
+#[cfg(doc)]
+use crate::audio_recorder::{AudioLevel, FlashClip};
+
+#[cfg(doc)]
+impl AudioRecorderGenerated {
+    /// Sample rate of recorded samples.
+    ///
+    /// See the [`audio_recorder`](mod@crate::audio_recorder) module docs for usage.
+    pub const SAMPLE_RATE_HZ: u32 = VOICE_16000_HZ;
+    /// Number of recorded samples buffered for [`Self::read`].
+    ///
+    /// Set by the `capacity` field of [`audio_recorder!`](macro@crate::audio_recorder)
+    /// (default: `2048`).
+    pub const CAPACITY: usize = 2048;
+
+    /// Creates and spawns the generated audio recorder instance.
+    ///
+    /// See the [`audio_recorder`](mod@crate::audio_recorder) module docs for usage.
+    pub fn new(
+        data_pin: embassy_rp::Peri<'static, embassy_rp::peripherals::PIN_2>,
+        bit_clock_pin: embassy_rp::Peri<'static, embassy_rp::peripherals::PIN_3>,
+        word_select_pin: embassy_rp::Peri<'static, embassy_rp::peripherals::PIN_4>,
+        pio: embassy_rp::Peri<'static, embassy_rp::peripherals::PIO1>,
+        dma: embassy_rp::Peri<'static, embassy_rp::peripherals::DMA_CH1>,
+        spawner: embassy_executor::Spawner,
+    ) -> Result<&'static Self> {
+        static INSTANCE: AudioRecorderGenerated = AudioRecorderGenerated;
+        let _ = (data_pin, bit_clock_pin, word_select_pin, pio, dma, spawner);
+        Ok(&INSTANCE)
+    }
+
+    /// Waits for recorded samples, then moves as many as fit into `samples`, oldest
+    /// first, returning how many were read.
+    ///
+    /// Samples not read within [`Self::CAPACITY`] samples of being recorded are dropped
+    /// (see [`Self::overrun_count`]).
+    ///
+    /// See the [`audio_recorder`](mod@crate::audio_recorder) module docs for usage.
+    pub async fn read(&self, samples: &mut [i16]) -> usize {
+        let _ = samples;
+        0
+    }
+
+    /// Moves as many buffered samples as fit into `samples` without waiting, returning
+    /// how many were read.
+    pub fn try_read(&self, samples: &mut [i16]) -> usize {
+        let _ = samples;
+        0
+    }
+
+    /// Discards buffered samples, so the next [`Self::read`] returns only new audio.
+    pub fn clear(&self) {}
+
+    /// Returns the level of the most recent block of
+    /// [`LEVEL_BLOCK_LEN`](crate::audio_recorder::LEVEL_BLOCK_LEN) samples.
+    ///
+    /// See the [`audio_recorder`](mod@crate::audio_recorder) module docs for usage.
+    #[must_use]
+    pub fn level(&self) -> AudioLevel {
+        AudioLevel::SILENCE
+    }
+
+    /// Waits for a block whose peak reaches `threshold`, and returns its level.
+    ///
+    /// `Volume::MUTE` returns the next block's level, whatever it is.
+    ///
+    /// See the [`audio_recorder`](mod@crate::audio_recorder) module docs for usage.
+    pub async fn wait_for_level(&self, threshold: Volume) -> AudioLevel {
+        let _ = threshold;
+        AudioLevel::SILENCE
+    }
+
+    /// Returns how many samples have been dropped because they weren't read in time.
+    #[must_use]
+    pub fn overrun_count(&self) -> u32 {
+        0
+    }
+
+    /// Records into `flash_clip` until `stop` completes or the clip is full, and
+    /// returns the number of samples recorded.
+    ///
+    /// The clip's blocks are erased first, which takes tens of milliseconds per block,
+    /// so recording starts a moment after this is called. Any previous recording in
+    /// the clip is replaced.
+    ///
+    /// See the [`audio_recorder`](mod@crate::audio_recorder) module docs for usage.
+    pub async fn record_to_flash<const BLOCK_COUNT: usize>(
+        &self,
+        flash_clip: &FlashClip<VOICE_16000_HZ, BLOCK_COUNT>,
+        stop: impl core::future::Future<Output = ()>,
+    ) -> Result<usize> {
+        let _ = (flash_clip, stop);
+        Ok(0)
+    }
+}
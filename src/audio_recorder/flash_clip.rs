@@ -0,0 +1,242 @@
+use core::cell::RefCell;
+use core::time::Duration;
+
+use embassy_rp::flash::ERASE_SIZE;
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+
+use crate::Result;
+use crate::audio_player::AudioStream;
+use crate::flash_array::FlashBlock;
+
+const MAGIC: u32 = 0x4443_4552; // 'RECD'
+const HEADER_LEN: usize = 16; // Magic + SampleRate + SampleCount + Reserved
+/// Samples moved between flash and RAM per flash operation.
+const CHUNK_LEN: usize = 64;
+
+/// A recording stored in [`FlashBlock`]s, written by
+/// [`record_to_flash`](super::audio_recorder_generated::AudioRecorderGenerated::record_to_flash)
+/// and played back as an [`AudioStream`].
+///
+/// Declare it as a `static`, give it blocks with [`Self::init`], then record into it
+/// and pass it to `play` like any other stream. The recording survives resets;
+/// [`Self::init`] finds it again.
+///
+/// `BLOCK_COUNT` blocks of 4 KB hold about `BLOCK_COUNT * 2048` samples
+/// (about 0.13 seconds per block at 16 kHz).
+///
+/// See the [audio_recorder module documentation](mod@crate::audio_recorder) for
+/// usage examples.
+pub struct FlashClip<const SAMPLE_RATE_HZ: u32, const BLOCK_COUNT: usize> {
+    state: Mutex<CriticalSectionRawMutex, RefCell<FlashClipState<BLOCK_COUNT>>>,
+}
+
+struct FlashClipState<const BLOCK_COUNT: usize> {
+    blocks: Option<[FlashBlock; BLOCK_COUNT]>,
+    /// Samples available for playback.
+    sample_count: usize,
+    /// Samples written by the recording in progress.
+    write_count: usize,
+    /// Next sample for playback.
+    read_index: usize,
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const BLOCK_COUNT: usize> FlashClip<SAMPLE_RATE_HZ, BLOCK_COUNT> {
+    /// Maximum number of samples the clip can hold.
+    pub const CAPACITY: usize = {
+        assert!(BLOCK_COUNT > 0, "block count must be > 0");
+        (BLOCK_COUNT * ERASE_SIZE - HEADER_LEN) / 2
+    };
+
+    /// Creates an empty clip. Call [`Self::init`] before recording or playing it.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            state: Mutex::new(RefCell::new(FlashClipState {
+                blocks: None,
+                sample_count: 0,
+                write_count: 0,
+                read_index: 0,
+            })),
+        }
+    }
+
+    /// Gives the clip its flash blocks and loads any recording already stored there.
+    ///
+    /// A recording made at a different sample rate is ignored.
+    pub fn init(&self, mut blocks: [FlashBlock; BLOCK_COUNT]) -> Result<()> {
+        let mut header = [0_u8; HEADER_LEN];
+        blocks[0].read_raw(0, &mut header)?;
+        let header_u32 = |index: usize| {
+            u32::from_le_bytes([
+                header[4 * index],
+                header[4 * index + 1],
+                header[4 * index + 2],
+                header[4 * index + 3],
+            ])
+        };
+        let sample_count = if header_u32(0) == MAGIC && header_u32(1) == SAMPLE_RATE_HZ {
+            (header_u32(2) as usize).min(Self::CAPACITY)
+        } else {
+            0
+        };
+        self.state.lock(|state| {
+            *state.borrow_mut() = FlashClipState {
+                blocks: Some(blocks),
+                sample_count,
+                write_count: 0,
+                read_index: 0,
+            };
+        });
+        Ok(())
+    }
+
+    /// Number of recorded samples.
+    #[must_use]
+    pub fn sample_count(&self) -> usize {
+        self.state.lock(|state| state.borrow().sample_count)
+    }
+
+    /// Length of the recording.
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        Duration::from_micros(self.sample_count() as u64 * 1_000_000 / SAMPLE_RATE_HZ as u64)
+    }
+
+    /// Erases the recording.
+    pub fn clear(&self) -> Result<()> {
+        self.begin_recording()
+    }
+
+    /// Erases every block so new samples can be written.
+    pub(crate) fn begin_recording(&self) -> Result<()> {
+        // Each erase runs inside the flash's critical section, so interrupts stay masked
+        // for milliseconds per block; record after, not during, time-critical work.
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            state.sample_count = 0;
+            state.write_count = 0;
+            state.read_index = 0;
+            let blocks = state
+                .blocks
+                .as_mut()
+                .expect("FlashClip::init must be called before recording");
+            for block in blocks {
+                block.clear()?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Appends samples to the recording in progress, returning how many fit.
+    pub(crate) fn append(&self, samples: &[i16]) -> Result<usize> {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            let state = &mut *state;
+            let blocks = state
+                .blocks
+                .as_mut()
+                .expect("FlashClip::init must be called before recording");
+            let append_count = samples.len().min(Self::CAPACITY - state.write_count);
+            let mut bytes = [0_u8; 2 * CHUNK_LEN];
+            let mut sample_index = 0;
+            while sample_index < append_count {
+                let (block_index, block_offset, block_room) = sample_location(state.write_count);
+                let chunk_len = (append_count - sample_index).min(CHUNK_LEN).min(block_room);
+                for (chunk_index, sample) in samples[sample_index..sample_index + chunk_len]
+                    .iter()
+                    .enumerate()
+                {
+                    bytes[2 * chunk_index..2 * chunk_index + 2]
+                        .copy_from_slice(&sample.to_le_bytes());
+                }
+                blocks[block_index].write_raw(block_offset, &bytes[..2 * chunk_len])?;
+                state.write_count += chunk_len;
+                sample_index += chunk_len;
+            }
+            Ok(append_count)
+        })
+    }
+
+    /// Writes the header for the recording in progress, making it playable.
+    pub(crate) fn finish_recording(&self) -> Result<usize> {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            let write_count = state.write_count;
+            let blocks = state
+                .blocks
+                .as_mut()
+                .expect("FlashClip::init must be called before recording");
+            let mut header = [0_u8; HEADER_LEN];
+            header[0..4].copy_from_slice(&MAGIC.to_le_bytes());
+            header[4..8].copy_from_slice(&SAMPLE_RATE_HZ.to_le_bytes());
+            header[8..12].copy_from_slice(&(write_count as u32).to_le_bytes());
+            blocks[0].write_raw(0, &header)?;
+            state.sample_count = write_count;
+            state.read_index = 0;
+            Ok(write_count)
+        })
+    }
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const BLOCK_COUNT: usize> Default
+    for FlashClip<SAMPLE_RATE_HZ, BLOCK_COUNT>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SAMPLE_RATE_HZ: u32, const BLOCK_COUNT: usize> AudioStream<SAMPLE_RATE_HZ>
+    for FlashClip<SAMPLE_RATE_HZ, BLOCK_COUNT>
+{
+    /// Plays the recording from the start, then rewinds for the next play.
+    fn fill(&self, samples: &mut [i16]) -> usize {
+        self.state.lock(|state| {
+            let mut state = state.borrow_mut();
+            let state = &mut *state;
+            let Some(blocks) = state.blocks.as_mut() else {
+                return 0;
+            };
+            let fill_count = samples.len().min(state.sample_count - state.read_index);
+            if fill_count == 0 {
+                state.read_index = 0;
+                return 0;
+            }
+            let mut bytes = [0_u8; 2 * CHUNK_LEN];
+            let mut sample_index = 0;
+            while sample_index < fill_count {
+                let (block_index, block_offset, block_room) = sample_location(state.read_index);
+                let chunk_len = (fill_count - sample_index).min(CHUNK_LEN).min(block_room);
+                if blocks[block_index]
+                    .read_raw(block_offset, &mut bytes[..2 * chunk_len])
+                    .is_err()
+                {
+                    // Play silence rather than ending the clip early.
+                    bytes[..2 * chunk_len].fill(0);
+                }
+                for (chunk_index, sample) in samples[sample_index..sample_index + chunk_len]
+                    .iter_mut()
+                    .enumerate()
+                {
+                    *sample =
+                        i16::from_le_bytes([bytes[2 * chunk_index], bytes[2 * chunk_index + 1]]);
+                }
+                state.read_index += chunk_len;
+                sample_index += chunk_len;
+            }
+            fill_count
+        })
+    }
+}
+
+/// Returns the block, byte offset within it, and samples left in it for sample `sample_index`.
+const fn sample_location(sample_index: usize) -> (usize, u32, usize) {
+    let byte_offset = HEADER_LEN + 2 * sample_index;
+    let block_offset = byte_offset % ERASE_SIZE;
+    (
+        byte_offset / ERASE_SIZE,
+        block_offset as u32,
+        (ERASE_SIZE - block_offset) / 2,
+    )
+}
@@ -0,0 +1,95 @@
+#![allow(missing_docs)]
+
+use super::{AudioLevel, AudioRecorder, AudioRecorderStatic, LEVEL_BLOCK_LEN, sample_from_slot};
+use crate::audio_player::{VOICE_16000_HZ, Volume};
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+
+type Recorder8 = AudioRecorder<VOICE_16000_HZ, 8>;
+
+#[test]
+fn audio_level_measures_peak_and_rms() {
+    assert_eq!(AudioLevel::from_samples(&[]), AudioLevel::SILENCE);
+    assert_eq!(AudioLevel::from_samples(&[0; 16]), AudioLevel::SILENCE);
+
+    // A full-scale square wave has equal peak and RMS.
+    let square_wave = [16_000_i16, -16_000, 16_000, -16_000];
+    let square_level = AudioLevel::from_samples(&square_wave);
+    assert_eq!(square_level.peak().to_i16(), 16_000);
+    assert_eq!(square_level.rms().to_i16(), 16_000);
+
+    // One loud sample among quiet ones raises the peak far more than the RMS.
+    let click = [0_i16, 0, 0, -20_000];
+    let click_level = AudioLevel::from_samples(&click);
+    assert_eq!(click_level.peak().to_i16(), 20_000);
+    assert_eq!(click_level.rms().to_i16(), 10_000);
+
+    // i16::MIN saturates to full scale instead of overflowing.
+    assert_eq!(AudioLevel::from_samples(&[i16::MIN]).peak(), Volume::MAX);
+    assert_eq!(
+        AudioLevel::from_u32(click_level.to_u32()),
+        click_level,
+        "level must round-trip through its atomic encoding"
+    );
+}
+
+#[test]
+fn recorder_reads_oldest_samples_and_counts_overruns() {
+    static RECORDER_STATIC: AudioRecorderStatic<VOICE_16000_HZ, 8> = Recorder8::new_static();
+    let recorder = Recorder8::new(&RECORDER_STATIC);
+    let mut samples = [0_i16; 4];
+    assert_eq!(recorder.try_read(&mut samples), 0);
+    assert_eq!(recorder.level(), AudioLevel::SILENCE);
+
+    RECORDER_STATIC.push_block(&[1, 2, 3]);
+    assert_eq!(recorder.try_read(&mut samples), 3);
+    assert_eq!(samples[..3], [1, 2, 3]);
+    assert_eq!(recorder.level().peak().to_i16(), 3);
+
+    // Ten samples into a buffer of eight drops the two oldest.
+    RECORDER_STATIC.push_block(&[10, 11, 12, 13, 14, 15, 16, 17, 18, 19]);
+    assert_eq!(recorder.overrun_count(), 2);
+    assert_eq!(recorder.try_read(&mut samples), 4);
+    assert_eq!(samples, [12, 13, 14, 15]);
+
+    recorder.clear();
+    assert_eq!(recorder.try_read(&mut samples), 0);
+}
+
+#[test]
+fn read_and_wait_for_level_wake_on_new_blocks() {
+    static RECORDER_STATIC: AudioRecorderStatic<VOICE_16000_HZ, 8> = Recorder8::new_static();
+    let recorder = Recorder8::new(&RECORDER_STATIC);
+    let mut context = Context::from_waker(Waker::noop());
+
+    let mut samples = [0_i16; 4];
+    {
+        let mut read_future = pin!(recorder.read(&mut samples));
+        assert!(read_future.as_mut().poll(&mut context).is_pending());
+        RECORDER_STATIC.push_block(&[7, 8]);
+        assert_eq!(read_future.as_mut().poll(&mut context), Poll::Ready(2));
+    }
+    assert_eq!(samples[..2], [7, 8]);
+
+    let mut wait_future = pin!(recorder.wait_for_level(Volume::percent(50)));
+    assert!(wait_future.as_mut().poll(&mut context).is_pending());
+    RECORDER_STATIC.push_block(&[100; LEVEL_BLOCK_LEN]);
+    assert!(
+        wait_future.as_mut().poll(&mut context).is_pending(),
+        "quiet blocks must not satisfy the threshold"
+    );
+    RECORDER_STATIC.push_block(&[30_000, -30_000]);
+    let Poll::Ready(audio_level) = wait_future.as_mut().poll(&mut context) else {
+        panic!("loud block must satisfy the threshold");
+    };
+    assert_eq!(audio_level.peak().to_i16(), 30_000);
+}
+
+#[test]
+fn sample_from_slot_keeps_top_16_bits_of_left_justified_slot() {
+    // INMP441 sends 24-bit samples, left-justified in 32-bit slots.
+    assert_eq!(sample_from_slot(0x1234_5600), 0x1234);
+    assert_eq!(sample_from_slot(0xFFFF_FF00), -1);
+    assert_eq!(sample_from_slot(0x8000_0000), i16::MIN);
+    assert_eq!(sample_from_slot(0x7FFF_FF00), i16::MAX);
+}
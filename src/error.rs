@@ -53,8 +53,20 @@ pub enum Error {
     #[display("Storage is invalid or corrupted")]
     StorageCorrupted,
 
+    #[display("LED layout is invalid: {_0}")]
+    InvalidLedLayout(#[error(not(source))] &'static str),
+
     #[display("animation disabled (max_frames = {_0})")]
     AnimationDisabled(#[error(not(source))] usize),
+
+    #[display("Servo bus: no reply from servo {_0}")]
+    ServoBusTimeout(#[error(not(source))] u8),
+
+    #[display("Servo bus: malformed reply: {_0}")]
+    ServoBusPacket(#[error(not(source))] &'static str),
+
+    #[display("Servo bus: servo rejected the instruction (error {_0})")]
+    ServoBusRejected(#[error(not(source))] u8),
 }
 
 impl From<()> for Error {
@@ -115,6 +115,28 @@ impl FlashBlock {
     pub fn clear(&mut self) -> Result<()> {
         clear_block(self.manager, self.block)
     }
+
+    /// Writes raw `bytes` at `offset` within this block, bypassing the postcard format.
+    ///
+    /// The bytes being written must have been erased with [`Self::clear`].
+    pub(crate) fn write_raw(&mut self, offset: u32, bytes: &[u8]) -> Result<()> {
+        let block_offset = raw_offset(self.block, offset, bytes.len())?;
+        self.manager.with_flash(|flash| {
+            flash
+                .blocking_write(block_offset, bytes)
+                .map_err(Error::Flash)
+        })
+    }
+
+    /// Reads raw bytes at `offset` within this block, bypassing the postcard format.
+    pub(crate) fn read_raw(&mut self, offset: u32, bytes: &mut [u8]) -> Result<()> {
+        let block_offset = raw_offset(self.block, offset, bytes.len())?;
+        self.manager.with_flash(|flash| {
+            flash
+                .blocking_read(block_offset, bytes)
+                .map_err(Error::Flash)
+        })
+    }
 }
 
 /// Static resources for [`FlashArray`].
@@ -357,6 +379,15 @@ fn block_offset(block_id: u32) -> u32 {
     capacity - (block_id + 1) * ERASE_SIZE as u32
 }
 
+/// Returns the flash offset of `len` raw bytes at `offset` within a block, or an error if
+/// they don't fit in the block.
+fn raw_offset(block_id: u32, offset: u32, len: usize) -> Result<u32> {
+    if offset as usize + len > ERASE_SIZE {
+        return Err(Error::IndexOutOfBounds);
+    }
+    Ok(block_offset(block_id) + offset)
+}
+
 /// Compute FNV-1a hash of the type name for type safety.
 fn compute_type_hash<T>() -> u32 {
     const FNV_PRIME: u32 = 16_777_619;
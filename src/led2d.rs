@@ -13,8 +13,10 @@
 //!
 //! - [`led2d!`](macro@crate::led2d) — Macro to generate an LED-panel struct type (includes syntax details). See [`Led2dGenerated`](`crate::led2d::led2d_generated::Led2dGenerated`) for a sample of a generated type.
 //! - [`Led2dGenerated`](`crate::led2d::led2d_generated::Led2dGenerated`) — Sample struct type generated by the [`led2d!`](macro@crate::led2d) macro, showing all methods and constants.
-//! - [`LedLayout`] — Compile-type description of panel geometry and wiring, including dimensions (with examples)
+//! - [`LedLayout`] — Compile-type description of panel geometry and wiring, including dimensions, sparse layouts, and rings (with examples)
 //! - [`Frame2d`] — 2D pixel array used for general graphics (includes examples)
+//! - [`CompressedAnimation`] — Flash-resident animation frames decoded one at a time, for animations longer than `max_frames` (includes examples)
+//! - [`Sprite`](sprite::Sprite) and [`TileMap`](sprite::TileMap) — Game-oriented layers with z-order, transparency, scrolling, and collision queries (includes examples)
 //! - [`led_strips!`](crate::led_strips) — Alternative macro to share a PIO resource with other panels or LED strips (includes examples)
 //!
 //! # Example: Write Text
@@ -151,10 +153,15 @@ pub use embedded_graphics::geometry::Point;
 /// # [`embedded-graphics::Size`](https://docs.rs/embedded-graphics/latest/embedded_graphics/geometry/struct.Size.html) Documentation:
 pub use embedded_graphics::geometry::Size;
 
+pub mod compressed;
 pub mod layout;
+#[cfg(not(feature = "host"))]
+mod layout_calibration;
 
 pub mod led2d_generated;
+pub mod sprite;
 
+pub use compressed::CompressedAnimation;
 pub use layout::LedLayout;
 
 use core::{
@@ -162,8 +169,6 @@ use core::{
     convert::Infallible,
     ops::{Deref, DerefMut, Index, IndexMut},
 };
-#[cfg(feature = "host")]
-use embassy_time::Duration;
 use embedded_graphics::pixelcolor::Rgb888;
 use embedded_graphics::{
     draw_target::DrawTarget,
@@ -181,28 +186,11 @@ use embedded_graphics::{
 };
 use smart_leds::RGB8;
 
-#[cfg(not(feature = "host"))]
-use crate::led_strip::{Frame1d as StripFrame, LedStrip};
-#[cfg(feature = "host")]
-type StripFrame<const N: usize> = [RGB8; N];
-#[cfg(feature = "host")]
-/// Stub LED strip type for host testing.
-///
-/// This type provides no-op implementations for testing 2D LED panel code on host machines.
-/// See the [`led2d`](self) module documentation for usage.
-pub struct LedStrip<const N: usize, const MAX_FRAMES: usize>;
-#[cfg(feature = "host")]
-impl<const N: usize, const MAX_FRAMES: usize> LedStrip<N, MAX_FRAMES> {
-    fn write_frame(&self, _frame: StripFrame<N>) -> Result<()> {
-        Ok(())
-    }
-
-    fn animate(&self, _frames: impl IntoIterator<Item = (StripFrame<N>, Duration)>) -> Result<()> {
-        Ok(())
-    }
-}
+// On the host, `LedStrip` is the recording strip from `preview`.
 use crate::Result;
-use crate::led_strip::ToRgb888;
+use crate::led_strip::{Frame1d as StripFrame, LedStrip};
+use crate::led_strip::{ToRgb888, blend_rgb, saturating_add_rgb, scale_rgb};
+use crate::led2d::compressed::CompressedPlayback;
 
 // Packed bitmap for the internal 3x4 font (ASCII 0x20-0x7E).
 const BIT_MATRIX3X4_FONT_DATA: [u8; 144] = [
@@ -505,6 +493,32 @@ impl Led2dFont {
 ///
 /// Tuple indexing matches display coordinates. Array indexing matches the underlying storage.
 ///
+/// ## Frame operations
+///
+/// `const` methods transform pixel content (for physical wiring, see [`LedLayout`] instead):
+///
+/// - Move: [`shift`](Self::shift) (fill vacated pixels) and [`scroll`](Self::scroll) (wrap around)
+/// - Mirror and rotate: [`flip_h`](Self::flip_h), [`flip_v`](Self::flip_v),
+///   [`rotate_cw`](Self::rotate_cw), [`rotate_ccw`](Self::rotate_ccw), [`rotate_180`](Self::rotate_180)
+/// - Copy: [`blit`](Self::blit) a sub-rectangle from another frame
+/// - Mix: [`blend`](Self::blend) (alpha), [`saturating_add`](Self::saturating_add) (additive),
+///   and [`fade`](Self::fade) (toward black)
+///
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// # #[panic_handler]
+/// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+/// use device_envoy::led2d::Frame2d;
+/// use smart_leds::colors;
+///
+/// const RED: Frame2d<4, 2> = Frame2d::filled(colors::RED);
+/// const BLUE: Frame2d<4, 2> = Frame2d::filled(colors::BLUE);
+/// // Half-way cross-fade, then slide one pixel right (wrapping).
+/// const MIXED: Frame2d<4, 2> = RED.blend(BLUE, 128).scroll(1, 0);
+/// const DIM: Frame2d<4, 2> = MIXED.fade(64);
+/// ```
+///
 /// ## Rendering pipeline (what happens when you display a frame)
 ///
 /// `Frame2d` is only pixel storage. When you render a frame through a generated panel type,
@@ -611,6 +625,204 @@ impl<const W: usize, const H: usize> Frame2d<W, H> {
     pub const fn filled(color: RGB8) -> Self {
         Self([[color; W]; H])
     }
+
+    /// Return this frame with content moved by `(dx, dy)` (positive is right and down).
+    /// Vacated pixels are set to `fill`.
+    #[must_use]
+    pub const fn shift(self, dx: i32, dy: i32, fill: RGB8) -> Self {
+        let mut pixels = [[fill; W]; H];
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let source_y = y_index as i64 - dy as i64;
+            if source_y >= 0 && source_y < H as i64 {
+                let mut x_index = 0;
+                while x_index < W {
+                    let source_x = x_index as i64 - dx as i64;
+                    if source_x >= 0 && source_x < W as i64 {
+                        pixels[y_index][x_index] = self.0[source_y as usize][source_x as usize];
+                    }
+                    x_index += 1;
+                }
+            }
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this frame with content moved by `(dx, dy)` (positive is right and down),
+    /// wrapping around at the edges.
+    #[must_use]
+    pub const fn scroll(self, dx: i32, dy: i32) -> Self {
+        let mut pixels = self.0;
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let source_y = (y_index as i64 - dy as i64).rem_euclid(H as i64) as usize;
+            let mut x_index = 0;
+            while x_index < W {
+                let source_x = (x_index as i64 - dx as i64).rem_euclid(W as i64) as usize;
+                pixels[y_index][x_index] = self.0[source_y][source_x];
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this frame mirrored left-to-right.
+    #[must_use]
+    pub const fn flip_h(self) -> Self {
+        let mut pixels = self.0;
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let mut x_index = 0;
+            while x_index < W {
+                pixels[y_index][x_index] = self.0[y_index][W - 1 - x_index];
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this frame mirrored top-to-bottom.
+    #[must_use]
+    pub const fn flip_v(self) -> Self {
+        let mut pixels = self.0;
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while y_index < H {
+            pixels[y_index] = self.0[H - 1 - y_index];
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this frame rotated 90° clockwise (dims swap).
+    #[must_use]
+    pub const fn rotate_cw(self) -> Frame2d<H, W> {
+        let mut pixels = [[RGB8::new(0, 0, 0); H]; W];
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let mut x_index = 0;
+            while x_index < W {
+                pixels[x_index][H - 1 - y_index] = self.0[y_index][x_index];
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Frame2d(pixels)
+    }
+
+    /// Return this frame rotated 90° counter-clockwise (dims swap).
+    #[must_use]
+    pub const fn rotate_ccw(self) -> Frame2d<H, W> {
+        let mut pixels = [[RGB8::new(0, 0, 0); H]; W];
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let mut x_index = 0;
+            while x_index < W {
+                pixels[W - 1 - x_index][y_index] = self.0[y_index][x_index];
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Frame2d(pixels)
+    }
+
+    /// Return this frame rotated 180°.
+    #[must_use]
+    pub const fn rotate_180(self) -> Self {
+        self.flip_h().flip_v()
+    }
+
+    /// Copy the `size` rectangle at `source_top_left` in `source` into this frame at
+    /// `dest_top_left`. Pixels that fall outside either frame are skipped.
+    pub const fn blit<const SW: usize, const SH: usize>(
+        &mut self,
+        source: &Frame2d<SW, SH>,
+        source_top_left: Point,
+        size: Size,
+        dest_top_left: Point,
+    ) {
+        let mut y_offset = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_offset < size.height as i64 {
+            let source_y = source_top_left.y as i64 + y_offset;
+            let dest_y = dest_top_left.y as i64 + y_offset;
+            if source_y >= 0 && source_y < SH as i64 && dest_y >= 0 && dest_y < H as i64 {
+                let mut x_offset = 0;
+                while x_offset < size.width as i64 {
+                    let source_x = source_top_left.x as i64 + x_offset;
+                    let dest_x = dest_top_left.x as i64 + x_offset;
+                    if source_x >= 0 && source_x < SW as i64 && dest_x >= 0 && dest_x < W as i64 {
+                        self.0[dest_y as usize][dest_x as usize] =
+                            source.0[source_y as usize][source_x as usize];
+                    }
+                    x_offset += 1;
+                }
+            }
+            y_offset += 1;
+        }
+    }
+
+    /// Return a blend of this frame and `other`: `alpha = 0` keeps this frame,
+    /// `alpha = 255` gives `other`.
+    #[must_use]
+    pub const fn blend(self, other: Self, alpha: u8) -> Self {
+        let mut pixels = self.0;
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let mut x_index = 0;
+            while x_index < W {
+                pixels[y_index][x_index] =
+                    blend_rgb(self.0[y_index][x_index], other.0[y_index][x_index], alpha);
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return the per-channel sum of this frame and `other`, saturating at 255.
+    #[must_use]
+    pub const fn saturating_add(self, other: Self) -> Self {
+        let mut pixels = self.0;
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let mut x_index = 0;
+            while x_index < W {
+                pixels[y_index][x_index] =
+                    saturating_add_rgb(self.0[y_index][x_index], other.0[y_index][x_index]);
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this frame faded toward black: `level = 255` is unchanged, `0` is black.
+    #[must_use]
+    pub const fn fade(self, level: u8) -> Self {
+        let mut pixels = self.0;
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let mut x_index = 0;
+            while x_index < W {
+                pixels[y_index][x_index] = scale_rgb(self.0[y_index][x_index], level);
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Self(pixels)
+    }
 }
 
 impl<const W: usize, const H: usize> Deref for Frame2d<W, H> {
@@ -694,9 +906,9 @@ impl<const W: usize, const H: usize> DrawTarget for Frame2d<W, H> {
 #[doc(hidden)]
 /// A device abstraction for rectangular NeoPixel-style (WS2812) LED matrix displays.
 ///
-/// Supports any size display with arbitrary LED-index-to-coordinate mapping. The provided mapping
-/// is reversed during initialization into an internal (row, col) → LED index lookup so frame
-/// conversion stays fast.
+/// Supports any size display with arbitrary LED-index-to-coordinate mapping, including sparse
+/// layouts where some (col, row) cells have no LED. Frame conversion walks the LEDs in strip
+/// order and reads each one's (col, row) pixel.
 ///
 /// Rows and columns are metadata used only for indexing - the core type is generic only over
 /// N (total LEDs) and MAX_FRAMES (animation capacity).
@@ -705,7 +917,7 @@ impl<const W: usize, const H: usize> DrawTarget for Frame2d<W, H> {
 /// a higher-level wrapper. See the [led2d](mod@crate::led2d) module docs for examples.
 pub struct Led2d<const N: usize, const MAX_FRAMES: usize> {
     led_strip: &'static LedStrip<N, MAX_FRAMES>,
-    mapping_by_xy: [u16; N],
+    xy_by_led: [(u16, u16); N],
     width: usize,
 }
 
@@ -713,8 +925,7 @@ impl<const N: usize, const MAX_FRAMES: usize> Led2d<N, MAX_FRAMES> {
     /// Create Led2d device handle.
     ///
     /// The `led_layout` defines how LED indices map to `(column, row)` coordinates. Entry `i`
-    /// provides the `(col, row)` destination for LED `i`. The layout is inverted via
-    /// [`LedLayout::xy_to_index`] so (row, col) queries are O(1) when converting frames.
+    /// provides the `(col, row)` destination for LED `i`. Layouts may be sparse (`N < W * H`).
     ///
     /// See the [Led2d struct example](Self) for usage.
     #[must_use]
@@ -722,35 +933,45 @@ impl<const N: usize, const MAX_FRAMES: usize> Led2d<N, MAX_FRAMES> {
         led_strip: &'static LedStrip<N, MAX_FRAMES>,
         led_layout: &LedLayout<N, W, H>,
     ) -> Self {
-        assert_eq!(
-            W.checked_mul(H).expect("width * height must fit in usize"),
-            N,
-            "width * height must equal N (total LEDs for led_layout reversal)"
+        assert!(
+            N <= W.checked_mul(H).expect("width * height must fit in usize"),
+            "N (total LEDs) must not exceed width * height"
         );
         Self {
             led_strip,
-            mapping_by_xy: led_layout.xy_to_index(),
+            xy_by_led: *led_layout.index_to_xy(),
             width: W,
         }
     }
 
-    /// Convert (column, row) coordinates to LED strip index using the stored LED layout.
+    /// Return the LED layout currently used to convert frames.
     #[must_use]
-    fn xy_to_index(&self, x_index: usize, y_index: usize) -> usize {
-        self.mapping_by_xy[y_index * self.width + x_index] as usize
+    pub fn led_layout<const W: usize, const H: usize>(&self) -> LedLayout<N, W, H> {
+        assert_eq!(W, self.width, "layout width must match the panel width");
+        LedLayout::from_valid_map(self.xy_by_led)
+    }
+
+    /// Replace the LED layout used to convert frames, for example with one loaded from flash.
+    ///
+    /// Takes effect with the next `write_frame` or `animate` call.
+    pub fn set_led_layout<const W: usize, const H: usize>(
+        &mut self,
+        led_layout: &LedLayout<N, W, H>,
+    ) {
+        assert_eq!(W, self.width, "layout width must match the panel width");
+        self.xy_by_led = *led_layout.index_to_xy();
     }
 
     /// Convert 2D frame to 1D array using the LED layout.
+    ///
+    /// Pixels on cells without an LED (sparse layouts) are ignored.
     fn convert_frame<const W: usize, const H: usize>(
         &self,
         frame_2d: Frame2d<W, H>,
     ) -> StripFrame<N> {
         let mut frame_1d = [RGB8::new(0, 0, 0); N];
-        for y_index in 0..H {
-            for x_index in 0..W {
-                let led_index = self.xy_to_index(x_index, y_index);
-                frame_1d[led_index] = frame_2d[(x_index, y_index)];
-            }
+        for (led_color, &(x_index, y_index)) in frame_1d.iter_mut().zip(self.xy_by_led.iter()) {
+            *led_color = frame_2d[(x_index as usize, y_index as usize)];
         }
         StripFrame::from(frame_1d)
     }
@@ -763,6 +984,17 @@ impl<const N: usize, const MAX_FRAMES: usize> Led2d<N, MAX_FRAMES> {
         self.led_strip.write_frame(strip_frame)
     }
 
+    /// Record `text` as displayed, already rendered into `frame`.
+    #[cfg(feature = "host")]
+    pub(crate) fn write_text_frame<const W: usize, const H: usize>(
+        &self,
+        text: &str,
+        frame: Frame2d<W, H>,
+    ) -> Result<()> {
+        let strip_frame = self.convert_frame(frame);
+        self.led_strip.write_text(text, strip_frame)
+    }
+
     /// Loop through a sequence of animation frames until interrupted by another command.
     ///
     /// Each frame is a tuple of `(Frame2d, Duration)`. Accepts arrays, `Vec`s, or any
@@ -782,6 +1014,30 @@ impl<const N: usize, const MAX_FRAMES: usize> Led2d<N, MAX_FRAMES> {
             (self.convert_frame(frame), duration)
         }))
     }
+
+    /// Loop through a [`CompressedAnimation`] until interrupted by another command.
+    ///
+    /// Frames are decoded one at a time from flash by the device loop, so this works for
+    /// animations far longer than `MAX_FRAMES` (including when `MAX_FRAMES` is 0).
+    ///
+    /// Returns immediately; the animation runs in the background until interrupted
+    /// by a new `animate` call or `write_frame`.
+    pub fn animate_compressed<const W: usize, const H: usize>(
+        &self,
+        animation: &'static CompressedAnimation<W, H>,
+    ) -> Result<()> {
+        assert_eq!(W, self.width, "animation width must match the panel width");
+        assert!(
+            N <= W.checked_mul(H).expect("width * height must fit in usize"),
+            "animation width * height must cover N (total LEDs)"
+        );
+        self.led_strip.animate_compressed(CompressedPlayback::new(
+            animation.data(),
+            W * H,
+            &self.xy_by_led,
+            W,
+        ))
+    }
 }
 
 /// Macro to generate an LED-panel struct type (includes syntax details). See [`Led2dGenerated`](`crate::led2d::led2d_generated::Led2dGenerated`) for a sample of a generated type.
@@ -810,7 +1066,8 @@ impl<const N: usize, const MAX_FRAMES: usize> Led2d<N, MAX_FRAMES> {
 /// **Required fields:**
 ///
 /// - `pin` — GPIO pin for LED data
-/// - `led_layout` — LED strip physical layout (see [`LedLayout`]); this defines the panel size
+/// - `led_layout` — LED strip physical layout (see [`LedLayout`]); this defines the panel size.
+///   Sparse layouts (rings, matrices with gaps) are supported.
 /// - `font` — Built-in font variant (see [`Led2dFont`]), e.g. `Led2dFont::Font4x6Trim`.
 ///   Bring `Led2dFont` into scope or use a full path like `device_envoy::led2d::Led2dFont::Font4x6Trim`.
 ///
@@ -1399,7 +1656,7 @@ macro_rules! led2d_from_strip {
                 pub const WIDTH: usize = $led_layout_const.width();
                 /// Number of rows in the panel.
                 pub const HEIGHT: usize = $led_layout_const.height();
-                /// Total number of LEDs (WIDTH * HEIGHT).
+                /// Total number of LEDs (WIDTH * HEIGHT, or fewer for a sparse layout).
                 pub const N: usize = $led_layout_const.len();
                 /// Frame dimensions as a [`Size`] for embedded-graphics.
                 pub const SIZE: $crate::led2d::Size = $crate::led2d::Frame2d::<{ $led_layout_const.width() }, { $led_layout_const.height() }>::SIZE;
@@ -1453,6 +1710,46 @@ macro_rules! led2d_from_strip {
                     self.led2d.animate(frames)
                 }
 
+                /// Loop through a compressed animation, decoding one frame at a time from flash.
+                $vis fn animate_compressed(
+                    &self,
+                    animation: &'static $crate::led2d::CompressedAnimation<{ $led_layout_const.width() }, { $led_layout_const.height() }>,
+                ) -> $crate::Result<()> {
+                    self.led2d.animate_compressed(animation)
+                }
+
+                /// Return the LED layout currently in use.
+                $vis fn led_layout(&self) -> $crate::led2d::LedLayout<{ $led_layout_const.len() }, { $led_layout_const.width() }, { $led_layout_const.height() }> {
+                    self.led2d.led_layout()
+                }
+
+                /// Replace the LED layout in use (for example, with one built at runtime).
+                $vis fn set_led_layout(
+                    &mut self,
+                    led_layout: &$crate::led2d::LedLayout<{ $led_layout_const.len() }, { $led_layout_const.width() }, { $led_layout_const.height() }>,
+                ) {
+                    self.led2d.set_led_layout(led_layout);
+                }
+
+                /// Use the LED layout saved in flash, falling back to the macro's const layout.
+                ///
+                /// Returns `Ok(true)` if a saved layout was loaded.
+                $vis fn load_led_layout(
+                    &mut self,
+                    flash_block: &mut $crate::flash_array::FlashBlock,
+                ) -> $crate::Result<bool> {
+                    self.led2d.load_led_layout::<{ $led_layout_const.width() }, { $led_layout_const.height() }>(flash_block)
+                }
+
+                /// Interactively choose the panel orientation with a button and save it to flash.
+                $vis async fn calibrate_led_layout(
+                    &mut self,
+                    button: &mut $crate::button::Button<'_>,
+                    flash_block: &mut $crate::flash_array::FlashBlock,
+                ) -> $crate::Result<$crate::led2d::LedLayout<{ $led_layout_const.len() }, { $led_layout_const.width() }, { $led_layout_const.height() }>> {
+                    self.led2d.calibrate_led_layout(button, flash_block).await
+                }
+
                 /// Render text into a frame using the configured font and spacing.
                 pub fn write_text_to_frame(
                     &self,
@@ -0,0 +1,654 @@
+//! Module containing [`CompressedAnimation`], flash-resident animation frames for
+//! [`led2d`](mod@crate::led2d) panels that are decoded one frame at a time.
+//!
+//! See [`CompressedAnimation`] for details and examples.
+
+use embassy_time::Duration;
+use smart_leds::RGB8;
+
+#[cfg(feature = "host")]
+use crate::led_strip::Frame1d;
+use crate::led2d::Frame2d;
+
+#[cfg(all(test, feature = "host"))]
+mod host_tests;
+
+/// Maximum number of palette entries. Colors beyond this are stored as literals.
+const PALETTE_CAPACITY: usize = 254;
+/// Run symbol for colors stored inline (3 bytes per pixel follow) instead of in the palette.
+const LITERAL_INDEX: u8 = 0xFE;
+/// Run symbol for pixels unchanged from the previous frame (delta frames only).
+const KEEP_INDEX: u8 = 0xFF;
+/// Longest run that fits in the one-byte run length.
+const MAX_RUN_LEN: usize = 255;
+/// Size of the open-addressing table used to look up palette entries at compile time.
+const PALETTE_SLOT_COUNT: usize = 1024;
+const PALETTE_SLOT_BITS: u32 = 10;
+/// Bytes used to store each frame's duration (microseconds, little-endian `u32`).
+const DURATION_BYTES_LEN: usize = 4;
+
+/// Unsized view of a static compressed animation for a `W`×`H` panel (with example).
+///
+/// For fixed-size, const-friendly storage, see [`CompressedAnimationBuf`]. Most users should
+/// create one with [`compressed_animation!`](macro@crate::compressed_animation) and play it with
+/// the generated panel's `animate_compressed` method.
+///
+/// Raw frames passed to `animate` are copied into RAM, up to `MAX_FRAMES` of them. A compressed
+/// animation instead stays in flash and the device loop decodes one frame at a time, so a
+/// multi-minute animation costs about two frames of RAM. This mirrors how
+/// [`AdpcmClip`](crate::audio_player::AdpcmClip) data is streamed by the audio player.
+///
+/// **Encoding:**
+/// - Colors are stored in a palette of up to 254 entries (first seen, first stored);
+///   additional colors are stored inline.
+/// - Each frame is run-length encoded in row-major order.
+/// - After the first frame, each frame is stored either in full or as a delta against the
+///   previous frame (unchanged pixels are skipped), whichever is smaller.
+///
+/// Animations with few colors and mostly static backgrounds compress best.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// # #[panic_handler]
+/// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+/// use device_envoy::led2d::compressed::CompressedAnimation;
+/// use device_envoy::compressed_animation;
+/// use embassy_time::Duration;
+/// use smart_leds::colors;
+///
+/// const FRAME_DURATION: Duration = Duration::from_millis(100);
+/// const FRAMES: [([[smart_leds::RGB8; 4]; 2], Duration); 2] = [
+///     ([[colors::RED; 4], [colors::BLACK; 4]], FRAME_DURATION),
+///     ([[colors::BLACK; 4], [colors::RED; 4]], FRAME_DURATION),
+/// ];
+///
+/// compressed_animation! {
+///     static BLINK_ANIMATION = FRAMES;
+/// }
+///
+/// // Unsized views are what panels accept.
+/// let animation: &'static CompressedAnimation<4, 2> = &BLINK_ANIMATION;
+/// assert_eq!(animation.frame_count(), 2);
+/// for (frame, duration) in animation.frames() {
+///     let _ = (frame, duration);
+/// }
+/// ```
+pub struct CompressedAnimation<const W: usize, const H: usize, T: ?Sized = [u8]> {
+    frame_count: usize,
+    data: T,
+}
+
+/// Sized, const-friendly storage for a compressed animation.
+pub type CompressedAnimationBuf<const W: usize, const H: usize, const DATA_LEN: usize> =
+    CompressedAnimation<W, H, [u8; DATA_LEN]>;
+
+impl<const W: usize, const H: usize, T: ?Sized> CompressedAnimation<W, H, T> {
+    /// Number of frames in the animation.
+    #[must_use]
+    pub const fn frame_count(&self) -> usize {
+        self.frame_count
+    }
+}
+
+impl<const W: usize, const H: usize, T: AsRef<[u8]> + ?Sized> CompressedAnimation<W, H, T> {
+    /// Number of bytes of compressed data (palette plus frames).
+    #[must_use]
+    pub fn data_len(&self) -> usize {
+        self.data.as_ref().len()
+    }
+
+    /// Iterate once through the decoded frames and their durations.
+    ///
+    /// The iterator holds a single working frame, so decoding uses constant RAM.
+    #[must_use]
+    pub fn frames(&self) -> CompressedFrames<'_, W, H> {
+        CompressedFrames {
+            reader: FrameReader::new(self.data.as_ref(), W * H),
+            frame: Frame2d::new(),
+            remaining: self.frame_count,
+        }
+    }
+
+    pub(crate) fn data(&self) -> &[u8] {
+        self.data.as_ref()
+    }
+}
+
+/// Iterator over the decoded frames of a [`CompressedAnimation`].
+///
+/// Created by [`CompressedAnimation::frames`].
+pub struct CompressedFrames<'a, const W: usize, const H: usize> {
+    reader: FrameReader<'a>,
+    frame: Frame2d<W, H>,
+    remaining: usize,
+}
+
+impl<const W: usize, const H: usize> Iterator for CompressedFrames<'_, W, H> {
+    type Item = (Frame2d<W, H>, Duration);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+        let frame = &mut self.frame;
+        let duration = self.reader.next_frame(|pixel_index, color| {
+            frame.0[pixel_index / W][pixel_index % W] = color;
+        });
+        Some((self.frame, duration))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<const W: usize, const H: usize> ExactSizeIterator for CompressedFrames<'_, W, H> {}
+
+/// Streaming decoder over compressed frame data.
+///
+/// Reports only the pixels each frame sets, so callers keep the previous frame's contents
+/// for delta frames. Wraps to the first frame after the last one.
+#[derive(Clone)]
+struct FrameReader<'a> {
+    data: &'a [u8],
+    pixel_count: usize,
+    frames_offset: usize,
+    offset: usize,
+}
+
+impl<'a> FrameReader<'a> {
+    fn new(data: &'a [u8], pixel_count: usize) -> Self {
+        let palette_len = data[0] as usize;
+        let frames_offset = 1 + palette_len * 3;
+        assert!(
+            frames_offset < data.len(),
+            "compressed animation data must contain at least one frame"
+        );
+        Self {
+            data,
+            pixel_count,
+            frames_offset,
+            offset: frames_offset,
+        }
+    }
+
+    /// Whether the last frame has been read, so the next read wraps to the first.
+    #[cfg(feature = "host")]
+    const fn is_at_end(&self) -> bool {
+        self.offset >= self.data.len()
+    }
+
+    fn next_frame(&mut self, mut set_pixel: impl FnMut(usize, RGB8)) -> Duration {
+        if self.offset >= self.data.len() {
+            self.offset = self.frames_offset;
+        }
+        let data = self.data;
+        let mut offset = self.offset;
+        let duration_micros = u32::from_le_bytes([
+            data[offset],
+            data[offset + 1],
+            data[offset + 2],
+            data[offset + 3],
+        ]);
+        offset += DURATION_BYTES_LEN;
+
+        let mut pixel_index = 0;
+        while pixel_index < self.pixel_count {
+            let run_len = data[offset] as usize;
+            let symbol = data[offset + 1];
+            offset += 2;
+            let color = match symbol {
+                KEEP_INDEX => None,
+                LITERAL_INDEX => {
+                    (pixel_index..pixel_index + run_len).for_each(|run_pixel_index| {
+                        let color = RGB8::new(data[offset], data[offset + 1], data[offset + 2]);
+                        offset += 3;
+                        set_pixel(run_pixel_index, color);
+                    });
+                    None
+                }
+                palette_index => {
+                    let color_offset = 1 + palette_index as usize * 3;
+                    Some(RGB8::new(
+                        data[color_offset],
+                        data[color_offset + 1],
+                        data[color_offset + 2],
+                    ))
+                }
+            };
+            if let Some(color) = color {
+                (pixel_index..pixel_index + run_len)
+                    .for_each(|run_pixel_index| set_pixel(run_pixel_index, color));
+            }
+            pixel_index += run_len;
+        }
+        assert!(
+            pixel_index == self.pixel_count,
+            "compressed frame runs must cover the panel exactly"
+        );
+
+        self.offset = offset;
+        Duration::from_micros(u64::from(duration_micros))
+    }
+}
+
+// Must be `pub` because it appears in the `led_strip` command enum; not user-facing.
+#[doc(hidden)]
+/// Looping playback state for a compressed animation on an `N`-LED strip.
+#[derive(Clone)]
+pub struct CompressedPlayback<const N: usize> {
+    reader: FrameReader<'static>,
+    /// `(pixel_index, led_index)` for every LED, sorted by pixel index.
+    leds_by_pixel: [(u16, u16); N],
+}
+
+impl<const N: usize> CompressedPlayback<N> {
+    pub(crate) fn new(
+        data: &'static [u8],
+        pixel_count: usize,
+        xy_by_led: &[(u16, u16); N],
+        width: usize,
+    ) -> Self {
+        assert!(
+            pixel_count <= usize::from(u16::MAX) + 1,
+            "compressed animation pixel count must fit in u16 indices"
+        );
+        let mut leds_by_pixel = [(0_u16, 0_u16); N];
+        for (led_index, (entry, &(x_index, y_index))) in
+            leds_by_pixel.iter_mut().zip(xy_by_led.iter()).enumerate()
+        {
+            let pixel_index = y_index as usize * width + x_index as usize;
+            *entry = (pixel_index as u16, led_index as u16);
+        }
+        leds_by_pixel.sort_unstable();
+        Self {
+            reader: FrameReader::new(data, pixel_count),
+            leds_by_pixel,
+        }
+    }
+
+    /// Decode the next frame into `frame` (in strip order) and return its duration.
+    ///
+    /// `frame` must still hold the previously decoded frame, since delta frames only
+    /// update changed pixels. Pixels on cells without an LED (sparse layouts) are skipped.
+    pub(crate) fn next_frame(&mut self, frame: &mut [RGB8; N]) -> Duration {
+        let leds_by_pixel = &self.leds_by_pixel;
+        // The reader reports pixels in increasing order, so one cursor walks the sorted table.
+        let mut cursor = 0;
+        self.reader.next_frame(|pixel_index, color| {
+            while cursor < N && (leds_by_pixel[cursor].0 as usize) < pixel_index {
+                cursor += 1;
+            }
+            if cursor < N && leds_by_pixel[cursor].0 as usize == pixel_index {
+                frame[leds_by_pixel[cursor].1 as usize] = color;
+            }
+        })
+    }
+
+    /// Decode one loop of the animation (in strip order), for the host `LedStrip`.
+    #[cfg(feature = "host")]
+    pub(crate) fn into_frames(mut self) -> std::vec::Vec<(Frame1d<N>, Duration)> {
+        let mut frame = [RGB8::new(0, 0, 0); N];
+        let mut frames = std::vec::Vec::new();
+        loop {
+            let duration = self.next_frame(&mut frame);
+            frames.push((Frame1d(frame), duration));
+            if self.reader.is_at_end() {
+                return frames;
+            }
+        }
+    }
+}
+
+// ============================================================================
+// Compile-time encoding (used by `compressed_animation!`)
+// ============================================================================
+
+// Must be `pub` so `compressed_animation!` expansions in downstream crates can name it.
+#[doc(hidden)]
+/// Palette gathered from animation frames, with a hash index for fast const lookups.
+#[derive(Clone, Copy)]
+pub struct CompressedPalette {
+    colors: [RGB8; PALETTE_CAPACITY],
+    len: usize,
+    // 0 means empty; otherwise palette index + 1.
+    slots: [u8; PALETTE_SLOT_COUNT],
+}
+
+impl CompressedPalette {
+    const fn new() -> Self {
+        Self {
+            colors: [RGB8::new(0, 0, 0); PALETTE_CAPACITY],
+            len: 0,
+            slots: [0; PALETTE_SLOT_COUNT],
+        }
+    }
+
+    const fn slot_for(color: RGB8) -> usize {
+        let key = ((color.r as u32) << 16) | ((color.g as u32) << 8) | color.b as u32;
+        (key.wrapping_mul(0x9E37_79B1) >> (u32::BITS - PALETTE_SLOT_BITS)) as usize
+    }
+
+    const fn index_of(&self, color: RGB8) -> Option<u8> {
+        let mut slot = Self::slot_for(color);
+        loop {
+            let entry = self.slots[slot];
+            if entry == 0 {
+                return None;
+            }
+            let palette_index = entry - 1;
+            if rgb_eq(self.colors[palette_index as usize], color) {
+                return Some(palette_index);
+            }
+            slot = (slot + 1) % PALETTE_SLOT_COUNT;
+        }
+    }
+
+    const fn insert(&mut self, color: RGB8) {
+        if self.len == PALETTE_CAPACITY {
+            return;
+        }
+        let mut slot = Self::slot_for(color);
+        loop {
+            let entry = self.slots[slot];
+            if entry == 0 {
+                self.colors[self.len] = color;
+                self.len += 1;
+                self.slots[slot] = self.len as u8;
+                return;
+            }
+            if rgb_eq(self.colors[(entry - 1) as usize], color) {
+                return;
+            }
+            slot = (slot + 1) % PALETTE_SLOT_COUNT;
+        }
+    }
+}
+
+const fn rgb_eq(left: RGB8, right: RGB8) -> bool {
+    left.r == right.r && left.g == right.g && left.b == right.b
+}
+
+const fn pixel_at<const W: usize, const H: usize>(
+    frames: &[([[RGB8; W]; H], Duration)],
+    frame_index: usize,
+    pixel_index: usize,
+) -> RGB8 {
+    frames[frame_index].0[pixel_index / W][pixel_index % W]
+}
+
+/// Writes `byte` at `cursor` when it fits in `out` and returns the next cursor.
+///
+/// Passing an empty `out` measures the encoded length without writing.
+const fn put_byte(out: &mut [u8], cursor: usize, byte: u8) -> usize {
+    if cursor < out.len() {
+        out[cursor] = byte;
+    }
+    cursor + 1
+}
+
+/// Writes one run. Literal runs are followed by the color of every pixel in the run.
+const fn put_run<const W: usize, const H: usize>(
+    frames: &[([[RGB8; W]; H], Duration)],
+    frame_index: usize,
+    run_start: usize,
+    run_len: usize,
+    symbol: u8,
+    out: &mut [u8],
+    mut cursor: usize,
+) -> usize {
+    cursor = put_byte(out, cursor, run_len as u8);
+    cursor = put_byte(out, cursor, symbol);
+    if symbol == LITERAL_INDEX {
+        let mut pixel_index = run_start;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while pixel_index < run_start + run_len {
+            let color = pixel_at(frames, frame_index, pixel_index);
+            cursor = put_byte(out, cursor, color.r);
+            cursor = put_byte(out, cursor, color.g);
+            cursor = put_byte(out, cursor, color.b);
+            pixel_index += 1;
+        }
+    }
+    cursor
+}
+
+/// Returns the run symbol for a pixel: a palette index, [`LITERAL_INDEX`], or [`KEEP_INDEX`].
+const fn symbol_at<const W: usize, const H: usize>(
+    frames: &[([[RGB8; W]; H], Duration)],
+    frame_index: usize,
+    is_delta: bool,
+    palette: &CompressedPalette,
+    pixel_index: usize,
+) -> u8 {
+    let color = pixel_at(frames, frame_index, pixel_index);
+    if is_delta && rgb_eq(color, pixel_at(frames, frame_index - 1, pixel_index)) {
+        return KEEP_INDEX;
+    }
+    match palette.index_of(color) {
+        Some(palette_index) => palette_index,
+        None => LITERAL_INDEX,
+    }
+}
+
+const fn encode_frame_runs<const W: usize, const H: usize>(
+    frames: &[([[RGB8; W]; H], Duration)],
+    frame_index: usize,
+    is_delta: bool,
+    palette: &CompressedPalette,
+    out: &mut [u8],
+    mut cursor: usize,
+) -> usize {
+    let pixel_count = W * H;
+    let mut run_symbol = symbol_at(frames, frame_index, is_delta, palette, 0);
+    let mut run_start = 0;
+    let mut pixel_index = 1;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while pixel_index < pixel_count {
+        let symbol = symbol_at(frames, frame_index, is_delta, palette, pixel_index);
+        if pixel_index - run_start < MAX_RUN_LEN && symbol == run_symbol {
+            pixel_index += 1;
+            continue;
+        }
+        cursor = put_run(
+            frames,
+            frame_index,
+            run_start,
+            pixel_index - run_start,
+            run_symbol,
+            out,
+            cursor,
+        );
+        run_symbol = symbol;
+        run_start = pixel_index;
+        pixel_index += 1;
+    }
+    put_run(
+        frames,
+        frame_index,
+        run_start,
+        pixel_count - run_start,
+        run_symbol,
+        out,
+        cursor,
+    )
+}
+
+const fn encode_frames<const W: usize, const H: usize>(
+    frames: &[([[RGB8; W]; H], Duration)],
+    palette: &CompressedPalette,
+    out: &mut [u8],
+) -> usize {
+    assert!(
+        W > 0 && H > 0,
+        "compressed animation panel must be non-empty"
+    );
+    assert!(
+        !frames.is_empty(),
+        "compressed animation requires at least one frame"
+    );
+
+    let mut cursor = put_byte(out, 0, palette.len as u8);
+    let mut palette_index = 0;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while palette_index < palette.len {
+        let color = palette.colors[palette_index];
+        cursor = put_byte(out, cursor, color.r);
+        cursor = put_byte(out, cursor, color.g);
+        cursor = put_byte(out, cursor, color.b);
+        palette_index += 1;
+    }
+
+    let mut frame_index = 0;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while frame_index < frames.len() {
+        let duration_micros = frames[frame_index].1.as_micros();
+        assert!(
+            duration_micros > 0,
+            "animation frame duration must be positive"
+        );
+        assert!(
+            duration_micros <= u32::MAX as u64,
+            "animation frame duration must fit in u32 microseconds"
+        );
+        let duration_bytes = (duration_micros as u32).to_le_bytes();
+        let mut byte_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while byte_index < DURATION_BYTES_LEN {
+            cursor = put_byte(out, cursor, duration_bytes[byte_index]);
+            byte_index += 1;
+        }
+
+        // The first frame is always stored in full so playback can loop back to it.
+        let is_delta = frame_index > 0 && {
+            let full_len = encode_frame_runs(frames, frame_index, false, palette, &mut [], 0);
+            let delta_len = encode_frame_runs(frames, frame_index, true, palette, &mut [], 0);
+            delta_len < full_len
+        };
+        cursor = encode_frame_runs(frames, frame_index, is_delta, palette, out, cursor);
+        frame_index += 1;
+    }
+    cursor
+}
+
+// Public for macro expansion in downstream crates.
+#[doc(hidden)]
+#[must_use]
+pub const fn __compressed_palette<const W: usize, const H: usize>(
+    frames: &[([[RGB8; W]; H], Duration)],
+) -> CompressedPalette {
+    let mut palette = CompressedPalette::new();
+    let mut frame_index = 0;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+    while frame_index < frames.len() {
+        let mut y_index = 0;
+        while y_index < H {
+            let mut x_index = 0;
+            while x_index < W {
+                palette.insert(frames[frame_index].0[y_index][x_index]);
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        frame_index += 1;
+    }
+    palette
+}
+
+// Public for macro expansion in downstream crates.
+#[doc(hidden)]
+#[must_use]
+pub const fn __compressed_animation_width<const W: usize, const H: usize>(
+    _frames: &[([[RGB8; W]; H], Duration)],
+) -> usize {
+    W
+}
+
+// Public for macro expansion in downstream crates.
+#[doc(hidden)]
+#[must_use]
+pub const fn __compressed_animation_height<const W: usize, const H: usize>(
+    _frames: &[([[RGB8; W]; H], Duration)],
+) -> usize {
+    H
+}
+
+// Public for macro expansion in downstream crates.
+#[doc(hidden)]
+#[must_use]
+pub const fn __compressed_animation_data_len<const W: usize, const H: usize>(
+    frames: &[([[RGB8; W]; H], Duration)],
+    palette: &CompressedPalette,
+) -> usize {
+    encode_frames(frames, palette, &mut [])
+}
+
+// Public for macro expansion in downstream crates.
+#[doc(hidden)]
+#[must_use]
+pub const fn __compressed_animation<const W: usize, const H: usize, const DATA_LEN: usize>(
+    frames: &[([[RGB8; W]; H], Duration)],
+    palette: &CompressedPalette,
+) -> CompressedAnimationBuf<W, H, DATA_LEN> {
+    let mut data = [0_u8; DATA_LEN];
+    let data_len = encode_frames(frames, palette, &mut data);
+    assert!(
+        data_len == DATA_LEN,
+        "DATA_LEN must match the compressed animation length"
+    );
+    CompressedAnimation {
+        frame_count: frames.len(),
+        data,
+    }
+}
+
+/// Macro to compress animation frames into a `static` [`CompressedAnimation`] at compile time
+/// (includes syntax details).
+///
+/// **Syntax:**
+///
+/// ```text
+/// compressed_animation! {
+///     [<visibility>] static <NAME> = <frames_expr>;
+/// }
+/// ```
+///
+/// `<frames_expr>` must be a const array (or slice) of `([[RGB8; W]; H], Duration)` tuples,
+/// the same shape produced by `cargo xtask video-frames-gen`. The raw frames are only used
+/// during compilation; only the compressed bytes are stored in flash.
+///
+/// The generated `static` has type [`CompressedAnimationBuf`]; pass `&NAME` wherever a
+/// `&'static CompressedAnimation<W, H>` is expected.
+///
+/// See [`CompressedAnimation`] for an example.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! compressed_animation {
+    ($vis:vis static $name:ident = $frames:expr $(;)?) => {
+        $crate::led2d::paste::paste! {
+            const [<$name _PALETTE>]: $crate::led2d::compressed::CompressedPalette =
+                $crate::led2d::compressed::__compressed_palette(&$frames);
+            const [<$name _WIDTH>]: usize =
+                $crate::led2d::compressed::__compressed_animation_width(&$frames);
+            const [<$name _HEIGHT>]: usize =
+                $crate::led2d::compressed::__compressed_animation_height(&$frames);
+            const [<$name _DATA_LEN>]: usize =
+                $crate::led2d::compressed::__compressed_animation_data_len(
+                    &$frames,
+                    &[<$name _PALETTE>],
+                );
+
+            $vis static $name: $crate::led2d::compressed::CompressedAnimationBuf<
+                { [<$name _WIDTH>] },
+                { [<$name _HEIGHT>] },
+                { [<$name _DATA_LEN>] },
+            > = $crate::led2d::compressed::__compressed_animation(&$frames, &[<$name _PALETTE>]);
+        }
+    };
+}
+#[doc(inline)]
+pub use compressed_animation;
@@ -0,0 +1,98 @@
+#![allow(missing_docs)]
+
+use embassy_time::Duration;
+use smart_leds::{RGB8, colors};
+
+use super::CompressedPlayback;
+use crate::compressed_animation;
+
+// Later frames change only some pixels, so they decode as deltas over the frame before.
+const FRAMES: [([[RGB8; 3]; 2], Duration); 3] = [
+    (
+        [
+            [colors::RED, colors::GREEN, colors::BLUE],
+            [colors::WHITE, colors::BLACK, colors::RED],
+        ],
+        Duration::from_millis(100),
+    ),
+    (
+        [
+            [colors::RED, colors::GREEN, colors::GREEN],
+            [colors::WHITE, colors::BLUE, colors::RED],
+        ],
+        Duration::from_millis(200),
+    ),
+    (
+        [
+            [colors::BLACK, colors::GREEN, colors::GREEN],
+            [colors::WHITE, colors::BLUE, colors::BLACK],
+        ],
+        Duration::from_millis(50),
+    ),
+];
+
+compressed_animation! {
+    static ANIMATION = FRAMES;
+}
+
+/// A 3×2 serpentine: left to right along the top row, then back along the bottom.
+const SERPENTINE: [(u16, u16); 6] = [(0, 0), (1, 0), (2, 0), (2, 1), (1, 1), (0, 1)];
+
+fn playback<const N: usize>(xy_by_led: &[(u16, u16); N]) -> CompressedPlayback<N> {
+    CompressedPlayback::new(ANIMATION.data(), 3 * 2, xy_by_led, 3)
+}
+
+/// The strip colors `frame` should produce, looked up cell by cell.
+fn strip_frame<const N: usize>(frame: &[[RGB8; 3]; 2], xy_by_led: &[(u16, u16); N]) -> [RGB8; N] {
+    xy_by_led.map(|(x_index, y_index)| frame[y_index as usize][x_index as usize])
+}
+
+#[test]
+fn next_frame_maps_pixels_to_strip_order() {
+    let mut playback = playback(&SERPENTINE);
+    let mut frame = [RGB8::default(); 6];
+
+    assert_eq!(playback.next_frame(&mut frame), Duration::from_millis(100));
+    assert_eq!(
+        frame,
+        [
+            colors::RED,
+            colors::GREEN,
+            colors::BLUE,
+            colors::RED,
+            colors::BLACK,
+            colors::WHITE,
+        ]
+    );
+    for (expected_frame, expected_duration) in &FRAMES[1..] {
+        assert_eq!(playback.next_frame(&mut frame), *expected_duration);
+        assert_eq!(frame, strip_frame(expected_frame, &SERPENTINE));
+    }
+}
+
+#[test]
+fn next_frame_skips_cells_without_leds() {
+    // Three LEDs, out of pixel order, on a six-cell panel.
+    const SPARSE: [(u16, u16); 3] = [(2, 1), (0, 0), (1, 1)];
+    let mut playback = playback(&SPARSE);
+    let mut frame = [RGB8::default(); 3];
+
+    assert_eq!(playback.next_frame(&mut frame), Duration::from_millis(100));
+    assert_eq!(frame, [colors::RED, colors::RED, colors::BLACK]);
+    for (expected_frame, expected_duration) in &FRAMES[1..] {
+        assert_eq!(playback.next_frame(&mut frame), *expected_duration);
+        assert_eq!(frame, strip_frame(expected_frame, &SPARSE));
+    }
+}
+
+#[test]
+fn next_frame_wraps_to_first_frame() {
+    let mut playback = playback(&SERPENTINE);
+    let mut frame = [RGB8::default(); 6];
+
+    // The working frame still holds the last frame when the first is decoded again.
+    for (expected_frame, expected_duration) in FRAMES.iter().cycle().take(3 * FRAMES.len()) {
+        assert_eq!(playback.next_frame(&mut frame), *expected_duration);
+        assert_eq!(frame, strip_frame(expected_frame, &SERPENTINE));
+    }
+}
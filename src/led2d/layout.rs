@@ -3,10 +3,15 @@
 //!
 //! See [`LedLayout`] for details and examples.
 
+use embedded_graphics::prelude::Point;
+
+use crate::{Error, Result};
+
 /// Compile-time description of panel geometry and wiring, including dimensions (with examples).
 ///
 /// `LedLayout` defines how a rectangular `(x, y)` panel of LEDs maps to the linear
-/// wiring order of LEDs on a NeoPixel-style (WS2812) panel.
+/// wiring order of LEDs on a NeoPixel-style (WS2812) panel. Layouts may also be
+/// *sparse*, leaving some cells of the grid without an LED (rings, matrices with gaps).
 ///
 /// For examples of `LedLayout` in use, see the [`led2d`](mod@crate::led2d) module,
 /// [`Frame2d`](crate::led2d::Frame2d), and the example below.
@@ -35,6 +40,10 @@
 /// For unusual wiring, you can construct a layout directly with [`LedLayout::new`]
 /// by listing `(x, y)` for each LED in the order the strip is wired.
 ///
+/// For shapes that don't fill their grid, use:
+/// - [`concentric_rings`](Self::concentric_rings) (ring boards, with [polar coordinates](Self::xy_to_polar) for rendering)
+/// - [`new_sparse`](Self::new_sparse) (any shape, listed LED by LED)
+///
 /// **The example below shows both construction methods.** Also, the documentation for every constructor
 /// and method includes illustrations of use.
 ///
@@ -44,12 +53,71 @@
 /// - rotate: [`rotate_cw`](Self::rotate_cw), [`rotate_ccw`](Self::rotate_ccw), [`rotate_180`](Self::rotate_180)
 /// - flip: [`flip_h`](Self::flip_h), [`flip_v`](Self::flip_v)
 /// - combine: [`combine_h`](Self::combine_h), [`combine_v`](Self::combine_v)  (join two layouts into a larger one)
+/// - reverse wiring: [`reverse`](Self::reverse)
+///
+/// ## Sparse layouts
+///
+/// A sparse layout has fewer LEDs than grid cells (`N < W * H`). The `led2d!` macro drives
+/// sparse layouts like any other: you draw into the full `W×H` [`Frame2d`](crate::led2d::Frame2d)
+/// and each LED shows the pixel at its `(x, y)` cell. Pixels drawn on empty cells are ignored.
 ///
 /// ## Validation
 ///
 /// Layouts are validated at **compile time**:
 /// - coordinates must be in-bounds
-/// - every `(x, y)` cell must appear exactly once
+/// - every `(x, y)` cell must appear exactly once ([`new`](Self::new)) or at most once
+///   ([`new_sparse`](Self::new_sparse))
+///
+/// Layouts loaded at runtime are validated by [`try_new`](Self::try_new) instead.
+///
+/// ## Field-reconfigurable panels
+///
+/// A panel assembled differently in the field (rotated, mirrored, or fed from the other end)
+/// doesn't need a recompile. The type generated by [`led2d!`](macro@crate::led2d) can
+/// [load a layout from flash](crate::led2d::led2d_generated::Led2dGenerated::load_led_layout),
+/// keeping the const layout as the fallback, and can
+/// [calibrate](crate::led2d::led2d_generated::Led2dGenerated::calibrate_led_layout) the
+/// layout with a button:
+///
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// # use panic_probe as _;
+/// # use core::convert::Infallible;
+/// # use core::future;
+/// # use embassy_executor::Spawner;
+/// use device_envoy::{Result, button::{Button, PressedTo}, flash_array::FlashArray, led2d, led2d::Led2dFont, led2d::layout::LedLayout, led_strip::colors};
+///
+/// const LED_LAYOUT_12X4: LedLayout<48, 12, 4> = LedLayout::serpentine_column_major();
+///
+/// led2d! {
+///     Led12x4 {
+///         pin: PIN_3,
+///         led_layout: LED_LAYOUT_12X4,
+///         font: Led2dFont::Font3x4Trim,
+///     }
+/// }
+///
+/// # #[embassy_executor::main]
+/// # pub async fn main(spawner: Spawner) -> ! {
+/// #     let err = example(spawner).await.unwrap_err();
+/// #     core::panic!("{err}");
+/// # }
+/// async fn example(spawner: Spawner) -> Result<Infallible> {
+///     let p = embassy_rp::init(Default::default());
+///     let [mut layout_flash_block] = FlashArray::<1>::new(p.FLASH)?;
+///     let mut button = Button::new(p.PIN_13, PressedTo::Ground);
+///     let mut led12x4 = Led12x4::new(p.PIN_3, p.PIO0, p.DMA_CH0, spawner)?;
+///
+///     // Use the saved layout if there is one; otherwise, calibrate and save one.
+///     if !led12x4.load_led_layout(&mut layout_flash_block)? {
+///         led12x4.calibrate_led_layout(&mut button, &mut layout_flash_block).await?;
+///     }
+///     led12x4.write_text("Hi", &[colors::WHITE]).await?;
+///
+///     future::pending().await // run forever
+/// }
+/// ```
 ///
 /// If you want the final mapping, use [`index_to_xy`](Self::index_to_xy).
 ///
@@ -102,50 +170,43 @@ impl<const N: usize, const W: usize, const H: usize> LedLayout<N, W, H> {
         H
     }
 
-    /// Total LEDs in this layout (width × height).
+    /// Total LEDs in this layout (width × height, or fewer for a sparse layout).
     #[must_use]
     pub const fn len(&self) -> usize {
         N
     }
 
+    /// Whether some grid cells have no LED (`N < W * H`).
     #[must_use]
-    pub(crate) const fn xy_to_index(&self) -> [u16; N] {
-        assert!(
-            N <= u16::MAX as usize,
-            "total LEDs must fit in u16 for xy_to_index"
-        );
-
-        let mut mapping = [None; N];
+    pub const fn is_sparse(&self) -> bool {
+        N < W * H
+    }
 
+    /// Return the LED index at `(x, y)`, or `None` if the cell has no LED.
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::layout::LedLayout;
+    ///
+    /// const PLUS: LedLayout<5, 3, 3> =
+    ///     LedLayout::new_sparse([(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)]);
+    /// const _: () = assert!(matches!(PLUS.index_at(1, 1), Some(2)));
+    /// const _: () = assert!(PLUS.index_at(0, 0).is_none());
+    /// ```
+    #[must_use]
+    pub const fn index_at(&self, x: u16, y: u16) -> Option<usize> {
         let mut led_index = 0;
         // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
         while led_index < N {
-            let (col, row) = self.map[led_index];
-            let col = col as usize;
-            let row = row as usize;
-            assert!(col < W, "column out of bounds in xy_to_index");
-            assert!(row < H, "row out of bounds in xy_to_index");
-            let target_index = row * W + col;
-
-            let slot = &mut mapping[target_index];
-            assert!(
-                slot.is_none(),
-                "duplicate (col,row) in xy_to_index inversion"
-            );
-            *slot = Some(led_index as u16);
-
+            if self.map[led_index].0 == x && self.map[led_index].1 == y {
+                return Some(led_index);
+            }
             led_index += 1;
         }
-
-        let mut finalized = [0u16; N];
-        let mut i = 0;
-        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
-        while i < N {
-            finalized[i] = mapping[i].expect("xy_to_index requires every (col,row) to be covered");
-            i += 1;
-        }
-
-        finalized
+        None
     }
 
     /// Const equality helper for doctests/examples.
@@ -255,6 +316,358 @@ impl<const N: usize, const W: usize, const H: usize> LedLayout<N, W, H> {
         Self { map }
     }
 
+    /// Construct a sparse `LedLayout`, where some grid cells have no LED.
+    ///
+    /// Like [`new`](Self::new), you provide the `(x, y)` coordinate for **each LED in strip
+    /// order**, but the layout may have fewer LEDs than cells (`N <= W * H`). Coordinates
+    /// must still be in-bounds and unique.
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::layout::LedLayout;
+    ///
+    /// // A 3×3 grid with only the corners and center populated.
+    /// const X_SHAPE: LedLayout<5, 3, 3> =
+    ///     LedLayout::new_sparse([(0, 0), (2, 0), (1, 1), (0, 2), (2, 2)]);
+    /// const _: () = assert!(X_SHAPE.is_sparse());
+    ///
+    /// // Transforms work on sparse layouts, too.
+    /// const FLIPPED: LedLayout<5, 3, 3> = X_SHAPE.flip_h();
+    /// const EXPECTED: LedLayout<5, 3, 3> =
+    ///     LedLayout::new_sparse([(2, 0), (0, 0), (1, 1), (2, 2), (0, 2)]);
+    /// const _: () = assert!(FLIPPED.equals(&EXPECTED));
+    /// ```
+    ///
+    /// ```text
+    /// X_SHAPE (3×3, `.` = no LED):
+    ///   LED0  .     LED1
+    ///   .     LED2  .
+    ///   LED3  .     LED4
+    /// ```
+    #[must_use]
+    pub const fn new_sparse(map: [(u16, u16); N]) -> Self {
+        assert!(W > 0 && H > 0, "W and H must be positive");
+        assert!(N <= W * H, "N must not exceed W*H");
+
+        let mut i = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while i < N {
+            let (c, r) = map[i];
+            assert!((c as usize) < W, "column out of bounds");
+            assert!((r as usize) < H, "row out of bounds");
+
+            let mut j = 0;
+            while j < i {
+                assert!(
+                    map[j].0 != c || map[j].1 != r,
+                    "duplicate (col,row) in mapping"
+                );
+                j += 1;
+            }
+
+            i += 1;
+        }
+
+        Self { map }
+    }
+
+    /// Construct a `LedLayout` from a map checked at runtime, such as one loaded from flash.
+    ///
+    /// Applies the same rules as [`new_sparse`](Self::new_sparse) (in-bounds, no duplicate
+    /// cells, `N <= W * H`), but returns [`Error::InvalidLedLayout`] instead of panicking.
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::layout::LedLayout;
+    ///
+    /// let loaded = [(0, 0), (1, 0), (1, 1), (0, 1)];
+    /// let led_layout = LedLayout::<4, 2, 2>::try_new(loaded)
+    ///     .unwrap_or(LedLayout::serpentine_column_major());
+    /// ```
+    pub fn try_new(map: [(u16, u16); N]) -> Result<Self> {
+        if W == 0 || H == 0 {
+            return Err(Error::InvalidLedLayout("W and H must be positive"));
+        }
+        if N > W * H {
+            return Err(Error::InvalidLedLayout("N must not exceed W*H"));
+        }
+        for (index, &(col, row)) in map.iter().enumerate() {
+            if usize::from(col) >= W {
+                return Err(Error::InvalidLedLayout("column out of bounds"));
+            }
+            if usize::from(row) >= H {
+                return Err(Error::InvalidLedLayout("row out of bounds"));
+            }
+            if map[..index].contains(&(col, row)) {
+                return Err(Error::InvalidLedLayout("duplicate (col,row) in mapping"));
+            }
+        }
+        Ok(Self { map })
+    }
+
+    /// Wrap a map that is already known to be valid (for example, taken from another layout).
+    pub(crate) const fn from_valid_map(map: [(u16, u16); N]) -> Self {
+        Self { map }
+    }
+
+    /// Validate a transformed map with [`new`](Self::new) when dense, else [`new_sparse`](Self::new_sparse).
+    const fn new_dense_or_sparse(map: [(u16, u16); N]) -> Self {
+        if N == W * H {
+            Self::new(map)
+        } else {
+            Self::new_sparse(map)
+        }
+    }
+
+    /// Concentric rings of LEDs (ring boards), placed on a `W×H` grid centered on the panel.
+    ///
+    /// `ring_led_counts` lists the LED count of each ring from the **innermost ring outward**,
+    /// in strip order. Within each ring, LEDs start at 12 o'clock and run clockwise. A first
+    /// ring with a single LED is the center LED. Rings are evenly spaced out to the edge of
+    /// the grid. If your board is wired from the outside in, apply [`reverse`](Self::reverse).
+    ///
+    /// The grid must be large enough that no two LEDs round to the same cell; this is checked
+    /// at compile time. Use [`xy_to_polar`](Self::xy_to_polar) to render by angle and radius.
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::layout::LedLayout;
+    ///
+    /// // A center LED and an 8-LED ring on a 5×5 grid.
+    /// const RINGS: LedLayout<9, 5, 5> = LedLayout::concentric_rings([1, 8]);
+    /// const EXPECTED: LedLayout<9, 5, 5> = LedLayout::new_sparse([
+    ///     (2, 2), (2, 0), (3, 1), (4, 2), (3, 3), (2, 4), (1, 3), (0, 2), (1, 1),
+    /// ]);
+    /// const _: () = assert!(RINGS.equals(&EXPECTED));
+    /// ```
+    ///
+    /// ```text
+    /// 5×5 grid (`.` = no LED):
+    ///   .     .     LED1  .     .
+    ///   .     LED8  .     LED2  .
+    ///   LED7  .     LED0  .     LED3
+    ///   .     LED6  .     LED4  .
+    ///   .     .     LED5  .     .
+    /// ```
+    #[must_use]
+    pub const fn concentric_rings<const R: usize>(ring_led_counts: [u16; R]) -> Self {
+        assert!(R > 0, "concentric_rings requires at least one ring");
+
+        let mut total = 0;
+        let mut ring_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while ring_index < R {
+            assert!(
+                ring_led_counts[ring_index] > 0,
+                "each ring needs at least one LED"
+            );
+            total += ring_led_counts[ring_index] as usize;
+            ring_index += 1;
+        }
+        assert!(total == N, "ring LED counts must sum to N");
+
+        // A single-LED first ring sits at the center; otherwise the first ring is one step out.
+        let first_step = if ring_led_counts[0] == 1 { 0 } else { 1 };
+        let step_count = R - 1 + first_step;
+        let max_radius = Self::max_radius();
+
+        let mut map = [(0_u16, 0_u16); N];
+        let mut led_index = 0;
+        let mut ring_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while ring_index < R {
+            let radius = if step_count == 0 {
+                0.0
+            } else {
+                max_radius * (ring_index + first_step) as f32 / step_count as f32
+            };
+            let ring_len = ring_led_counts[ring_index] as usize;
+            let mut position = 0;
+            while position < ring_len {
+                let angle_degrees = 360.0 * position as f32 / ring_len as f32;
+                let point = Self::polar_to_point(radius, angle_degrees);
+                assert!(
+                    point.x >= 0 && point.y >= 0,
+                    "ring LED falls outside the grid"
+                );
+                map[led_index] = (point.x as u16, point.y as u16);
+                led_index += 1;
+                position += 1;
+            }
+            ring_index += 1;
+        }
+
+        Self::new_dense_or_sparse(map)
+    }
+
+    /// Convert a grid cell to polar coordinates `(radius, angle_degrees)` around the grid center.
+    ///
+    /// The radius is measured in cells. The angle is in `[0, 360)`, measured clockwise from
+    /// 12 o'clock, matching [`concentric_rings`](Self::concentric_rings). Use it to render ring
+    /// boards by angle (spinners, clocks, color wheels) or by radius (ripples).
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::{Frame2d, layout::LedLayout};
+    /// use device_envoy::led_strip::colors;
+    ///
+    /// const RINGS: LedLayout<25, 7, 7> = LedLayout::concentric_rings([1, 8, 16]);
+    ///
+    /// // Light the right half of every ring red and the left half blue.
+    /// let mut frame = Frame2d::<7, 7>::new();
+    /// for &(x, y) in RINGS.index_to_xy() {
+    ///     let (_radius, angle_degrees) = RINGS.xy_to_polar(x, y);
+    ///     frame[(x as usize, y as usize)] = if angle_degrees < 180.0 {
+    ///         colors::RED
+    ///     } else {
+    ///         colors::BLUE
+    ///     };
+    /// }
+    /// ```
+    #[must_use]
+    pub const fn xy_to_polar(&self, x: u16, y: u16) -> (f32, f32) {
+        let (center_x, center_y) = Self::center();
+        let dx = x as f32 - center_x;
+        let dy = y as f32 - center_y;
+        let radius = sqrt(dx * dx + dy * dy);
+        // Clockwise from 12 o'clock: "up" is -y in screen coordinates.
+        let mut angle_degrees = atan2(dx, -dy) * DEGREES_PER_RADIAN;
+        if angle_degrees < 0.0 {
+            angle_degrees += 360.0;
+        }
+        if angle_degrees >= 360.0 {
+            angle_degrees -= 360.0;
+        }
+        (radius, angle_degrees)
+    }
+
+    /// Convert polar coordinates around the grid center to the nearest grid cell.
+    ///
+    /// The inverse of [`xy_to_polar`](Self::xy_to_polar): `radius` is in cells and
+    /// `angle_degrees` is clockwise from 12 o'clock. The returned [`Point`] may lie outside
+    /// the grid (or on a cell without an LED in a sparse layout), so it can be passed
+    /// directly to embedded-graphics drawing, which clips.
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::{Point, layout::LedLayout};
+    ///
+    /// const RINGS: LedLayout<9, 5, 5> = LedLayout::concentric_rings([1, 8]);
+    /// const THREE_OCLOCK: Point = RINGS.polar_to_xy(2.0, 90.0);
+    /// const _: () = assert!(THREE_OCLOCK.x == 4 && THREE_OCLOCK.y == 2);
+    /// ```
+    #[must_use]
+    pub const fn polar_to_xy(&self, radius: f32, angle_degrees: f32) -> Point {
+        Self::polar_to_point(radius, angle_degrees)
+    }
+
+    const fn center() -> (f32, f32) {
+        ((W - 1) as f32 / 2.0, (H - 1) as f32 / 2.0)
+    }
+
+    const fn max_radius() -> f32 {
+        let (center_x, center_y) = Self::center();
+        if center_x < center_y {
+            center_x
+        } else {
+            center_y
+        }
+    }
+
+    const fn polar_to_point(radius: f32, angle_degrees: f32) -> Point {
+        let (center_x, center_y) = Self::center();
+        let (sin, cos) = sin_cos_degrees(angle_degrees);
+        Point::new(
+            round_to_i32(center_x + radius * sin),
+            round_to_i32(center_y - radius * cos),
+        )
+    }
+
+    /// Reverse the wiring order (LED 0 becomes the last LED), keeping every LED's cell.
+    ///
+    /// Use this when a strip is fed from its other end.
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::layout::LedLayout;
+    ///
+    /// const REVERSED: LedLayout<4, 4, 1> = LedLayout::linear_h().reverse();
+    /// const EXPECTED: LedLayout<4, 4, 1> = LedLayout::new([(3, 0), (2, 0), (1, 0), (0, 0)]);
+    /// const _: () = assert!(REVERSED.equals(&EXPECTED));
+    /// ```
+    ///
+    /// ```text
+    /// Before: LED0  LED1  LED2  LED3
+    /// After:  LED3  LED2  LED1  LED0
+    /// ```
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        let mut out = [(0u16, 0u16); N];
+        let mut i = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while i < N {
+            out[i] = self.map[N - 1 - i];
+            i += 1;
+        }
+        Self { map: out }
+    }
+
+    /// Return this layout and its seven same-size reorientations.
+    ///
+    /// The order is: unchanged, [`flip_h`](Self::flip_h), [`flip_v`](Self::flip_v),
+    /// [`rotate_180`](Self::rotate_180), and then each of those with [`reverse`](Self::reverse)d
+    /// wiring. Together they cover a panel mounted upside down or mirrored, or fed from the
+    /// other end of its strip. LED layout calibration steps through these candidates.
+    /// Symmetric wirings repeat some candidates (for example, rotating an odd-width
+    /// serpentine by 180° just reverses it).
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::layout::LedLayout;
+    ///
+    /// const LED_LAYOUT: LedLayout<6, 3, 2> = LedLayout::serpentine_column_major();
+    /// const ORIENTATIONS: [LedLayout<6, 3, 2>; 8] = LED_LAYOUT.orientations();
+    /// const _: () = assert!(ORIENTATIONS[3].equals(&LED_LAYOUT.rotate_180()));
+    /// const _: () = assert!(ORIENTATIONS[5].equals(&LED_LAYOUT.flip_h().reverse()));
+    /// ```
+    #[must_use]
+    pub const fn orientations(self) -> [Self; 8] {
+        let flipped_h = self.flip_h();
+        let flipped_v = self.flip_v();
+        let rotated_180 = self.rotate_180();
+        [
+            self,
+            flipped_h,
+            flipped_v,
+            rotated_180,
+            self.reverse(),
+            flipped_h.reverse(),
+            flipped_v.reverse(),
+            rotated_180.reverse(),
+        ]
+    }
+
     /// Linear row-major mapping for a single-row strip (cols increase left-to-right).
     ///
     /// ```rust,no_run
@@ -451,7 +864,7 @@ impl<const N: usize, const W: usize, const H: usize> LedLayout<N, W, H> {
             out[i] = ((H - 1 - r) as u16, c as u16);
             i += 1;
         }
-        LedLayout::<N, H, W>::new(out)
+        LedLayout::<N, H, W>::new_dense_or_sparse(out)
     }
 
     /// Flip horizontally (mirror columns).
@@ -485,7 +898,7 @@ impl<const N: usize, const W: usize, const H: usize> LedLayout<N, W, H> {
             out[i] = ((W - 1 - c) as u16, r);
             i += 1;
         }
-        Self::new(out)
+        Self::new_dense_or_sparse(out)
     }
 
     /// Rotate 180° derived from rotate_cw.
@@ -620,7 +1033,7 @@ impl<const N: usize, const W: usize, const H: usize> LedLayout<N, W, H> {
             j += 1;
         }
 
-        LedLayout::<OUT_N, OUT_W, H>::new(out)
+        LedLayout::<OUT_N, OUT_W, H>::new_dense_or_sparse(out)
     }
 
     /// Concatenate vertically with another mapping sharing the same columns.
@@ -675,3 +1088,90 @@ impl<const N: usize, const W: usize, const H: usize> LedLayout<N, W, H> {
         combined_t.rotate_cw().flip_h() // transpose back to W x OUT_H
     }
 }
+
+const DEGREES_PER_RADIAN: f32 = 180.0 / core::f32::consts::PI;
+
+const fn round_to_i32(value: f32) -> i32 {
+    if value < 0.0 {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+/// Const sine and cosine of an angle in degrees.
+const fn sin_cos_degrees(angle_degrees: f32) -> (f32, f32) {
+    (
+        sin_degrees(angle_degrees),
+        sin_degrees(angle_degrees + 90.0),
+    )
+}
+
+const fn sin_degrees(angle_degrees: f32) -> f32 {
+    // Reduce to [-180, 180], then fold into [-90, 90] where the series converges quickly.
+    let mut degrees = angle_degrees - 360.0 * (angle_degrees / 360.0) as i32 as f32;
+    if degrees > 180.0 {
+        degrees -= 360.0;
+    } else if degrees < -180.0 {
+        degrees += 360.0;
+    }
+    if degrees > 90.0 {
+        degrees = 180.0 - degrees;
+    } else if degrees < -90.0 {
+        degrees = -180.0 - degrees;
+    }
+
+    let x = degrees / DEGREES_PER_RADIAN;
+    let x2 = x * x;
+    // Taylor series through x^11.
+    x * (1.0
+        - x2 / 6.0 * (1.0 - x2 / 20.0 * (1.0 - x2 / 42.0 * (1.0 - x2 / 72.0 * (1.0 - x2 / 110.0)))))
+}
+
+/// Const `atan2(y, x)` in radians, in `(-PI, PI]`.
+const fn atan2(y: f32, x: f32) -> f32 {
+    use core::f32::consts::{FRAC_PI_2, PI};
+
+    if x == 0.0 {
+        return if y > 0.0 {
+            FRAC_PI_2
+        } else if y < 0.0 {
+            -FRAC_PI_2
+        } else {
+            0.0
+        };
+    }
+    let y_abs = if y < 0.0 { -y } else { y };
+    let x_abs = if x < 0.0 { -x } else { x };
+    // atan on [0, 1], then mirror into the right octant and quadrant.
+    let angle = if y_abs <= x_abs {
+        atan_unit(y_abs / x_abs)
+    } else {
+        FRAC_PI_2 - atan_unit(x_abs / y_abs)
+    };
+    let angle = if x < 0.0 { PI - angle } else { angle };
+    if y < 0.0 { -angle } else { angle }
+}
+
+/// Polynomial `atan(z)` for `z` in `[0, 1]` (max error about 1e-5 radians).
+const fn atan_unit(z: f32) -> f32 {
+    let z2 = z * z;
+    z * (0.999_977_3
+        + z2 * (-0.332_623_5
+            + z2 * (0.193_543_5 + z2 * (-0.116_432_87 + z2 * (0.052_653_32 - z2 * 0.011_721_2)))))
+}
+
+/// Const square root by Newton's method.
+const fn sqrt(value: f32) -> f32 {
+    if value <= 0.0 {
+        return 0.0;
+    }
+    let mut estimate = if value > 1.0 { value } else { 1.0 };
+    let mut iteration = 0;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while iteration < 20 {
+        estimate = 0.5 * (estimate + value / estimate);
+        iteration += 1;
+    }
+    estimate
+}
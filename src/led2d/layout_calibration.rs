@@ -0,0 +1,133 @@
+//! LED layouts saved in flash, and interactive calibration to choose one in the field.
+//!
+//! See the generated `load_led_layout` and `calibrate_led_layout` methods for usage.
+
+use core::convert::Infallible;
+
+use embassy_futures::select::{Either, select};
+use embassy_time::{Duration, Timer};
+use serde::{Deserialize, Serialize};
+
+use crate::Result;
+use crate::button::{Button, PressDuration};
+use crate::flash_array::FlashBlock;
+use crate::led_strip::colors;
+use crate::led2d::{Frame2d, Led2d, LedLayout};
+
+/// How long calibration lights each LED before moving to the next one.
+const CALIBRATION_STEP: Duration = Duration::from_millis(150);
+
+/// Flash representation of a [`LedLayout`].
+///
+/// The dimensions are stored so that a layout saved for a different panel is ignored.
+#[derive(Serialize, Deserialize)]
+struct StoredLedLayout<const N: usize> {
+    width: u16,
+    height: u16,
+    map: heapless::Vec<(u16, u16), N>,
+}
+
+impl<const N: usize, const MAX_FRAMES: usize> Led2d<N, MAX_FRAMES> {
+    /// Use the LED layout saved in `flash_block`, if there is one.
+    ///
+    /// Returns `Ok(true)` if a saved layout was loaded. Returns `Ok(false)` and keeps the
+    /// current layout (normally the const layout from the macro) if the block is empty or
+    /// holds a layout that doesn't fit this panel.
+    pub fn load_led_layout<const W: usize, const H: usize>(
+        &mut self,
+        flash_block: &mut FlashBlock,
+    ) -> Result<bool> {
+        let Some(stored) = flash_block.load::<StoredLedLayout<N>>()? else {
+            return Ok(false);
+        };
+        if usize::from(stored.width) != W || usize::from(stored.height) != H {
+            return Ok(false);
+        }
+        let Ok(map) = <[(u16, u16); N]>::try_from(stored.map.as_slice()) else {
+            return Ok(false);
+        };
+        let Ok(led_layout) = LedLayout::<N, W, H>::try_new(map) else {
+            return Ok(false);
+        };
+        self.set_led_layout(&led_layout);
+        Ok(true)
+    }
+
+    /// Save the current LED layout to `flash_block`.
+    pub fn save_led_layout<const W: usize, const H: usize>(
+        &self,
+        flash_block: &mut FlashBlock,
+    ) -> Result<()> {
+        let mut map = heapless::Vec::new();
+        map.extend_from_slice(&self.xy_by_led)
+            .expect("map capacity is N");
+        flash_block.save(&StoredLedLayout::<N> {
+            width: W as u16,
+            height: H as u16,
+            map,
+        })
+    }
+
+    /// Interactively choose the panel's orientation and save it to `flash_block`.
+    ///
+    /// Lights each LED in turn, in reading order (left to right, top to bottom), starting
+    /// with a green LED. If the sweep doesn't run that way on the panel, a short press tries
+    /// the next of the layout's [`orientations`](LedLayout::orientations). A long press
+    /// confirms the current orientation, saves it, and returns it.
+    pub async fn calibrate_led_layout<const W: usize, const H: usize>(
+        &mut self,
+        button: &mut Button<'_>,
+        flash_block: &mut FlashBlock,
+    ) -> Result<LedLayout<N, W, H>> {
+        let candidates = self.led_layout::<W, H>().orientations();
+        let mut candidate_index = 0;
+        loop {
+            self.set_led_layout(&candidates[candidate_index]);
+            match select(self.sweep_leds::<W, H>(), button.wait_for_press_duration()).await {
+                Either::First(result) => match result? {},
+                Either::Second(PressDuration::Short) => {
+                    // Symmetric wirings repeat candidates; skip ones already offered.
+                    loop {
+                        candidate_index = (candidate_index + 1) % candidates.len();
+                        let candidate = &candidates[candidate_index];
+                        if !candidates[..candidate_index]
+                            .iter()
+                            .any(|earlier| earlier.equals(candidate))
+                        {
+                            break;
+                        }
+                    }
+                }
+                Either::Second(PressDuration::Long) => break,
+            }
+        }
+
+        self.write_frame(Frame2d::<W, H>::new())?;
+        self.save_led_layout::<W, H>(flash_block)?;
+        Ok(candidates[candidate_index])
+    }
+
+    /// Light each LED in turn, in reading order of its cell, until cancelled.
+    async fn sweep_leds<const W: usize, const H: usize>(&self) -> Result<Infallible> {
+        let xy_by_led = &self.xy_by_led;
+        let mut led_order: [usize; N] = core::array::from_fn(|led_index| led_index);
+        led_order.sort_unstable_by_key(|&led_index| {
+            let (x_index, y_index) = xy_by_led[led_index];
+            (y_index, x_index)
+        });
+
+        loop {
+            for (step, &led_index) in led_order.iter().enumerate() {
+                let (x_index, y_index) = xy_by_led[led_index];
+                let mut frame = Frame2d::<W, H>::new();
+                frame[(x_index as usize, y_index as usize)] = if step == 0 {
+                    colors::GREEN
+                } else {
+                    colors::WHITE
+                };
+                self.write_frame(frame)?;
+                Timer::after(CALIBRATION_STEP).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,564 @@
+//! Module containing [`Sprite`] and [`TileMap`], a small game-oriented layer on top of
+//! [`Frame2d`].
+//!
+//! See [`Sprite`] for details and examples.
+
+use embedded_graphics::{
+    Drawable, Pixel,
+    draw_target::DrawTarget,
+    geometry::{Point, Size},
+    pixelcolor::Rgb888,
+    primitives::{PointsIter, Rectangle},
+};
+use smart_leds::RGB8;
+
+use crate::led_strip::ToRgb888;
+use crate::led2d::Frame2d;
+
+/// Fixed-size sprite or tile image where `None` pixels are transparent.
+///
+/// Images are usually built at compile time with [`SpriteImage::from_ascii`].
+/// See [`Sprite`] for an example.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpriteImage<const W: usize, const H: usize>(pub [[Option<RGB8>; W]; H]);
+
+impl<const W: usize, const H: usize> SpriteImage<W, H> {
+    /// The width of the image.
+    pub const WIDTH: usize = W;
+    /// The height of the image.
+    pub const HEIGHT: usize = H;
+
+    /// Create a fully transparent image.
+    #[must_use]
+    pub const fn transparent() -> Self {
+        Self([[None; W]; H])
+    }
+
+    /// Create an image filled with a single opaque color.
+    #[must_use]
+    pub const fn filled(color: RGB8) -> Self {
+        Self([[Some(color); W]; H])
+    }
+
+    /// Create an image from ASCII art, one string per row.
+    ///
+    /// Each byte is looked up in `palette`; `.` and ` ` are transparent. Panics (at compile
+    /// time when used in a `const`) if a row has the wrong length or a byte is not in `palette`.
+    ///
+    /// ```rust,no_run
+    /// # #![no_std]
+    /// # #![no_main]
+    /// # #[panic_handler]
+    /// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+    /// use device_envoy::led2d::sprite::SpriteImage;
+    /// use smart_leds::colors;
+    ///
+    /// const BALL: SpriteImage<3, 3> = SpriteImage::from_ascii(
+    ///     [".W.", "WWW", ".W."],
+    ///     &[(b'W', colors::WHITE)],
+    /// );
+    /// const _: () = assert!(BALL.0[0][0].is_none());
+    /// ```
+    #[must_use]
+    pub const fn from_ascii(rows: [&str; H], palette: &[(u8, RGB8)]) -> Self {
+        let mut pixels = [[None; W]; H];
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let row = rows[y_index].as_bytes();
+            assert!(row.len() == W, "each ASCII row must have exactly W bytes");
+            let mut x_index = 0;
+            while x_index < W {
+                let byte = row[x_index];
+                if byte != b'.' && byte != b' ' {
+                    let mut palette_index = 0;
+                    while palette_index < palette.len() && palette[palette_index].0 != byte {
+                        palette_index += 1;
+                    }
+                    assert!(
+                        palette_index < palette.len(),
+                        "ASCII sprite byte not found in palette"
+                    );
+                    pixels[y_index][x_index] = Some(palette[palette_index].1);
+                }
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this image mirrored left-to-right.
+    #[must_use]
+    pub const fn flip_h(self) -> Self {
+        let mut pixels = [[None; W]; H];
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace these while loops with for loops.
+        while y_index < H {
+            let mut x_index = 0;
+            while x_index < W {
+                pixels[y_index][x_index] = self.0[y_index][W - 1 - x_index];
+                x_index += 1;
+            }
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this image mirrored top-to-bottom.
+    #[must_use]
+    pub const fn flip_v(self) -> Self {
+        let mut pixels = [[None; W]; H];
+        let mut y_index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while y_index < H {
+            pixels[y_index] = self.0[H - 1 - y_index];
+            y_index += 1;
+        }
+        Self(pixels)
+    }
+}
+
+/// Anything that can be composited by [`render_layers`]: sprites, tile maps, or your own types.
+pub trait Layer {
+    /// Stacking order. Higher values are drawn on top.
+    fn z(&self) -> i8;
+
+    /// The opaque color at a panel coordinate, or `None` if transparent there.
+    fn pixel_at(&self, point: Point) -> Option<RGB8>;
+}
+
+/// Composite `layers` onto `frame` (with example).
+///
+/// For each pixel, the opaque pixel from the layer with the highest [`z`](Layer::z) wins.
+/// When layers share a `z`, later layers in the slice are on top. Pixels where every layer is
+/// transparent keep the frame's existing content, so fill or draw a background first.
+///
+/// See [`Sprite`] for an example.
+pub fn render_layers<const W: usize, const H: usize>(
+    frame: &mut Frame2d<W, H>,
+    layers: &[&dyn Layer],
+) {
+    for y_index in 0..H {
+        for x_index in 0..W {
+            let point = Point::new(x_index as i32, y_index as i32);
+            let mut top: Option<(i8, RGB8)> = None;
+            for layer in layers {
+                let z = layer.z();
+                if top.is_some_and(|(top_z, _)| z < top_z) {
+                    continue;
+                }
+                if let Some(color) = layer.pixel_at(point) {
+                    top = Some((z, color));
+                }
+            }
+            if let Some((_, color)) = top {
+                frame.0[y_index][x_index] = color;
+            }
+        }
+    }
+}
+
+/// A movable image with position, z-order, transparency, and flipping (with example).
+///
+/// Sprites use panel coordinates: `(0, 0)` is the top-left LED, and sprites may be partly or
+/// fully off the panel. Draw them with [`render_layers`] (for z-ordering with other layers)
+/// or directly onto any [`embedded-graphics`](https://docs.rs/embedded-graphics) draw target,
+/// such as [`Frame2d`].
+///
+/// # Example: A tiny Pong frame
+///
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// # #[panic_handler]
+/// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+/// use device_envoy::led2d::Frame2d;
+/// use device_envoy::led2d::sprite::{Sprite, SpriteImage, render_layers};
+/// use embedded_graphics::prelude::Point;
+/// use smart_leds::colors;
+///
+/// const PADDLE: SpriteImage<1, 3> = SpriteImage::filled(colors::WHITE);
+/// const BALL: SpriteImage<1, 1> = SpriteImage::filled(colors::YELLOW);
+///
+/// # fn example() {
+/// let left_paddle = Sprite::new(&PADDLE, Point::new(0, 2));
+/// let right_paddle = Sprite::new(&PADDLE, Point::new(11, 4));
+/// let mut ball = Sprite::new(&BALL, Point::new(5, 3)).with_z(1);
+///
+/// ball.move_by(1, 1);
+/// let bounced = ball.collides_with(&left_paddle) || ball.collides_with(&right_paddle);
+///
+/// let mut frame = Frame2d::<12, 8>::new();
+/// render_layers(&mut frame, &[&left_paddle, &right_paddle, &ball]);
+/// # let _ = bounced;
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct Sprite<'a> {
+    pixels: &'a [Option<RGB8>],
+    size: Size,
+    position: Point,
+    z: i8,
+    is_flipped_h: bool,
+    is_flipped_v: bool,
+    is_visible: bool,
+}
+
+impl<'a> Sprite<'a> {
+    /// Create a visible, unflipped sprite at `position` (its top-left corner) with `z = 0`.
+    #[must_use]
+    pub const fn new<const W: usize, const H: usize>(
+        image: &'a SpriteImage<W, H>,
+        position: Point,
+    ) -> Self {
+        Self {
+            pixels: image.0.as_flattened(),
+            size: Size::new(W as u32, H as u32),
+            position,
+            z: 0,
+            is_flipped_h: false,
+            is_flipped_v: false,
+            is_visible: true,
+        }
+    }
+
+    /// Return this sprite with the given z-order.
+    #[must_use]
+    pub const fn with_z(mut self, z: i8) -> Self {
+        self.z = z;
+        self
+    }
+
+    /// Return this sprite with the given flips applied at draw time.
+    #[must_use]
+    pub const fn with_flip(mut self, flip_h: bool, flip_v: bool) -> Self {
+        self.is_flipped_h = flip_h;
+        self.is_flipped_v = flip_v;
+        self
+    }
+
+    /// Top-left corner of the sprite in panel coordinates.
+    #[must_use]
+    pub const fn position(&self) -> Point {
+        self.position
+    }
+
+    /// Move the sprite so its top-left corner is at `position`.
+    pub const fn set_position(&mut self, position: Point) {
+        self.position = position;
+    }
+
+    /// Move the sprite by `(dx, dy)`.
+    pub const fn move_by(&mut self, dx: i32, dy: i32) {
+        self.position = Point::new(self.position.x + dx, self.position.y + dy);
+    }
+
+    /// Set the z-order. Higher values are drawn on top.
+    pub const fn set_z(&mut self, z: i8) {
+        self.z = z;
+    }
+
+    /// Mirror the sprite left-to-right when drawn.
+    pub const fn set_flip_h(&mut self, flip_h: bool) {
+        self.is_flipped_h = flip_h;
+    }
+
+    /// Mirror the sprite top-to-bottom when drawn.
+    pub const fn set_flip_v(&mut self, flip_v: bool) {
+        self.is_flipped_v = flip_v;
+    }
+
+    /// Show or hide the sprite. Hidden sprites draw nothing and never collide.
+    pub const fn set_visible(&mut self, visible: bool) {
+        self.is_visible = visible;
+    }
+
+    /// Whether the sprite is visible.
+    #[must_use]
+    pub const fn is_visible(&self) -> bool {
+        self.is_visible
+    }
+
+    /// The sprite's bounding box in panel coordinates.
+    #[must_use]
+    pub const fn bounding_box(&self) -> Rectangle {
+        Rectangle::new(self.position, self.size)
+    }
+
+    /// Whether any opaque pixel of this sprite overlaps an opaque pixel of `other`.
+    #[must_use]
+    pub fn collides_with(&self, other: &Sprite<'_>) -> bool {
+        self.is_visible
+            && other.is_visible
+            && self
+                .bounding_box()
+                .intersection(&other.bounding_box())
+                .points()
+                .any(|point| self.pixel_at(point).is_some() && other.pixel_at(point).is_some())
+    }
+
+    /// Whether any opaque pixel of this sprite lies within `area` (for example, a wall or goal).
+    #[must_use]
+    pub fn overlaps(&self, area: &Rectangle) -> bool {
+        self.is_visible
+            && self
+                .bounding_box()
+                .intersection(area)
+                .points()
+                .any(|point| self.pixel_at(point).is_some())
+    }
+
+    /// Iterate over the panel coordinates of this sprite's opaque pixels.
+    pub fn opaque_points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.bounding_box()
+            .points()
+            .filter(|&point| self.pixel_at(point).is_some())
+    }
+}
+
+impl Layer for Sprite<'_> {
+    fn z(&self) -> i8 {
+        self.z
+    }
+
+    fn pixel_at(&self, point: Point) -> Option<RGB8> {
+        if !self.is_visible {
+            return None;
+        }
+        let width = self.size.width as i32;
+        let height = self.size.height as i32;
+        let mut x_offset = point.x - self.position.x;
+        let mut y_offset = point.y - self.position.y;
+        if x_offset < 0 || y_offset < 0 || x_offset >= width || y_offset >= height {
+            return None;
+        }
+        if self.is_flipped_h {
+            x_offset = width - 1 - x_offset;
+        }
+        if self.is_flipped_v {
+            y_offset = height - 1 - y_offset;
+        }
+        self.pixels[(y_offset * width + x_offset) as usize]
+    }
+}
+
+impl Drawable for Sprite<'_> {
+    type Color = Rgb888;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> core::result::Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        target.draw_iter(self.bounding_box().points().filter_map(|point| {
+            self.pixel_at(point)
+                .map(|color| Pixel(point, color.to_rgb888()))
+        }))
+    }
+}
+
+/// Sentinel tile index for an empty (transparent) map cell.
+pub const EMPTY_TILE: u8 = u8::MAX;
+
+/// A scrollable grid of tiles that can be larger than the panel (with example).
+///
+/// `TW`×`TH` is the tile size in pixels and `MAP_W`×`MAP_H` is the map size in tiles. Each
+/// cell holds an index into the tile set, or [`EMPTY_TILE`]. The scroll offset is the world
+/// pixel shown at the panel's top-left corner.
+///
+/// # Example: A side-scrolling ground strip
+///
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// # #[panic_handler]
+/// # fn panic(_: &core::panic::PanicInfo) -> ! { loop {} }
+/// use device_envoy::led2d::Frame2d;
+/// use device_envoy::led2d::sprite::{EMPTY_TILE, Sprite, SpriteImage, TileMap, render_layers};
+/// use embedded_graphics::prelude::Point;
+/// use smart_leds::colors;
+///
+/// const GROUND: SpriteImage<2, 2> = SpriteImage::filled(colors::GREEN);
+/// const TILES: [SpriteImage<2, 2>; 1] = [GROUND];
+/// const E: u8 = EMPTY_TILE;
+/// const PLAYER: SpriteImage<1, 2> = SpriteImage::filled(colors::RED);
+///
+/// # fn example() {
+/// let mut level = TileMap::new(
+///     &TILES,
+///     [
+///         [E, E, E, E, E, E, E, E, E, E],
+///         [E, E, E, E, E, 0, E, E, E, E],
+///         [E, E, E, E, E, 0, E, E, E, E],
+///         [0, 0, 0, 0, 0, 0, 0, 0, 0, 0],
+///     ],
+/// );
+/// let player = Sprite::new(&PLAYER, Point::new(2, 4)).with_z(1);
+///
+/// level.scroll_by(1, 0);
+/// let blocked = level.collides_with_sprite(&player, |tile| tile == 0);
+///
+/// let mut frame = Frame2d::<12, 8>::new();
+/// render_layers(&mut frame, &[&level, &player]);
+/// # let _ = blocked;
+/// # }
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct TileMap<'a, const TW: usize, const TH: usize, const MAP_W: usize, const MAP_H: usize> {
+    tiles: &'a [SpriteImage<TW, TH>],
+    map: [[u8; MAP_W]; MAP_H],
+    scroll: Point,
+    z: i8,
+    is_wrapping: bool,
+}
+
+impl<'a, const TW: usize, const TH: usize, const MAP_W: usize, const MAP_H: usize>
+    TileMap<'a, TW, TH, MAP_W, MAP_H>
+{
+    /// Width of the whole map in pixels.
+    pub const PIXEL_WIDTH: usize = TW * MAP_W;
+    /// Height of the whole map in pixels.
+    pub const PIXEL_HEIGHT: usize = TH * MAP_H;
+
+    /// Create a tile map with scroll `(0, 0)`, `z = 0`, and no wrapping.
+    #[must_use]
+    pub const fn new(tiles: &'a [SpriteImage<TW, TH>], map: [[u8; MAP_W]; MAP_H]) -> Self {
+        assert!(TW > 0 && TH > 0, "tile size must be non-zero");
+        Self {
+            tiles,
+            map,
+            scroll: Point::new(0, 0),
+            z: 0,
+            is_wrapping: false,
+        }
+    }
+
+    /// Return this tile map with the given z-order.
+    #[must_use]
+    pub const fn with_z(mut self, z: i8) -> Self {
+        self.z = z;
+        self
+    }
+
+    /// Return this tile map with wrapping enabled or disabled.
+    ///
+    /// When wrapping, the map repeats endlessly in both directions (useful for scrolling
+    /// backgrounds). Otherwise, everything outside the map is transparent.
+    #[must_use]
+    pub const fn with_wrap(mut self, wrap: bool) -> Self {
+        self.is_wrapping = wrap;
+        self
+    }
+
+    /// The world pixel shown at the panel's top-left corner.
+    #[must_use]
+    pub const fn scroll(&self) -> Point {
+        self.scroll
+    }
+
+    /// Set the world pixel shown at the panel's top-left corner.
+    pub const fn set_scroll(&mut self, scroll: Point) {
+        self.scroll = scroll;
+    }
+
+    /// Scroll the view by `(dx, dy)` pixels.
+    pub const fn scroll_by(&mut self, dx: i32, dy: i32) {
+        self.scroll = Point::new(self.scroll.x + dx, self.scroll.y + dy);
+    }
+
+    /// Set the z-order. Higher values are drawn on top.
+    pub const fn set_z(&mut self, z: i8) {
+        self.z = z;
+    }
+
+    /// The tile index at map cell `(column, row)`.
+    #[must_use]
+    pub const fn tile(&self, column: usize, row: usize) -> u8 {
+        self.map[row][column]
+    }
+
+    /// Replace the tile index at map cell `(column, row)`.
+    pub const fn set_tile(&mut self, column: usize, row: usize, tile: u8) {
+        self.map[row][column] = tile;
+    }
+
+    /// The map cell `(column, row)` under a panel coordinate, if it is inside the map.
+    #[must_use]
+    pub fn cell_at(&self, point: Point) -> Option<(usize, usize)> {
+        self.world_pixel(point)
+            .map(|(world_x, world_y)| (world_x / TW, world_y / TH))
+    }
+
+    /// The non-empty tile index under a panel coordinate.
+    #[must_use]
+    pub fn tile_at(&self, point: Point) -> Option<u8> {
+        self.cell_at(point)
+            .map(|(column, row)| self.map[row][column])
+            .filter(|&tile| tile != EMPTY_TILE)
+    }
+
+    /// Whether any opaque pixel of `sprite` lies on an opaque pixel of a tile for which
+    /// `is_solid` returns `true`.
+    #[must_use]
+    pub fn collides_with_sprite(&self, sprite: &Sprite<'_>, is_solid: impl Fn(u8) -> bool) -> bool {
+        sprite.opaque_points().any(|point| {
+            self.tile_at(point).is_some_and(&is_solid) && self.pixel_at(point).is_some()
+        })
+    }
+
+    /// Convert a panel coordinate to a world pixel, applying scroll and wrapping.
+    fn world_pixel(&self, point: Point) -> Option<(usize, usize)> {
+        let world_x = i64::from(point.x) + i64::from(self.scroll.x);
+        let world_y = i64::from(point.y) + i64::from(self.scroll.y);
+        let pixel_width = Self::PIXEL_WIDTH as i64;
+        let pixel_height = Self::PIXEL_HEIGHT as i64;
+        if pixel_width == 0 || pixel_height == 0 {
+            return None;
+        }
+        if self.is_wrapping {
+            return Some((
+                world_x.rem_euclid(pixel_width) as usize,
+                world_y.rem_euclid(pixel_height) as usize,
+            ));
+        }
+        if world_x < 0 || world_y < 0 || world_x >= pixel_width || world_y >= pixel_height {
+            return None;
+        }
+        Some((world_x as usize, world_y as usize))
+    }
+}
+
+impl<const TW: usize, const TH: usize, const MAP_W: usize, const MAP_H: usize> Layer
+    for TileMap<'_, TW, TH, MAP_W, MAP_H>
+{
+    fn z(&self) -> i8 {
+        self.z
+    }
+
+    fn pixel_at(&self, point: Point) -> Option<RGB8> {
+        let (world_x, world_y) = self.world_pixel(point)?;
+        let tile = self.map[world_y / TH][world_x / TW];
+        self.tiles
+            .get(tile as usize)
+            .and_then(|image| image.0[world_y % TH][world_x % TW])
+    }
+}
+
+impl<const TW: usize, const TH: usize, const MAP_W: usize, const MAP_H: usize> Drawable
+    for TileMap<'_, TW, TH, MAP_W, MAP_H>
+{
+    type Color = Rgb888;
+    type Output = ();
+
+    fn draw<D>(&self, target: &mut D) -> core::result::Result<Self::Output, D::Error>
+    where
+        D: DrawTarget<Color = Self::Color>,
+    {
+        let bounding_box = target.bounding_box();
+        target.draw_iter(bounding_box.points().filter_map(|point| {
+            self.pixel_at(point)
+                .map(|color| Pixel(point, color.to_rgb888()))
+        }))
+    }
+}
@@ -395,6 +395,8 @@ use heapless::Vec;
 
 #[cfg(not(feature = "host"))]
 use crate::Result;
+#[cfg(not(feature = "host"))]
+use crate::led2d::compressed::CompressedPlayback;
 
 // ============================================================================
 // Submodules
@@ -407,6 +409,10 @@ pub mod led_strip_generated;
 /// See the [led_strip module documentation](mod@crate::led_strip) for usage examples.
 ///
 /// Frames deref to `[RGB8; N]`, so you can mutate pixels directly before passing them to the generated strip's `write_frame` method.
+///
+/// `const` methods transform pixel content: [`shift`](Self::shift), [`scroll`](Self::scroll)
+/// (wrapping), [`reverse`](Self::reverse), [`blit`](Self::blit), [`blend`](Self::blend),
+/// [`saturating_add`](Self::saturating_add), and [`fade`](Self::fade).
 #[derive(Clone, Copy, Debug)]
 pub struct Frame1d<const N: usize>(pub [RGB8; N]);
 
@@ -429,6 +435,138 @@ impl<const N: usize> Frame1d<N> {
     pub const fn filled(color: RGB8) -> Self {
         Self([color; N])
     }
+
+    /// Return this frame with pixels moved `offset` positions toward the end of the strip
+    /// (negative moves toward the start). Vacated pixels are set to `fill`.
+    #[must_use]
+    pub const fn shift(self, offset: i32, fill: RGB8) -> Self {
+        let mut pixels = [fill; N];
+        let mut index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while index < N {
+            let source_index = index as i64 - offset as i64;
+            if source_index >= 0 && source_index < N as i64 {
+                pixels[index] = self.0[source_index as usize];
+            }
+            index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this frame with pixels moved `offset` positions toward the end of the strip
+    /// (negative moves toward the start), wrapping around at the ends.
+    #[must_use]
+    pub const fn scroll(self, offset: i32) -> Self {
+        let mut pixels = self.0;
+        let mut index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while index < N {
+            let source_index = (index as i64 - offset as i64).rem_euclid(N as i64);
+            pixels[index] = self.0[source_index as usize];
+            index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this frame with the pixel order reversed.
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        let mut pixels = self.0;
+        let mut index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while index < N {
+            pixels[index] = self.0[N - 1 - index];
+            index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Copy `len` pixels starting at `source_start` in `source` into this frame at
+    /// `dest_start`. Pixels that fall outside either frame are skipped.
+    pub const fn blit<const M: usize>(
+        &mut self,
+        source: &Frame1d<M>,
+        source_start: usize,
+        len: usize,
+        dest_start: usize,
+    ) {
+        let mut offset = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while offset < len && source_start + offset < M && dest_start + offset < N {
+            self.0[dest_start + offset] = source.0[source_start + offset];
+            offset += 1;
+        }
+    }
+
+    /// Return a blend of this frame and `other`: `alpha = 0` keeps this frame,
+    /// `alpha = 255` gives `other`.
+    #[must_use]
+    pub const fn blend(self, other: Self, alpha: u8) -> Self {
+        let mut pixels = self.0;
+        let mut index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while index < N {
+            pixels[index] = blend_rgb(self.0[index], other.0[index], alpha);
+            index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return the per-channel sum of this frame and `other`, saturating at 255.
+    #[must_use]
+    pub const fn saturating_add(self, other: Self) -> Self {
+        let mut pixels = self.0;
+        let mut index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while index < N {
+            pixels[index] = saturating_add_rgb(self.0[index], other.0[index]);
+            index += 1;
+        }
+        Self(pixels)
+    }
+
+    /// Return this frame faded toward black: `level = 255` is unchanged, `0` is black.
+    #[must_use]
+    pub const fn fade(self, level: u8) -> Self {
+        let mut pixels = self.0;
+        let mut index = 0;
+        // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+        while index < N {
+            pixels[index] = scale_rgb(self.0[index], level);
+            index += 1;
+        }
+        Self(pixels)
+    }
+}
+
+/// Per-channel `(from * (255 - alpha) + to * alpha) / 255`, rounded.
+#[must_use]
+pub(crate) const fn blend_rgb(from: RGB8, to: RGB8, alpha: u8) -> RGB8 {
+    const fn blend_channel(from: u8, to: u8, alpha: u8) -> u8 {
+        let weighted = from as u32 * (255 - alpha as u32) + to as u32 * alpha as u32;
+        ((weighted + 127) / 255) as u8
+    }
+    RGB8::new(
+        blend_channel(from.r, to.r, alpha),
+        blend_channel(from.g, to.g, alpha),
+        blend_channel(from.b, to.b, alpha),
+    )
+}
+
+/// Per-channel sum, saturating at 255.
+#[must_use]
+pub(crate) const fn saturating_add_rgb(left: RGB8, right: RGB8) -> RGB8 {
+    RGB8::new(
+        left.r.saturating_add(right.r),
+        left.g.saturating_add(right.g),
+        left.b.saturating_add(right.b),
+    )
+}
+
+/// Per-channel `color * level / 255`, rounded.
+#[must_use]
+pub(crate) const fn scale_rgb(color: RGB8, level: u8) -> RGB8 {
+    blend_rgb(RGB8::new(0, 0, 0), color, level)
 }
 
 impl<const N: usize> Deref for Frame1d<N> {
@@ -564,6 +702,7 @@ pub type LedStripCommandSignal<const N: usize, const MAX_FRAMES: usize> =
 pub enum Command<const N: usize, const MAX_FRAMES: usize> {
     DisplayStatic(Frame1d<N>),
     Animate(Vec<(Frame1d<N>, Duration), MAX_FRAMES>),
+    AnimateCompressed(CompressedPlayback<N>),
 }
 
 /// Static used to construct LED strip instances with animation support.
@@ -590,6 +729,11 @@ impl<const N: usize, const MAX_FRAMES: usize> LedStripStatic<N, MAX_FRAMES> {
     }
 }
 
+// On the host, the recording strip from `preview` stands in for the hardware one.
+#[cfg(feature = "host")]
+#[doc(hidden)]
+pub use crate::preview::LedStrip;
+
 // Public so macro-generated types can deref to it; hidden from docs.
 #[cfg(not(feature = "host"))]
 #[doc(hidden)]
@@ -673,6 +817,12 @@ impl<const N: usize, const MAX_FRAMES: usize> LedStrip<N, MAX_FRAMES> {
         self.command_signal.signal(Command::Animate(sequence));
         Ok(())
     }
+
+    pub(crate) fn animate_compressed(&self, playback: CompressedPlayback<N>) -> Result<()> {
+        self.command_signal
+            .signal(Command::AnimateCompressed(playback));
+        Ok(())
+    }
 }
 
 #[cfg(not(feature = "host"))]
@@ -707,6 +857,15 @@ where
                     command =
                         run_frame_animation(&mut driver, frames, command_signal, combo_table).await;
                 }
+                Command::AnimateCompressed(playback) => {
+                    command = run_compressed_animation(
+                        &mut driver,
+                        playback,
+                        command_signal,
+                        combo_table,
+                    )
+                    .await;
+                }
             }
         }
     }
@@ -742,6 +901,42 @@ where
     }
 }
 
+#[cfg(not(feature = "host"))]
+async fn run_compressed_animation<
+    PIO,
+    const SM: usize,
+    const N: usize,
+    const MAX_FRAMES: usize,
+    ORDER,
+>(
+    driver: &mut PioWs2812<'static, PIO, SM, N, ORDER>,
+    mut playback: CompressedPlayback<N>,
+    command_signal: &'static LedStripCommandSignal<N, MAX_FRAMES>,
+    combo_table: &'static [u8; 256],
+) -> Command<N, MAX_FRAMES>
+where
+    PIO: Instance,
+    ORDER: embassy_rp::pio_programs::ws2812::RgbColorOrder,
+{
+    // Delta frames update the previous uncorrected frame, so keep it separate from
+    // the corrected copy sent to the driver.
+    let mut frame = Frame1d::<N>::new();
+    loop {
+        let duration = playback.next_frame(&mut frame);
+        let mut corrected_frame = frame;
+        apply_correction(&mut corrected_frame, combo_table);
+        driver.write(&corrected_frame).await;
+
+        match select(command_signal.wait(), Timer::after(duration)).await {
+            Either::First(new_command) => {
+                command_signal.reset();
+                return new_command;
+            }
+            Either::Second(()) => continue,
+        }
+    }
+}
+
 #[cfg(not(feature = "host"))]
 fn apply_correction<const N: usize>(frame: &mut Frame1d<N>, combo_table: &[u8; 256]) {
     frame.iter_mut().for_each(|pixel| {
@@ -38,11 +38,16 @@ pub(crate) mod bit_matrix_led4;
 #[doc(hidden)]
 pub mod pio_irqs;
 #[cfg(feature = "host")]
+pub mod preview;
+#[cfg(feature = "host")]
 /// Utilities for converting frames to PNG images (host testing only).
 pub mod to_png;
+// On the host, for unit tests and for rendering sequences with `AudioRenderer`.
+#[cfg(any(target_os = "none", feature = "host"))]
+pub mod audio_player;
 // Embedded-only in normal builds, but compiled for host unit tests.
 #[cfg(any(target_os = "none", all(test, feature = "host")))]
-pub mod audio_player;
+pub mod audio_recorder;
 #[cfg(target_os = "none")]
 pub mod button;
 #[cfg(target_os = "none")]
@@ -64,9 +69,14 @@ pub mod led4;
 pub mod led_strip;
 #[cfg(target_os = "none")]
 pub mod rfid;
-#[cfg(target_os = "none")]
+// Embedded-only in normal builds, but compiled for host unit tests.
+#[cfg(any(target_os = "none", all(test, feature = "host")))]
 pub mod servo;
-#[cfg(target_os = "none")]
+// Embedded-only in normal builds, but compiled for host unit tests.
+#[cfg(any(target_os = "none", all(test, feature = "host")))]
+pub mod servo_bus;
+// Embedded-only in normal builds, but compiled for host unit tests.
+#[cfg(any(target_os = "none", all(test, feature = "host")))]
 pub mod servo_player;
 #[cfg(target_os = "none")]
 pub(crate) mod time_sync;
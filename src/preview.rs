@@ -0,0 +1,569 @@
+#![cfg(feature = "host")]
+//! Host-side simulation of LED strips and panels, with previews exported as APNG or GIF.
+//!
+//! On the host, [`LedStrip`] stands in for the strip behind the types generated by
+//! `led_strip!` and `led2d!`, so [`Led2d`] panels and other code written against them run
+//! unchanged. Instead of driving hardware, it records every `write_frame`, `animate`, and
+//! `write_text` call on a [`Timeline`] stamped with a simulated clock. [`Led2dSimulator`]
+//! stands in for a generated panel type, adding its font and `write_text`. Call
+//! [`advance`](LedStrip::advance) wherever the program would wait (for example,
+//! `Timer::after(...)`).
+//!
+//! A timeline can be flattened into the frames a viewer would see with
+//! [`Timeline::playback`] (handy for golden tests) or exported as an animation with the
+//! physical [`LedLayout`] drawn to scale: each LED is a dot at its `(x, y)` cell, unlit LEDs
+//! are dim gray, and cells without an LED (sparse layouts) stay black.
+//!
+//! ```rust,no_run
+//! use device_envoy::led2d::{Frame2d, Led2dFont, layout::LedLayout};
+//! use device_envoy::led_strip::colors;
+//! use device_envoy::preview::{Led2dSimulator, LedStrip};
+//! use embassy_time::Duration;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! const LED_LAYOUT: LedLayout<48, 12, 4> = LedLayout::serpentine_column_major();
+//! static LED_STRIP: LedStrip<48, 16> = LedStrip::new();
+//! let led12x4 = Led2dSimulator::new(&LED_STRIP, &LED_LAYOUT, Led2dFont::Font3x4Trim);
+//!
+//! embassy_futures::block_on(led12x4.write_text("Rust", &[colors::CYAN]))?;
+//! led12x4.advance(Duration::from_secs(1));
+//! led12x4.animate([
+//!     (Frame2d::filled(colors::RED), Duration::from_millis(250)),
+//!     (Frame2d::filled(colors::BLUE), Duration::from_millis(250)),
+//! ])?;
+//! led12x4.advance(Duration::from_secs(2));
+//!
+//! led12x4.write_apng("target/preview/led12x4.png", 400)?;
+//! led12x4.write_gif("target/preview/led12x4.gif", 400)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use core::borrow::Borrow;
+use std::error::Error;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use embassy_time::Duration;
+use embedded_graphics::mono_font::MonoFont;
+use smart_leds::RGB8;
+
+use crate::Result;
+use crate::led_strip::Frame1d;
+use crate::led2d::compressed::CompressedPlayback;
+use crate::led2d::{
+    CompressedAnimation, Frame2d, Led2d, Led2dFont, LedLayout, render_text_to_frame,
+};
+use crate::to_png::{
+    PREVIEW_INVERSE_GAMMA, create_parent_dir, leds_pixels, select_cell_size, write_apng_pixels,
+};
+
+/// How long a final `write_frame` is shown when the clock was not advanced past it.
+const FINAL_FRAME_HOLD: Duration = Duration::from_secs(1);
+
+/// Preview color for LEDs that are off, so the physical layout stays visible.
+const UNLIT_PREVIEW_COLOR: RGB8 = RGB8::new(40, 40, 40);
+
+/// A call recorded by a simulator.
+#[derive(Clone, Debug)]
+pub enum Command<const N: usize> {
+    /// `write_frame` with the frame in strip (wiring) order.
+    WriteFrame(Frame1d<N>),
+    /// `write_text` with its text and the rendered frame in strip order.
+    WriteText {
+        /// The text that was written.
+        text: String,
+        /// The rendered frame in strip order.
+        frame: Frame1d<N>,
+    },
+    /// `animate` (or `animate_compressed`) with its looping frames in strip order.
+    Animate(Vec<(Frame1d<N>, Duration)>),
+}
+
+/// A [`Command`] and the simulated time at which it was issued.
+#[derive(Clone, Debug)]
+pub struct TimelineEvent<const N: usize> {
+    /// Simulated time since the simulator was created.
+    pub at: Duration,
+    /// The recorded call.
+    pub command: Command<N>,
+}
+
+/// Every call recorded by a simulator, plus the simulated clock.
+///
+/// See the [module documentation](self) for usage.
+#[derive(Clone, Debug, Default)]
+pub struct Timeline<const N: usize> {
+    now: Duration,
+    events: Vec<TimelineEvent<N>>,
+}
+
+impl<const N: usize> Timeline<N> {
+    /// Create an empty timeline at time zero.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            now: Duration::from_ticks(0),
+            events: Vec::new(),
+        }
+    }
+
+    /// The current simulated time.
+    #[must_use]
+    pub const fn now(&self) -> Duration {
+        self.now
+    }
+
+    /// The recorded calls, in the order they were made.
+    #[must_use]
+    pub fn events(&self) -> &[TimelineEvent<N>] {
+        &self.events
+    }
+
+    /// Record `command` at the current simulated time.
+    pub fn record(&mut self, command: Command<N>) {
+        if let Command::Animate(frames) = &command {
+            assert!(!frames.is_empty(), "animation must have at least one frame");
+            for (_, duration) in frames {
+                assert!(
+                    duration.as_micros() > 0,
+                    "animation frame duration must be positive"
+                );
+            }
+        }
+        self.events.push(TimelineEvent {
+            at: self.now,
+            command,
+        });
+    }
+
+    /// Move the simulated clock forward.
+    pub fn advance(&mut self, duration: Duration) {
+        self.now += duration;
+    }
+
+    /// Flatten the timeline into what the LEDs show: each frame (in strip order) and how long
+    /// it stays lit.
+    ///
+    /// The LEDs are dark until the first call. Each call lasts until the next one; animations
+    /// loop (cutting the last frame short). The final call lasts until the current simulated
+    /// time, or if the clock was not advanced past it, for one animation loop or
+    /// one second for a still frame.
+    #[must_use]
+    pub fn playback(&self) -> Vec<(Frame1d<N>, Duration)> {
+        let mut playback = Vec::new();
+        let Some(first) = self.events.first() else {
+            return playback;
+        };
+        if first.at > Duration::from_ticks(0) {
+            playback.push((Frame1d::new(), first.at));
+        }
+
+        for (index, event) in self.events.iter().enumerate() {
+            let end = match self.events.get(index + 1) {
+                Some(next) => next.at,
+                None if self.now > event.at => self.now,
+                None => event.at + natural_length(&event.command),
+            };
+            let mut remaining = end - event.at;
+            match &event.command {
+                Command::WriteFrame(frame) | Command::WriteText { frame, .. } => {
+                    if remaining > Duration::from_ticks(0) {
+                        playback.push((*frame, remaining));
+                    }
+                }
+                Command::Animate(frames) => 'animation: loop {
+                    for &(frame, duration) in frames {
+                        if remaining == Duration::from_ticks(0) {
+                            break 'animation;
+                        }
+                        let shown = duration.min(remaining);
+                        playback.push((frame, shown));
+                        remaining -= shown;
+                    }
+                },
+            }
+        }
+        playback
+    }
+
+    /// Export the playback as a looping APNG with the physical `led_layout` drawn to scale.
+    ///
+    /// `target_max_dimension` bounds the image's width and height in pixels.
+    pub fn write_apng<const W: usize, const H: usize>(
+        &self,
+        led_layout: &LedLayout<N, W, H>,
+        output_path: impl AsRef<Path>,
+        target_max_dimension: u32,
+    ) -> core::result::Result<(), Box<dyn Error>> {
+        let output_path = output_path.as_ref();
+        let images = self.render(led_layout, target_max_dimension)?;
+        let width = images.width;
+        let height = images.height;
+        let mut frames = Vec::with_capacity(images.frames.len());
+        for (pixels, duration) in images.frames {
+            // APNG delays are u16 fractions, so express them in milliseconds and split long holds.
+            let mut remaining_ms = duration.as_millis().max(1);
+            while remaining_ms > 0 {
+                let delay_ms = remaining_ms.min(u64::from(u16::MAX));
+                frames.push((pixels.clone(), delay_ms as u16, 1000));
+                remaining_ms -= delay_ms;
+            }
+        }
+        write_apng_pixels(output_path, width, height, &frames)
+    }
+
+    /// Export the playback as a looping GIF with the physical `led_layout` drawn to scale.
+    ///
+    /// `target_max_dimension` bounds the image's width and height in pixels. GIF stores
+    /// 256 colors per frame and centisecond delays, so prefer [`write_apng`](Self::write_apng)
+    /// for golden tests.
+    pub fn write_gif<const W: usize, const H: usize>(
+        &self,
+        led_layout: &LedLayout<N, W, H>,
+        output_path: impl AsRef<Path>,
+        target_max_dimension: u32,
+    ) -> core::result::Result<(), Box<dyn Error>> {
+        let output_path = output_path.as_ref();
+        let images = self.render(led_layout, target_max_dimension)?;
+        let width = u16::try_from(images.width)?;
+        let height = u16::try_from(images.height)?;
+        create_parent_dir(output_path)?;
+
+        let file = File::create(output_path)?;
+        let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &[])?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for (pixels, duration) in images.frames {
+            let srgb = linear16_to_srgb8(&pixels);
+            let mut remaining_cs = duration.as_millis().div_ceil(10).max(1);
+            while remaining_cs > 0 {
+                let delay_cs = remaining_cs.min(u64::from(u16::MAX));
+                let mut frame = gif::Frame::from_rgb_speed(width, height, &srgb, 10);
+                frame.delay = delay_cs as u16;
+                encoder.write_frame(&frame)?;
+                remaining_cs -= delay_cs;
+            }
+        }
+        println!("wrote GIF to {}", output_path.display());
+        Ok(())
+    }
+
+    /// Render the playback as 16-bit linear RGB images.
+    fn render<const W: usize, const H: usize>(
+        &self,
+        led_layout: &LedLayout<N, W, H>,
+        target_max_dimension: u32,
+    ) -> core::result::Result<RenderedFrames, Box<dyn Error>> {
+        let playback = self.playback();
+        if playback.is_empty() {
+            return Err("timeline has no recorded calls to preview".into());
+        }
+        let panel_width = W as u32;
+        let panel_height = H as u32;
+        let cell_size = select_cell_size(panel_width, panel_height, target_max_dimension);
+        let led_margin = (cell_size / 8).max(1);
+
+        let mut rendered = RenderedFrames {
+            width: 0,
+            height: 0,
+            frames: Vec::with_capacity(playback.len()),
+        };
+        for (frame, duration) in playback {
+            let leds =
+                led_layout
+                    .index_to_xy()
+                    .iter()
+                    .zip(frame.0)
+                    .map(|(&(x_index, y_index), color)| {
+                        let color = if color == RGB8::new(0, 0, 0) {
+                            UNLIT_PREVIEW_COLOR
+                        } else {
+                            color
+                        };
+                        (u32::from(x_index), u32::from(y_index), color)
+                    });
+            let (width, height, pixels) = leds_pixels(
+                panel_width,
+                panel_height,
+                leds,
+                cell_size,
+                led_margin,
+                PREVIEW_INVERSE_GAMMA,
+            );
+            rendered.width = width;
+            rendered.height = height;
+            rendered.frames.push((pixels, duration));
+        }
+        Ok(rendered)
+    }
+}
+
+struct RenderedFrames {
+    width: u32,
+    height: u32,
+    frames: Vec<(Vec<u8>, Duration)>,
+}
+
+fn natural_length<const N: usize>(command: &Command<N>) -> Duration {
+    match command {
+        Command::WriteFrame(_) | Command::WriteText { .. } => FINAL_FRAME_HOLD,
+        Command::Animate(frames) => frames
+            .iter()
+            .fold(Duration::from_ticks(0), |total, (_, duration)| {
+                total + *duration
+            }),
+    }
+}
+
+/// Convert big-endian 16-bit linear RGB to 8-bit sRGB-encoded RGB (GIF has no gamma chunk).
+fn linear16_to_srgb8(pixels: &[u8]) -> Vec<u8> {
+    pixels
+        .chunks_exact(2)
+        .map(|channel| {
+            let linear = f32::from(u16::from_be_bytes([channel[0], channel[1]])) / 65535.0;
+            (linear.powf(1.0 / PREVIEW_INVERSE_GAMMA) * 255.0).round() as u8
+        })
+        .collect()
+}
+
+/// Host stand-in for the LED strip behind types generated by `led_strip!` and `led2d!`.
+///
+/// Instead of driving hardware, it records every call on a [`Timeline`]. Panels
+/// ([`Led2d`] and [`Led2dSimulator`]) convert frames to strip order through their
+/// [`LedLayout`] exactly as on hardware before recording them. Like the hardware strip,
+/// `animate` holds at most `MAX_FRAMES` frames.
+///
+/// `new` is const, so a strip can live in a `static` for panels that need a
+/// `&'static LedStrip`. See the [module documentation](self) for usage.
+pub struct LedStrip<const N: usize, const MAX_FRAMES: usize> {
+    timeline: Mutex<Timeline<N>>,
+}
+
+impl<const N: usize, const MAX_FRAMES: usize> LedStrip<N, MAX_FRAMES> {
+    /// Number of LEDs in the strip.
+    pub const LEN: usize = N;
+    /// Maximum number of animation frames.
+    pub const MAX_FRAMES: usize = MAX_FRAMES;
+
+    /// Create a strip at simulated time zero.
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            timeline: Mutex::new(Timeline::new()),
+        }
+    }
+
+    /// Record a frame written to the strip.
+    pub fn write_frame(&self, frame: Frame1d<N>) -> Result<()> {
+        self.record(Command::WriteFrame(frame));
+        Ok(())
+    }
+
+    /// Record a looping animation.
+    pub fn animate<I>(&self, frames: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(Frame1d<N>, Duration)>,
+    {
+        if MAX_FRAMES == 0 {
+            return Err(crate::Error::AnimationDisabled(MAX_FRAMES));
+        }
+        let frames: Vec<_> = frames.into_iter().map(|frame| *frame.borrow()).collect();
+        assert!(
+            frames.len() <= MAX_FRAMES,
+            "animation sequence fits within MAX_FRAMES"
+        );
+        self.record(Command::Animate(frames));
+        Ok(())
+    }
+
+    /// Record one loop of a compressed animation, decoded in full.
+    pub(crate) fn animate_compressed(&self, playback: CompressedPlayback<N>) -> Result<()> {
+        self.record(Command::Animate(playback.into_frames()));
+        Ok(())
+    }
+
+    /// Record text rendered by a panel's `write_text`.
+    pub(crate) fn write_text(&self, text: &str, frame: Frame1d<N>) -> Result<()> {
+        self.record(Command::WriteText {
+            text: String::from(text),
+            frame,
+        });
+        Ok(())
+    }
+
+    /// Move the simulated clock forward, as the program would by waiting.
+    pub fn advance(&self, duration: Duration) {
+        self.lock().advance(duration);
+    }
+
+    /// A copy of the recorded timeline.
+    #[must_use]
+    pub fn timeline(&self) -> Timeline<N> {
+        self.lock().clone()
+    }
+
+    /// Export the recording as a looping APNG, with the strip drawn as a single row.
+    pub fn write_apng(
+        &self,
+        output_path: impl AsRef<Path>,
+        target_max_dimension: u32,
+    ) -> core::result::Result<(), Box<dyn Error>> {
+        self.lock().write_apng(
+            &LedLayout::<N, N, 1>::linear_h(),
+            output_path,
+            target_max_dimension,
+        )
+    }
+
+    /// Export the recording as a looping GIF, with the strip drawn as a single row.
+    pub fn write_gif(
+        &self,
+        output_path: impl AsRef<Path>,
+        target_max_dimension: u32,
+    ) -> core::result::Result<(), Box<dyn Error>> {
+        self.lock().write_gif(
+            &LedLayout::<N, N, 1>::linear_h(),
+            output_path,
+            target_max_dimension,
+        )
+    }
+
+    fn record(&self, command: Command<N>) {
+        self.lock().record(command);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Timeline<N>> {
+        // A panic while recording leaves the timeline consistent, so ignore poisoning.
+        self.timeline.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl<const N: usize, const MAX_FRAMES: usize> Default for LedStrip<N, MAX_FRAMES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Host stand-in for a type generated by `led2d!`.
+///
+/// Wraps a [`Led2d`] panel on a recording [`LedStrip`], so frames reach the strip in
+/// wiring order through the same conversion as on hardware, and adds the generated type's
+/// text methods. See the [module documentation](self) for usage.
+pub struct Led2dSimulator<const N: usize, const W: usize, const H: usize, const MAX_FRAMES: usize> {
+    led_strip: &'static LedStrip<N, MAX_FRAMES>,
+    led2d: Led2d<N, MAX_FRAMES>,
+    font: MonoFont<'static>,
+    font_variant: Led2dFont,
+}
+
+impl<const N: usize, const W: usize, const H: usize, const MAX_FRAMES: usize>
+    Led2dSimulator<N, W, H, MAX_FRAMES>
+{
+    /// Number of columns in the panel.
+    pub const WIDTH: usize = W;
+    /// Number of rows in the panel.
+    pub const HEIGHT: usize = H;
+    /// Total number of LEDs (WIDTH * HEIGHT, or fewer for a sparse layout).
+    pub const LEN: usize = N;
+    /// Maximum number of animation frames.
+    pub const MAX_FRAMES: usize = MAX_FRAMES;
+
+    /// Create a panel drawing into `led_strip` with the given layout and font.
+    #[must_use]
+    pub fn new(
+        led_strip: &'static LedStrip<N, MAX_FRAMES>,
+        led_layout: &LedLayout<N, W, H>,
+        font_variant: Led2dFont,
+    ) -> Self {
+        Self {
+            led_strip,
+            led2d: Led2d::new(led_strip, led_layout),
+            font: font_variant.to_font(),
+            font_variant,
+        }
+    }
+
+    /// The panel's LED layout.
+    #[must_use]
+    pub fn led_layout(&self) -> LedLayout<N, W, H> {
+        self.led2d.led_layout()
+    }
+
+    /// Record a frame written to the panel.
+    pub fn write_frame(&self, frame: Frame2d<W, H>) -> Result<()> {
+        self.led2d.write_frame(frame)
+    }
+
+    /// Record a looping animation.
+    pub fn animate<I>(&self, frames: I) -> Result<()>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<(Frame2d<W, H>, Duration)>,
+    {
+        self.led2d.animate(frames)
+    }
+
+    /// Record one loop of a compressed animation, decoded in full.
+    pub fn animate_compressed(&self, animation: &'static CompressedAnimation<W, H>) -> Result<()> {
+        self.led2d.animate_compressed(animation)
+    }
+
+    /// Render text into a frame using the configured font and spacing.
+    pub fn write_text_to_frame(
+        &self,
+        text: &str,
+        colors: &[RGB8],
+        frame: &mut Frame2d<W, H>,
+    ) -> Result<()> {
+        render_text_to_frame(
+            frame,
+            &self.font,
+            text,
+            colors,
+            self.font_variant.spacing_reduction(),
+        )
+    }
+
+    /// Render text and record it as displayed on the panel.
+    pub async fn write_text(&self, text: &str, colors: &[RGB8]) -> Result<()> {
+        let mut frame = Frame2d::<W, H>::new();
+        self.write_text_to_frame(text, colors, &mut frame)?;
+        self.led2d.write_text_frame(text, frame)
+    }
+
+    /// Move the simulated clock forward, as the program would by waiting.
+    pub fn advance(&self, duration: Duration) {
+        self.led_strip.advance(duration);
+    }
+
+    /// A copy of the recorded timeline.
+    #[must_use]
+    pub fn timeline(&self) -> Timeline<N> {
+        self.led_strip.timeline()
+    }
+
+    /// Export the recording as a looping APNG with the panel's layout drawn to scale.
+    pub fn write_apng(
+        &self,
+        output_path: impl AsRef<Path>,
+        target_max_dimension: u32,
+    ) -> core::result::Result<(), Box<dyn Error>> {
+        self.led_strip
+            .lock()
+            .write_apng(&self.led_layout(), output_path, target_max_dimension)
+    }
+
+    /// Export the recording as a looping GIF with the panel's layout drawn to scale.
+    pub fn write_gif(
+        &self,
+        output_path: impl AsRef<Path>,
+        target_max_dimension: u32,
+    ) -> core::result::Result<(), Box<dyn Error>> {
+        self.led_strip
+            .lock()
+            .write_gif(&self.led_layout(), output_path, target_max_dimension)
+    }
+}
@@ -4,9 +4,43 @@
 //! like the SG90. See [`Servo`] for usage examples.
 //!
 //! Use the [`servo!`] macro for a keyword-driven constructor with defaults.
+//!
+//! For continuous-rotation servos and hobby ESCs, where the pulse sets speed rather than
+//! angle, see [`SpeedServo`] and [`speed_servo!`].
+//!
+//! To drive more servos than the PWM slices allow, or servos on pins that share a slice
+//! channel, see [`PioServo`] and [`pio_servos!`].
+#![cfg_attr(all(test, feature = "host"), allow(dead_code))]
+
+#[cfg(target_os = "none")]
+mod calibration;
+mod pio_servos;
+#[cfg(target_os = "none")]
+mod speed;
+
+#[cfg(target_os = "none")]
+pub use calibration::{ServoCalibration, ServoJog, ServoJogInput};
+#[cfg(target_os = "none")]
+pub use pio_servos::{PIO_SERVOS_MAX, PioServo};
+#[cfg(target_os = "none")]
+pub use speed::{SPEED_NEUTRAL_US_DEFAULT, SPEED_RANGE_US_DEFAULT, SpeedServo};
+
+// Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub use paste;
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub use pio_servos::{PioServosStatic, pio_servos_configure, pio_servos_device_loop};
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub use speed::speed_servo_from_pin_slice;
 
+#[cfg(target_os = "none")]
 use defmt::info;
+#[cfg(target_os = "none")]
 use embassy_rp::clocks::clk_sys_freq;
+#[cfg(target_os = "none")]
 use embassy_rp::pwm::{Config, Pwm};
 
 const SERVO_PERIOD_US: u16 = 20_000; // 20 ms
@@ -41,15 +75,24 @@ pub const SERVO_MAX_US_DEFAULT: u16 = 2_500;
 /// plus `channel: A/B` or `odd`/`even` to override the inferred channel.
 ///
 /// See [`Servo`] for details and examples.
+#[cfg(target_os = "none")]
 #[macro_export]
 #[doc(hidden)]
 macro_rules! servo {
     ($($tt:tt)*) => { $crate::__servo_impl! { $($tt)* } };
 }
+#[cfg(target_os = "none")]
+#[doc(inline)]
+pub use crate::pio_servos;
+#[cfg(target_os = "none")]
+#[doc(inline)]
+pub use crate::speed_servo;
+#[cfg(target_os = "none")]
 #[doc(inline)]
 pub use servo;
 
 // Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __servo_impl {
@@ -541,6 +584,53 @@ macro_rules! __servo_impl {
 }
 
 // Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+/// A servo that [`servo_player!`](macro@crate::servo_player) can animate: a [`Servo`] on a
+/// PWM slice or a [`PioServo`] in a PIO bank.
+pub trait ServoOutput {
+    fn set_degrees(&mut self, degrees: u16);
+    fn set_millidegrees(&mut self, millidegrees: u32);
+    fn max_degrees(&self) -> u16;
+    fn hold(&mut self);
+    fn relax(&mut self);
+    fn is_enabled(&self) -> bool;
+    fn set_calibration(&mut self, calibration: ServoCalibration);
+}
+
+#[cfg(target_os = "none")]
+impl ServoOutput for Servo<'_> {
+    fn set_degrees(&mut self, degrees: u16) {
+        Servo::set_degrees(self, degrees);
+    }
+
+    fn set_millidegrees(&mut self, millidegrees: u32) {
+        Servo::set_millidegrees(self, millidegrees);
+    }
+
+    fn max_degrees(&self) -> u16 {
+        Servo::max_degrees(self)
+    }
+
+    fn hold(&mut self) {
+        Servo::hold(self);
+    }
+
+    fn relax(&mut self) {
+        Servo::relax(self);
+    }
+
+    fn is_enabled(&self) -> bool {
+        Servo::is_enabled(self)
+    }
+
+    fn set_calibration(&mut self, calibration: ServoCalibration) {
+        Servo::set_calibration(self, calibration);
+    }
+}
+
+// Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
 #[doc(hidden)]
 pub trait ServoPwmPin<S: embassy_rp::PeripheralType>: embassy_rp::PeripheralType {
     const IS_CHANNEL_A: bool;
@@ -548,6 +638,7 @@ pub trait ServoPwmPin<S: embassy_rp::PeripheralType>: embassy_rp::PeripheralType
 }
 
 // Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
 #[doc(hidden)]
 pub fn servo_from_pin_slice<'d, P, S>(
     pin: embassy_rp::Peri<'d, P>,
@@ -570,6 +661,7 @@ where
 
 macro_rules! servo_pin_map {
     ($pin:ident, $slice:ident, A) => {
+        #[cfg(target_os = "none")]
         impl ServoPwmPin<embassy_rp::peripherals::$slice> for embassy_rp::peripherals::$pin {
             const IS_CHANNEL_A: bool = true;
             fn new_pwm<'d>(
@@ -581,6 +673,7 @@ macro_rules! servo_pin_map {
         }
     };
     ($pin:ident, $slice:ident, B) => {
+        #[cfg(target_os = "none")]
         impl ServoPwmPin<embassy_rp::peripherals::$slice> for embassy_rp::peripherals::$pin {
             const IS_CHANNEL_A: bool = false;
             fn new_pwm<'d>(
@@ -693,29 +786,71 @@ servo_pin_map!(PIN_47, PWM_SLICE11, B);
 ///     servo.relax();                                  // Let the servo relax. It will re-enable on next set_degrees()
 /// }
 /// ```
+///
+/// # Calibration
+///
+/// The macro's `min_us` and `max_us` suit most servos, but real servos vary unit to unit.
+/// [`calibrate`](Self::calibrate) lets you jog a servo to its endpoints and center with a
+/// [`Button`](crate::button::Button) or IR remote, then saves the resulting
+/// [`ServoCalibration`] to a [`FlashBlock`](crate::flash_array::FlashBlock), so swapping a
+/// servo doesn't mean recompiling.
+///
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// use device_envoy::{
+///     Result,
+///     button::{Button, PressedTo},
+///     flash_array::FlashArray,
+///     servo,
+/// };
+/// # use core::panic::PanicInfo;
+/// # #[panic_handler]
+/// # fn panic(_info: &PanicInfo) -> ! { loop {} }
+/// async fn example(p: embassy_rp::Peripherals) -> Result<()> {
+///     let [mut calibration_flash_block] = FlashArray::<1>::new(p.FLASH)?;
+///     let mut button = Button::new(p.PIN_13, PressedTo::Ground);
+///     let mut servo = servo! {
+///         pin: p.PIN_11,
+///         slice: p.PWM_SLICE5,
+///     };
+///
+///     // Calibrate on first boot, or when the button is held at power-up.
+///     if button.is_pressed() || !servo.load_calibration(&mut calibration_flash_block)? {
+///         // Short press: jog. Long press: mark 0°, then max_degrees, then center.
+///         servo.calibrate(&mut button, &mut calibration_flash_block).await?;
+///     }
+///
+///     servo.set_degrees(90); // Uses the calibrated pulse widths
+///     Ok(())
+/// }
+/// ```
+#[cfg(target_os = "none")]
 pub struct Servo<'d> {
     pwm: Pwm<'d>,
     cfg: Config, // Store config to avoid recreating default (which resets divider)
     top: u16,
-    min_us: u16,
-    max_us: u16,
+    calibration: ServoCalibration,
     max_degrees: u16,
     channel: ServoChannel, // Track which channel (A or B) this servo uses
     state: ServoState,
 }
 
+#[cfg(target_os = "none")]
 #[derive(Debug, Clone, Copy)]
 enum ServoChannel {
     A,
     B,
 }
 
+#[cfg(target_os = "none")]
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 enum ServoState {
     Disabled,
     Enabled,
 }
 
+#[cfg(target_os = "none")]
 impl<'d> Servo<'d> {
     /// Default maximum rotation range in degrees (180°).
     pub const DEFAULT_MAX_DEGREES: u16 = 180;
@@ -783,18 +918,17 @@ impl<'d> Servo<'d> {
             pwm,
             cfg, // Store config to avoid losing divider on reconfiguration
             top,
-            min_us,
-            max_us,
+            calibration: ServoCalibration::centered(min_us, max_us),
             max_degrees,
             channel,
             state: ServoState::Enabled,
         };
-        let center_us = min_us + (max_us - min_us) / 2;
-        servo.set_pulse_us(center_us);
+        servo.set_pulse_us(servo.calibration.center_us());
         servo
     }
 
-    /// Set position in degrees 0..=max_degrees mapped into [min_us, max_us].
+    /// Set position in degrees 0..=max_degrees, mapped to a pulse width by the servo's
+    /// [`calibration`](Self::calibration).
     ///
     /// Automatically enables the servo if it was disabled.
     ///
@@ -802,11 +936,28 @@ impl<'d> Servo<'d> {
     pub fn set_degrees(&mut self, degrees: u16) {
         assert!((0..=self.max_degrees).contains(&degrees));
         self.ensure_enabled();
-        let us = self.min_us as u32
-            + (u32::from(degrees)) * (u32::from(self.max_us) - u32::from(self.min_us))
-                / u32::from(self.max_degrees);
+        let us = self
+            .calibration
+            .pulse_us(u32::from(degrees) * 1000, self.max_degrees);
         info!("Servo set_degrees({}) -> {}µs", degrees, us);
-        self.set_pulse_us(us as u16);
+        self.set_pulse_us(us);
+    }
+
+    /// Set position in thousandths of a degree, for smooth motion between whole degrees.
+    ///
+    /// Automatically enables the servo if it was disabled. Unlike
+    /// [`set_degrees`](Self::set_degrees) this does not log, because the servo player's motion
+    /// profiles call it every PWM frame.
+    pub(crate) fn set_millidegrees(&mut self, millidegrees: u32) {
+        assert!(millidegrees <= u32::from(self.max_degrees) * 1000);
+        self.ensure_enabled();
+        let us = self.calibration.pulse_us(millidegrees, self.max_degrees);
+        self.set_pulse_us(us);
+    }
+
+    /// Maximum angle in degrees, as configured at construction.
+    pub(crate) const fn max_degrees(&self) -> u16 {
+        self.max_degrees
     }
 
     /// Set raw pulse width in microseconds.
@@ -826,6 +977,11 @@ impl<'d> Servo<'d> {
         self.pwm.set_config(&self.cfg);
     }
 
+    /// Whether the servo is receiving control signals (not relaxed).
+    pub(crate) const fn is_enabled(&self) -> bool {
+        matches!(self.state, ServoState::Enabled)
+    }
+
     fn ensure_enabled(&mut self) {
         if self.state == ServoState::Enabled {
             return;
@@ -0,0 +1,312 @@
+//! Servo pulse-width calibration saved in flash, and an interactive routine to measure it.
+//!
+//! See [`ServoCalibration`] and [`Servo::calibrate`] for usage.
+
+use defmt::info;
+use serde::{Deserialize, Serialize};
+
+use super::{SERVO_PERIOD_US, Servo};
+use crate::Result;
+use crate::button::{Button, PressDuration};
+use crate::flash_array::FlashBlock;
+use crate::ir::IrMapping;
+
+/// How far each jog moves the servo during calibration (microseconds).
+const JOG_STEP_US: u16 = 10;
+
+/// Narrowest pulse width calibration will try (microseconds).
+const CALIBRATION_MIN_US: u16 = 200;
+
+/// Widest pulse width calibration will try (microseconds).
+const CALIBRATION_MAX_US: u16 = 2_800;
+
+/// How far below the midpoint the center step starts (microseconds), so that a single
+/// button, which only jogs upward there, can still trim to either side.
+const CENTER_START_BELOW_US: u16 = 100;
+
+/// Pulse widths measured for one particular servo.
+///
+/// `min_us` drives the servo to 0°, `max_us` to its `max_degrees`, and `center_us` to
+/// half of `max_degrees`. Angles in between are interpolated separately on each side of
+/// the center, so a horn that isn't centered at the nominal midpoint can be trimmed.
+///
+/// Measure a calibration with [`Servo::calibrate`], and load it with
+/// [`Servo::load_calibration`] or the generated servo player's
+/// [`load_calibration`](crate::servo_player::servo_player_generated::ServoPlayerGenerated::load_calibration).
+#[derive(Clone, Copy, Debug, defmt::Format, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServoCalibration {
+    min_us: u16,
+    center_us: u16,
+    max_us: u16,
+}
+
+impl ServoCalibration {
+    /// Create a calibration. Can be used in const contexts.
+    ///
+    /// # Panics
+    ///
+    /// Panics unless `min_us <= center_us <= max_us`, `min_us < max_us`, and `max_us` fits
+    /// in the 20 ms servo frame.
+    #[must_use]
+    pub const fn new(min_us: u16, center_us: u16, max_us: u16) -> Self {
+        let calibration = Self {
+            min_us,
+            center_us,
+            max_us,
+        };
+        assert!(
+            calibration.is_valid(),
+            "calibration needs min_us <= center_us <= max_us, min_us < max_us, and max_us < 20_000"
+        );
+        calibration
+    }
+
+    /// Create a calibration with the center halfway between `min_us` and `max_us`, as the
+    /// [`servo!`](macro@crate::servo) macro's `min_us` and `max_us` do.
+    #[must_use]
+    pub const fn centered(min_us: u16, max_us: u16) -> Self {
+        assert!(min_us < max_us, "min_us must be less than max_us");
+        Self::new(min_us, min_us + (max_us - min_us) / 2, max_us)
+    }
+
+    /// Pulse width for 0° (microseconds).
+    #[must_use]
+    pub const fn min_us(self) -> u16 {
+        self.min_us
+    }
+
+    /// Pulse width for half of `max_degrees` (microseconds).
+    #[must_use]
+    pub const fn center_us(self) -> u16 {
+        self.center_us
+    }
+
+    /// Pulse width for `max_degrees` (microseconds).
+    #[must_use]
+    pub const fn max_us(self) -> u16 {
+        self.max_us
+    }
+
+    // Also checks values loaded from flash, which bypass `new`.
+    const fn is_valid(self) -> bool {
+        self.min_us < self.max_us
+            && self.min_us <= self.center_us
+            && self.center_us <= self.max_us
+            && self.max_us < SERVO_PERIOD_US
+    }
+
+    /// Pulse width for `millidegrees` on a servo whose range is `max_degrees`.
+    pub(crate) const fn pulse_us(self, millidegrees: u32, max_degrees: u16) -> u16 {
+        let full_millidegrees = max_degrees as u64 * 1000;
+        let twice_millidegrees = 2 * millidegrees as u64;
+        let us = if twice_millidegrees <= full_millidegrees {
+            self.min_us as u64
+                + (self.center_us - self.min_us) as u64 * twice_millidegrees / full_millidegrees
+        } else {
+            self.center_us as u64
+                + (self.max_us - self.center_us) as u64 * (twice_millidegrees - full_millidegrees)
+                    / full_millidegrees
+        };
+        us as u16
+    }
+
+    /// Angle in thousandths of a degree for pulse width `us`: the inverse of
+    /// [`pulse_us`](Self::pulse_us), clamped to `0..=max_degrees`.
+    pub(crate) const fn millidegrees(self, us: u16, max_degrees: u16) -> u32 {
+        let half_millidegrees = max_degrees as u64 * 500;
+        let millidegrees = if us <= self.min_us {
+            0
+        } else if us >= self.max_us {
+            2 * half_millidegrees
+        } else if us <= self.center_us {
+            let span_us = (self.center_us - self.min_us) as u64;
+            ((us - self.min_us) as u64 * half_millidegrees + span_us / 2) / span_us
+        } else {
+            let span_us = (self.max_us - self.center_us) as u64;
+            half_millidegrees
+                + ((us - self.center_us) as u64 * half_millidegrees + span_us / 2) / span_us
+        };
+        millidegrees as u32
+    }
+
+    /// Load a calibration saved by [`save`](Self::save), ignoring one that isn't valid.
+    pub(crate) fn load(flash_block: &mut FlashBlock) -> Result<Option<Self>> {
+        Ok(flash_block
+            .load::<Self>()?
+            .filter(|calibration| calibration.is_valid()))
+    }
+
+    pub(crate) fn save(self, flash_block: &mut FlashBlock) -> Result<()> {
+        flash_block.save(&self)
+    }
+}
+
+/// One input during [`Servo::calibrate`].
+///
+/// Map IR remote buttons to these with an [`IrMapping<ServoJog, N>`](IrMapping).
+#[derive(Clone, Copy, Debug, defmt::Format, PartialEq, Eq)]
+pub enum ServoJog {
+    /// Shorten the pulse by one step, turning the servo toward 0°.
+    Decrease,
+    /// Lengthen the pulse by one step, turning the servo toward `max_degrees`.
+    Increase,
+    /// Accept the current pulse width and go on to the next calibration step.
+    Mark,
+}
+
+/// An input that can jog a servo during [`Servo::calibrate`].
+///
+/// Implemented for:
+///
+/// - [`Button`] — A short press jogs outward (toward the endpoint being measured, or
+///   upward while trimming the center). A long press marks.
+/// - [`IrMapping<ServoJog, N>`](IrMapping) — Each mapped remote button sends its
+///   [`ServoJog`].
+#[allow(private_bounds)]
+pub trait ServoJogInput: sealed::ServoJogInputSealed {}
+
+impl<T: ?Sized> ServoJogInput for T where T: sealed::ServoJogInputSealed {}
+
+mod sealed {
+    use super::ServoJog;
+
+    pub(crate) trait ServoJogInputSealed {
+        /// Wait for the next jog. `outward` is the direction the current step usually moves.
+        async fn wait_for_jog(&mut self, outward: ServoJog) -> ServoJog;
+    }
+}
+
+impl sealed::ServoJogInputSealed for Button<'_> {
+    async fn wait_for_jog(&mut self, outward: ServoJog) -> ServoJog {
+        match self.wait_for_press_duration().await {
+            PressDuration::Short => outward,
+            PressDuration::Long => ServoJog::Mark,
+        }
+    }
+}
+
+impl<const N: usize> sealed::ServoJogInputSealed for IrMapping<'_, ServoJog, N> {
+    async fn wait_for_jog(&mut self, _outward: ServoJog) -> ServoJog {
+        self.wait_for_press().await
+    }
+}
+
+impl Servo<'_> {
+    /// The pulse widths this servo currently maps angles to.
+    #[must_use]
+    pub const fn calibration(&self) -> ServoCalibration {
+        self.calibration
+    }
+
+    /// Map angles with `calibration` instead of the `min_us`/`max_us` given at construction.
+    ///
+    /// Takes effect on the next [`set_degrees`](Self::set_degrees).
+    pub fn set_calibration(&mut self, calibration: ServoCalibration) {
+        self.calibration = calibration;
+    }
+
+    /// Use the calibration saved in `flash_block`, if there is one.
+    ///
+    /// Returns `Ok(true)` if a saved calibration was loaded. Returns `Ok(false)` and keeps
+    /// the current calibration (normally the macro's `min_us`/`max_us`) if the block is
+    /// empty.
+    pub fn load_calibration(&mut self, flash_block: &mut FlashBlock) -> Result<bool> {
+        let Some(calibration) = ServoCalibration::load(flash_block)? else {
+            return Ok(false);
+        };
+        self.set_calibration(calibration);
+        Ok(true)
+    }
+
+    /// Save the current calibration to `flash_block`.
+    pub fn save_calibration(&self, flash_block: &mut FlashBlock) -> Result<()> {
+        self.calibration.save(flash_block)
+    }
+
+    /// Interactively measure this servo's pulse widths and save them to `flash_block`.
+    ///
+    /// Calibration runs in three steps, each ended with [`ServoJog::Mark`]:
+    ///
+    /// 1. **0° endpoint** — Starting from the current `min_us`, jog down until the servo
+    ///    reaches its end stop (or the lowest angle you want), then mark.
+    /// 2. **`max_degrees` endpoint** — Starting from the current `max_us`, jog up the same
+    ///    way, then mark.
+    /// 3. **Center** — Starting a little below the midpoint of the two endpoints, jog until
+    ///    the horn points where half of `max_degrees` should be, then mark.
+    ///
+    /// The servo then moves to its new center, and the calibration is saved and returned.
+    pub async fn calibrate(
+        &mut self,
+        jog_input: &mut impl ServoJogInput,
+        flash_block: &mut FlashBlock,
+    ) -> Result<ServoCalibration> {
+        self.ensure_enabled();
+        let current = self.calibration;
+
+        let min_us = self
+            .jog_to_mark(
+                jog_input,
+                current.min_us.max(CALIBRATION_MIN_US),
+                ServoJog::Decrease,
+                CALIBRATION_MIN_US,
+                CALIBRATION_MAX_US - JOG_STEP_US,
+            )
+            .await;
+        info!("Servo calibration: min_us = {}", min_us);
+
+        let max_us = self
+            .jog_to_mark(
+                jog_input,
+                current
+                    .max_us
+                    .clamp(min_us + JOG_STEP_US, CALIBRATION_MAX_US),
+                ServoJog::Increase,
+                min_us + JOG_STEP_US,
+                CALIBRATION_MAX_US,
+            )
+            .await;
+        info!("Servo calibration: max_us = {}", max_us);
+
+        let midpoint_us = min_us + (max_us - min_us) / 2;
+        let center_us = self
+            .jog_to_mark(
+                jog_input,
+                midpoint_us
+                    .saturating_sub(CENTER_START_BELOW_US)
+                    .max(min_us),
+                ServoJog::Increase,
+                min_us,
+                max_us,
+            )
+            .await;
+        info!("Servo calibration: center_us = {}", center_us);
+
+        let calibration = ServoCalibration::new(min_us, center_us, max_us);
+        self.set_calibration(calibration);
+        self.save_calibration(flash_block)?;
+        self.set_pulse_us(center_us);
+        Ok(calibration)
+    }
+
+    /// Jog the pulse width within `low_us..=high_us` until marked, and return it.
+    async fn jog_to_mark(
+        &mut self,
+        jog_input: &mut impl ServoJogInput,
+        start_us: u16,
+        outward: ServoJog,
+        low_us: u16,
+        high_us: u16,
+    ) -> u16 {
+        use sealed::ServoJogInputSealed as _;
+
+        let mut pulse_us = start_us;
+        loop {
+            self.set_pulse_us(pulse_us);
+            pulse_us = match jog_input.wait_for_jog(outward).await {
+                ServoJog::Decrease => pulse_us.saturating_sub(JOG_STEP_US).max(low_us),
+                ServoJog::Increase => (pulse_us + JOG_STEP_US).min(high_us),
+                ServoJog::Mark => return pulse_us,
+            };
+        }
+    }
+}
@@ -0,0 +1,724 @@
+//! Many servos on arbitrary GPIO pins, driven by one PIO state machine.
+//!
+//! See [`PioServo`] and [`pio_servos!`](macro@crate::servo::pio_servos) for usage.
+
+#[cfg(target_os = "none")]
+use core::sync::atomic::{AtomicU16, Ordering};
+
+#[cfg(target_os = "none")]
+use defmt::info;
+#[cfg(target_os = "none")]
+use embassy_rp::Peri;
+#[cfg(target_os = "none")]
+use embassy_rp::dma::Channel;
+#[cfg(target_os = "none")]
+use embassy_rp::gpio::Level;
+#[cfg(target_os = "none")]
+use embassy_rp::pio::{
+    Common, Config, Direction, FifoJoin, Instance, Pin, ShiftConfig, ShiftDirection, StateMachine,
+};
+#[cfg(target_os = "none")]
+use fixed::traits::ToFixed;
+
+use super::SERVO_PERIOD_US;
+#[cfg(target_os = "none")]
+use super::{ServoCalibration, ServoOutput};
+#[cfg(target_os = "none")]
+use crate::Result;
+#[cfg(target_os = "none")]
+use crate::flash_array::FlashBlock;
+
+#[cfg(all(test, feature = "host"))]
+mod host_tests;
+
+/// Most servos one bank can drive: the width of a PIO state machine's pin window.
+pub const PIO_SERVOS_MAX: usize = 32;
+
+/// PIO cycles per microsecond of pulse width.
+const PIO_CYCLES_PER_US: u32 = 10;
+
+/// PIO cycles in each (pin mask, delay) pair spent outside the delay loop.
+const PAIR_OVERHEAD_CYCLES: u32 = 5;
+
+/// Words in the longest frame: a (pin mask, delay) pair per edge plus the first pair.
+const FRAME_WORDS_MAX: usize = 2 * (PIO_SERVOS_MAX + 1);
+
+/// Create a bank of servos on arbitrary GPIO pins, all driven by one PIO state machine.
+///
+/// **Syntax:**
+///
+/// ```text
+/// pio_servos! {
+///     [<visibility>] <Name> {
+///         pins: [<pin_ident>, ...],
+///         pio: <pio_ident>,           // optional
+///         dma: <dma_ident>,           // optional
+///         min_us: <u16_expr>,         // optional
+///         max_us: <u16_expr>,         // optional
+///         max_degrees: <u16_expr>,    // optional
+///     }
+/// }
+/// ```
+///
+/// # Configuration
+///
+/// ## Required Fields
+///
+/// - `pins` — GPIO pins, one per servo, in any order. They needn't share (or avoid) PWM
+///   slices, but must lie within a 32-pin window (always true on the Pico's GPIO 0–29).
+///
+/// ## Optional Fields
+///
+/// - `pio` — PIO resource to use (default: `PIO0`)
+/// - `dma` — DMA channel that feeds the state machine (default: `DMA_CH0`)
+/// - `min_us`, `max_us`, `max_degrees` — As for [`servo!`](macro@crate::servo), applied
+///   to every servo in the bank (defaults: 500, 2500, 180)
+///
+/// # Generated Methods
+///
+/// - `new(<one argument per pin>, pio, dma, spawner) -> Result<[PioServo; N]>` — Claim
+///   the pins, start the state machine, and return one [`PioServo`] per pin, in `pins`
+///   order.
+///
+/// See [`PioServo`] for details and examples.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! pio_servos {
+    ($($tt:tt)*) => { $crate::__pio_servos_impl! { $($tt)* } };
+}
+
+// Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pio_servos_impl {
+    // Entry point - name without visibility defaults to private
+    (
+        $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__pio_servos_impl! {
+            @__fill_defaults
+            vis: pub(self),
+            name: $name,
+            pins: _UNSET_,
+            pio: PIO0,
+            dma: DMA_CH0,
+            min_us: $crate::servo::SERVO_MIN_US_DEFAULT,
+            max_us: $crate::servo::SERVO_MAX_US_DEFAULT,
+            max_degrees: $crate::servo::Servo::DEFAULT_MAX_DEGREES,
+            fields: [ $($fields)* ]
+        }
+    };
+
+    // Entry point - name with explicit visibility
+    (
+        $vis:vis $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__pio_servos_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pins: _UNSET_,
+            pio: PIO0,
+            dma: DMA_CH0,
+            min_us: $crate::servo::SERVO_MIN_US_DEFAULT,
+            max_us: $crate::servo::SERVO_MAX_US_DEFAULT,
+            max_degrees: $crate::servo::Servo::DEFAULT_MAX_DEGREES,
+            fields: [ $($fields)* ]
+        }
+    };
+
+    // Fill defaults: pins
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pins: $pins:tt,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ pins: [$($pin_value:ident),+ $(,)?] $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__pio_servos_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pins: [$($pin_value),+],
+            pio: $pio,
+            dma: $dma,
+            min_us: $min_us,
+            max_us: $max_us,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: pio
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pins: $pins:tt,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ pio: $pio_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__pio_servos_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pins: $pins,
+            pio: $pio_value,
+            dma: $dma,
+            min_us: $min_us,
+            max_us: $max_us,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: dma
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pins: $pins:tt,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ dma: $dma_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__pio_servos_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pins: $pins,
+            pio: $pio,
+            dma: $dma_value,
+            min_us: $min_us,
+            max_us: $max_us,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: min_us
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pins: $pins:tt,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ min_us: $min_us_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__pio_servos_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pins: $pins,
+            pio: $pio,
+            dma: $dma,
+            min_us: $min_us_value,
+            max_us: $max_us,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: max_us
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pins: $pins:tt,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ max_us: $max_us_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__pio_servos_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pins: $pins,
+            pio: $pio,
+            dma: $dma,
+            min_us: $min_us,
+            max_us: $max_us_value,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: max_degrees
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pins: $pins:tt,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ max_degrees: $max_degrees_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__pio_servos_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pins: $pins,
+            pio: $pio,
+            dma: $dma,
+            min_us: $min_us,
+            max_us: $max_us,
+            max_degrees: $max_degrees_value,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: terminate and build
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pins: _UNSET_,
+        pio: $pio:ident,
+        dma: $dma:ident,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("pio_servos! requires `pins: [...]`");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pins: [$($pin:ident),+],
+        pio: $pio:ident,
+        dma: $dma:ident,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ]
+    ) => {
+        $crate::servo::paste::paste! {
+            const [<$name:upper _SERVO_COUNT>]: usize = [$(stringify!($pin)),+].len();
+            static [<$name:upper _PIO_SERVOS_STATIC>]:
+                $crate::servo::PioServosStatic<[<$name:upper _SERVO_COUNT>]> =
+                $crate::servo::PioServosStatic::new_static();
+
+            #[allow(missing_docs)]
+            $vis struct $name;
+
+            #[allow(missing_docs)]
+            impl $name {
+                /// Number of servos in the bank.
+                pub const SERVO_COUNT: usize = [<$name:upper _SERVO_COUNT>];
+
+                /// Claim the pins, start the PIO state machine and its background task, and
+                /// return one `PioServo` per pin, in `pins` order.
+                ///
+                /// # Parameters
+                ///
+                /// - One GPIO pin per servo, in `pins` order
+                /// - `pio` — PIO resource that generates the pulses
+                /// - `dma` — DMA channel that feeds the PIO
+                /// - `spawner` — Task spawner for background operations
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    $( [<$pin:lower>]: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$pin>>, )+
+                    pio: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$pio>>,
+                    dma: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$dma>>,
+                    spawner: ::embassy_executor::Spawner,
+                ) -> $crate::Result<[$crate::servo::PioServo; [<$name:upper _SERVO_COUNT>]]> {
+                    let ::embassy_rp::pio::Pio { mut common, mut sm0, .. } = ::embassy_rp::pio::Pio::new(
+                        pio.into(),
+                        <::embassy_rp::peripherals::$pio as $crate::pio_irqs::PioIrqMap>::irqs(),
+                    );
+                    let pins = [$( common.make_pio_pin([<$pin:lower>].into()) ),+];
+                    let pin_bits = $crate::servo::pio_servos_configure(&mut common, &mut sm0, &pins);
+                    let token = [<$name:snake _pio_servos_task>](
+                        &[<$name:upper _PIO_SERVOS_STATIC>],
+                        sm0,
+                        dma.into(),
+                        pin_bits,
+                    );
+                    spawner.spawn(token)?;
+                    Ok([<$name:upper _PIO_SERVOS_STATIC>].servos($min_us, $max_us, $max_degrees))
+                }
+            }
+
+            #[::embassy_executor::task]
+            async fn [<$name:snake _pio_servos_task>](
+                pio_servos_static: &'static $crate::servo::PioServosStatic<[<$name:upper _SERVO_COUNT>]>,
+                state_machine: ::embassy_rp::pio::StateMachine<'static, ::embassy_rp::peripherals::$pio, 0>,
+                dma: ::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$dma>,
+                pin_bits: [u32; [<$name:upper _SERVO_COUNT>]],
+            ) -> ! {
+                $crate::servo::pio_servos_device_loop(pio_servos_static, state_machine, dma, pin_bits).await
+            }
+        }
+    };
+}
+
+// Public so macro-generated code can reference it; hidden from docs.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+/// Static resources for a [`pio_servos!`](macro@crate::servo::pio_servos) bank: the pulse
+/// width each servo's pin should carry, or 0 while relaxed.
+pub struct PioServosStatic<const N: usize> {
+    pulses_us: [AtomicU16; N],
+}
+
+#[cfg(target_os = "none")]
+impl<const N: usize> PioServosStatic<N> {
+    /// Create static resources for a servo bank.
+    #[must_use]
+    pub const fn new_static() -> Self {
+        assert!(N > 0, "pio_servos! needs at least one pin");
+        assert!(N <= PIO_SERVOS_MAX, "pio_servos! supports at most 32 pins");
+        Self {
+            pulses_us: [const { AtomicU16::new(0) }; N],
+        }
+    }
+
+    /// One handle per pin, each starting at its center position.
+    #[must_use]
+    pub fn servos(&'static self, min_us: u16, max_us: u16, max_degrees: u16) -> [PioServo; N] {
+        assert!(max_degrees > 0, "max_degrees must be positive");
+        let calibration = ServoCalibration::centered(min_us, max_us);
+        core::array::from_fn(|index| {
+            let mut pio_servo = PioServo {
+                pulse_us: &self.pulses_us[index],
+                calibration,
+                max_degrees,
+                held_pulse_us: calibration.center_us(),
+                is_enabled: false,
+            };
+            pio_servo.hold();
+            pio_servo
+        })
+    }
+}
+
+/// One servo in a bank created by [`pio_servos!`](macro@crate::servo::pio_servos).
+///
+/// `PioServo` has the same `set_degrees`/`hold`/`relax` API as [`Servo`](super::Servo), but
+/// instead of a PWM slice, one PIO state machine generates the pulses for every servo in
+/// the bank. Use it when servos would otherwise share a PWM slice and channel, when you need
+/// more servos than PWM slices, or just to pick pins freely.
+///
+/// To animate a `PioServo` in the background, generate a
+/// [`servo_player!`](macro@crate::servo_player) type with the `pio_servo` flag instead of
+/// a `pin`, and pass the `PioServo` to its `new`.
+///
+/// # Examples
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// use device_envoy::{Result, servo::pio_servos};
+/// use embassy_time::{Duration, Timer};
+/// # use core::panic::PanicInfo;
+/// # #[panic_handler]
+/// # fn panic(_info: &PanicInfo) -> ! { loop {} }
+///
+/// // PIN_2/PIN_18 and PIN_3/PIN_19 would each share a PWM slice and channel.
+/// pio_servos! {
+///     LegServos {
+///         pins: [PIN_2, PIN_3, PIN_18, PIN_19],
+///         pio: PIO1,
+///         dma: DMA_CH1,
+///     }
+/// }
+///
+/// async fn example(
+///     p: embassy_rp::Peripherals,
+///     spawner: embassy_executor::Spawner,
+/// ) -> Result<()> {
+///     let [mut front_left, mut front_right, mut back_left, mut back_right] = LegServos::new(
+///         p.PIN_2, p.PIN_3, p.PIN_18, p.PIN_19, p.PIO1, p.DMA_CH1, spawner,
+///     )?;
+///
+///     front_left.set_degrees(45);
+///     back_right.set_degrees(45);
+///     front_right.set_degrees(135);
+///     back_left.set_degrees(135);
+///     Timer::after(Duration::from_secs(1)).await;
+///
+///     front_left.relax();
+///     front_right.relax();
+///     back_left.relax();
+///     back_right.relax();
+///     Ok(())
+/// }
+/// ```
+#[cfg(target_os = "none")]
+pub struct PioServo {
+    pulse_us: &'static AtomicU16,
+    calibration: ServoCalibration,
+    max_degrees: u16,
+    held_pulse_us: u16,
+    is_enabled: bool,
+}
+
+#[cfg(target_os = "none")]
+impl PioServo {
+    /// Set position in degrees 0..=max_degrees, mapped to a pulse width by the servo's
+    /// [`calibration`](Self::calibration).
+    ///
+    /// Automatically enables the servo if it was relaxed.
+    ///
+    /// See the [`PioServo`] example for usage.
+    pub fn set_degrees(&mut self, degrees: u16) {
+        assert!((0..=self.max_degrees).contains(&degrees));
+        let us = self
+            .calibration
+            .pulse_us(u32::from(degrees) * 1000, self.max_degrees);
+        info!("PioServo set_degrees({}) -> {}µs", degrees, us);
+        self.set_pulse_us(us);
+        self.hold();
+    }
+
+    /// Set position in thousandths of a degree, without logging.
+    pub(crate) fn set_millidegrees(&mut self, millidegrees: u32) {
+        assert!(millidegrees <= u32::from(self.max_degrees) * 1000);
+        let us = self.calibration.pulse_us(millidegrees, self.max_degrees);
+        self.set_pulse_us(us);
+        self.hold();
+    }
+
+    /// Set raw pulse width in microseconds. Takes effect at the next 20 ms frame.
+    #[doc(hidden)]
+    pub fn set_pulse_us(&mut self, us: u16) {
+        assert!(
+            us > 0 && us < SERVO_PERIOD_US,
+            "pulse width must fit in the frame"
+        );
+        self.held_pulse_us = us;
+        if self.is_enabled {
+            self.pulse_us.store(us, Ordering::Relaxed);
+        }
+    }
+
+    /// Stop sending control signals to the servo.
+    ///
+    /// This allows the servo to relax and move freely, reducing power consumption
+    /// and mechanical stress.
+    ///
+    /// See the [`PioServo`] example for usage.
+    pub fn relax(&mut self) {
+        self.is_enabled = false;
+        self.pulse_us.store(0, Ordering::Relaxed);
+    }
+
+    /// Resume sending control signals to the servo.
+    ///
+    /// The servo will move back to its last commanded position.
+    pub fn hold(&mut self) {
+        self.is_enabled = true;
+        self.pulse_us.store(self.held_pulse_us, Ordering::Relaxed);
+    }
+
+    /// The pulse widths this servo currently maps angles to.
+    #[must_use]
+    pub const fn calibration(&self) -> ServoCalibration {
+        self.calibration
+    }
+
+    /// Map angles with `calibration` instead of the bank's `min_us`/`max_us`.
+    ///
+    /// Takes effect on the next [`set_degrees`](Self::set_degrees).
+    pub fn set_calibration(&mut self, calibration: ServoCalibration) {
+        self.calibration = calibration;
+    }
+
+    /// Use the calibration saved in `flash_block` by
+    /// [`Servo::calibrate`](super::Servo::calibrate), if there is one.
+    ///
+    /// Returns `Ok(true)` if a saved calibration was loaded. Returns `Ok(false)` and keeps
+    /// the current calibration if the block is empty.
+    pub fn load_calibration(&mut self, flash_block: &mut FlashBlock) -> Result<bool> {
+        let Some(calibration) = ServoCalibration::load(flash_block)? else {
+            return Ok(false);
+        };
+        self.set_calibration(calibration);
+        Ok(true)
+    }
+}
+
+#[cfg(target_os = "none")]
+impl ServoOutput for PioServo {
+    fn set_degrees(&mut self, degrees: u16) {
+        Self::set_degrees(self, degrees);
+    }
+
+    fn set_millidegrees(&mut self, millidegrees: u32) {
+        Self::set_millidegrees(self, millidegrees);
+    }
+
+    fn max_degrees(&self) -> u16 {
+        self.max_degrees
+    }
+
+    fn hold(&mut self) {
+        Self::hold(self);
+    }
+
+    fn relax(&mut self) {
+        Self::relax(self);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    fn set_calibration(&mut self, calibration: ServoCalibration) {
+        Self::set_calibration(self, calibration);
+    }
+}
+
+// Called by macro-generated code in downstream crates; must be public.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+/// Load the pulse program into `state_machine` with an OUT window covering `pins`, and
+/// return each pin's bit within that window.
+#[allow(unsafe_code)] // Required to set an OUT window wider than the bank's pins
+pub fn pio_servos_configure<PIO: Instance, const N: usize>(
+    common: &mut Common<'static, PIO>,
+    state_machine: &mut StateMachine<'static, PIO, 0>,
+    pins: &[Pin<'static, PIO>; N],
+) -> [u32; N] {
+    // Each pair of words sets every pin in the window from a mask, then waits
+    // `x + PAIR_OVERHEAD_CYCLES` cycles in all (two pulls, out, mov, and `x + 1` jmps).
+    let program = pio::pio_asm!(
+        ".wrap_target",
+        "    pull block",
+        "    out pins, 32",
+        "    pull block",
+        "    mov x, osr",
+        "delay:",
+        "    jmp x-- delay",
+        ".wrap",
+    );
+    let loaded_program = common.load_program(&program.program);
+
+    let lowest_pin = pins.iter().map(Pin::pin).min().expect("at least one pin");
+    let highest_pin = pins.iter().map(Pin::pin).max().expect("at least one pin");
+    assert!(
+        usize::from(highest_pin - lowest_pin) < PIO_SERVOS_MAX,
+        "pio_servos! pins must lie within a 32-pin window"
+    );
+
+    let mut config = Config::default();
+    config.use_program(&loaded_program, &[]);
+    let mut pin_config = config.get_pins();
+    pin_config.out_base = lowest_pin;
+    pin_config.out_count = highest_pin - lowest_pin + 1;
+    // `set_out_pins` insists on consecutive pins, so set the window directly.
+    // SAFETY: `out pins, 32` also writes the window's gap pins, which is harmless: the
+    // macro's `new` takes the whole PIO block and starts only `sm0`, so no other state
+    // machine drives them, and only the bank's pins get `make_pio_pin`, so gap pins
+    // aren't muxed to this PIO at all.
+    unsafe {
+        config.set_pins(pin_config);
+    }
+    config.clock_divider = (embassy_rp::clocks::clk_sys_freq() as f64
+        / (1_000_000.0 * f64::from(PIO_CYCLES_PER_US)))
+    .to_fixed();
+    config.shift_out = ShiftConfig {
+        threshold: 32,
+        direction: ShiftDirection::Right,
+        auto_fill: false,
+    };
+    config.fifo_join = FifoJoin::TxOnly;
+
+    let pin_refs: [&Pin<'static, PIO>; N] = core::array::from_fn(|index| &pins[index]);
+    state_machine.set_config(&config);
+    state_machine.set_pins(Level::Low, &pin_refs);
+    state_machine.set_pin_dirs(Direction::Out, &pin_refs);
+    state_machine.set_enable(true);
+
+    info!(
+        "PioServos: {} servos on GPIO {}..={}",
+        N, lowest_pin, highest_pin
+    );
+    core::array::from_fn(|index| 1 << (pins[index].pin() - lowest_pin))
+}
+
+// Called by macro-generated code in downstream crates; must be public.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub async fn pio_servos_device_loop<PIO: Instance, DMA: Channel, const N: usize>(
+    pio_servos_static: &'static PioServosStatic<N>,
+    mut state_machine: StateMachine<'static, PIO, 0>,
+    mut dma: Peri<'static, DMA>,
+    pin_bits: [u32; N],
+) -> ! {
+    let mut frame = [0_u32; FRAME_WORDS_MAX];
+    loop {
+        let pulses_us: [u16; N] = core::array::from_fn(|index| {
+            pio_servos_static.pulses_us[index].load(Ordering::Relaxed)
+        });
+        let frame_len = fill_frame(&pulses_us, &pin_bits, &mut frame);
+        // Returns once the last word is queued; the frame's long low tail leaves plenty
+        // of time to build and queue the next one.
+        state_machine
+            .tx()
+            .dma_push(dma.reborrow(), &frame[..frame_len], false)
+            .await;
+    }
+}
+
+/// Fill `frame` with (pin mask, delay) word pairs for one 20 ms servo frame and return the
+/// number of words used.
+///
+/// Every servo with a nonzero pulse goes high at the start of the frame; each pair then
+/// holds until the next falling edge. Servos whose pulse is 0 stay low.
+fn fill_frame<const N: usize>(
+    pulses_us: &[u16; N],
+    pin_bits: &[u32; N],
+    frame: &mut [u32],
+) -> usize {
+    let mut mask = pulses_us
+        .iter()
+        .zip(pin_bits)
+        .filter(|&(&pulse_us, _)| pulse_us > 0)
+        .fold(0, |mask, (_, &pin_bit)| mask | pin_bit);
+    let mut elapsed_us = 0_u16;
+    let mut frame_len = 0;
+    loop {
+        let next_edge_us = pulses_us
+            .iter()
+            .copied()
+            .filter(|&pulse_us| pulse_us > elapsed_us)
+            .min()
+            .unwrap_or(SERVO_PERIOD_US);
+        frame[frame_len] = mask;
+        frame[frame_len + 1] =
+            u32::from(next_edge_us - elapsed_us) * PIO_CYCLES_PER_US - PAIR_OVERHEAD_CYCLES;
+        frame_len += 2;
+        if next_edge_us == SERVO_PERIOD_US {
+            return frame_len;
+        }
+        for (&pulse_us, &pin_bit) in pulses_us.iter().zip(pin_bits) {
+            if pulse_us == next_edge_us {
+                mask &= !pin_bit;
+            }
+        }
+        elapsed_us = next_edge_us;
+    }
+}
@@ -0,0 +1,122 @@
+#![allow(missing_docs)]
+
+use super::{
+    FRAME_WORDS_MAX, PAIR_OVERHEAD_CYCLES, PIO_CYCLES_PER_US, PIO_SERVOS_MAX, SERVO_PERIOD_US,
+    fill_frame,
+};
+
+/// The delay word a pair needs to hold its mask for `us` microseconds.
+const fn delay_word(us: u16) -> u32 {
+    us as u32 * PIO_CYCLES_PER_US - PAIR_OVERHEAD_CYCLES
+}
+
+/// Fill a frame and return just the words used.
+fn frame_words<const N: usize>(pulses_us: [u16; N], pin_bits: [u32; N]) -> std::vec::Vec<u32> {
+    let mut frame = [0_u32; FRAME_WORDS_MAX];
+    let frame_len = fill_frame(&pulses_us, &pin_bits, &mut frame);
+    frame[..frame_len].to_vec()
+}
+
+/// Total microseconds a frame's pairs take to run on the state machine.
+fn frame_us(words: &[u32]) -> u32 {
+    words
+        .chunks(2)
+        .map(|pair| (pair[1] + PAIR_OVERHEAD_CYCLES) / PIO_CYCLES_PER_US)
+        .sum()
+}
+
+#[test]
+fn single_servo_goes_high_then_low() {
+    let words = frame_words([1_500], [1 << 3]);
+    assert_eq!(
+        words,
+        [
+            1 << 3,
+            delay_word(1_500),
+            0,
+            delay_word(SERVO_PERIOD_US - 1_500)
+        ]
+    );
+}
+
+#[test]
+fn edges_fall_in_pulse_order_regardless_of_pin_order() {
+    let words = frame_words([2_000, 1_000, 1_500], [0b001, 0b010, 0b100]);
+    assert_eq!(
+        words,
+        [
+            0b111,
+            delay_word(1_000),
+            0b101,
+            delay_word(500),
+            0b001,
+            delay_word(500),
+            0,
+            delay_word(SERVO_PERIOD_US - 2_000),
+        ]
+    );
+}
+
+#[test]
+fn equal_pulses_share_one_edge() {
+    let words = frame_words([1_200, 1_800, 1_200], [1 << 0, 1 << 5, 1 << 17]);
+    assert_eq!(
+        words,
+        [
+            1 << 0 | 1 << 5 | 1 << 17,
+            delay_word(1_200),
+            1 << 5,
+            delay_word(600),
+            0,
+            delay_word(SERVO_PERIOD_US - 1_800),
+        ]
+    );
+}
+
+#[test]
+fn relaxed_servos_stay_low() {
+    let words = frame_words([0, 900], [1 << 0, 1 << 1]);
+    assert_eq!(
+        words,
+        [
+            1 << 1,
+            delay_word(900),
+            0,
+            delay_word(SERVO_PERIOD_US - 900)
+        ]
+    );
+
+    let words = frame_words([0, 0], [1 << 0, 1 << 1]);
+    assert_eq!(words, [0, delay_word(SERVO_PERIOD_US)]);
+}
+
+#[test]
+fn full_bank_fits_and_lasts_one_period() {
+    let pulses_us: [u16; PIO_SERVOS_MAX] = core::array::from_fn(|index| 500 + 60 * index as u16);
+    let pin_bits: [u32; PIO_SERVOS_MAX] = core::array::from_fn(|index| 1 << index);
+    let words = frame_words(pulses_us, pin_bits);
+
+    assert_eq!(words.len(), FRAME_WORDS_MAX);
+    assert_eq!(frame_us(&words), u32::from(SERVO_PERIOD_US));
+    // Each edge clears exactly the lowest pin still high.
+    for (pair_index, pair) in words.chunks(2).enumerate() {
+        assert_eq!(
+            pair[0],
+            u32::MAX.checked_shl(pair_index as u32).unwrap_or(0)
+        );
+    }
+}
+
+#[test]
+fn every_frame_lasts_one_period() {
+    for pulses_us in [
+        [500, 2_500, 1_500, 0],
+        [2_500, 2_500, 2_500, 2_500],
+        [700, 0, 700, 1_300],
+    ] {
+        let words = frame_words(pulses_us, [1 << 2, 1 << 3, 1 << 18, 1 << 19]);
+        assert_eq!(frame_us(&words), u32::from(SERVO_PERIOD_US));
+        // Every pin is low for the rest of the period.
+        assert_eq!(words[words.len() - 2], 0);
+    }
+}
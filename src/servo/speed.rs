@@ -0,0 +1,484 @@
+//! Speed control for continuous-rotation servos and hobby ESCs.
+//!
+//! See [`SpeedServo`] for usage examples.
+
+use defmt::info;
+use embassy_time::{Duration, Timer};
+
+use super::{SERVO_PERIOD_US, Servo, ServoPwmPin};
+
+/// Default pulse width that stops the motor (microseconds).
+pub const SPEED_NEUTRAL_US_DEFAULT: u16 = 1_500;
+
+/// Default pulse width change from neutral to full speed (microseconds).
+pub const SPEED_RANGE_US_DEFAULT: u16 = 500;
+
+/// Create a speed-controlled servo or ESC with keyword arguments and defaults.
+///
+/// **Syntax:**
+///
+/// ```text
+/// speed_servo! {
+///     pin: <pin_expr>,
+///     slice: <pwm_slice_expr>,
+///     neutral_us: <u16_expr>,     // optional
+///     range_us: <u16_expr>,       // optional
+///     deadband_us: <u16_expr>,    // optional
+///     reversible: <bool_expr>,    // optional
+/// }
+/// ```
+///
+/// Required fields: `pin`, `slice`.
+///
+/// Optional fields:
+///
+/// - `neutral_us` — Pulse width that stops the motor (default:
+///   [`SPEED_NEUTRAL_US_DEFAULT`])
+/// - `range_us` — Pulse width change from neutral to full speed (default:
+///   [`SPEED_RANGE_US_DEFAULT`])
+/// - `deadband_us` — Width of the band around neutral where the motor doesn't respond yet;
+///   the smallest nonzero speed starts just past it (default: 0)
+/// - `reversible` — Whether negative speeds are allowed (default: `true`). Set to `false`
+///   for one-way ESCs, along with `neutral_us: 1000, range_us: 1000`.
+///
+/// See [`SpeedServo`] for details and examples.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! speed_servo {
+    ($($tt:tt)*) => { $crate::__speed_servo_impl! { $($tt)* } };
+}
+
+// Public for macro expansion in downstream crates.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __speed_servo_impl {
+    (@__fill_defaults
+        pin: $pin:tt,
+        slice: $slice:tt,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr,
+        fields: [ ]
+    ) => {
+        $crate::__speed_servo_impl! {
+            @__build
+            pin: $pin,
+            slice: $slice,
+            neutral_us: $neutral_us,
+            range_us: $range_us,
+            deadband_us: $deadband_us,
+            reversible: $reversible
+        }
+    };
+
+    (@__fill_defaults
+        pin: $pin:tt,
+        slice: $slice:tt,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr,
+        fields: [ pin: $pin_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__speed_servo_impl! {
+            @__fill_defaults
+            pin: $pin_value,
+            slice: $slice,
+            neutral_us: $neutral_us,
+            range_us: $range_us,
+            deadband_us: $deadband_us,
+            reversible: $reversible,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        pin: $pin:tt,
+        slice: $slice:tt,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr,
+        fields: [ slice: $slice_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__speed_servo_impl! {
+            @__fill_defaults
+            pin: $pin,
+            slice: $slice_value,
+            neutral_us: $neutral_us,
+            range_us: $range_us,
+            deadband_us: $deadband_us,
+            reversible: $reversible,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        pin: $pin:tt,
+        slice: $slice:tt,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr,
+        fields: [ neutral_us: $neutral_us_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__speed_servo_impl! {
+            @__fill_defaults
+            pin: $pin,
+            slice: $slice,
+            neutral_us: $neutral_us_value,
+            range_us: $range_us,
+            deadband_us: $deadband_us,
+            reversible: $reversible,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        pin: $pin:tt,
+        slice: $slice:tt,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr,
+        fields: [ range_us: $range_us_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__speed_servo_impl! {
+            @__fill_defaults
+            pin: $pin,
+            slice: $slice,
+            neutral_us: $neutral_us,
+            range_us: $range_us_value,
+            deadband_us: $deadband_us,
+            reversible: $reversible,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        pin: $pin:tt,
+        slice: $slice:tt,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr,
+        fields: [ deadband_us: $deadband_us_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__speed_servo_impl! {
+            @__fill_defaults
+            pin: $pin,
+            slice: $slice,
+            neutral_us: $neutral_us,
+            range_us: $range_us,
+            deadband_us: $deadband_us_value,
+            reversible: $reversible,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__fill_defaults
+        pin: $pin:tt,
+        slice: $slice:tt,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr,
+        fields: [ reversible: $reversible_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__speed_servo_impl! {
+            @__fill_defaults
+            pin: $pin,
+            slice: $slice,
+            neutral_us: $neutral_us,
+            range_us: $range_us,
+            deadband_us: $deadband_us,
+            reversible: $reversible_value,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    (@__build
+        pin: _UNSET_,
+        slice: $slice:tt,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr
+    ) => {
+        compile_error!("speed_servo! requires `pin: ...`");
+    };
+
+    (@__build
+        pin: $pin:expr,
+        slice: _UNSET_,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr
+    ) => {
+        compile_error!("speed_servo! requires `slice: ...`");
+    };
+
+    (@__build
+        pin: $pin:expr,
+        slice: $slice:expr,
+        neutral_us: $neutral_us:expr,
+        range_us: $range_us:expr,
+        deadband_us: $deadband_us:expr,
+        reversible: $reversible:expr
+    ) => {
+        $crate::servo::speed_servo_from_pin_slice(
+            $pin,
+            $slice,
+            $neutral_us,
+            $range_us,
+            $deadband_us,
+            $reversible,
+        )
+    };
+
+    (
+        $($fields:tt)*
+    ) => {
+        $crate::__speed_servo_impl! {
+            @__fill_defaults
+            pin: _UNSET_,
+            slice: _UNSET_,
+            neutral_us: $crate::servo::SPEED_NEUTRAL_US_DEFAULT,
+            range_us: $crate::servo::SPEED_RANGE_US_DEFAULT,
+            deadband_us: 0,
+            reversible: true,
+            fields: [ $($fields)* ]
+        }
+    };
+}
+
+// Public for macro expansion in downstream crates.
+#[doc(hidden)]
+pub fn speed_servo_from_pin_slice<'d, P, S>(
+    pin: embassy_rp::Peri<'d, P>,
+    slice: embassy_rp::Peri<'d, S>,
+    neutral_us: u16,
+    range_us: u16,
+    deadband_us: u16,
+    reversible: bool,
+) -> SpeedServo<'d>
+where
+    P: ServoPwmPin<S>,
+    S: embassy_rp::PeripheralType,
+{
+    assert!(range_us > 0, "range_us must be positive");
+    assert!(
+        deadband_us < range_us,
+        "deadband_us must be less than range_us"
+    );
+    assert!(
+        !reversible || neutral_us >= range_us,
+        "neutral_us - range_us must not be negative"
+    );
+    assert!(
+        u32::from(neutral_us) + u32::from(range_us) < u32::from(SERVO_PERIOD_US),
+        "neutral_us + range_us must fit in the PWM frame"
+    );
+    // The angle mapping is unused; the pulse range just has to hold neutral.
+    let servo = super::servo_from_pin_slice(
+        pin,
+        slice,
+        neutral_us.saturating_sub(range_us),
+        neutral_us + range_us,
+        Servo::DEFAULT_MAX_DEGREES,
+    );
+    let mut speed_servo = SpeedServo {
+        servo,
+        neutral_us,
+        range_us,
+        deadband_us,
+        reversible,
+        neutral_trim_us: 0,
+        speed_percent: 0,
+    };
+    speed_servo.stop();
+    speed_servo
+}
+
+/// A device abstraction for continuous-rotation servos and hobby ESCs (electronic speed
+/// controllers), where the pulse width sets speed and direction instead of an angle.
+///
+/// Speeds are signed percentages: `0` stops, `100` is full speed forward, and `-100` is
+/// full speed in reverse. A speed of `s` sends `neutral_us ± (deadband_us + s% of the rest
+/// of range_us)`, so even small speeds clear the motor's deadband.
+///
+/// Use the [`speed_servo!`](macro@crate::servo::speed_servo) macro to create one. It uses
+/// the same pin-to-[PWM slice](crate#glossary) mapping as [`servo!`](macro@crate::servo).
+///
+/// # Examples
+///
+/// A continuous-rotation servo that creeps at rest can be trimmed at runtime:
+///
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// use device_envoy::servo::speed_servo;
+/// use embassy_time::{Duration, Timer};
+/// # use core::panic::PanicInfo;
+/// # #[panic_handler]
+/// # fn panic(_info: &PanicInfo) -> ! { loop {} }
+/// async fn example(p: embassy_rp::Peripherals) {
+///     // GPIO 11 → (11/2) % 8 = 5 → PWM_SLICE5
+///     let mut wheel = speed_servo! {
+///         pin: p.PIN_11,
+///         slice: p.PWM_SLICE5,
+///         deadband_us: 30,    // This servo ignores pulses within 30 µs of neutral
+///     };
+///
+///     wheel.set_neutral_trim_us(-8);                  // It crept forward at rest
+///     wheel.set_speed(50);                            // Half speed forward
+///     Timer::after(Duration::from_secs(2)).await;
+///     wheel.set_speed(-20);                           // Slowly in reverse
+///     Timer::after(Duration::from_secs(2)).await;
+///     wheel.stop();
+/// }
+/// ```
+///
+/// An ESC must see a steady stop signal for a few seconds before it will run the motor:
+///
+/// ```rust,no_run
+/// # #![no_std]
+/// # #![no_main]
+/// use device_envoy::servo::{SpeedServo, speed_servo};
+/// use embassy_time::{Duration, Timer};
+/// # use core::panic::PanicInfo;
+/// # #[panic_handler]
+/// # fn panic(_info: &PanicInfo) -> ! { loop {} }
+/// async fn example(p: embassy_rp::Peripherals) {
+///     let mut motor = speed_servo! {
+///         pin: p.PIN_12,
+///         slice: p.PWM_SLICE6,
+///         neutral_us: 1000,   // One-way ESC: 1000 µs stops ...
+///         range_us: 1000,     // ... and 2000 µs is full throttle
+///         reversible: false,
+///     };
+///
+///     motor.arm(SpeedServo::DEFAULT_ARM_DURATION).await;
+///     motor.set_speed(30);
+///     Timer::after(Duration::from_secs(5)).await;
+///     motor.stop();
+/// }
+/// ```
+pub struct SpeedServo<'d> {
+    servo: Servo<'d>,
+    neutral_us: u16,
+    range_us: u16,
+    deadband_us: u16,
+    reversible: bool,
+    neutral_trim_us: i16,
+    speed_percent: i8,
+}
+
+impl SpeedServo<'_> {
+    /// How long [`arm`](Self::arm) holds the stop signal by default. Long enough for
+    /// common hobby ESCs; check your ESC's manual.
+    pub const DEFAULT_ARM_DURATION: Duration = Duration::from_secs(3);
+
+    /// Set the speed, from `-100` (full reverse) through `0` (stop) to `100` (full
+    /// forward).
+    ///
+    /// Automatically enables the output if it was relaxed.
+    ///
+    /// See the [`SpeedServo`] example for usage.
+    pub fn set_speed(&mut self, speed_percent: i8) {
+        assert!(
+            (-100..=100).contains(&speed_percent),
+            "speed must be between -100 and 100"
+        );
+        assert!(
+            self.reversible || speed_percent >= 0,
+            "speed must not be negative unless reversible"
+        );
+        self.speed_percent = speed_percent;
+        let us = self.pulse_us();
+        info!("SpeedServo set_speed({}) -> {}µs", speed_percent, us);
+        self.servo.hold();
+        self.servo.set_pulse_us(us);
+    }
+
+    /// The most recently set speed.
+    #[must_use]
+    pub const fn speed(&self) -> i8 {
+        self.speed_percent
+    }
+
+    /// Stop the motor by sending the (trimmed) neutral pulse.
+    ///
+    /// See the [`SpeedServo`] example for usage.
+    pub fn stop(&mut self) {
+        self.set_speed(0);
+    }
+
+    /// Shift the neutral pulse by `neutral_trim_us`, for servos that creep when stopped.
+    ///
+    /// Applies immediately to the current speed.
+    ///
+    /// See the [`SpeedServo`] example for usage.
+    pub fn set_neutral_trim_us(&mut self, neutral_trim_us: i16) {
+        assert!(
+            neutral_trim_us.unsigned_abs() < self.range_us,
+            "neutral trim must be smaller than range_us"
+        );
+        let lowest_us = i32::from(self.neutral_us) + i32::from(neutral_trim_us)
+            - if self.reversible {
+                i32::from(self.range_us)
+            } else {
+                0
+            };
+        let highest_us =
+            i32::from(self.neutral_us) + i32::from(neutral_trim_us) + i32::from(self.range_us);
+        assert!(
+            lowest_us >= 0 && highest_us < i32::from(SERVO_PERIOD_US),
+            "trimmed pulses must fit in the PWM frame"
+        );
+        self.neutral_trim_us = neutral_trim_us;
+        if self.servo.is_enabled() {
+            self.servo.set_pulse_us(self.pulse_us());
+        }
+    }
+
+    /// The current neutral trim in microseconds.
+    #[must_use]
+    pub const fn neutral_trim_us(&self) -> i16 {
+        self.neutral_trim_us
+    }
+
+    /// Arm an ESC by sending the stop signal for `duration`.
+    ///
+    /// Most ESCs refuse to drive the motor until they have seen a steady stop signal after
+    /// power-up, often beeping once armed. Leaves the speed at `0`.
+    ///
+    /// See the [`SpeedServo`] example for usage.
+    pub async fn arm(&mut self, duration: Duration) {
+        info!("SpeedServo arming for {} ms", duration.as_millis());
+        self.stop();
+        Timer::after(duration).await;
+    }
+
+    /// Stop sending control signals.
+    ///
+    /// A continuous-rotation servo coasts to a stop; most ESCs stop the motor and may need
+    /// [`arm`](Self::arm) again. Re-enables on the next [`set_speed`](Self::set_speed).
+    pub fn relax(&mut self) {
+        self.servo.relax();
+    }
+
+    /// Pulse width for the current speed and trim.
+    fn pulse_us(&self) -> u16 {
+        let neutral_us = i32::from(self.neutral_us) + i32::from(self.neutral_trim_us);
+        if self.speed_percent == 0 {
+            return neutral_us as u16;
+        }
+        let magnitude = u32::from(self.speed_percent.unsigned_abs());
+        let offset_us = i32::from(self.deadband_us)
+            + ((u32::from(self.range_us - self.deadband_us) * magnitude + 50) / 100) as i32;
+        (neutral_us + i32::from(self.speed_percent.signum()) * offset_us) as u16
+    }
+}
@@ -0,0 +1,934 @@
+//! A device abstraction for smart servos on a half-duplex serial bus: Feetech SCS/STS and
+//! Dynamixel Protocol 2.0.
+//!
+//! Unlike hobby servos, which only receive a pulse width, bus servos share one data wire,
+//! each has an id, and each reports its position, load, voltage, temperature, and faults.
+//!
+//! **After reading the examples below, see also:**
+//!
+//! - [`servo_bus!`](macro@crate::servo_bus::servo_bus) — Macro to generate a servo bus type
+//!   (includes syntax details).
+//! - [`BusServo`] — One servo on the bus, with the same `set_degrees`/`hold`/`relax` API as
+//!   [`Servo`](crate::servo::Servo).
+//! - [`BusServoMonitor`] & [`BusServoStatus`] — Read back position, load, voltage,
+//!   temperature, and [`BusServoFaults`].
+//! - [`servo_player!`](macro@crate::servo_player) — With the `bus_servo` flag, animates a
+//!   `BusServo` with steps and [`AtEnd`](crate::servo_player::AtEnd) like any other servo.
+//!
+//! # Wiring
+//!
+//! The Pico's UART has separate TX and RX pins, but the servos share one data wire. Connect
+//! them through an adapter:
+//!
+//! - **Auto-direction adapters** (e.g. Feetech FE-URT-1, most "bus servo driver" boards) —
+//!   Connect TX and RX. If the adapter loops your transmission back to RX (or TX and RX are
+//!   simply joined through a 1 kΩ resistor), set `echo: true`.
+//! - **Direction-pin adapters** (e.g. a 74HC126 buffer or an RS-485 transceiver for
+//!   Dynamixel) — Also give a `direction` pin, driven high while transmitting.
+//!
+//! Servos must answer every instruction, which is the factory setting (Feetech response
+//! level 1, Dynamixel status return level 2), and need unique ids.
+//!
+//! # Example: Feetech STS Servos
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::default::Default;
+//! # use core::result::Result::Ok;
+//! use defmt::info;
+//! use device_envoy::{Result, servo_bus::servo_bus};
+//! use embassy_time::{Duration, Timer};
+//!
+//! servo_bus! {
+//!     ArmBus {
+//!         protocol: FeetechSts,
+//!         ids: [1, 2],
+//!         uart: UART0,
+//!         tx: PIN_0,
+//!         rx: PIN_1,
+//!         echo: true, // TX and RX joined through a resistor
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     let p = embassy_rp::init(Default::default());
+//!
+//!     let [mut shoulder, mut elbow] =
+//!         ArmBus::new(p.UART0, p.PIN_0, p.PIN_1, p.DMA_CH0, p.DMA_CH1, spawner)?;
+//!     let elbow_monitor = elbow.monitor();
+//!
+//!     shoulder.set_degrees(45);
+//!     elbow.set_degrees(120);
+//!     loop {
+//!         Timer::after(Duration::from_millis(500)).await;
+//!         if let Some(status) = elbow_monitor.status() {
+//!             info!(
+//!                 "elbow at {}°, load {}‰, {}°C",
+//!                 elbow_monitor.degrees(),
+//!                 status.load_permille(),
+//!                 status.temperature_celsius()
+//!             );
+//!             if !status.faults().is_empty() {
+//!                 elbow.relax();
+//!                 shoulder.relax();
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! # Example: Animating a Dynamixel Servo
+//!
+//! Give a [`BusServo`] to a [`servo_player!`](macro@crate::servo_player) type generated with
+//! the `bus_servo` flag, and animate it like a PWM servo. Its monitor keeps reporting the
+//! servo's actual position.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::default::Default;
+//! # use core::result::Result::Ok;
+//! use defmt::{info, warn};
+//! use device_envoy::{
+//!     Result,
+//!     servo_bus::servo_bus,
+//!     servo_player::{AtEnd, servo_player},
+//! };
+//! use embassy_time::{Duration, Timer};
+//!
+//! servo_bus! {
+//!     PanBus {
+//!         protocol: Dynamixel2,
+//!         ids: [1],
+//!         uart: UART1,
+//!         tx: PIN_4,
+//!         rx: PIN_5,
+//!         direction: PIN_6, // RS-485 transceiver's DE/RE pins
+//!         tx_dma: DMA_CH2,
+//!         rx_dma: DMA_CH3,
+//!         baud: 1_000_000,
+//!     }
+//! }
+//!
+//! servo_player! {
+//!     Pan {
+//!         bus_servo,
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     let p = embassy_rp::init(Default::default());
+//!
+//!     let [pan_servo] = PanBus::new(
+//!         p.UART1, p.PIN_4, p.PIN_5, p.PIN_6, p.DMA_CH2, p.DMA_CH3, spawner,
+//!     )?;
+//!     let pan_monitor = pan_servo.monitor();
+//!     let pan = Pan::new(pan_servo, spawner)?;
+//!
+//!     pan.animate(
+//!         [(30, Duration::from_secs(2)), (150, Duration::from_secs(2))],
+//!         AtEnd::Loop,
+//!     );
+//!     loop {
+//!         Timer::after(Duration::from_secs(1)).await;
+//!         if pan_monitor.is_responding() {
+//!             info!("commanded {}°, actual {}°", pan.degrees(), pan_monitor.degrees());
+//!         } else {
+//!             warn!("pan servo is not answering");
+//!         }
+//!     }
+//! }
+//! ```
+#![cfg_attr(all(test, feature = "host"), allow(dead_code))]
+
+#[cfg(target_os = "none")]
+mod bus;
+#[cfg(all(test, feature = "host"))]
+mod host_tests;
+mod protocol;
+
+#[cfg(target_os = "none")]
+pub use bus::{BusServo, BusServoMonitor};
+pub use protocol::{BusServoFaults, BusServoStatus, ServoBusProtocol};
+
+// Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub use bus::{ServoBusPort, ServoBusStatic, servo_bus_device_loop};
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub use paste;
+
+/// Macro to generate a servo bus type (includes syntax details).
+///
+/// See the [servo_bus module documentation](mod@crate::servo_bus) for complete examples.
+///
+/// **Syntax:**
+///
+/// ```text
+/// servo_bus! {
+///     [<visibility>] <Name> {
+///         protocol: FeetechSts | FeetechScs | Dynamixel2,
+///         ids: [<u8_expr>, ...],
+///         uart: <uart_ident>,         // optional
+///         tx: <pin_ident>,
+///         rx: <pin_ident>,
+///         direction: <pin_ident>,     // optional
+///         tx_dma: <dma_ident>,        // optional
+///         rx_dma: <dma_ident>,        // optional
+///         baud: <u32_expr>,           // optional
+///         echo: <bool_expr>,          // optional
+///         max_degrees: <u16_expr>,    // optional
+///     }
+/// }
+/// ```
+///
+/// # Configuration
+///
+/// ## Required Fields
+///
+/// - `protocol` — The servos' [`ServoBusProtocol`]
+/// - `ids` — Bus ids of the servos (below 253, no repeats), one [`BusServo`] each
+/// - `tx`, `rx` — UART pins wired to the bus adapter
+///
+/// ## Optional Fields
+///
+/// - `uart` — UART resource to use (default: `UART0`). The macro binds its interrupt.
+/// - `direction` — Pin driven high while transmitting, for adapters that need one
+///   (default: none)
+/// - `tx_dma`, `rx_dma` — DMA channels for the UART (defaults: `DMA_CH0`, `DMA_CH1`)
+/// - `baud` — Bus baud rate (default: [`ServoBusProtocol::default_baudrate`])
+/// - `echo` — Whether transmitted bytes come back on `rx` before each reply
+///   (default: `false`)
+/// - `max_degrees` — Angle range of `set_degrees`, centered on each servo's middle position
+///   (default: 180). Change a servo's mapping with [`BusServo::set_calibration`].
+///
+/// # Generated Methods
+///
+/// - `new(uart, tx, rx, [direction,] tx_dma, rx_dma, spawner) -> Result<[BusServo; N]>` —
+///   Open the bus, start its background task, and return one [`BusServo`] per id, in `ids`
+///   order.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! servo_bus {
+    ($($tt:tt)*) => { $crate::__servo_bus_impl! { $($tt)* } };
+}
+#[cfg(target_os = "none")]
+#[doc(inline)]
+pub use servo_bus;
+
+// Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __servo_bus_impl {
+    // Entry point - name without visibility defaults to private
+    (
+        $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: pub(self),
+            name: $name,
+            protocol: _UNSET_,
+            ids: _UNSET_,
+            uart: UART0,
+            tx: _UNSET_,
+            rx: _UNSET_,
+            direction: _UNSET_,
+            tx_dma: DMA_CH0,
+            rx_dma: DMA_CH1,
+            baud: ::core::option::Option::<u32>::None,
+            echo: false,
+            max_degrees: $crate::servo::Servo::DEFAULT_MAX_DEGREES,
+            fields: [ $($fields)* ]
+        }
+    };
+
+    // Entry point - name with explicit visibility
+    (
+        $vis:vis $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: _UNSET_,
+            ids: _UNSET_,
+            uart: UART0,
+            tx: _UNSET_,
+            rx: _UNSET_,
+            direction: _UNSET_,
+            tx_dma: DMA_CH0,
+            rx_dma: DMA_CH1,
+            baud: ::core::option::Option::<u32>::None,
+            echo: false,
+            max_degrees: $crate::servo::Servo::DEFAULT_MAX_DEGREES,
+            fields: [ $($fields)* ]
+        }
+    };
+
+    // Fill defaults: protocol
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ protocol: $protocol_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol_value,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: ids
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ids: [$($id_value:expr),+ $(,)?] $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: [$($id_value),+],
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: uart
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ uart: $uart_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart_value,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: tx
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ tx: $tx_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx_value,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: rx
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ rx: $rx_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx_value,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: direction
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ direction: $direction_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction_value,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: tx_dma
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ tx_dma: $tx_dma_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma_value,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: rx_dma
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ rx_dma: $rx_dma_value:ident $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma_value,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: baud
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ baud: $baud_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: ::core::option::Option::Some($baud_value),
+            echo: $echo,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: echo
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ echo: $echo_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo_value,
+            max_degrees: $max_degrees,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: max_degrees
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ max_degrees: $max_degrees_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            direction: $direction,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees_value,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: terminate and build
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: _UNSET_,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("servo_bus! requires `protocol: FeetechSts | FeetechScs | Dynamixel2`");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: _UNSET_,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("servo_bus! requires `ids: [...]`");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: _UNSET_,
+        rx: $rx:tt,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("servo_bus! requires `tx: ...`");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: _UNSET_,
+        direction: $direction:tt,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("servo_bus! requires `rx: ...`");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: _UNSET_,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__build
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            direction_param: [ ],
+            direction_output: [ ::core::option::Option::None ]
+        }
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:tt,
+        ids: $ids:tt,
+        uart: $uart:ident,
+        tx: $tx:tt,
+        rx: $rx:tt,
+        direction: $direction:ident,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        fields: [ ]
+    ) => {
+        $crate::__servo_bus_impl! {
+            @__build
+            vis: $vis,
+            name: $name,
+            protocol: $protocol,
+            ids: $ids,
+            uart: $uart,
+            tx: $tx,
+            rx: $rx,
+            tx_dma: $tx_dma,
+            rx_dma: $rx_dma,
+            baud: $baud,
+            echo: $echo,
+            max_degrees: $max_degrees,
+            direction_param: [
+                direction: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$direction>>,
+            ],
+            direction_output: [
+                ::core::option::Option::Some(::embassy_rp::gpio::Output::new(
+                    direction.into(),
+                    ::embassy_rp::gpio::Level::Low,
+                ))
+            ]
+        }
+    };
+
+    (@__build
+        vis: $vis:vis,
+        name: $name:ident,
+        protocol: $protocol:ident,
+        ids: [$($id:expr),+],
+        uart: $uart:ident,
+        tx: $tx:ident,
+        rx: $rx:ident,
+        tx_dma: $tx_dma:ident,
+        rx_dma: $rx_dma:ident,
+        baud: $baud:expr,
+        echo: $echo:expr,
+        max_degrees: $max_degrees:expr,
+        direction_param: [ $($direction_param:tt)* ],
+        direction_output: [ $($direction_output:tt)* ]
+    ) => {
+        $crate::servo_bus::paste::paste! {
+            const [<$name:upper _SERVO_COUNT>]: usize = [$($id),+].len();
+            static [<$name:upper _SERVO_BUS_STATIC>]:
+                $crate::servo_bus::ServoBusStatic<[<$name:upper _SERVO_COUNT>]> =
+                $crate::servo_bus::ServoBusStatic::new_static();
+
+            ::embassy_rp::bind_interrupts! {
+                struct [<$name Irqs>] {
+                    [<$uart _IRQ>] => ::embassy_rp::uart::InterruptHandler<::embassy_rp::peripherals::$uart>;
+                }
+            }
+
+            #[allow(missing_docs)]
+            $vis struct $name;
+
+            #[allow(missing_docs)]
+            impl $name {
+                /// Number of servos on the bus.
+                pub const SERVO_COUNT: usize = [<$name:upper _SERVO_COUNT>];
+
+                /// Open the UART, start the bus's background task, and return one `BusServo`
+                /// per id, in `ids` order.
+                ///
+                /// # Parameters
+                ///
+                /// - `uart` — UART resource for the bus
+                /// - `tx`, `rx` — UART pins wired to the servo adapter
+                /// - `direction` — Only if configured: pin driven high while transmitting
+                /// - `tx_dma`, `rx_dma` — DMA channels for the UART
+                /// - `spawner` — Task spawner for background operations
+                #[allow(clippy::too_many_arguments)]
+                pub fn new(
+                    uart: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$uart>>,
+                    tx: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$tx>>,
+                    rx: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$rx>>,
+                    $($direction_param)*
+                    tx_dma: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$tx_dma>>,
+                    rx_dma: impl Into<::embassy_rp::Peri<'static, ::embassy_rp::peripherals::$rx_dma>>,
+                    spawner: ::embassy_executor::Spawner,
+                ) -> $crate::Result<[$crate::servo_bus::BusServo; [<$name:upper _SERVO_COUNT>]]> {
+                    const PROTOCOL: $crate::servo_bus::ServoBusProtocol =
+                        $crate::servo_bus::ServoBusProtocol::$protocol;
+                    let ids: [u8; [<$name:upper _SERVO_COUNT>]] = [$($id),+];
+                    let mut config = ::embassy_rp::uart::Config::default();
+                    config.baudrate = $baud.unwrap_or(PROTOCOL.default_baudrate());
+                    let uart = ::embassy_rp::uart::Uart::new(
+                        uart.into(),
+                        tx.into(),
+                        rx.into(),
+                        [<$name Irqs>],
+                        tx_dma.into(),
+                        rx_dma.into(),
+                        config,
+                    );
+                    let port = $crate::servo_bus::ServoBusPort::new(
+                        uart,
+                        $($direction_output)*,
+                        PROTOCOL,
+                        $echo,
+                    );
+                    let servos = [<$name:upper _SERVO_BUS_STATIC>].servos(PROTOCOL, &ids, $max_degrees);
+                    let token = [<$name:snake _servo_bus_task>](&[<$name:upper _SERVO_BUS_STATIC>], port, ids);
+                    spawner.spawn(token)?;
+                    Ok(servos)
+                }
+            }
+
+            #[::embassy_executor::task]
+            async fn [<$name:snake _servo_bus_task>](
+                servo_bus_static: &'static $crate::servo_bus::ServoBusStatic<[<$name:upper _SERVO_COUNT>]>,
+                port: $crate::servo_bus::ServoBusPort,
+                ids: [u8; [<$name:upper _SERVO_COUNT>]],
+            ) -> ! {
+                $crate::servo_bus::servo_bus_device_loop(servo_bus_static, port, ids).await
+            }
+        }
+    };
+}
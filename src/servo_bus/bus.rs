@@ -0,0 +1,484 @@
+//! The bus's UART port, its background task, and the per-servo handles.
+
+use core::cell::Cell;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use defmt::{Display2Format, info, warn};
+use embassy_futures::select::select;
+use embassy_rp::gpio::Output;
+use embassy_rp::uart::{Async, Uart};
+use embassy_sync::blocking_mutex::Mutex;
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+use embassy_sync::signal::Signal;
+use embassy_time::{Duration, Timer, with_timeout};
+
+use super::protocol::{Instruction, REPLY_MAX, Reply};
+use super::{BusServoStatus, ServoBusProtocol};
+use crate::servo::{ServoCalibration, ServoOutput};
+use crate::{Error, Result};
+
+/// How long a servo has to answer an instruction, echo included.
+const REPLY_TIMEOUT: Duration = Duration::from_millis(20);
+
+/// How long to wait for stray bytes to stop after a failed exchange.
+const DISCARD_TIMEOUT: Duration = Duration::from_millis(2);
+
+/// How often the task reads back one servo's status when no commands arrive.
+const STATUS_POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+/// Set in a command when torque should be on. The low 16 bits hold the goal position.
+const TORQUE_BIT: u32 = 1 << 16;
+
+// Public so macro-generated code can reference it; hidden from docs.
+#[doc(hidden)]
+/// Static resources for a [`servo_bus!`](macro@crate::servo_bus::servo_bus): each servo's
+/// latest command and readback.
+pub struct ServoBusStatic<const N: usize> {
+    servos: [BusServoSlot; N],
+    command_signal: Signal<CriticalSectionRawMutex, ()>,
+}
+
+struct BusServoSlot {
+    command: AtomicU32,
+    // Replaced by `ServoBusStatic::servos` before any handle exists.
+    calibration: Mutex<CriticalSectionRawMutex, Cell<ServoCalibration>>,
+    status: Mutex<CriticalSectionRawMutex, Cell<Option<BusServoStatus>>>,
+    is_responding: AtomicBool,
+}
+
+impl BusServoSlot {
+    const fn new() -> Self {
+        Self {
+            command: AtomicU32::new(0),
+            calibration: Mutex::new(Cell::new(ServoCalibration::new(0, 0, 1))),
+            status: Mutex::new(Cell::new(None)),
+            is_responding: AtomicBool::new(false),
+        }
+    }
+
+    fn calibration(&self) -> ServoCalibration {
+        self.calibration.lock(Cell::get)
+    }
+
+    fn set_calibration(&self, calibration: ServoCalibration) {
+        self.calibration.lock(|cell| cell.set(calibration));
+    }
+}
+
+impl<const N: usize> ServoBusStatic<N> {
+    /// Create static resources for a servo bus.
+    #[must_use]
+    pub const fn new_static() -> Self {
+        assert!(N > 0, "servo_bus! needs at least one id");
+        Self {
+            servos: [const { BusServoSlot::new() }; N],
+            command_signal: Signal::new(),
+        }
+    }
+
+    /// One handle per servo, each starting at its center position.
+    #[must_use]
+    pub fn servos(
+        &'static self,
+        protocol: ServoBusProtocol,
+        ids: &[u8; N],
+        max_degrees: u16,
+    ) -> [BusServo; N] {
+        assert!(max_degrees > 0, "max_degrees must be positive");
+        for (index, &id) in ids.iter().enumerate() {
+            // 253 and up are reserved or broadcast on both protocols.
+            assert!(id < 253, "servo bus ids must be below 253");
+            assert!(!ids[..index].contains(&id), "servo bus ids must be unique");
+        }
+        let calibration = default_calibration(protocol, max_degrees);
+        core::array::from_fn(|index| {
+            let slot = &self.servos[index];
+            slot.set_calibration(calibration);
+            let mut bus_servo = BusServo {
+                slot,
+                command_signal: &self.command_signal,
+                position_max: protocol.position_max(),
+                max_degrees,
+                position: calibration.center_us(),
+                is_enabled: false,
+            };
+            bus_servo.hold();
+            bus_servo
+        })
+    }
+}
+
+/// `max_degrees` centered on the middle position, using the protocol's nominal resolution.
+fn default_calibration(protocol: ServoBusProtocol, max_degrees: u16) -> ServoCalibration {
+    let center = protocol.position_max() / 2 + 1;
+    let half_span = (u32::from(max_degrees) * (u32::from(protocol.position_max()) + 1)
+        / u32::from(protocol.position_span_degrees())
+        / 2)
+    .min(u32::from(center - 1)) as u16;
+    ServoCalibration::new(center - half_span, center, center + half_span)
+}
+
+/// One servo on a bus created by [`servo_bus!`](macro@crate::servo_bus::servo_bus).
+///
+/// `BusServo` has the same `set_degrees`/`hold`/`relax` API as [`Servo`](crate::servo::Servo).
+/// The bus's background task sends each change to the servo, and reads back the servo's
+/// position, load, voltage, temperature, and faults, available from a
+/// [`BusServoMonitor`].
+///
+/// To animate a `BusServo`, generate a [`servo_player!`](macro@crate::servo_player) type with
+/// the `bus_servo` flag instead of a `pin`, and pass the `BusServo` to its `new`. Take a
+/// [`monitor`](Self::monitor) first to keep reading back status.
+///
+/// See the [servo_bus module documentation](mod@crate::servo_bus) for examples.
+pub struct BusServo {
+    slot: &'static BusServoSlot,
+    command_signal: &'static Signal<CriticalSectionRawMutex, ()>,
+    position_max: u16,
+    max_degrees: u16,
+    position: u16,
+    is_enabled: bool,
+}
+
+impl BusServo {
+    /// Set position in degrees 0..=max_degrees, mapped to a goal position by the servo's
+    /// [`calibration`](Self::calibration).
+    ///
+    /// Automatically turns on torque if the servo was relaxed.
+    pub fn set_degrees(&mut self, degrees: u16) {
+        assert!((0..=self.max_degrees).contains(&degrees));
+        let position = self
+            .calibration()
+            .pulse_us(u32::from(degrees) * 1000, self.max_degrees);
+        info!("BusServo set_degrees({}) -> position {}", degrees, position);
+        self.position = position;
+        self.hold();
+    }
+
+    /// Set position in thousandths of a degree, without logging.
+    pub(crate) fn set_millidegrees(&mut self, millidegrees: u32) {
+        assert!(millidegrees <= u32::from(self.max_degrees) * 1000);
+        self.position = self.calibration().pulse_us(millidegrees, self.max_degrees);
+        self.hold();
+    }
+
+    /// Set the raw goal position, in the servo's own units (see
+    /// [`ServoBusProtocol::position_max`]).
+    ///
+    /// Unlike [`set_degrees`](Self::set_degrees), this leaves a relaxed servo relaxed.
+    pub fn set_position(&mut self, position: u16) {
+        assert!(position <= self.position_max, "position out of range");
+        self.position = position;
+        self.send();
+    }
+
+    /// Turn off torque, so the servo moves freely.
+    ///
+    /// This reduces power consumption and mechanical stress.
+    pub fn relax(&mut self) {
+        self.is_enabled = false;
+        self.send();
+    }
+
+    /// Turn torque back on.
+    ///
+    /// The servo will move back to its last commanded position.
+    pub fn hold(&mut self) {
+        self.is_enabled = true;
+        self.send();
+    }
+
+    /// The goal positions this servo maps angles to, as a [`ServoCalibration`] whose
+    /// "microseconds" are goal positions.
+    ///
+    /// By default, `max_degrees` is centered on the middle position at the protocol's nominal
+    /// resolution.
+    #[must_use]
+    pub fn calibration(&self) -> ServoCalibration {
+        self.slot.calibration()
+    }
+
+    /// Map angles to the goal positions in `calibration` (in place of microseconds).
+    ///
+    /// Takes effect on the next [`set_degrees`](Self::set_degrees). Also changes
+    /// [`BusServoMonitor::degrees`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if `calibration.max_us()` is above
+    /// [`ServoBusProtocol::position_max`].
+    pub fn set_calibration(&mut self, calibration: ServoCalibration) {
+        assert!(
+            calibration.max_us() <= self.position_max,
+            "calibration must fit in the servo's positions"
+        );
+        self.slot.set_calibration(calibration);
+    }
+
+    /// A copyable handle for reading back this servo's status, which keeps working after
+    /// the `BusServo` is handed to a servo player.
+    #[must_use]
+    pub const fn monitor(&self) -> BusServoMonitor {
+        BusServoMonitor {
+            slot: self.slot,
+            max_degrees: self.max_degrees,
+        }
+    }
+
+    fn send(&self) {
+        let torque = if self.is_enabled { TORQUE_BIT } else { 0 };
+        self.slot
+            .command
+            .store(u32::from(self.position) | torque, Ordering::Relaxed);
+        self.command_signal.signal(());
+    }
+}
+
+impl ServoOutput for BusServo {
+    fn set_degrees(&mut self, degrees: u16) {
+        Self::set_degrees(self, degrees);
+    }
+
+    fn set_millidegrees(&mut self, millidegrees: u32) {
+        Self::set_millidegrees(self, millidegrees);
+    }
+
+    fn max_degrees(&self) -> u16 {
+        self.max_degrees
+    }
+
+    fn hold(&mut self) {
+        Self::hold(self);
+    }
+
+    fn relax(&mut self) {
+        Self::relax(self);
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.is_enabled
+    }
+
+    fn set_calibration(&mut self, calibration: ServoCalibration) {
+        // Runs in a servo player's task, so skip a calibration that doesn't fit rather
+        // than panic there.
+        if calibration.max_us() > self.position_max {
+            warn!("Bus servo: ignoring calibration beyond the servo's positions");
+            return;
+        }
+        Self::set_calibration(self, calibration);
+    }
+}
+
+/// Reads back the status of one [`BusServo`].
+///
+/// The bus's background task reads each servo's status in turn, about every 20 ms times the
+/// number of servos on the bus.
+#[derive(Clone, Copy)]
+pub struct BusServoMonitor {
+    slot: &'static BusServoSlot,
+    max_degrees: u16,
+}
+
+impl BusServoMonitor {
+    /// The most recent status read from the servo, or `None` before the first read.
+    #[must_use]
+    pub fn status(&self) -> Option<BusServoStatus> {
+        self.slot.status.lock(Cell::get)
+    }
+
+    /// The servo's present position in degrees, according to its calibration, or `None`
+    /// before the first read. Positions outside the calibration read as 0 or `max_degrees`.
+    #[must_use]
+    pub fn degrees(&self) -> Option<u16> {
+        let status = self.status()?;
+        let millidegrees = self
+            .slot
+            .calibration()
+            .millidegrees(status.position(), self.max_degrees);
+        Some(((millidegrees + 500) / 1000) as u16)
+    }
+
+    /// Whether the servo answered its most recent instruction. A servo that stops answering
+    /// keeps its last [`status`](Self::status).
+    #[must_use]
+    pub fn is_responding(&self) -> bool {
+        self.slot.is_responding.load(Ordering::Relaxed)
+    }
+}
+
+// Public so macro-generated code can reference it; hidden from docs.
+#[doc(hidden)]
+/// The half-duplex UART, plus the direction pin for adapters that need one.
+pub struct ServoBusPort {
+    uart: Uart<'static, Async>,
+    direction: Option<Output<'static>>,
+    protocol: ServoBusProtocol,
+    echo: bool,
+}
+
+impl ServoBusPort {
+    /// Wrap a configured UART. `direction` is driven high while transmitting. With `echo`,
+    /// each transmitted byte is expected back before the reply.
+    #[must_use]
+    pub const fn new(
+        uart: Uart<'static, Async>,
+        direction: Option<Output<'static>>,
+        protocol: ServoBusProtocol,
+        echo: bool,
+    ) -> Self {
+        Self {
+            uart,
+            direction,
+            protocol,
+            echo,
+        }
+    }
+
+    /// Send `command` (see [`TORQUE_BIT`]) to servo `id`, given the last command it accepted.
+    async fn apply(&mut self, id: u8, sent: Option<u32>, command: u32) -> Result<()> {
+        if command & TORQUE_BIT == 0 {
+            let instruction = self.protocol.write_torque_enable(id, false);
+            self.transact(id, &instruction).await?;
+            return Ok(());
+        }
+        // Goal first, so turning torque on doesn't jerk toward a stale goal.
+        let instruction = self.protocol.write_goal_position(id, command as u16);
+        self.transact(id, &instruction).await?;
+        if sent.is_none_or(|sent| sent & TORQUE_BIT == 0) {
+            let instruction = self.protocol.write_torque_enable(id, true);
+            self.transact(id, &instruction).await?;
+        }
+        Ok(())
+    }
+
+    async fn read_status(&mut self, id: u8) -> Result<BusServoStatus> {
+        let instruction = self.protocol.read_status_block(id);
+        let reply = self.transact(id, &instruction).await?;
+        let hardware_error = if self.protocol.needs_hardware_error(&reply) {
+            let instruction = self.protocol.read_hardware_error(id);
+            let fault_reply = self.transact(id, &instruction).await?;
+            *fault_reply
+                .params
+                .first()
+                .ok_or(Error::ServoBusPacket("bad status length"))?
+        } else {
+            0
+        };
+        self.protocol.decode_status(&reply, hardware_error)
+    }
+
+    /// Send `instruction` to servo `id` and return its checked reply.
+    async fn transact(&mut self, id: u8, instruction: &Instruction) -> Result<Reply> {
+        let result = with_timeout(REPLY_TIMEOUT, self.exchange(id, instruction))
+            .await
+            .unwrap_or(Err(Error::ServoBusTimeout(id)));
+        if result.is_err() {
+            self.discard_input().await;
+        }
+        result
+    }
+
+    async fn exchange(&mut self, id: u8, instruction: &Instruction) -> Result<Reply> {
+        if let Some(direction) = &mut self.direction {
+            direction.set_high();
+        }
+        let written = match self.uart.write(instruction).await {
+            // Release the bus only once the last stop bit is out.
+            Ok(()) => self.uart.blocking_flush(),
+            Err(err) => Err(err),
+        };
+        if let Some(direction) = &mut self.direction {
+            direction.set_low();
+        }
+        written.map_err(uart_error)?;
+
+        let mut reply = [0_u8; REPLY_MAX];
+        if self.echo {
+            self.uart
+                .read(&mut reply[..instruction.len()])
+                .await
+                .map_err(uart_error)?;
+        }
+        let header_len = self.protocol.reply_header_len();
+        self.uart
+            .read(&mut reply[..header_len])
+            .await
+            .map_err(uart_error)?;
+        let reply_len = header_len + self.protocol.reply_remaining_len(&reply[..header_len])?;
+        self.uart
+            .read(&mut reply[header_len..reply_len])
+            .await
+            .map_err(uart_error)?;
+        self.protocol.decode_reply(id, &reply[..reply_len])
+    }
+
+    /// Drop whatever is left of a bad reply so the next exchange starts clean.
+    async fn discard_input(&mut self) {
+        let mut byte = [0_u8];
+        for _ in 0..REPLY_MAX {
+            if !matches!(
+                with_timeout(DISCARD_TIMEOUT, self.uart.read(&mut byte)).await,
+                Ok(Ok(()))
+            ) {
+                break;
+            }
+        }
+    }
+}
+
+fn uart_error(_err: embassy_rp::uart::Error) -> Error {
+    Error::ServoBusPacket("UART error")
+}
+
+// Called by macro-generated code in downstream crates; must be public.
+#[doc(hidden)]
+pub async fn servo_bus_device_loop<const N: usize>(
+    servo_bus_static: &'static ServoBusStatic<N>,
+    mut port: ServoBusPort,
+    ids: [u8; N],
+) -> ! {
+    let mut sent: [Option<u32>; N] = [None; N];
+    let mut poll_index = 0;
+    loop {
+        for (index, slot) in servo_bus_static.servos.iter().enumerate() {
+            let command = slot.command.load(Ordering::Relaxed);
+            if sent[index] == Some(command) {
+                continue;
+            }
+            match port.apply(ids[index], sent[index], command).await {
+                Ok(()) => {
+                    sent[index] = Some(command);
+                    slot.is_responding.store(true, Ordering::Relaxed);
+                }
+                Err(err) => {
+                    warn!("Servo bus: servo {}: {}", ids[index], Display2Format(&err));
+                    slot.is_responding.store(false, Ordering::Relaxed);
+                }
+            }
+        }
+
+        // Read back one servo per pass, so commands are never held up for long.
+        let slot = &servo_bus_static.servos[poll_index];
+        match port.read_status(ids[poll_index]).await {
+            Ok(status) => {
+                slot.status.lock(|cell| cell.set(Some(status)));
+                slot.is_responding.store(true, Ordering::Relaxed);
+            }
+            Err(err) => {
+                warn!(
+                    "Servo bus: servo {}: {}",
+                    ids[poll_index],
+                    Display2Format(&err)
+                );
+                slot.is_responding.store(false, Ordering::Relaxed);
+            }
+        }
+        poll_index = (poll_index + 1) % N;
+
+        select(
+            servo_bus_static.command_signal.wait(),
+            Timer::after(STATUS_POLL_INTERVAL),
+        )
+        .await;
+    }
+}
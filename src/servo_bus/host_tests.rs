@@ -0,0 +1,213 @@
+#![allow(missing_docs)]
+
+use super::protocol::{BusServoFaults, Reply, ServoBusProtocol, dynamixel_packet, feetech_packet};
+use crate::Error;
+
+const DYNAMIXEL_STATUS: u8 = 0x55;
+
+/// Decode a whole reply, reading the header and remainder the way the bus loop does.
+fn decode(protocol: ServoBusProtocol, id: u8, packet: &[u8]) -> crate::Result<Reply> {
+    let header_len = protocol.reply_header_len();
+    let remaining_len = protocol.reply_remaining_len(&packet[..header_len])?;
+    assert_eq!(header_len + remaining_len, packet.len());
+    protocol.decode_reply(id, packet)
+}
+
+#[test]
+fn feetech_read_status_block_packet() {
+    let packet = ServoBusProtocol::FeetechSts.read_status_block(1);
+    assert_eq!(
+        packet.as_slice(),
+        &[0xFF, 0xFF, 0x01, 0x04, 0x02, 0x38, 0x08, 0xB8]
+    );
+}
+
+#[test]
+fn feetech_goal_position_byte_order() {
+    let sts = ServoBusProtocol::FeetechSts.write_goal_position(1, 2048);
+    assert_eq!(
+        sts.as_slice(),
+        &[0xFF, 0xFF, 0x01, 0x05, 0x03, 0x2A, 0x00, 0x08, 0xC4]
+    );
+    let scs = ServoBusProtocol::FeetechScs.write_goal_position(1, 512);
+    assert_eq!(
+        scs.as_slice(),
+        &[0xFF, 0xFF, 0x01, 0x05, 0x03, 0x2A, 0x02, 0x00, 0xCA]
+    );
+}
+
+#[test]
+fn feetech_torque_enable_packet() {
+    let packet = ServoBusProtocol::FeetechSts.write_torque_enable(3, true);
+    assert_eq!(
+        packet.as_slice(),
+        &[0xFF, 0xFF, 0x03, 0x04, 0x03, 0x28, 0x01, 0xCC]
+    );
+}
+
+#[test]
+fn dynamixel_ping_crc() {
+    // Ping example from the Dynamixel Protocol 2.0 manual.
+    let packet = dynamixel_packet(1, 0x01, &[]);
+    assert_eq!(
+        packet.as_slice(),
+        &[0xFF, 0xFF, 0xFD, 0x00, 0x01, 0x03, 0x00, 0x01, 0x19, 0x4E]
+    );
+}
+
+#[test]
+fn dynamixel_goal_position_packet() {
+    // Write example from the Dynamixel Protocol 2.0 manual: goal position 512 on id 1.
+    let packet = ServoBusProtocol::Dynamixel2.write_goal_position(1, 512);
+    assert_eq!(
+        packet.as_slice(),
+        &[
+            0xFF, 0xFF, 0xFD, 0x00, 0x01, 0x09, 0x00, 0x03, 0x74, 0x00, 0x00, 0x02, 0x00, 0x00,
+            0xCA, 0x89
+        ]
+    );
+}
+
+#[test]
+fn dynamixel_decode_manual_status() {
+    // Status example from the Dynamixel Protocol 2.0 manual: a model number reply.
+    let packet = [
+        0xFF, 0xFF, 0xFD, 0x00, 0x01, 0x08, 0x00, 0x55, 0x00, 0xA6, 0x00, 0x00, 0x00, 0x8C, 0xC0,
+    ];
+    let reply = decode(ServoBusProtocol::Dynamixel2, 1, &packet).unwrap();
+    assert_eq!(reply.error, 0);
+    assert_eq!(reply.params.as_slice(), &[0xA6, 0x00, 0x00, 0x00]);
+}
+
+#[test]
+fn dynamixel_byte_stuffing_round_trip() {
+    let packet = dynamixel_packet(7, DYNAMIXEL_STATUS, &[0x00, 0xFF, 0xFF, 0xFD, 0x01]);
+    // One `FD` stuffed after the `FF FF FD` in the parameters.
+    assert_eq!(
+        &packet[7..13],
+        &[DYNAMIXEL_STATUS, 0x00, 0xFF, 0xFF, 0xFD, 0xFD]
+    );
+    assert_eq!(usize::from(packet[5]), packet.len() - 7);
+    let reply = decode(ServoBusProtocol::Dynamixel2, 7, &packet).unwrap();
+    assert_eq!(reply.params.as_slice(), &[0xFF, 0xFF, 0xFD, 0x01]);
+}
+
+#[test]
+fn feetech_rejects_bad_replies() {
+    let protocol = ServoBusProtocol::FeetechSts;
+    let mut packet = feetech_packet(1, 0x00, &[0x10, 0x20]);
+    assert!(decode(protocol, 1, &packet).is_ok());
+    assert!(matches!(
+        decode(protocol, 2, &packet),
+        Err(Error::ServoBusPacket(_))
+    ));
+    let last = packet.len() - 1;
+    packet[last] ^= 0x01;
+    assert!(matches!(
+        decode(protocol, 1, &packet),
+        Err(Error::ServoBusPacket("bad checksum"))
+    ));
+    assert!(matches!(
+        protocol.reply_remaining_len(&[0xFF, 0x00, 0x01, 0x04]),
+        Err(Error::ServoBusPacket("bad header"))
+    ));
+    assert!(matches!(
+        protocol.reply_remaining_len(&[0xFF, 0xFF, 0x01, 0x01]),
+        Err(Error::ServoBusPacket("bad length"))
+    ));
+}
+
+#[test]
+fn dynamixel_rejects_bad_replies() {
+    let protocol = ServoBusProtocol::Dynamixel2;
+    let mut packet = dynamixel_packet(1, DYNAMIXEL_STATUS, &[0x00, 0x42]);
+    assert!(decode(protocol, 1, &packet).is_ok());
+    assert!(matches!(
+        decode(protocol, 2, &packet),
+        Err(Error::ServoBusPacket(_))
+    ));
+    packet[9] ^= 0x01;
+    assert!(matches!(
+        decode(protocol, 1, &packet),
+        Err(Error::ServoBusPacket("bad CRC"))
+    ));
+
+    // An instruction packet echoed back is not a status packet.
+    let echo = protocol.write_torque_enable(1, true);
+    assert!(matches!(
+        decode(protocol, 1, &echo),
+        Err(Error::ServoBusPacket("not a status packet"))
+    ));
+
+    // Error 0x07 (access error) rejects; the alert bit alone does not.
+    let rejected = dynamixel_packet(1, DYNAMIXEL_STATUS, &[0x87]);
+    assert!(matches!(
+        decode(protocol, 1, &rejected),
+        Err(Error::ServoBusRejected(0x07))
+    ));
+    let alert = dynamixel_packet(1, DYNAMIXEL_STATUS, &[0x80]);
+    let reply = decode(protocol, 1, &alert).unwrap();
+    assert!(protocol.needs_hardware_error(&reply));
+    assert!(!ServoBusProtocol::FeetechSts.needs_hardware_error(&reply));
+}
+
+#[test]
+fn feetech_decode_status() {
+    // Position 2048, speed 0, load 100 toward lower positions, 12.0 V, 35 °C,
+    // with overheat and overload flagged.
+    let packet = feetech_packet(
+        1,
+        0b0010_0100,
+        &[0x00, 0x08, 0x00, 0x00, 0x64, 0x04, 120, 35],
+    );
+    let protocol = ServoBusProtocol::FeetechSts;
+    let reply = decode(protocol, 1, &packet).unwrap();
+    let status = protocol.decode_status(&reply, 0).unwrap();
+    assert_eq!(status.position(), 2048);
+    assert_eq!(status.load_permille(), -100);
+    assert_eq!(status.voltage_decivolts(), 120);
+    assert_eq!(status.temperature_celsius(), 35);
+    let faults = status.faults();
+    assert!(faults.overheat() && faults.overload());
+    assert!(!faults.voltage() && !faults.sensor() && !faults.overcurrent());
+
+    // SCS registers are big-endian.
+    let packet = feetech_packet(1, 0, &[0x02, 0x00, 0x00, 0x00, 0x00, 0x32, 60, 30]);
+    let protocol = ServoBusProtocol::FeetechScs;
+    let reply = decode(protocol, 1, &packet).unwrap();
+    let status = protocol.decode_status(&reply, 0).unwrap();
+    assert_eq!(status.position(), 512);
+    assert_eq!(status.load_permille(), 50);
+    assert!(status.faults().is_empty());
+
+    let short = feetech_packet(1, 0, &[0x00, 0x08]);
+    let reply = decode(protocol, 1, &short).unwrap();
+    assert!(matches!(
+        protocol.decode_status(&reply, 0),
+        Err(Error::ServoBusPacket(_))
+    ));
+}
+
+#[test]
+fn dynamixel_decode_status() {
+    let mut params = [0_u8; 22];
+    // Error byte, then the 21-byte status block starting at Present Load (126).
+    params[0] = 0x80;
+    params[1..3].copy_from_slice(&(-50_i16).to_le_bytes());
+    params[7..11].copy_from_slice(&3000_i32.to_le_bytes());
+    params[19..21].copy_from_slice(&118_u16.to_le_bytes());
+    params[21] = 41;
+    let packet = dynamixel_packet(5, DYNAMIXEL_STATUS, &params);
+    let protocol = ServoBusProtocol::Dynamixel2;
+    let reply = decode(protocol, 5, &packet).unwrap();
+    assert!(protocol.needs_hardware_error(&reply));
+    let status = protocol.decode_status(&reply, 0b0010_0100).unwrap();
+    assert_eq!(status.position(), 3000);
+    assert_eq!(status.load_permille(), -50);
+    assert_eq!(status.voltage_decivolts(), 118);
+    assert_eq!(status.temperature_celsius(), 41);
+    let faults = status.faults();
+    assert!(faults.overheat() && faults.overload());
+    assert!(!faults.sensor() && !faults.voltage() && !faults.overcurrent());
+    assert_ne!(faults, BusServoFaults::default());
+}
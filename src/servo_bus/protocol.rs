@@ -0,0 +1,488 @@
+//! Packet encoding and decoding for Feetech SCS/STS and Dynamixel Protocol 2.0 servo buses.
+//!
+//! Everything here is plain byte manipulation, so it is unit-tested on the host.
+
+use heapless::Vec;
+
+use crate::{Error, Result};
+
+/// Longest instruction packet sent on the bus (bytes). Short enough that an echo of it
+/// fits in the UART receive FIFO.
+pub(crate) const INSTRUCTION_MAX: usize = 32;
+
+/// Longest status packet read from the bus (bytes).
+pub(crate) const REPLY_MAX: usize = 64;
+
+/// An encoded instruction packet.
+pub(crate) type Instruction = Vec<u8, INSTRUCTION_MAX>;
+
+const READ: u8 = 0x02;
+const WRITE: u8 = 0x03;
+
+const FEETECH_TORQUE_ENABLE: u8 = 40;
+const FEETECH_GOAL_POSITION: u8 = 42;
+/// Present position, speed, load, voltage and temperature.
+const FEETECH_STATUS_BLOCK: (u8, u8) = (56, 8);
+
+const DYNAMIXEL_HEADER: [u8; 4] = [0xFF, 0xFF, 0xFD, 0x00];
+const DYNAMIXEL_STATUS: u8 = 0x55;
+/// Set in a Dynamixel reply's error byte when the Hardware Error Status register is nonzero.
+const DYNAMIXEL_ALERT: u8 = 0x80;
+const DYNAMIXEL_TORQUE_ENABLE: u16 = 64;
+const DYNAMIXEL_HARDWARE_ERROR_STATUS: u16 = 70;
+const DYNAMIXEL_GOAL_POSITION: u16 = 116;
+/// Present load, velocity, position, trajectories, input voltage and temperature.
+const DYNAMIXEL_STATUS_BLOCK: (u16, u16) = (126, 21);
+
+/// The wire protocol spoken by the servos on a [`servo_bus!`](macro@crate::servo_bus::servo_bus).
+#[derive(Clone, Copy, Debug, defmt::Format, PartialEq, Eq)]
+pub enum ServoBusProtocol {
+    /// Feetech STS/SMS series (e.g. STS3215): 4096 positions per turn, little-endian registers.
+    FeetechSts,
+    /// Feetech SCS series (e.g. SCS0009, SCS15): 1024 positions over about 300°, big-endian
+    /// registers.
+    FeetechScs,
+    /// Dynamixel Protocol 2.0, X series control table (e.g. XL430, XM430): 4096 positions
+    /// per turn.
+    Dynamixel2,
+}
+
+impl ServoBusProtocol {
+    /// Baud rate the servos ship with.
+    #[must_use]
+    pub const fn default_baudrate(self) -> u32 {
+        match self {
+            Self::FeetechSts | Self::FeetechScs => 1_000_000,
+            Self::Dynamixel2 => 57_600,
+        }
+    }
+
+    /// Highest goal position the servo accepts.
+    #[must_use]
+    pub const fn position_max(self) -> u16 {
+        match self {
+            Self::FeetechSts | Self::Dynamixel2 => 4095,
+            Self::FeetechScs => 1023,
+        }
+    }
+
+    /// Degrees covered by positions `0..=position_max()`.
+    #[must_use]
+    pub const fn position_span_degrees(self) -> u16 {
+        match self {
+            Self::FeetechSts | Self::Dynamixel2 => 360,
+            Self::FeetechScs => 300,
+        }
+    }
+
+    pub(crate) fn write_torque_enable(self, id: u8, enable: bool) -> Instruction {
+        let enable = u8::from(enable);
+        match self {
+            Self::FeetechSts | Self::FeetechScs => {
+                feetech_packet(id, WRITE, &[FEETECH_TORQUE_ENABLE, enable])
+            }
+            Self::Dynamixel2 => {
+                let [address_low, address_high] = DYNAMIXEL_TORQUE_ENABLE.to_le_bytes();
+                dynamixel_packet(id, WRITE, &[address_low, address_high, enable])
+            }
+        }
+    }
+
+    pub(crate) fn write_goal_position(self, id: u8, position: u16) -> Instruction {
+        assert!(
+            position <= self.position_max(),
+            "goal position out of range"
+        );
+        match self {
+            Self::FeetechSts => {
+                let [low, high] = position.to_le_bytes();
+                feetech_packet(id, WRITE, &[FEETECH_GOAL_POSITION, low, high])
+            }
+            Self::FeetechScs => {
+                let [high, low] = position.to_be_bytes();
+                feetech_packet(id, WRITE, &[FEETECH_GOAL_POSITION, high, low])
+            }
+            Self::Dynamixel2 => {
+                let [address_low, address_high] = DYNAMIXEL_GOAL_POSITION.to_le_bytes();
+                let [b0, b1, b2, b3] = u32::from(position).to_le_bytes();
+                dynamixel_packet(id, WRITE, &[address_low, address_high, b0, b1, b2, b3])
+            }
+        }
+    }
+
+    pub(crate) fn read_status_block(self, id: u8) -> Instruction {
+        match self {
+            Self::FeetechSts | Self::FeetechScs => {
+                let (address, len) = FEETECH_STATUS_BLOCK;
+                feetech_packet(id, READ, &[address, len])
+            }
+            Self::Dynamixel2 => dynamixel_read(id, DYNAMIXEL_STATUS_BLOCK),
+        }
+    }
+
+    /// Read the Hardware Error Status register, for a Dynamixel reply with
+    /// [`needs_hardware_error`](Self::needs_hardware_error) set.
+    pub(crate) fn read_hardware_error(self, id: u8) -> Instruction {
+        assert!(
+            matches!(self, Self::Dynamixel2),
+            "only Dynamixel reports faults in a separate register"
+        );
+        dynamixel_read(id, (DYNAMIXEL_HARDWARE_ERROR_STATUS, 1))
+    }
+
+    /// Whether `reply` flags a hardware fault whose details need
+    /// [`read_hardware_error`](Self::read_hardware_error). Feetech replies carry the details.
+    pub(crate) const fn needs_hardware_error(self, reply: &Reply) -> bool {
+        matches!(self, Self::Dynamixel2) && reply.error & DYNAMIXEL_ALERT != 0
+    }
+
+    /// Bytes to read before [`reply_remaining_len`](Self::reply_remaining_len) can tell how
+    /// long the reply is.
+    pub(crate) const fn reply_header_len(self) -> usize {
+        match self {
+            Self::FeetechSts | Self::FeetechScs => 4,
+            Self::Dynamixel2 => 7,
+        }
+    }
+
+    /// Bytes that follow `header` in the reply.
+    pub(crate) fn reply_remaining_len(self, header: &[u8]) -> Result<usize> {
+        assert_eq!(header.len(), self.reply_header_len());
+        let (remaining_len, min_len) = match self {
+            Self::FeetechSts | Self::FeetechScs => {
+                if header[..2] != [0xFF, 0xFF] {
+                    return Err(Error::ServoBusPacket("bad header"));
+                }
+                // Error byte and checksum.
+                (usize::from(header[3]), 2)
+            }
+            Self::Dynamixel2 => {
+                if header[..4] != DYNAMIXEL_HEADER {
+                    return Err(Error::ServoBusPacket("bad header"));
+                }
+                // Instruction, error byte, and CRC.
+                (usize::from(u16::from_le_bytes([header[5], header[6]])), 4)
+            }
+        };
+        if remaining_len < min_len || header.len() + remaining_len > REPLY_MAX {
+            return Err(Error::ServoBusPacket("bad length"));
+        }
+        Ok(remaining_len)
+    }
+
+    /// Check a whole status packet from servo `id` and extract its error byte and parameters.
+    pub(crate) fn decode_reply(self, id: u8, packet: &[u8]) -> Result<Reply> {
+        let header_len = self.reply_header_len();
+        if packet.len() < header_len
+            || header_len + self.reply_remaining_len(&packet[..header_len])? != packet.len()
+        {
+            return Err(Error::ServoBusPacket("bad length"));
+        }
+        match self {
+            Self::FeetechSts | Self::FeetechScs => {
+                let (body, checksum) = packet.split_at(packet.len() - 1);
+                if feetech_checksum(&body[2..]) != checksum[0] {
+                    return Err(Error::ServoBusPacket("bad checksum"));
+                }
+                if packet[2] != id {
+                    return Err(Error::ServoBusPacket("reply from another servo"));
+                }
+                Ok(Reply {
+                    error: packet[4],
+                    params: Vec::from_slice(&body[5..]).expect("reply params fit in REPLY_MAX"),
+                })
+            }
+            Self::Dynamixel2 => {
+                let (body, crc) = packet.split_at(packet.len() - 2);
+                if dynamixel_crc(body).to_le_bytes() != [crc[0], crc[1]] {
+                    return Err(Error::ServoBusPacket("bad CRC"));
+                }
+                if packet[4] != id {
+                    return Err(Error::ServoBusPacket("reply from another servo"));
+                }
+                if packet[7] != DYNAMIXEL_STATUS {
+                    return Err(Error::ServoBusPacket("not a status packet"));
+                }
+                let error = packet[8];
+                if error & !DYNAMIXEL_ALERT != 0 {
+                    return Err(Error::ServoBusRejected(error & !DYNAMIXEL_ALERT));
+                }
+                // Stuffing covers the instruction and error bytes too, so unstuff from there.
+                let unstuffed = dynamixel_unstuff(&body[7..]);
+                Ok(Reply {
+                    error,
+                    params: Vec::from_slice(&unstuffed[2..])
+                        .expect("reply params fit in REPLY_MAX"),
+                })
+            }
+        }
+    }
+
+    /// Decode the reply to [`read_status_block`](Self::read_status_block).
+    ///
+    /// `hardware_error` is the reply to [`read_hardware_error`](Self::read_hardware_error) for
+    /// a Dynamixel reply that needed one, else 0.
+    pub(crate) fn decode_status(self, reply: &Reply, hardware_error: u8) -> Result<BusServoStatus> {
+        let params = reply.params.as_slice();
+        match self {
+            Self::FeetechSts | Self::FeetechScs => {
+                if params.len() != usize::from(FEETECH_STATUS_BLOCK.1) {
+                    return Err(Error::ServoBusPacket("bad status length"));
+                }
+                let read_u16 = |offset: usize| {
+                    let bytes = [params[offset], params[offset + 1]];
+                    if matches!(self, Self::FeetechScs) {
+                        u16::from_be_bytes(bytes)
+                    } else {
+                        u16::from_le_bytes(bytes)
+                    }
+                };
+                // Bit 15 marks a negative (multi-turn) position; bit 10 the load direction.
+                let position = read_u16(0);
+                let position = if position & 0x8000 == 0 { position } else { 0 };
+                let load = read_u16(4);
+                let load_permille = (load & 0x3FF).min(1000) as i16;
+                let load_permille = if load & 0x400 == 0 {
+                    load_permille
+                } else {
+                    -load_permille
+                };
+                Ok(BusServoStatus {
+                    position,
+                    load_permille,
+                    voltage_decivolts: u16::from(params[6]),
+                    temperature_celsius: params[7],
+                    faults: BusServoFaults::from_feetech(reply.error),
+                })
+            }
+            Self::Dynamixel2 => {
+                if params.len() != usize::from(DYNAMIXEL_STATUS_BLOCK.1) {
+                    return Err(Error::ServoBusPacket("bad status length"));
+                }
+                let position = i32::from_le_bytes([params[6], params[7], params[8], params[9]]);
+                Ok(BusServoStatus {
+                    position: position.clamp(0, i32::from(u16::MAX)) as u16,
+                    load_permille: i16::from_le_bytes([params[0], params[1]]),
+                    voltage_decivolts: u16::from_le_bytes([params[18], params[19]]),
+                    temperature_celsius: params[20],
+                    faults: BusServoFaults::from_dynamixel(hardware_error),
+                })
+            }
+        }
+    }
+}
+
+/// A checked status packet: the servo's error byte and the parameters that followed it.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct Reply {
+    pub(crate) error: u8,
+    pub(crate) params: Vec<u8, REPLY_MAX>,
+}
+
+/// The most recent status read back from a bus servo.
+///
+/// See [`BusServoMonitor::status`](crate::servo_bus::BusServoMonitor::status) for usage.
+#[derive(Clone, Copy, Debug, defmt::Format, PartialEq, Eq)]
+pub struct BusServoStatus {
+    position: u16,
+    load_permille: i16,
+    voltage_decivolts: u16,
+    temperature_celsius: u8,
+    faults: BusServoFaults,
+}
+
+impl BusServoStatus {
+    /// Present position, in the servo's own units (see
+    /// [`ServoBusProtocol::position_max`]).
+    #[must_use]
+    pub const fn position(self) -> u16 {
+        self.position
+    }
+
+    /// Present load (or current, on models that report it instead) in thousandths of the
+    /// maximum. Negative when pushing toward lower positions.
+    #[must_use]
+    pub const fn load_permille(self) -> i16 {
+        self.load_permille
+    }
+
+    /// Supply voltage at the servo, in tenths of a volt.
+    #[must_use]
+    pub const fn voltage_decivolts(self) -> u16 {
+        self.voltage_decivolts
+    }
+
+    /// Internal temperature in °C.
+    #[must_use]
+    pub const fn temperature_celsius(self) -> u8 {
+        self.temperature_celsius
+    }
+
+    /// Faults the servo reports. Most servos turn off torque until power-cycled after one.
+    #[must_use]
+    pub const fn faults(self) -> BusServoFaults {
+        self.faults
+    }
+}
+
+/// Hardware faults reported by a bus servo.
+#[derive(Clone, Copy, Debug, Default, defmt::Format, PartialEq, Eq)]
+pub struct BusServoFaults(u8);
+
+impl BusServoFaults {
+    const VOLTAGE: u8 = 1 << 0;
+    const SENSOR: u8 = 1 << 1;
+    const OVERHEAT: u8 = 1 << 2;
+    const OVERCURRENT: u8 = 1 << 3;
+    const OVERLOAD: u8 = 1 << 4;
+
+    /// Map the error byte of a Feetech status packet.
+    const fn from_feetech(error: u8) -> Self {
+        // Bits 0-3 (voltage, sensor, overheat, overcurrent) already line up.
+        let mut faults = error & 0b0000_1111;
+        if error & (1 << 5) != 0 {
+            faults |= Self::OVERLOAD;
+        }
+        Self(faults)
+    }
+
+    /// Map a Dynamixel Hardware Error Status register.
+    const fn from_dynamixel(hardware_error: u8) -> Self {
+        let mut faults = 0;
+        if hardware_error & (1 << 0) != 0 {
+            faults |= Self::VOLTAGE;
+        }
+        if hardware_error & (1 << 2) != 0 {
+            faults |= Self::OVERHEAT;
+        }
+        if hardware_error & (1 << 3) != 0 {
+            faults |= Self::SENSOR;
+        }
+        if hardware_error & (1 << 4) != 0 {
+            faults |= Self::OVERCURRENT;
+        }
+        if hardware_error & (1 << 5) != 0 {
+            faults |= Self::OVERLOAD;
+        }
+        Self(faults)
+    }
+
+    /// No faults reported.
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Supply voltage outside the servo's configured range.
+    #[must_use]
+    pub const fn voltage(self) -> bool {
+        self.0 & Self::VOLTAGE != 0
+    }
+
+    /// Position sensor or encoder failure.
+    #[must_use]
+    pub const fn sensor(self) -> bool {
+        self.0 & Self::SENSOR != 0
+    }
+
+    /// Internal temperature above the servo's configured limit.
+    #[must_use]
+    pub const fn overheat(self) -> bool {
+        self.0 & Self::OVERHEAT != 0
+    }
+
+    /// Motor current above the servo's limit (electrical shock, on Dynamixel).
+    #[must_use]
+    pub const fn overcurrent(self) -> bool {
+        self.0 & Self::OVERCURRENT != 0
+    }
+
+    /// Sustained load above the servo's limit.
+    #[must_use]
+    pub const fn overload(self) -> bool {
+        self.0 & Self::OVERLOAD != 0
+    }
+}
+
+/// Feetech packet: `FF FF id len instruction params… checksum`. Status packets have the same
+/// framing, with the error byte in place of the instruction.
+pub(crate) fn feetech_packet(id: u8, instruction: u8, params: &[u8]) -> Instruction {
+    let len = u8::try_from(params.len() + 2).expect("params fit in a packet");
+    let mut packet = Instruction::new();
+    packet
+        .extend_from_slice(&[0xFF, 0xFF, id, len, instruction])
+        .and_then(|()| packet.extend_from_slice(params))
+        .expect("instruction fits in INSTRUCTION_MAX");
+    let checksum = feetech_checksum(&packet[2..]);
+    packet
+        .push(checksum)
+        .expect("instruction fits in INSTRUCTION_MAX");
+    packet
+}
+
+/// Inverted low byte of the sum of everything after the `FF FF` header.
+fn feetech_checksum(bytes: &[u8]) -> u8 {
+    !bytes.iter().fold(0_u8, |sum, &byte| sum.wrapping_add(byte))
+}
+
+/// Dynamixel Protocol 2.0 packet: `FF FF FD 00 id len_l len_h instruction params… crc_l crc_h`,
+/// with `FD` stuffed after any `FF FF FD` in the instruction and parameters.
+pub(crate) fn dynamixel_packet(id: u8, instruction: u8, params: &[u8]) -> Instruction {
+    let mut packet = Instruction::new();
+    packet
+        .extend_from_slice(&DYNAMIXEL_HEADER)
+        .and_then(|()| packet.extend_from_slice(&[id, 0, 0]))
+        .expect("instruction fits in INSTRUCTION_MAX");
+    for &byte in core::iter::once(&instruction).chain(params) {
+        packet
+            .push(byte)
+            .expect("instruction fits in INSTRUCTION_MAX");
+        if packet.len() - 7 >= 3 && packet[packet.len() - 3..] == [0xFF, 0xFF, 0xFD] {
+            packet
+                .push(0xFD)
+                .expect("instruction fits in INSTRUCTION_MAX");
+        }
+    }
+    // Length counts the (stuffed) instruction and parameters, plus the CRC.
+    let [len_low, len_high] = u16::try_from(packet.len() - 7 + 2)
+        .expect("packet length fits in u16")
+        .to_le_bytes();
+    packet[5] = len_low;
+    packet[6] = len_high;
+    let [crc_low, crc_high] = dynamixel_crc(&packet).to_le_bytes();
+    packet
+        .extend_from_slice(&[crc_low, crc_high])
+        .expect("instruction fits in INSTRUCTION_MAX");
+    packet
+}
+
+fn dynamixel_read(id: u8, (address, len): (u16, u16)) -> Instruction {
+    let [address_low, address_high] = address.to_le_bytes();
+    let [len_low, len_high] = len.to_le_bytes();
+    dynamixel_packet(id, READ, &[address_low, address_high, len_low, len_high])
+}
+
+/// Remove the `FD` stuffed after each `FF FF FD`.
+fn dynamixel_unstuff(bytes: &[u8]) -> Vec<u8, REPLY_MAX> {
+    let mut unstuffed = Vec::new();
+    for (index, &byte) in bytes.iter().enumerate() {
+        if index >= 3 && byte == 0xFD && bytes[index - 3..index] == [0xFF, 0xFF, 0xFD] {
+            continue;
+        }
+        unstuffed.push(byte).expect("reply fits in REPLY_MAX");
+    }
+    unstuffed
+}
+
+/// CRC-16 with polynomial 0x8005, initial value 0, no reflection (as used by Dynamixel 2.0).
+fn dynamixel_crc(bytes: &[u8]) -> u16 {
+    bytes.iter().fold(0, |crc, &byte| {
+        (0..8).fold(crc ^ (u16::from(byte) << 8), |crc, _| {
+            if crc & 0x8000 == 0 {
+                crc << 1
+            } else {
+                (crc << 1) ^ 0x8005
+            }
+        })
+    })
+}
@@ -13,6 +13,19 @@
 //!   type showing all methods and associated constants.
 //! - [`combine!`](macro@crate::servo_player::combine) & [`linear`] — Macro and function for creating
 //!   complex motion sequences.
+//! - [`trapezoidal`], [`s_curve`], [`ease_in_out`] & [`MotionLimits`] — Smooth starts and stops,
+//!   as const step sequences or as speed and acceleration limits applied by the background task.
+//! - [`servo_group!`](macro@crate::servo_group), [`ServoGroup`] & [`keyframes!`](macro@crate::servo_player::keyframes)
+//!   — Play one keyframed timeline across several servo players on a shared clock.
+//! - [`ServoCalibration`](crate::servo::ServoCalibration) — Per-servo pulse widths measured
+//!   with [`Servo::calibrate`] and loaded at runtime with `load_calibration`, overriding the
+//!   macro's `min_us`/`max_us`.
+//! - [`pio_servos!`](macro@crate::servo::pio_servos) & [`PioServo`](crate::servo::PioServo)
+//!   — Up to 32 servos on arbitrary pins from one PIO state machine, each animated by its own
+//!   servo player.
+//! - [`servo_bus!`](macro@crate::servo_bus::servo_bus) & [`BusServo`](crate::servo_bus::BusServo)
+//!   — Feetech or Dynamixel smart servos on a UART bus, each animated by its own servo
+//!   player.
 //! - [`Servo`] — Direct servo control without animation support. Use `Servo` for direct,
 //!   immediate control; use `servo_player` when you want motion to continue in the background.
 
@@ -28,6 +41,13 @@
 //! use the formula: `PWM slice = (pin / 2) % 8`. For example, PIN_10 and PIN_11 must both use PWM_SLICE5
 //! ((10 / 2) % 8 = 5, (11 / 2) % 8 = 5). Therefore, either of these these two pins can have a servo, but not both.
 //!
+//! For more servos, or for servos on pins that share a slice, create them with
+//! [`pio_servos!`](macro@crate::servo::pio_servos), which drives up to 32 servos from one PIO
+//! state machine, and give each [`PioServo`](crate::servo::PioServo) its own servo player
+//! with the `pio_servo` flag. See [Example: Servos on a PIO](#example-servos-on-a-pio).
+//! Smart servos on a [`servo_bus!`](macro@crate::servo_bus::servo_bus) use the `bus_servo`
+//! flag the same way.
+//!
 //!
 //! # Example: Basic Servo Control
 //!
@@ -81,6 +101,65 @@
 //! }
 //! ```
 //!
+//! # Example: Servos on a PIO
+//!
+//! PIN_2 and PIN_18 would share PWM_SLICE1's channel A, so they can't both use PWM servos.
+//! Here a [`pio_servos!`](macro@crate::servo::pio_servos) bank drives both, and each gets
+//! a servo player generated with the `pio_servo` flag instead of a `pin`.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::default::Default;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{Result, servo::pio_servos, servo_player::{AtEnd, servo_player}};
+//! use embassy_time::Duration;
+//!
+//! pio_servos! {
+//!     ArmServos {
+//!         pins: [PIN_2, PIN_18],
+//!         pio: PIO1,
+//!         dma: DMA_CH1,
+//!     }
+//! }
+//!
+//! servo_player! {
+//!     Shoulder {
+//!         pio_servo,
+//!     }
+//! }
+//!
+//! servo_player! {
+//!     Elbow {
+//!         pio_servo,
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     let p = embassy_rp::init(Default::default());
+//!
+//!     let [shoulder_servo, elbow_servo] =
+//!         ArmServos::new(p.PIN_2, p.PIN_18, p.PIO1, p.DMA_CH1, spawner)?;
+//!     let shoulder = Shoulder::new(shoulder_servo, spawner)?;
+//!     let elbow = Elbow::new(elbow_servo, spawner)?;
+//!
+//!     shoulder.set_degrees(45);
+//!     elbow.animate(
+//!         [(30, Duration::from_millis(500)), (150, Duration::from_millis(500))],
+//!         AtEnd::Loop,
+//!     );
+//!
+//!     core::future::pending().await // run forever
+//! }
+//! ```
+//!
 //! # Example: Multi-Step Animation
 //!
 //! This example combines 40 animation steps using `linear` and`combine!` to
@@ -136,22 +215,174 @@
 //!     core::future::pending().await // run forever
 //! }
 //! ```
+//!
+//! # Example: Smooth Motion
+//!
+//! `linear` moves at a constant speed, starting and stopping abruptly, which is hard on
+//! the gears of heavier mechanisms. `trapezoidal`, `s_curve`, and `ease_in_out` build the
+//! same kind of step array with gentle starts and stops. For moves that aren't scripted
+//! ahead of time, [`MotionLimits`] makes the background task ramp toward every
+//! `set_degrees` target (and every `animate` step) instead of jumping there.
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::default::Default;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     servo_player::{AtEnd, MotionLimits, combine, s_curve, servo_player},
+//! };
+//! use embassy_time::{Duration, Timer};
+//!
+//! servo_player! {
+//!     ServoArm {
+//!         pin: PIN_12,
+//!         max_steps: 40,
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     let p = embassy_rp::init(Default::default());
+//!     let servo_arm = ServoArm::new(p.PIN_12, p.PWM_SLICE6, spawner)?;
+//!
+//!     // Scripted: a 2-second S-curve sweep each way.
+//!     const SWEEP: [(u16, Duration); 40] = combine!(
+//!         s_curve::<20>(0, 180, Duration::from_secs(2)),
+//!         s_curve::<20>(180, 0, Duration::from_secs(2))
+//!     );
+//!     servo_arm.animate(SWEEP, AtEnd::Hold);
+//!     Timer::after(Duration::from_secs(4)).await;
+//!
+//!     // Runtime: at most 120°/s, reached after a quarter second of acceleration.
+//!     servo_arm.set_motion_limits(Some(MotionLimits::new(120, 480)));
+//!     servo_arm.set_degrees(150); // ramps up, cruises, and ramps down
+//!     Timer::after(Duration::from_secs(2)).await;
+//!     servo_arm.set_degrees(30);
+//!
+//!     core::future::pending().await // run forever
+//! }
+//! ```
+//!
+//! # Example: Synchronized Servos
+//!
+//! Separate `animate` calls on two players start a moment apart and drift over many loops.
+//! A group generated by [`servo_group!`](macro@crate::servo_group) plays one timeline of
+//! [`Keyframe`]s across several players on a shared clock. Each keyframe gives every
+//! servo's angle, the time to get there, and an optional [`Easing`].
+//!
+//! ```rust,no_run
+//! # #![no_std]
+//! # #![no_main]
+//! # use panic_probe as _;
+//! # use core::convert::Infallible;
+//! # use core::default::Default;
+//! # use core::result::Result::Ok;
+//! use device_envoy::{
+//!     Result,
+//!     servo_player::{AtEnd, Keyframe, keyframes, servo_group, servo_player},
+//! };
+//!
+//! servo_player! {
+//!     Shoulder {
+//!         pin: PIN_11,
+//!     }
+//! }
+//!
+//! servo_player! {
+//!     Elbow {
+//!         pin: PIN_12,
+//!     }
+//! }
+//!
+//! // Define ArmGroup, a group of two servos.
+//! servo_group! {
+//!     ArmGroup {
+//!         servos: 2,
+//!         max_keyframes: 8, // Reduce from default (16)
+//!     }
+//! }
+//!
+//! # #[embassy_executor::main]
+//! # async fn main(spawner: embassy_executor::Spawner) -> ! {
+//! #     let err = example(spawner).await.unwrap_err();
+//! #     core::panic!("{err}");
+//! # }
+//! async fn example(spawner: embassy_executor::Spawner) -> Result<Infallible> {
+//!     let p = embassy_rp::init(Default::default());
+//!     let shoulder = Shoulder::new(p.PIN_11, p.PWM_SLICE5, spawner)?;
+//!     let elbow = Elbow::new(p.PIN_12, p.PWM_SLICE6, spawner)?;
+//!     let arm_group = ArmGroup::new([shoulder, elbow], spawner)?;
+//!
+//!     // Raise the arm, wave the elbow twice, then lower the arm.
+//!     const WAVE: [Keyframe<2>; 5] = keyframes![
+//!         1000 => [120, 90], SCurve;     // shoulder up
+//!         400 => [120, 150], EaseInOut;  // elbow out
+//!         400 => [120, 30], EaseInOut;   // elbow in
+//!         400 => [120, 90], EaseInOut;   // elbow center
+//!         1000 => [0, 90], SCurve;       // shoulder down
+//!     ];
+//!
+//!     arm_group.play(WAVE, AtEnd::Relax);
+//!     arm_group.wait_until_stopped().await;
+//!
+//!     core::future::pending().await // run forever
+//! }
+//! ```
+
+#![cfg_attr(all(test, feature = "host"), allow(dead_code))]
 
+#[cfg(target_os = "none")]
+use crate::Result;
+#[cfg(target_os = "none")]
+use crate::flash_array::FlashBlock;
+#[cfg(doc)]
 use crate::servo::Servo;
+#[cfg(target_os = "none")]
+use crate::servo::{ServoCalibration, ServoOutput};
+#[cfg(target_os = "none")]
 use core::borrow::Borrow;
+#[cfg(target_os = "none")]
+use core::sync::atomic::{AtomicU32, Ordering};
+#[cfg(target_os = "none")]
 use embassy_futures::select::{Either, select};
+#[cfg(target_os = "none")]
 use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+#[cfg(target_os = "none")]
 use embassy_sync::signal::Signal;
-use embassy_time::{Duration, Timer};
+use embassy_time::Duration;
+#[cfg(target_os = "none")]
+use embassy_time::{Instant, Timer};
+#[cfg(target_os = "none")]
 use heapless::Vec;
 
 #[doc(inline)]
 pub use crate::combine;
+#[doc(inline)]
+pub use crate::keyframes;
+#[cfg(target_os = "none")]
+#[doc(inline)]
+pub use crate::servo_group;
+pub use group::Keyframe;
+#[cfg(target_os = "none")]
+pub use group::{GroupServo, ServoGroup};
+// Public so macro-generated types can reference them; hidden from docs.
 /// Re-exported [`servo!`](macro@crate::servo) macro from the [`servo`](mod@crate::servo)
 /// module for convenience.
 ///
 /// See the [`servo`](mod@crate::servo) module for direct servo control without animation.
+#[cfg(target_os = "none")]
 pub use crate::servo::servo;
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub use group::{ServoGroupStatic, device_loop as group_device_loop};
 #[doc(hidden)]
 pub use paste;
 
@@ -159,8 +390,12 @@ pub use paste;
 // Submodules
 // ============================================================================
 
+mod group;
+#[cfg(all(test, feature = "host"))]
+mod host_tests;
 pub mod servo_player_generated;
 
+#[cfg(target_os = "none")]
 /// Commands sent to the servo player device.
 enum PlayerCommand<const MAX_STEPS: usize> {
     Set {
@@ -170,6 +405,10 @@ enum PlayerCommand<const MAX_STEPS: usize> {
         steps: Vec<(u16, Duration), MAX_STEPS>,
         mode: AtEnd,
     },
+    /// Drive straight to a position chosen by a [`ServoGroup`] timeline.
+    Follow {
+        millidegrees: u32,
+    },
     Hold,
     Relax,
 }
@@ -229,6 +468,155 @@ pub const fn linear<const N: usize>(
     result
 }
 
+/// Build a const trapezoidal-velocity sequence of animation steps as an array.
+///
+/// Like [`linear`], but the servo speeds up at a constant rate for the first third of
+/// `total_duration`, cruises for the middle third, and slows down for the last third, so
+/// heavy loads start and stop gently. Can be used in const contexts.
+///
+/// See the [servo_player module documentation](mod@crate::servo_player) for usage.
+///
+/// This uses [`embassy_time::Duration`] for step timing.
+#[must_use]
+pub const fn trapezoidal<const N: usize>(
+    start_degrees: u16,
+    end_degrees: u16,
+    total_duration: embassy_time::Duration,
+) -> [(u16, embassy_time::Duration); N] {
+    profile_steps(
+        Easing::Trapezoidal,
+        start_degrees,
+        end_degrees,
+        total_duration,
+    )
+}
+
+/// Build a const S-curve sequence of animation steps as an array.
+///
+/// Like [`linear`], but follows the minimum-jerk curve `10t³ − 15t⁴ + 6t⁵`: speed and
+/// acceleration both start and end at zero, for the smoothest starts and stops. Can be used
+/// in const contexts.
+///
+/// See the [servo_player module documentation](mod@crate::servo_player) for usage.
+///
+/// This uses [`embassy_time::Duration`] for step timing.
+#[must_use]
+pub const fn s_curve<const N: usize>(
+    start_degrees: u16,
+    end_degrees: u16,
+    total_duration: embassy_time::Duration,
+) -> [(u16, embassy_time::Duration); N] {
+    profile_steps(Easing::SCurve, start_degrees, end_degrees, total_duration)
+}
+
+/// Build a const cosine ease-in/ease-out sequence of animation steps as an array.
+///
+/// Like [`linear`], but follows half a cosine wave, so speed rises and falls smoothly
+/// and peaks mid-move. Can be used in const contexts.
+///
+/// See the [servo_player module documentation](mod@crate::servo_player) for usage.
+///
+/// This uses [`embassy_time::Duration`] for step timing.
+#[must_use]
+pub const fn ease_in_out<const N: usize>(
+    start_degrees: u16,
+    end_degrees: u16,
+    total_duration: embassy_time::Duration,
+) -> [(u16, embassy_time::Duration); N] {
+    profile_steps(
+        Easing::EaseInOut,
+        start_degrees,
+        end_degrees,
+        total_duration,
+    )
+}
+
+/// How a move speeds up and slows down between two angles.
+///
+/// Used by [`Keyframe`]s in a [`ServoGroup`] timeline. [`linear`], [`trapezoidal`],
+/// [`s_curve`], and [`ease_in_out`] build step arrays with the matching shapes.
+#[derive(Clone, Copy, Debug, defmt::Format, PartialEq, Eq, Default)]
+pub enum Easing {
+    /// Constant speed, starting and stopping abruptly.
+    #[default]
+    Linear,
+    /// Constant acceleration for the first third, cruise, then constant deceleration.
+    Trapezoidal,
+    /// Minimum-jerk curve: speed and acceleration start and end at zero.
+    SCurve,
+    /// Half a cosine wave: speed rises and falls smoothly.
+    EaseInOut,
+}
+
+impl Easing {
+    /// Fraction of the move completed at `time_fraction`, both in `[0, 1]`.
+    const fn position(self, time_fraction: f32) -> f32 {
+        let t = time_fraction;
+        match self {
+            Self::Linear => t,
+            // Accelerate for a third, cruise for a third at 1.5x the average speed, then
+            // decelerate.
+            Self::Trapezoidal => {
+                if t < 1.0 / 3.0 {
+                    2.25 * t * t
+                } else if t <= 2.0 / 3.0 {
+                    1.5 * t - 0.25
+                } else {
+                    let remaining = 1.0 - t;
+                    1.0 - 2.25 * remaining * remaining
+                }
+            }
+            Self::SCurve => t * t * t * (10.0 + t * (-15.0 + t * 6.0)),
+            Self::EaseInOut => {
+                if t <= 0.5 {
+                    0.5 * (1.0 - cos_half_pi(t))
+                } else {
+                    0.5 * (1.0 + cos_half_pi(1.0 - t))
+                }
+            }
+        }
+    }
+}
+
+/// Polynomial `cos(πt)` for `t` in `[0, 0.5]` (max error about 1e-7).
+const fn cos_half_pi(t: f32) -> f32 {
+    let x = core::f32::consts::PI * t;
+    let x2 = x * x;
+    1.0 + x2
+        * (-0.5
+            + x2 * (1.0 / 24.0 + x2 * (-1.0 / 720.0 + x2 * (1.0 / 40_320.0 - x2 / 3_628_800.0))))
+}
+
+/// Shared body of the eased step builders: step `i` holds the eased position at
+/// `i / (N - 1)` for `total_duration / N`, matching [`linear`]'s spacing.
+const fn profile_steps<const N: usize>(
+    easing: Easing,
+    start_degrees: u16,
+    end_degrees: u16,
+    total_duration: Duration,
+) -> [(u16, Duration); N] {
+    assert!(N > 0, "at least one step required");
+    let step_duration = Duration::from_micros(total_duration.as_micros() / (N as u64));
+    let delta = end_degrees as f32 - start_degrees as f32;
+    let denom = if N == 1 { 1.0 } else { (N - 1) as f32 };
+
+    let mut result = [(0u16, Duration::from_micros(0)); N];
+    let mut step_index = 0;
+    // TODO_NIGHTLY When nightly feature const_for becomes stable, replace this while loop with a for loop.
+    while step_index < N {
+        let degrees = if N == 1 {
+            start_degrees
+        } else {
+            let position = easing.position(step_index as f32 / denom);
+            // Both ends are non-negative, so adding 0.5 before truncating rounds.
+            (start_degrees as f32 + delta * position + 0.5) as u16
+        };
+        result[step_index] = (degrees, step_duration);
+        step_index += 1;
+    }
+    result
+}
+
 /// Combine two animation step arrays into one larger array.
 ///
 /// For combining more than two arrays, use the `combine!` macro.
@@ -303,19 +691,103 @@ macro_rules! combine {
     }};
 }
 
+/// Speed and acceleration limits for servo moves.
+///
+/// Once set with
+/// [`set_motion_limits`](servo_player_generated::ServoPlayerGenerated::set_motion_limits),
+/// the background task moves toward each new `set_degrees` target and each `animate` step,
+/// rather than jumping there. It speeds up at no
+/// more than the acceleration limit, cruises at no more than the speed limit, and slows
+/// down in time to stop at the target (a trapezoidal velocity profile). The position is
+/// updated once per 20 ms servo frame.
+///
+/// See the [servo_player module documentation](mod@crate::servo_player) for usage.
+#[derive(Clone, Copy, Debug, defmt::Format, PartialEq, Eq)]
+pub struct MotionLimits {
+    max_degrees_per_sec: u16,
+    max_degrees_per_sec2: u16,
+}
+
+impl MotionLimits {
+    /// Create motion limits from a top speed (degrees per second) and an acceleration
+    /// (degrees per second, per second).
+    ///
+    /// For example, `MotionLimits::new(120, 480)` reaches 120°/s after 0.25 s and takes
+    /// about 1.75 s for a 180° move.
+    #[must_use]
+    pub const fn new(max_degrees_per_sec: u16, max_degrees_per_sec2: u16) -> Self {
+        assert!(
+            max_degrees_per_sec > 0,
+            "max_degrees_per_sec must be positive"
+        );
+        assert!(
+            max_degrees_per_sec2 > 0,
+            "max_degrees_per_sec2 must be positive"
+        );
+        Self {
+            max_degrees_per_sec,
+            max_degrees_per_sec2,
+        }
+    }
+
+    /// Top speed in degrees per second.
+    #[must_use]
+    pub const fn max_degrees_per_sec(self) -> u16 {
+        self.max_degrees_per_sec
+    }
+
+    /// Acceleration and deceleration limit in degrees per second, per second.
+    #[must_use]
+    pub const fn max_degrees_per_sec2(self) -> u16 {
+        self.max_degrees_per_sec2
+    }
+
+    // Zero means "no limits"; `new` never produces a zero field.
+    const fn option_to_u32(motion_limits: Option<Self>) -> u32 {
+        match motion_limits {
+            Some(motion_limits) => {
+                ((motion_limits.max_degrees_per_sec as u32) << 16)
+                    | motion_limits.max_degrees_per_sec2 as u32
+            }
+            None => 0,
+        }
+    }
+
+    const fn option_from_u32(motion_limits_u32: u32) -> Option<Self> {
+        if motion_limits_u32 == 0 {
+            None
+        } else {
+            Some(Self {
+                max_degrees_per_sec: (motion_limits_u32 >> 16) as u16,
+                max_degrees_per_sec2: motion_limits_u32 as u16,
+            })
+        }
+    }
+}
+
+#[cfg(target_os = "none")]
 // Public so macro-generated types can reference it; hidden from docs.
 #[doc(hidden)]
 /// Static resources for [`ServoPlayer`].
 pub struct ServoPlayerStatic<const MAX_STEPS: usize> {
     command: Signal<CriticalSectionRawMutex, PlayerCommand<MAX_STEPS>>,
+    // Kept outside the command signal so a following `set_degrees` can't replace it.
+    motion_limits_u32: AtomicU32,
+    position_millidegrees: AtomicU32,
+    // Also separate from commands, so loading a calibration doesn't interrupt a move.
+    calibration: Signal<CriticalSectionRawMutex, ServoCalibration>,
 }
 
+#[cfg(target_os = "none")]
 impl<const MAX_STEPS: usize> ServoPlayerStatic<MAX_STEPS> {
     /// Create static resources for the servo player device.
     #[must_use]
     pub const fn new_static() -> Self {
         Self {
             command: Signal::new(),
+            motion_limits_u32: AtomicU32::new(0),
+            position_millidegrees: AtomicU32::new(0),
+            calibration: Signal::new(),
         }
     }
 
@@ -323,11 +795,48 @@ impl<const MAX_STEPS: usize> ServoPlayerStatic<MAX_STEPS> {
         self.command.signal(command);
     }
 
-    async fn wait(&self) -> PlayerCommand<MAX_STEPS> {
-        self.command.wait().await
+    fn set_motion_limits(&self, motion_limits: Option<MotionLimits>) {
+        self.motion_limits_u32.store(
+            MotionLimits::option_to_u32(motion_limits),
+            Ordering::Relaxed,
+        );
+    }
+
+    fn motion_limits(&self) -> Option<MotionLimits> {
+        MotionLimits::option_from_u32(self.motion_limits_u32.load(Ordering::Relaxed))
+    }
+
+    fn set_position_millidegrees(&self, position_millidegrees: u32) {
+        self.position_millidegrees
+            .store(position_millidegrees, Ordering::Relaxed);
+    }
+
+    fn position_millidegrees(&self) -> u32 {
+        self.position_millidegrees.load(Ordering::Relaxed)
+    }
+
+    /// Wait for the next command, applying any new calibration to `servo` meanwhile.
+    async fn wait(
+        &self,
+        servo: &mut impl ServoOutput,
+        motion: &Motion,
+    ) -> PlayerCommand<MAX_STEPS> {
+        loop {
+            match select(self.command.wait(), self.calibration.wait()).await {
+                Either::First(command) => return command,
+                Either::Second(calibration) => {
+                    servo.set_calibration(calibration);
+                    // Re-drive the current angle so a holding servo moves to match.
+                    if servo.is_enabled() {
+                        servo.set_millidegrees(motion.position_millidegrees);
+                    }
+                }
+            }
+        }
     }
 }
 
+#[cfg(target_os = "none")]
 // Public so macro-generated types can deref to it; hidden from docs.
 #[doc(hidden)]
 /// Internal deref target for generated servo player types.
@@ -336,8 +845,10 @@ impl<const MAX_STEPS: usize> ServoPlayerStatic<MAX_STEPS> {
 /// See [`servo_player!`] macro documentation for usage.
 pub struct ServoPlayer<const MAX_STEPS: usize> {
     servo_player_static: &'static ServoPlayerStatic<MAX_STEPS>,
+    max_degrees: u16,
 }
 
+#[cfg(target_os = "none")]
 impl<const MAX_STEPS: usize> ServoPlayer<MAX_STEPS> {
     /// Create static resources for a servo player.
     #[must_use]
@@ -345,13 +856,18 @@ impl<const MAX_STEPS: usize> ServoPlayer<MAX_STEPS> {
         ServoPlayerStatic::new_static()
     }
 
-    /// Create a servo player handle. The device loop must already be running.
+    /// Create a servo player handle for a servo with range `0..=max_degrees`. The device
+    /// loop must already be running.
     ///
     /// See the [servo_player module documentation](mod@crate::servo_player) for usage.
     #[must_use]
-    pub const fn new(servo_player_static: &'static ServoPlayerStatic<MAX_STEPS>) -> Self {
+    pub const fn new(
+        servo_player_static: &'static ServoPlayerStatic<MAX_STEPS>,
+        max_degrees: u16,
+    ) -> Self {
         Self {
             servo_player_static,
+            max_degrees,
         }
     }
 
@@ -364,6 +880,61 @@ impl<const MAX_STEPS: usize> ServoPlayer<MAX_STEPS> {
             .signal(PlayerCommand::Set { degrees });
     }
 
+    /// Limit the speed and acceleration of moves, or pass `None` to jump straight to each
+    /// target (the default).
+    ///
+    /// Takes effect immediately, including for a move already in progress, and applies to
+    /// both [`set_degrees`](Self::set_degrees) and [`animate`](Self::animate) steps.
+    ///
+    /// See the [servo_player module documentation](mod@crate::servo_player) for
+    /// usage.
+    pub fn set_motion_limits(&self, motion_limits: Option<MotionLimits>) {
+        self.servo_player_static.set_motion_limits(motion_limits);
+    }
+
+    /// The angle the servo was most recently driven to, rounded to the nearest degree.
+    ///
+    /// During a move limited by [`MotionLimits`] or a [`ServoGroup`] timeline, this follows
+    /// the servo along the way.
+    #[must_use]
+    pub fn degrees(&self) -> u16 {
+        ((self.servo_player_static.position_millidegrees() + 500) / 1000) as u16
+    }
+
+    /// Map angles with `calibration` instead of the macro's `min_us`/`max_us`.
+    ///
+    /// Takes effect immediately, without interrupting a move or animation in progress.
+    ///
+    /// See [`Servo::calibrate`] for measuring a calibration.
+    pub fn set_calibration(&self, calibration: ServoCalibration) {
+        self.servo_player_static.calibration.signal(calibration);
+    }
+
+    /// Use the calibration saved in `flash_block` by [`Servo::calibrate`], if there is one.
+    ///
+    /// Returns `Ok(true)` if a saved calibration was loaded. Returns `Ok(false)` and keeps
+    /// the macro's `min_us`/`max_us` if the block is empty.
+    pub fn load_calibration(&self, flash_block: &mut FlashBlock) -> Result<bool> {
+        let Some(calibration) = ServoCalibration::load(flash_block)? else {
+            return Ok(false);
+        };
+        self.set_calibration(calibration);
+        Ok(true)
+    }
+
+    pub(crate) fn follow_millidegrees(&self, millidegrees: u32) {
+        self.servo_player_static
+            .signal(PlayerCommand::Follow { millidegrees });
+    }
+
+    pub(crate) fn millidegrees(&self) -> u32 {
+        self.servo_player_static.position_millidegrees()
+    }
+
+    pub(crate) const fn max_degrees(&self) -> u16 {
+        self.max_degrees
+    }
+
     /// Hold the servo at its current position.
     ///
     /// See the [servo_player module documentation](mod@crate::servo_player) for
@@ -439,7 +1010,7 @@ impl<const MAX_STEPS: usize> ServoPlayer<MAX_STEPS> {
 /// ```text
 /// servo_player! {
 ///     [<visibility>] <Name> {
-///         pin: <pin_ident>,
+///         pin: <pin_ident>,           // or the bare flag `pio_servo` or `bus_servo`
 ///         min_us: <u16_expr>,         // optional
 ///         max_us: <u16_expr>,         // optional
 ///         max_degrees: <u16_expr>,    // optional
@@ -452,7 +1023,13 @@ impl<const MAX_STEPS: usize> ServoPlayer<MAX_STEPS> {
 ///
 /// ## Required Fields
 ///
-/// - `pin` — GPIO pin for servo
+/// - `pin` — GPIO pin for servo, or
+/// - `pio_servo` — Take a [`PioServo`](crate::servo::PioServo) from a
+///   [`pio_servos!`](macro@crate::servo::pio_servos) bank in `new(servo, spawner)` instead
+///   of a pin and slice. The bank sets `min_us`, `max_us`, and `max_degrees`.
+/// - `bus_servo` — Take a [`BusServo`](crate::servo_bus::BusServo) from a
+///   [`servo_bus!`](macro@crate::servo_bus::servo_bus) in `new(servo, spawner)` instead of a
+///   pin and slice. The bus sets `max_degrees` and the servo's calibration.
 ///
 /// ## Optional Fields
 ///
@@ -464,6 +1041,9 @@ impl<const MAX_STEPS: usize> ServoPlayer<MAX_STEPS> {
 ///
 /// `max_steps = 0` disables animation and allocates no step storage; `set_degrees()`,
 /// `hold()`, and `relax()` are still supported.
+///
+/// `min_us` and `max_us` are only defaults: `load_calibration()` replaces them at runtime
+/// with pulse widths saved by [`Servo::calibrate`].
 
 #[cfg(not(feature = "host"))]
 #[doc(hidden)]
@@ -471,6 +1051,7 @@ impl<const MAX_STEPS: usize> ServoPlayer<MAX_STEPS> {
 macro_rules! servo_player {
     ($($tt:tt)*) => { $crate::__servo_player_impl! { $($tt)* } };
 }
+#[cfg(target_os = "none")]
 #[doc(inline)]
 pub use servo_player;
 
@@ -575,6 +1156,62 @@ macro_rules! __servo_player_impl {
         }
     };
 
+    // Fill defaults: pio_servo (a PioServo from a pio_servos! bank instead of a pin)
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pin: $pin:tt,
+        slice: $slice:tt,
+        channel: $channel:tt,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        max_steps: $max_steps:expr,
+        fields: [ pio_servo $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_player_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pin: [$crate::servo::PioServo],
+            slice: $slice,
+            channel: $channel,
+            min_us: $min_us,
+            max_us: $max_us,
+            max_degrees: $max_degrees,
+            max_steps: $max_steps,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: bus_servo (a BusServo from a servo_bus! instead of a pin)
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        pin: $pin:tt,
+        slice: $slice:tt,
+        channel: $channel:tt,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        max_steps: $max_steps:expr,
+        fields: [ bus_servo $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_player_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            pin: [$crate::servo_bus::BusServo],
+            slice: $slice,
+            channel: $channel,
+            min_us: $min_us,
+            max_us: $max_us,
+            max_degrees: $max_degrees,
+            max_steps: $max_steps,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
     // Fill defaults: slice
     (@__fill_defaults
         vis: $vis:vis,
@@ -1106,7 +1743,85 @@ macro_rules! __servo_player_impl {
         max_degrees: $max_degrees:expr,
         max_steps: $max_steps:expr
     ) => {
-        compile_error!("servo_player! requires `pin: ...`");
+        compile_error!("servo_player! requires `pin: ...`, `pio_servo`, or `bus_servo`");
+    };
+
+    // Build for a PioServo or BusServo: its bank or bus owns the pin and pulse widths
+    (@__build
+        vis: $vis:vis,
+        name: $name:ident,
+        pin: [$servo_ty:ty],
+        slice: _UNSET_,
+        channel: _UNSET_,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        max_steps: $max_steps:expr
+    ) => {
+        $crate::servo_player::paste::paste! {
+            static [<$name:upper _SERVO_PLAYER_STATIC>]: $crate::servo_player::ServoPlayerStatic<$max_steps> =
+                $crate::servo_player::ServoPlayer::<$max_steps>::new_static();
+            static [<$name:upper _SERVO_PLAYER_CELL>]: ::static_cell::StaticCell<$name> =
+                ::static_cell::StaticCell::new();
+
+            #[allow(missing_docs)]
+            $vis struct $name {
+                player: $crate::servo_player::ServoPlayer<$max_steps>,
+            }
+
+            #[allow(missing_docs)]
+            impl $name {
+                /// Create the servo player and spawn its background task.
+                ///
+                /// # Parameters
+                ///
+                /// - `servo` — A `PioServo` from a `pio_servos!` bank, or a `BusServo` from a
+                ///   `servo_bus!`
+                /// - `spawner` — Task spawner for background operations
+                ///
+                /// See the `PioServo` struct and `servo_bus` module examples for usage.
+                pub fn new(
+                    servo: $servo_ty,
+                    spawner: ::embassy_executor::Spawner,
+                ) -> $crate::Result<&'static Self> {
+                    let max_degrees = $crate::servo::ServoOutput::max_degrees(&servo);
+                    let token = [<$name:snake _servo_player_task>](&[<$name:upper _SERVO_PLAYER_STATIC>], servo);
+                    spawner.spawn(token)?;
+                    let player = $crate::servo_player::ServoPlayer::new(&[<$name:upper _SERVO_PLAYER_STATIC>], max_degrees);
+                    Ok([<$name:upper _SERVO_PLAYER_CELL>].init(Self { player }))
+                }
+            }
+
+            impl ::core::ops::Deref for $name {
+                type Target = $crate::servo_player::ServoPlayer<$max_steps>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.player
+                }
+            }
+
+            #[::embassy_executor::task]
+            async fn [<$name:snake _servo_player_task>](
+                servo_player_static: &'static $crate::servo_player::ServoPlayerStatic<$max_steps>,
+                servo: $servo_ty,
+            ) -> ! {
+                $crate::servo_player::device_loop(servo_player_static, servo).await
+            }
+        }
+    };
+
+    (@__build
+        vis: $vis:vis,
+        name: $name:ident,
+        pin: [$servo_ty:ty],
+        slice: $slice:tt,
+        channel: $channel:tt,
+        min_us: $min_us:expr,
+        max_us: $max_us:expr,
+        max_degrees: $max_degrees:expr,
+        max_steps: $max_steps:expr
+    ) => {
+        compile_error!("servo_player! with `pio_servo` or `bus_servo` takes no `slice` or channel");
     };
 
     // Build with all fields set (slice can be _UNSET_ - it's in the new() signature)
@@ -1170,9 +1885,10 @@ macro_rules! __servo_player_impl {
                         $max_us,
                         $max_degrees
                     );
+                    let max_degrees = $crate::servo::ServoOutput::max_degrees(&servo);
                     let token = [<$name:snake _servo_player_task>](&[<$name:upper _SERVO_PLAYER_STATIC>], servo);
                     spawner.spawn(token)?;
-                    let player = $crate::servo_player::ServoPlayer::new(&[<$name:upper _SERVO_PLAYER_STATIC>]);
+                    let player = $crate::servo_player::ServoPlayer::new(&[<$name:upper _SERVO_PLAYER_STATIC>], max_degrees);
                     Ok([<$name:upper _SERVO_PLAYER_CELL>].init(Self { player }))
                 }
             }
@@ -1250,9 +1966,10 @@ macro_rules! __servo_player_impl {
                         max_us: $max_us,
                         max_degrees: $max_degrees
                     };
+                    let max_degrees = $crate::servo::ServoOutput::max_degrees(&servo);
                     let token = [<$name:snake _servo_player_task>](&[<$name:upper _SERVO_PLAYER_STATIC>], servo);
                     spawner.spawn(token)?;
-                    let player = $crate::servo_player::ServoPlayer::new(&[<$name:upper _SERVO_PLAYER_STATIC>]);
+                    let player = $crate::servo_player::ServoPlayer::new(&[<$name:upper _SERVO_PLAYER_STATIC>], max_degrees);
                     Ok([<$name:upper _SERVO_PLAYER_CELL>].init(Self { player }))
                 }
             }
@@ -1348,61 +2065,68 @@ macro_rules! __servo_player_impl {
     };
 }
 
+#[cfg(target_os = "none")]
 // Called by macro-generated code in downstream crates; must be public.
 #[doc(hidden)]
-pub async fn device_loop<const MAX_STEPS: usize>(
+pub async fn device_loop<const MAX_STEPS: usize, S: ServoOutput>(
     servo_player_static: &'static ServoPlayerStatic<MAX_STEPS>,
-    mut servo: Servo<'static>,
+    mut servo: S,
 ) -> ! {
-    let mut current_degrees: u16 = 0;
-    servo.set_degrees(current_degrees);
+    let mut motion = Motion::new(0);
+    servo.set_degrees(0);
 
-    let mut command = servo_player_static.wait().await;
+    let mut command = servo_player_static.wait(&mut servo, &motion).await;
     loop {
         match command {
             PlayerCommand::Set { degrees } => {
-                current_degrees = degrees;
-                servo.set_degrees(current_degrees);
-                command = servo_player_static.wait().await;
+                if let Some(next_command) =
+                    move_toward(degrees, None, &mut servo, &mut motion, servo_player_static).await
+                {
+                    command = next_command;
+                    continue;
+                }
+                // Re-enable a relaxed servo that was already at the target.
+                servo.hold();
+                command = servo_player_static.wait(&mut servo, &motion).await;
+            }
+            PlayerCommand::Follow { millidegrees } => {
+                servo.set_millidegrees(millidegrees);
+                motion.jump_to(millidegrees);
+                servo_player_static.set_position_millidegrees(millidegrees);
+                command = servo_player_static.wait(&mut servo, &motion).await;
             }
             PlayerCommand::Hold => {
                 servo.hold();
-                command = servo_player_static.wait().await;
+                command = servo_player_static.wait(&mut servo, &motion).await;
             }
             PlayerCommand::Relax => {
                 servo.relax();
-                command = servo_player_static.wait().await;
+                motion.stop();
+                command = servo_player_static.wait(&mut servo, &motion).await;
             }
             PlayerCommand::Animate { steps, mode } => {
-                command = run_animation(
-                    &steps,
-                    mode,
-                    &mut servo,
-                    servo_player_static,
-                    &mut current_degrees,
-                )
-                .await;
+                command =
+                    run_animation(&steps, mode, &mut servo, servo_player_static, &mut motion).await;
             }
         }
     }
 }
 
-async fn run_animation<const MAX_STEPS: usize>(
+#[cfg(target_os = "none")]
+async fn run_animation<const MAX_STEPS: usize, S: ServoOutput>(
     steps: &[(u16, Duration)],
     mode: AtEnd,
-    servo: &mut Servo<'static>,
+    servo: &mut S,
     servo_player_static: &'static ServoPlayerStatic<MAX_STEPS>,
-    current_degrees: &mut u16,
+    motion: &mut Motion,
 ) -> PlayerCommand<MAX_STEPS> {
     loop {
         for step in steps {
-            if *current_degrees != step.0 {
-                servo.set_degrees(step.0);
-                *current_degrees = step.0;
-            }
-            match select(Timer::after(step.1), servo_player_static.wait()).await {
-                Either::First(_) => {}
-                Either::Second(command) => return command,
+            let deadline = Instant::now() + step.1;
+            if let Some(command) =
+                move_toward(step.0, Some(deadline), servo, motion, servo_player_static).await
+            {
+                return command;
             }
         }
 
@@ -1413,13 +2137,152 @@ async fn run_animation<const MAX_STEPS: usize>(
             }
             AtEnd::Hold => {
                 // Hold final position and wait for next command
-                return servo_player_static.wait().await;
+                return servo_player_static.wait(servo, motion).await;
             }
             AtEnd::Relax => {
                 // Stop holding position (servo relaxes) and wait for next command
                 servo.relax();
-                return servo_player_static.wait().await;
+                motion.stop();
+                return servo_player_static.wait(servo, motion).await;
             }
         }
     }
 }
+
+/// Moves toward `target_degrees` under the current [`MotionLimits`] (or jumps there without
+/// limits), then waits out the rest of `deadline`, if any.
+///
+/// Returns `None` when the target is reached (without a deadline) or the deadline passes,
+/// possibly mid-move, in which case the next target picks up the current speed. Returns
+/// a new command as soon as one arrives.
+#[cfg(target_os = "none")]
+async fn move_toward<const MAX_STEPS: usize, S: ServoOutput>(
+    target_degrees: u16,
+    deadline: Option<Instant>,
+    servo: &mut S,
+    motion: &mut Motion,
+    servo_player_static: &'static ServoPlayerStatic<MAX_STEPS>,
+) -> Option<PlayerCommand<MAX_STEPS>> {
+    loop {
+        let Some(motion_limits) = servo_player_static.motion_limits() else {
+            // `set_degrees` always writes; animation steps only write changes.
+            if deadline.is_none() || motion.position_degrees() != Some(target_degrees) {
+                servo.set_degrees(target_degrees);
+            }
+            motion.jump_to(u32::from(target_degrees) * 1000);
+            servo_player_static.set_position_millidegrees(motion.position_millidegrees);
+            break;
+        };
+        let is_at_target = motion.step(target_degrees, motion_limits, servo.max_degrees());
+        if motion.take_moved() {
+            servo.set_millidegrees(motion.position_millidegrees);
+            servo_player_static.set_position_millidegrees(motion.position_millidegrees);
+        }
+        if is_at_target {
+            break;
+        }
+        if let Either::Second(command) = select(
+            Timer::after(MOTION_TICK),
+            servo_player_static.wait(servo, motion),
+        )
+        .await
+        {
+            return Some(command);
+        }
+        if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+            return None;
+        }
+    }
+
+    let deadline = deadline?;
+    match select(Timer::at(deadline), servo_player_static.wait(servo, motion)).await {
+        Either::First(_) => None,
+        Either::Second(command) => Some(command),
+    }
+}
+
+/// Motion update period: one servo PWM frame.
+const MOTION_TICK: Duration = Duration::from_millis(20);
+const MOTION_TICKS_PER_SEC: i32 = 50;
+
+/// Position and velocity of the servo as the device loop moves it, in thousandths of a
+/// degree so slow moves still advance smoothly.
+struct Motion {
+    position_millidegrees: u32,
+    velocity_millidegrees_per_sec: i32,
+    has_moved: bool,
+}
+
+impl Motion {
+    const fn new(position_millidegrees: u32) -> Self {
+        Self {
+            position_millidegrees,
+            velocity_millidegrees_per_sec: 0,
+            has_moved: false,
+        }
+    }
+
+    /// Whole-degree position, if the servo is exactly on one.
+    const fn position_degrees(&self) -> Option<u16> {
+        if self.position_millidegrees % 1000 == 0 {
+            Some((self.position_millidegrees / 1000) as u16)
+        } else {
+            None
+        }
+    }
+
+    const fn jump_to(&mut self, position_millidegrees: u32) {
+        *self = Self::new(position_millidegrees);
+    }
+
+    const fn stop(&mut self) {
+        self.velocity_millidegrees_per_sec = 0;
+    }
+
+    /// Returns whether the position changed since the last call.
+    const fn take_moved(&mut self) -> bool {
+        let has_moved = self.has_moved;
+        self.has_moved = false;
+        has_moved
+    }
+
+    /// Advances one [`MOTION_TICK`] toward `target_degrees`, returning `true` once the servo
+    /// is there and stopped.
+    fn step(&mut self, target_degrees: u16, motion_limits: MotionLimits, max_degrees: u16) -> bool {
+        let target = i32::from(target_degrees) * 1000;
+        let position = self.position_millidegrees as i32;
+        let distance = target - position;
+        let velocity = self.velocity_millidegrees_per_sec;
+        if distance == 0 && velocity == 0 {
+            return true;
+        }
+
+        let max_speed = i32::from(motion_limits.max_degrees_per_sec) * 1000;
+        let acceleration = i64::from(motion_limits.max_degrees_per_sec2) * 1000;
+        let max_velocity_change = ((acceleration / i64::from(MOTION_TICKS_PER_SEC)) as i32).max(1);
+        // The fastest speed from which the servo can still stop at the target: v² = 2ad.
+        let stopping_speed =
+            (2 * acceleration as u64 * u64::from(distance.unsigned_abs())).isqrt() as i32;
+        let desired_velocity = distance.signum() * max_speed.min(stopping_speed);
+        let velocity = velocity
+            + (desired_velocity - velocity).clamp(-max_velocity_change, max_velocity_change);
+
+        let mut position_change = velocity / MOTION_TICKS_PER_SEC;
+        if position_change == 0 {
+            position_change = velocity.signum();
+        }
+        let is_toward_target = velocity.signum() == distance.signum();
+        if distance == 0 || (is_toward_target && position_change.abs() >= distance.abs()) {
+            // Finish on the target rather than overshooting it.
+            self.position_millidegrees = target as u32;
+            self.velocity_millidegrees_per_sec = 0;
+            self.has_moved = distance != 0;
+            return true;
+        }
+        self.position_millidegrees =
+            (position + position_change).clamp(0, i32::from(max_degrees) * 1000) as u32;
+        self.velocity_millidegrees_per_sec = velocity;
+        self.has_moved = true;
+        false
+    }
+}
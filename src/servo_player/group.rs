@@ -0,0 +1,665 @@
+//! Several servo players driven by one keyframed timeline on a shared clock.
+//!
+//! See [`ServoGroup`] and [`servo_group!`](macro@crate::servo_group).
+
+#[cfg(target_os = "none")]
+use core::borrow::Borrow;
+#[cfg(target_os = "none")]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+#[cfg(target_os = "none")]
+use embassy_futures::select::{Either, select};
+#[cfg(target_os = "none")]
+use embassy_sync::blocking_mutex::raw::CriticalSectionRawMutex;
+#[cfg(target_os = "none")]
+use embassy_sync::signal::Signal;
+use embassy_time::Duration;
+#[cfg(target_os = "none")]
+use embassy_time::{Instant, Timer};
+#[cfg(target_os = "none")]
+use heapless::Vec;
+
+#[cfg(target_os = "none")]
+use self::sealed::GroupServoSealed as _;
+#[cfg(target_os = "none")]
+use super::AtEnd;
+use super::Easing;
+
+/// How often a playing timeline sends new positions: one servo PWM frame.
+#[cfg(target_os = "none")]
+const GROUP_TICK: Duration = Duration::from_millis(20);
+
+/// One pose in a [`ServoGroup`] timeline: every servo moves from the previous pose to
+/// `degrees` over `duration`, following `easing`.
+///
+/// A zero `duration` jumps straight to the pose; repeating a pose holds it. Build arrays
+/// of keyframes with [`keyframes!`](macro@crate::servo_player::keyframes).
+///
+/// See the [servo_player module documentation](mod@crate::servo_player) for usage.
+#[derive(Clone, Copy, Debug, defmt::Format, PartialEq, Eq)]
+pub struct Keyframe<const SERVOS: usize> {
+    /// Target angle for each servo, in the order the servos were given to the group.
+    pub degrees: [u16; SERVOS],
+    /// Time to reach `degrees` from the previous pose.
+    pub duration: Duration,
+    /// How the servos speed up and slow down on the way.
+    pub easing: Easing,
+}
+
+impl<const SERVOS: usize> Keyframe<SERVOS> {
+    /// Create a keyframe. Can be used in const contexts.
+    #[must_use]
+    pub const fn new(degrees: [u16; SERVOS], duration: Duration, easing: Easing) -> Self {
+        Self {
+            degrees,
+            duration,
+            easing,
+        }
+    }
+}
+
+/// Build a const array of [`Keyframe`]s.
+///
+/// Each entry is `<milliseconds> => [<degrees>, ...]`, optionally followed by `, <Easing>`
+/// (default [`Easing::Linear`]). Entries are separated by `;`.
+///
+/// **Syntax:**
+///
+/// ```text
+/// keyframes![
+///     <ms_expr> => [<degrees_expr>, ...] [, <Easing variant>];
+///     ...
+/// ]
+/// ```
+///
+/// See the [servo_player module documentation](mod@crate::servo_player) for usage.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! keyframes {
+    (@easing) => {
+        $crate::servo_player::Easing::Linear
+    };
+    (@easing $easing:ident) => {
+        $crate::servo_player::Easing::$easing
+    };
+    ($($duration_ms:expr => [$($degrees:expr),+ $(,)?] $(, $easing:ident)?);* $(;)?) => {
+        [$(
+            $crate::servo_player::Keyframe::new(
+                [$($degrees),+],
+                ::embassy_time::Duration::from_millis($duration_ms),
+                $crate::keyframes!(@easing $($easing)?),
+            )
+        ),*]
+    };
+}
+
+/// A servo that a [`ServoGroup`] can drive.
+///
+/// Implemented by every type generated by [`servo_player!`](macro@crate::servo_player), so
+/// pass the players to a group's `new` as `&'static dyn GroupServo`.
+#[cfg(target_os = "none")]
+#[allow(private_bounds)]
+pub trait GroupServo: sealed::GroupServoSealed {}
+
+#[cfg(target_os = "none")]
+impl<T: ?Sized> GroupServo for T where T: sealed::GroupServoSealed {}
+
+#[cfg(target_os = "none")]
+mod sealed {
+    use core::ops::Deref;
+
+    use crate::servo_player::ServoPlayer;
+
+    // `Sync` so `&'static dyn GroupServo` can be passed to the group's task.
+    pub(crate) trait GroupServoSealed: Sync {
+        fn follow_millidegrees(&self, millidegrees: u32);
+        fn millidegrees(&self) -> u32;
+        fn max_degrees(&self) -> u16;
+        fn relax(&self);
+    }
+
+    impl<T, const MAX_STEPS: usize> GroupServoSealed for T
+    where
+        T: Deref<Target = ServoPlayer<MAX_STEPS>> + Sync,
+    {
+        fn follow_millidegrees(&self, millidegrees: u32) {
+            (**self).follow_millidegrees(millidegrees);
+        }
+
+        fn millidegrees(&self) -> u32 {
+            (**self).millidegrees()
+        }
+
+        fn max_degrees(&self) -> u16 {
+            (**self).max_degrees()
+        }
+
+        fn relax(&self) {
+            (**self).relax();
+        }
+    }
+}
+
+/// Commands sent to the servo group device.
+#[cfg(target_os = "none")]
+enum GroupCommand<const SERVOS: usize, const MAX_KEYFRAMES: usize> {
+    Play {
+        keyframes: Vec<Keyframe<SERVOS>, MAX_KEYFRAMES>,
+        at_end: AtEnd,
+    },
+    Stop,
+    Relax,
+}
+
+// Public so macro-generated types can reference it; hidden from docs.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+/// Static resources for [`ServoGroup`].
+pub struct ServoGroupStatic<const SERVOS: usize, const MAX_KEYFRAMES: usize> {
+    command: Signal<CriticalSectionRawMutex, GroupCommand<SERVOS, MAX_KEYFRAMES>>,
+    stopped_signal: Signal<CriticalSectionRawMutex, ()>,
+    has_pending_play: AtomicBool,
+    is_playing: AtomicBool,
+}
+
+#[cfg(target_os = "none")]
+impl<const SERVOS: usize, const MAX_KEYFRAMES: usize> ServoGroupStatic<SERVOS, MAX_KEYFRAMES> {
+    /// Create static resources for the servo group device.
+    #[must_use]
+    pub const fn new_static() -> Self {
+        Self {
+            command: Signal::new(),
+            stopped_signal: Signal::new(),
+            has_pending_play: AtomicBool::new(false),
+            is_playing: AtomicBool::new(false),
+        }
+    }
+
+    fn signal(&self, command: GroupCommand<SERVOS, MAX_KEYFRAMES>) {
+        self.command.signal(command);
+    }
+
+    async fn wait(&self) -> GroupCommand<SERVOS, MAX_KEYFRAMES> {
+        self.command.wait().await
+    }
+
+    fn mark_pending_play(&self) {
+        self.has_pending_play.store(true, Ordering::Relaxed);
+    }
+
+    fn mark_playing(&self) {
+        self.has_pending_play.store(false, Ordering::Relaxed);
+        self.is_playing.store(true, Ordering::Relaxed);
+    }
+
+    fn mark_stopped(&self) {
+        self.has_pending_play.store(false, Ordering::Relaxed);
+        self.is_playing.store(false, Ordering::Relaxed);
+        self.stopped_signal.signal(());
+    }
+
+    fn is_idle(&self) -> bool {
+        !self.has_pending_play.load(Ordering::Relaxed) && !self.is_playing.load(Ordering::Relaxed)
+    }
+}
+
+/// Plays keyframed timelines across several servo players on one shared clock.
+///
+/// Each `servo_player!` type runs its own background task, so separate `animate` calls
+/// start at slightly different times and drift apart. A group's task instead computes
+/// every servo's position from one timeline every 20 ms servo frame and sends them
+/// together, so all the servos stay in step however long the timeline loops. Use
+/// [`servo_group!`](macro@crate::servo_group) to create one.
+///
+/// The first keyframe starts from wherever the servos are (see
+/// [`degrees`](super::servo_player_generated::ServoPlayerGenerated::degrees)). Timeline
+/// positions bypass each player's [`MotionLimits`](super::MotionLimits), since the keyframe
+/// easing already shapes the motion. While a timeline plays, each new position it sends
+/// replaces whatever a servo was last told to do by its own player.
+///
+/// See the [servo_player module documentation](mod@crate::servo_player) for usage.
+#[cfg(target_os = "none")]
+pub struct ServoGroup<const SERVOS: usize, const MAX_KEYFRAMES: usize> {
+    servo_group_static: &'static ServoGroupStatic<SERVOS, MAX_KEYFRAMES>,
+    max_degrees: [u16; SERVOS],
+}
+
+#[cfg(target_os = "none")]
+impl<const SERVOS: usize, const MAX_KEYFRAMES: usize> ServoGroup<SERVOS, MAX_KEYFRAMES> {
+    /// Create static resources for a servo group.
+    #[must_use]
+    #[doc(hidden)]
+    pub const fn new_static() -> ServoGroupStatic<SERVOS, MAX_KEYFRAMES> {
+        ServoGroupStatic::new_static()
+    }
+
+    /// Create a servo group handle for `servos`. The device loop must already be running.
+    #[must_use]
+    #[doc(hidden)]
+    pub fn new(
+        servo_group_static: &'static ServoGroupStatic<SERVOS, MAX_KEYFRAMES>,
+        servos: &[&'static dyn GroupServo; SERVOS],
+    ) -> Self {
+        Self {
+            servo_group_static,
+            max_degrees: servos.map(|servo| servo.max_degrees()),
+        }
+    }
+
+    /// Play a timeline of keyframes, replacing any timeline already playing.
+    ///
+    /// Accepts both owned iterators and references to collections. With [`AtEnd::Loop`],
+    /// the timeline repeats from its first keyframe without drifting; the timeline must
+    /// then take a positive total time. Every keyframe angle must be within its servo's
+    /// `max_degrees`.
+    ///
+    /// See the [servo_player module documentation](mod@crate::servo_player) for usage.
+    pub fn play<I>(&self, keyframes: I, at_end: AtEnd)
+    where
+        I: IntoIterator,
+        I::Item: Borrow<Keyframe<SERVOS>>,
+    {
+        assert!(MAX_KEYFRAMES > 0, "play disabled: max_keyframes is 0");
+        let mut timeline: Vec<Keyframe<SERVOS>, MAX_KEYFRAMES> = Vec::new();
+        for keyframe in keyframes {
+            let keyframe = *keyframe.borrow();
+            assert!(
+                keyframe
+                    .degrees
+                    .iter()
+                    .zip(self.max_degrees)
+                    .all(|(&degrees, max_degrees)| degrees <= max_degrees),
+                "keyframe degrees must be <= each servo's max_degrees"
+            );
+            timeline
+                .push(keyframe)
+                .expect("timeline fits within max_keyframes");
+        }
+        assert!(!timeline.is_empty(), "play requires at least one keyframe");
+        if matches!(at_end, AtEnd::Loop) {
+            assert!(
+                timeline
+                    .iter()
+                    .any(|keyframe| keyframe.duration.as_ticks() > 0),
+                "a looping timeline must take a positive total time"
+            );
+        }
+
+        self.servo_group_static.mark_pending_play();
+        self.servo_group_static.signal(GroupCommand::Play {
+            keyframes: timeline,
+            at_end,
+        });
+    }
+
+    /// Stop the timeline, leaving every servo holding its current position.
+    pub fn stop(&self) {
+        self.servo_group_static.signal(GroupCommand::Stop);
+    }
+
+    /// Stop the timeline and relax every servo in the group.
+    pub fn relax(&self) {
+        self.servo_group_static.signal(GroupCommand::Relax);
+    }
+
+    /// Returns `true` while a timeline is playing (or about to start).
+    #[must_use]
+    pub fn is_playing(&self) -> bool {
+        !self.servo_group_static.is_idle()
+    }
+
+    /// Waits until no timeline is playing.
+    ///
+    /// If the group is idle, this returns immediately. Otherwise it waits for the
+    /// timeline's end with [`AtEnd::Hold`] or [`AtEnd::Relax`], or for a
+    /// [`stop`](Self::stop) or [`relax`](Self::relax).
+    pub async fn wait_until_stopped(&self) {
+        while !self.servo_group_static.is_idle() {
+            self.servo_group_static.stopped_signal.wait().await;
+        }
+    }
+}
+
+/// Macro to generate a servo group struct type that plays synchronized timelines on
+/// several [`servo_player!`](macro@crate::servo_player) types.
+///
+/// See the [servo_player module documentation](mod@crate::servo_player) for a complete
+/// example.
+///
+/// **Syntax:**
+///
+/// ```text
+/// servo_group! {
+///     [<visibility>] <Name> {
+///         servos: <usize_expr>,           // number of servos in the group
+///         max_keyframes: <usize_expr>,    // optional
+///     }
+/// }
+/// ```
+///
+/// # Configuration
+///
+/// ## Required Fields
+///
+/// - `servos` — Number of servo players in the group; the length of each
+///   [`Keyframe`]'s `degrees`
+///
+/// ## Optional Fields
+///
+/// - `max_keyframes` — Maximum number of keyframes in a timeline (default: 16)
+///
+/// # Generated Methods
+///
+/// - `new(servos: [&'static dyn GroupServo; SERVOS], spawner) -> Result<&'static Self>` —
+///   Create the group from already-created servo players and spawn its background task.
+/// - All [`ServoGroup`] methods, through `Deref`.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! servo_group {
+    ($($tt:tt)*) => { $crate::__servo_group_impl! { $($tt)* } };
+}
+
+// Public for macro expansion in downstream crates.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __servo_group_impl {
+    // Entry point - name without visibility defaults to private
+    (
+        $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__servo_group_impl! {
+            @__fill_defaults
+            vis: pub(self),
+            name: $name,
+            servos: _UNSET_,
+            max_keyframes: 16,
+            fields: [ $($fields)* ]
+        }
+    };
+
+    // Entry point - name with explicit visibility
+    (
+        $vis:vis $name:ident {
+            $($fields:tt)*
+        }
+    ) => {
+        $crate::__servo_group_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            servos: _UNSET_,
+            max_keyframes: 16,
+            fields: [ $($fields)* ]
+        }
+    };
+
+    // Fill defaults: servos
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        servos: $servos:tt,
+        max_keyframes: $max_keyframes:expr,
+        fields: [ servos: $servos_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_group_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            servos: ($servos_value),
+            max_keyframes: $max_keyframes,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: max_keyframes
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        servos: $servos:tt,
+        max_keyframes: $max_keyframes:expr,
+        fields: [ max_keyframes: $max_keyframes_value:expr $(, $($rest:tt)* )? ]
+    ) => {
+        $crate::__servo_group_impl! {
+            @__fill_defaults
+            vis: $vis,
+            name: $name,
+            servos: $servos,
+            max_keyframes: $max_keyframes_value,
+            fields: [ $($($rest)*)? ]
+        }
+    };
+
+    // Fill defaults: terminate and build
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        servos: _UNSET_,
+        max_keyframes: $max_keyframes:expr,
+        fields: [ ]
+    ) => {
+        compile_error!("servo_group! requires `servos: ...`");
+    };
+
+    (@__fill_defaults
+        vis: $vis:vis,
+        name: $name:ident,
+        servos: $servos:tt,
+        max_keyframes: $max_keyframes:expr,
+        fields: [ ]
+    ) => {
+        $crate::servo_player::paste::paste! {
+            static [<$name:upper _SERVO_GROUP_STATIC>]:
+                $crate::servo_player::ServoGroupStatic<{ $servos }, { $max_keyframes }> =
+                $crate::servo_player::ServoGroup::<{ $servos }, { $max_keyframes }>::new_static();
+            static [<$name:upper _SERVO_GROUP_CELL>]: ::static_cell::StaticCell<$name> =
+                ::static_cell::StaticCell::new();
+
+            #[allow(missing_docs)]
+            $vis struct $name {
+                group: $crate::servo_player::ServoGroup<{ $servos }, { $max_keyframes }>,
+            }
+
+            #[allow(missing_docs)]
+            impl $name {
+                /// Create the servo group and spawn its background task.
+                ///
+                /// # Parameters
+                ///
+                /// - `servos` — The group's servo players, in keyframe order
+                /// - `spawner` — Task spawner for background operations
+                pub fn new(
+                    servos: [&'static dyn $crate::servo_player::GroupServo; $servos],
+                    spawner: ::embassy_executor::Spawner,
+                ) -> $crate::Result<&'static Self> {
+                    let group = $crate::servo_player::ServoGroup::new(&[<$name:upper _SERVO_GROUP_STATIC>], &servos);
+                    let token = [<$name:snake _servo_group_task>](&[<$name:upper _SERVO_GROUP_STATIC>], servos);
+                    spawner.spawn(token)?;
+                    Ok([<$name:upper _SERVO_GROUP_CELL>].init(Self { group }))
+                }
+            }
+
+            impl ::core::ops::Deref for $name {
+                type Target = $crate::servo_player::ServoGroup<{ $servos }, { $max_keyframes }>;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.group
+                }
+            }
+
+            #[::embassy_executor::task]
+            async fn [<$name:snake _servo_group_task>](
+                servo_group_static: &'static $crate::servo_player::ServoGroupStatic<{ $servos }, { $max_keyframes }>,
+                servos: [&'static dyn $crate::servo_player::GroupServo; $servos],
+            ) -> ! {
+                $crate::servo_player::group_device_loop(servo_group_static, servos).await
+            }
+        }
+    };
+}
+
+// Called by macro-generated code in downstream crates; must be public.
+#[cfg(target_os = "none")]
+#[doc(hidden)]
+pub async fn device_loop<const SERVOS: usize, const MAX_KEYFRAMES: usize>(
+    servo_group_static: &'static ServoGroupStatic<SERVOS, MAX_KEYFRAMES>,
+    servos: [&'static dyn GroupServo; SERVOS],
+) -> ! {
+    let mut command = servo_group_static.wait().await;
+    loop {
+        command = match command {
+            GroupCommand::Play { keyframes, at_end } => {
+                servo_group_static.mark_playing();
+                run_timeline(&keyframes, at_end, &servos, servo_group_static).await
+            }
+            GroupCommand::Stop => {
+                servo_group_static.mark_stopped();
+                servo_group_static.wait().await
+            }
+            GroupCommand::Relax => {
+                for servo in servos {
+                    servo.relax();
+                }
+                servo_group_static.mark_stopped();
+                servo_group_static.wait().await
+            }
+        };
+    }
+}
+
+#[cfg(target_os = "none")]
+async fn run_timeline<const SERVOS: usize, const MAX_KEYFRAMES: usize>(
+    keyframes: &[Keyframe<SERVOS>],
+    at_end: AtEnd,
+    servos: &[&'static dyn GroupServo; SERVOS],
+    servo_group_static: &'static ServoGroupStatic<SERVOS, MAX_KEYFRAMES>,
+) -> GroupCommand<SERVOS, MAX_KEYFRAMES> {
+    let timeline_start = Instant::now();
+    let mut playhead = Playhead::new(keyframes, servos.map(|servo| servo.millidegrees()));
+    let mut sent_millidegrees = [None; SERVOS];
+    loop {
+        let now_us = (Instant::now() - timeline_start).as_micros();
+        let pose_millidegrees = playhead.pose_millidegrees(now_us);
+        for (servo_index, servo) in servos.iter().enumerate() {
+            let millidegrees = pose_millidegrees[servo_index];
+            if sent_millidegrees[servo_index] != Some(millidegrees) {
+                servo.follow_millidegrees(millidegrees);
+                sent_millidegrees[servo_index] = Some(millidegrees);
+            }
+        }
+
+        let keyframe_end_us = playhead.keyframe_end_us();
+        if now_us < keyframe_end_us {
+            let next_tick_us = (now_us + GROUP_TICK.as_micros()).min(keyframe_end_us);
+            let next_tick = timeline_start + Duration::from_micros(next_tick_us);
+            if let Either::Second(command) =
+                select(Timer::at(next_tick), servo_group_static.wait()).await
+            {
+                return command;
+            }
+            continue;
+        }
+        if playhead.advance() {
+            continue;
+        }
+
+        match at_end {
+            AtEnd::Loop => {}
+            AtEnd::Hold => {
+                servo_group_static.mark_stopped();
+                return servo_group_static.wait().await;
+            }
+            AtEnd::Relax => {
+                for servo in servos {
+                    servo.relax();
+                }
+                servo_group_static.mark_stopped();
+                return servo_group_static.wait().await;
+            }
+        }
+    }
+}
+
+/// Walks a timeline one keyframe at a time. Times are microseconds since the timeline
+/// started playing.
+///
+/// Each keyframe starts when the previous one was due to end, not when it actually
+/// ended, so late ticks never accumulate into drift.
+pub(super) struct Playhead<'a, const SERVOS: usize> {
+    keyframes: &'a [Keyframe<SERVOS>],
+    keyframe_index: usize,
+    keyframe_start_us: u64,
+    start_pose_millidegrees: [u32; SERVOS],
+}
+
+impl<'a, const SERVOS: usize> Playhead<'a, SERVOS> {
+    /// Start the first keyframe at time 0, moving from `start_pose_millidegrees`.
+    pub(super) fn new(
+        keyframes: &'a [Keyframe<SERVOS>],
+        start_pose_millidegrees: [u32; SERVOS],
+    ) -> Self {
+        assert!(!keyframes.is_empty(), "play requires at least one keyframe");
+        Self {
+            keyframes,
+            keyframe_index: 0,
+            keyframe_start_us: 0,
+            start_pose_millidegrees,
+        }
+    }
+
+    /// When the current keyframe is due to end.
+    pub(super) fn keyframe_end_us(&self) -> u64 {
+        self.keyframe_start_us + self.keyframes[self.keyframe_index].duration.as_micros()
+    }
+
+    /// Every servo's position at `now_us`, which must not be before the current
+    /// keyframe's start.
+    pub(super) fn pose_millidegrees(&self, now_us: u64) -> [u32; SERVOS] {
+        keyframe_pose_millidegrees(
+            &self.keyframes[self.keyframe_index],
+            self.keyframe_start_us,
+            now_us,
+            self.start_pose_millidegrees,
+        )
+    }
+
+    /// Start the next keyframe from the current one's pose, at its due end. Returns
+    /// `false`, and wraps back to the first keyframe, when the timeline is over.
+    pub(super) fn advance(&mut self) -> bool {
+        let keyframe = &self.keyframes[self.keyframe_index];
+        self.start_pose_millidegrees = keyframe.degrees.map(|degrees| u32::from(degrees) * 1000);
+        self.keyframe_start_us += keyframe.duration.as_micros();
+        self.keyframe_index = (self.keyframe_index + 1) % self.keyframes.len();
+        self.keyframe_index != 0
+    }
+}
+
+/// Every servo's position, in millidegrees, at `now_us` during `keyframe`, which started
+/// at `keyframe_start_us` from `start_pose_millidegrees`.
+///
+/// Once the keyframe is due to end (at once, for a zero duration), this is exactly the
+/// keyframe's own pose.
+pub(super) fn keyframe_pose_millidegrees<const SERVOS: usize>(
+    keyframe: &Keyframe<SERVOS>,
+    keyframe_start_us: u64,
+    now_us: u64,
+    start_pose_millidegrees: [u32; SERVOS],
+) -> [u32; SERVOS] {
+    let duration_us = keyframe.duration.as_micros();
+    let elapsed_us = now_us - keyframe_start_us;
+    let time_fraction = if elapsed_us >= duration_us {
+        1.0
+    } else {
+        elapsed_us as f32 / duration_us as f32
+    };
+    let position = keyframe.easing.position(time_fraction);
+    core::array::from_fn(|servo_index| {
+        let start = start_pose_millidegrees[servo_index] as f32;
+        let end = f32::from(keyframe.degrees[servo_index]) * 1000.0;
+        // Both ends are non-negative, so adding 0.5 before truncating rounds.
+        (start + (end - start) * position + 0.5) as u32
+    })
+}
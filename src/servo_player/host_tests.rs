@@ -0,0 +1,314 @@
+#![allow(missing_docs)]
+
+use core::sync::atomic::{AtomicU32, Ordering};
+
+use embassy_time::Duration;
+
+use super::group::{Playhead, keyframe_pose_millidegrees};
+use super::{
+    Easing, Keyframe, MOTION_TICKS_PER_SEC, Motion, MotionLimits, ease_in_out, keyframes, linear,
+    profile_steps, s_curve, trapezoidal,
+};
+
+const EASINGS: [Easing; 4] = [
+    Easing::Linear,
+    Easing::Trapezoidal,
+    Easing::SCurve,
+    Easing::EaseInOut,
+];
+
+/// Step `motion` toward `target_degrees` until it arrives, returning every position.
+fn run_to(
+    motion: &mut Motion,
+    target_degrees: u16,
+    motion_limits: MotionLimits,
+    max_degrees: u16,
+) -> std::vec::Vec<u32> {
+    let mut positions = std::vec::Vec::new();
+    for _ in 0..10_000 {
+        let is_at_target = motion.step(target_degrees, motion_limits, max_degrees);
+        positions.push(motion.position_millidegrees);
+        if is_at_target {
+            return positions;
+        }
+    }
+    panic!("never reached {target_degrees}°");
+}
+
+#[test]
+fn profile_steps_count_and_timing() {
+    for easing in EASINGS {
+        let steps = profile_steps::<19>(easing, 0, 180, Duration::from_millis(1_900));
+        assert_eq!(steps.len(), 19);
+        assert!(
+            steps
+                .iter()
+                .all(|(_, duration)| *duration == Duration::from_millis(100))
+        );
+    }
+    assert_eq!(
+        profile_steps::<1>(Easing::SCurve, 30, 150, Duration::from_secs(1)),
+        [(30, Duration::from_secs(1))]
+    );
+}
+
+#[test]
+fn profile_steps_endpoints_are_exact() {
+    for easing in EASINGS {
+        for (start_degrees, end_degrees) in [(0, 180), (180, 0), (17, 163), (90, 90)] {
+            let steps =
+                profile_steps::<25>(easing, start_degrees, end_degrees, Duration::from_secs(1));
+            assert_eq!(steps[0].0, start_degrees, "{easing:?}");
+            assert_eq!(steps[24].0, end_degrees, "{easing:?}");
+        }
+    }
+}
+
+#[test]
+fn profile_steps_are_monotonic() {
+    for easing in EASINGS {
+        let rising = profile_steps::<40>(easing, 10, 170, Duration::from_secs(2));
+        assert!(
+            rising.windows(2).all(|pair| pair[0].0 <= pair[1].0),
+            "{easing:?}: {rising:?}"
+        );
+        let falling = profile_steps::<40>(easing, 170, 10, Duration::from_secs(2));
+        assert!(
+            falling.windows(2).all(|pair| pair[0].0 >= pair[1].0),
+            "{easing:?}: {falling:?}"
+        );
+    }
+}
+
+#[test]
+fn eased_builders_match_profile_steps() {
+    const TOTAL: Duration = Duration::from_millis(900);
+    assert_eq!(
+        trapezoidal::<9>(0, 90, TOTAL),
+        profile_steps::<9>(Easing::Trapezoidal, 0, 90, TOTAL)
+    );
+    assert_eq!(
+        s_curve::<9>(0, 90, TOTAL),
+        profile_steps::<9>(Easing::SCurve, 0, 90, TOTAL)
+    );
+    assert_eq!(
+        ease_in_out::<9>(0, 90, TOTAL),
+        profile_steps::<9>(Easing::EaseInOut, 0, 90, TOTAL)
+    );
+    // `linear` truncates rather than rounds, but agrees on the ends.
+    let linear_steps = linear::<9>(0, 90, TOTAL);
+    assert_eq!((linear_steps[0].0, linear_steps[8].0), (0, 90));
+}
+
+#[test]
+fn motion_step_follows_trapezoid() {
+    // Reaches 120°/s after 0.25 s and 15°, cruises 60°, then stops in 15°: about 1 s, or
+    // 50 ticks, a little less because each tick accelerates before it moves.
+    let motion_limits = MotionLimits::new(120, 480);
+    let mut motion = Motion::new(0);
+    let positions = run_to(&mut motion, 90, motion_limits, 180);
+
+    assert_eq!(*positions.last().unwrap(), 90_000);
+    assert!(
+        (45..=52).contains(&positions.len()),
+        "{} ticks",
+        positions.len()
+    );
+    assert!(positions.windows(2).all(|pair| pair[0] <= pair[1]));
+    assert!(positions.iter().all(|&position| position <= 90_000));
+
+    let max_position_change = 120_000 / MOTION_TICKS_PER_SEC as u32;
+    assert!(
+        positions
+            .windows(2)
+            .all(|pair| pair[1] - pair[0] <= max_position_change)
+    );
+    assert_eq!(motion.velocity_millidegrees_per_sec, 0);
+    // Once there, further steps leave the servo alone.
+    assert!(motion.take_moved());
+    assert!(motion.step(90, motion_limits, 180));
+    assert!(!motion.take_moved());
+}
+
+#[test]
+fn motion_step_limits_acceleration() {
+    let motion_limits = MotionLimits::new(600, 300);
+    let max_velocity_change = 300_000 / MOTION_TICKS_PER_SEC;
+    let mut motion = Motion::new(90_000);
+    let mut previous_velocity = 0;
+    loop {
+        let is_at_target = motion.step(0, motion_limits, 180);
+        let velocity = motion.velocity_millidegrees_per_sec;
+        if is_at_target {
+            break;
+        }
+        assert!(velocity <= 0);
+        assert!(
+            (velocity - previous_velocity).abs() <= max_velocity_change,
+            "{previous_velocity} -> {velocity}"
+        );
+        previous_velocity = velocity;
+    }
+    assert_eq!(motion.position_degrees(), Some(0));
+}
+
+#[test]
+fn motion_step_reverses_without_overshooting_bounds() {
+    let motion_limits = MotionLimits::new(180, 360);
+    let mut motion = Motion::new(170_000);
+    for _ in 0..10 {
+        motion.step(180, motion_limits, 180);
+    }
+    assert!(motion.velocity_millidegrees_per_sec > 0);
+
+    // Retarget behind the servo mid-move: it slows, turns around, and never leaves the range.
+    let positions = run_to(&mut motion, 0, motion_limits, 180);
+    assert!(positions.iter().all(|&position| position <= 180_000));
+    assert_eq!(*positions.last().unwrap(), 0);
+}
+
+#[test]
+fn motion_step_creeps_at_slow_limits() {
+    // 1°/s is 20 millidegrees per tick; the move must still finish exactly.
+    let motion_limits = MotionLimits::new(1, 1);
+    let mut motion = Motion::new(45_000);
+    let positions = run_to(&mut motion, 46, motion_limits, 180);
+    assert_eq!(*positions.last().unwrap(), 46_000);
+    assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+}
+
+#[test]
+fn motion_limits_pack_round_trip() {
+    for motion_limits in [
+        None,
+        Some(MotionLimits::new(1, 1)),
+        Some(MotionLimits::new(120, 480)),
+        Some(MotionLimits::new(u16::MAX, 1)),
+        Some(MotionLimits::new(1, u16::MAX)),
+        Some(MotionLimits::new(u16::MAX, u16::MAX)),
+    ] {
+        let motion_limits_u32 = AtomicU32::new(0);
+        motion_limits_u32.store(
+            MotionLimits::option_to_u32(motion_limits),
+            Ordering::Relaxed,
+        );
+        assert_eq!(
+            MotionLimits::option_from_u32(motion_limits_u32.load(Ordering::Relaxed)),
+            motion_limits
+        );
+    }
+    assert_eq!(MotionLimits::option_to_u32(None), 0);
+    assert_eq!(MotionLimits::option_from_u32(0), None);
+}
+
+#[test]
+fn keyframes_macro_builds_keyframes() {
+    const TIMELINE: [Keyframe<2>; 3] = keyframes![
+        1000 => [120, 90], SCurve;
+        0 => [0, 180];
+        250 => [45, 45,], EaseInOut;
+    ];
+    assert_eq!(
+        TIMELINE,
+        [
+            Keyframe::new([120, 90], Duration::from_millis(1000), Easing::SCurve),
+            Keyframe::new([0, 180], Duration::from_millis(0), Easing::Linear),
+            Keyframe::new([45, 45], Duration::from_millis(250), Easing::EaseInOut),
+        ]
+    );
+}
+
+#[test]
+fn keyframe_pose_interpolates_with_easing() {
+    let start_us = 1_000_000;
+    let linear_keyframe = Keyframe::new([180, 0], Duration::from_secs(1), Easing::Linear);
+    let pose_at = |keyframe: &Keyframe<2>, elapsed_ms: u64| {
+        keyframe_pose_millidegrees(
+            keyframe,
+            start_us,
+            start_us + elapsed_ms * 1_000,
+            [0, 90_000],
+        )
+    };
+    assert_eq!(pose_at(&linear_keyframe, 0), [0, 90_000]);
+    assert_eq!(pose_at(&linear_keyframe, 250), [45_000, 67_500]);
+    assert_eq!(pose_at(&linear_keyframe, 1_000), [180_000, 0]);
+    // Late ticks clamp to the keyframe's pose.
+    assert_eq!(pose_at(&linear_keyframe, 5_000), [180_000, 0]);
+
+    let s_curve_keyframe = Keyframe {
+        easing: Easing::SCurve,
+        ..linear_keyframe
+    };
+    // 180° × (10t³ − 15t⁴ + 6t⁵) at t = 1/4 and 1/2.
+    assert_eq!(pose_at(&s_curve_keyframe, 250)[0], 18_633);
+    assert_eq!(pose_at(&s_curve_keyframe, 500), [90_000, 45_000]);
+    assert_eq!(pose_at(&s_curve_keyframe, 1_000), [180_000, 0]);
+}
+
+#[test]
+fn zero_duration_keyframe_jumps() {
+    let keyframe = Keyframe::new([10, 170], Duration::from_millis(0), Easing::EaseInOut);
+    assert_eq!(
+        keyframe_pose_millidegrees(&keyframe, 20_000, 20_000, [90_000, 90_000]),
+        [10_000, 170_000]
+    );
+
+    // The playhead still visits each zero-duration pose, in order, before moving on.
+    let timeline: [Keyframe<1>; 3] = keyframes![
+        0 => [10];
+        0 => [20];
+        100 => [30];
+    ];
+    let mut playhead = Playhead::new(&timeline, [0]);
+    assert_eq!(playhead.keyframe_end_us(), 0);
+    assert_eq!(playhead.pose_millidegrees(0), [10_000]);
+    assert!(playhead.advance());
+    assert_eq!(playhead.keyframe_end_us(), 0);
+    assert_eq!(playhead.pose_millidegrees(0), [20_000]);
+    assert!(playhead.advance());
+    assert_eq!(playhead.keyframe_end_us(), 100_000);
+    assert_eq!(playhead.pose_millidegrees(0), [20_000]);
+    assert_eq!(playhead.pose_millidegrees(50_000), [25_000]);
+    assert!(!playhead.advance());
+}
+
+#[test]
+fn looping_playhead_does_not_drift() {
+    let timeline: [Keyframe<2>; 3] = keyframes![
+        500 => [180, 0], SCurve;
+        0 => [0, 180];
+        300 => [90, 90], EaseInOut;
+    ];
+    let total_us = 800_000;
+    let mut playhead = Playhead::new(&timeline, [90_000, 90_000]);
+
+    // Tick like the group's task, but wake up to 7 ms late each time.
+    let mut now_us = 0_u64;
+    let mut passes = 0_u64;
+    let mut tick_index = 0_u64;
+    while passes < 100 {
+        let pose_millidegrees = playhead.pose_millidegrees(now_us);
+        let keyframe_end_us = playhead.keyframe_end_us();
+        if now_us < keyframe_end_us {
+            let lateness_us = (tick_index % 8) * 1_000;
+            now_us = (now_us + 20_000).min(keyframe_end_us) + lateness_us;
+            tick_index += 1;
+            continue;
+        }
+        if !playhead.advance() {
+            passes += 1;
+            assert_eq!(pose_millidegrees, [90_000, 90_000]);
+            // The next pass starts exactly `passes` timelines after the first, from the
+            // last keyframe's pose.
+            assert_eq!(playhead.keyframe_end_us(), passes * total_us + 500_000);
+            assert_eq!(
+                playhead.pose_millidegrees(passes * total_us),
+                [90_000, 90_000]
+            );
+        }
+    }
+    // Despite about 3.5 ms of lateness per tick, the clock stays within one tick of the
+    // timeline.
+    assert!(now_us - passes * total_us < 30_000);
+}
@@ -2,12 +2,13 @@
 
 use crate::led2d::Frame2d;
 use png::{BitDepth, ColorType, Encoder, ScaledFloat};
+use smart_leds::RGB8;
 use std::error::Error;
 use std::fs::File;
 use std::io::BufWriter;
 use std::path::Path;
 
-const PREVIEW_INVERSE_GAMMA: f32 = 2.2;
+pub(crate) const PREVIEW_INVERSE_GAMMA: f32 = 2.2;
 
 /// Render a `Frame2d` into a PNG file sized to the requested maximum dimension.
 pub fn write_frame_png<const W: usize, const H: usize>(
@@ -85,27 +86,35 @@ pub fn write_frames_apng_with_gamma<const W: usize, const H: usize>(
     let panel_height = H as u32;
     let cell_size = select_cell_size(panel_width, panel_height, target_max_dimension);
     let led_margin = (cell_size / 8).max(1);
-    let frame_count = u32::try_from(frames.len()).expect("frame count must fit in u32");
     let delay_num = u16::try_from(frame_delay_ms).expect("frame_delay_ms must fit in u16");
     let delay_den = 1000u16;
 
     let (width, height, first_pixels) =
         panel_pixels(&frames[0], cell_size, led_margin, preview_inverse_gamma);
     let mut pixels = Vec::with_capacity(frames.len());
-    pixels.push(first_pixels);
+    pixels.push((first_pixels, delay_num, delay_den));
     for frame in frames.iter().skip(1) {
         let (frame_width, frame_height, frame_pixels) =
             panel_pixels(frame, cell_size, led_margin, preview_inverse_gamma);
         assert!(frame_width == width, "frame width must match");
         assert!(frame_height == height, "frame height must match");
-        pixels.push(frame_pixels);
+        pixels.push((frame_pixels, delay_num, delay_den));
     }
 
-    if let Some(parent) = output_path.parent() {
-        if !parent.as_os_str().is_empty() {
-            std::fs::create_dir_all(parent)?;
-        }
-    }
+    write_apng_pixels(output_path, width, height, &pixels)
+}
+
+/// Write 16-bit linear RGB images, each with its `(numerator, denominator)` delay in seconds,
+/// as a looping APNG.
+pub(crate) fn write_apng_pixels(
+    output_path: &Path,
+    width: u32,
+    height: u32,
+    frames: &[(Vec<u8>, u16, u16)],
+) -> Result<(), Box<dyn Error>> {
+    assert!(!frames.is_empty(), "frames must not be empty");
+    let frame_count = u32::try_from(frames.len()).expect("frame count must fit in u32");
+    create_parent_dir(output_path)?;
 
     let file = File::create(output_path)?;
     let mut encoder = Encoder::new(BufWriter::new(file), width, height);
@@ -114,16 +123,29 @@ pub fn write_frames_apng_with_gamma<const W: usize, const H: usize>(
     encoder.set_source_gamma(ScaledFloat::new(1.0));
     encoder.set_animated(frame_count, 0)?;
     let mut writer = encoder.write_header()?;
-    for frame_pixels in pixels {
-        writer.set_frame_delay(delay_num, delay_den)?;
-        writer.write_image_data(&frame_pixels)?;
+    for (frame_pixels, delay_num, delay_den) in frames {
+        writer.set_frame_delay(*delay_num, *delay_den)?;
+        writer.write_image_data(frame_pixels)?;
     }
     writer.finish()?;
     println!("wrote APNG to {}", output_path.display());
     Ok(())
 }
 
-fn select_cell_size(panel_width: u32, panel_height: u32, target_max_dimension: u32) -> u32 {
+pub(crate) fn create_parent_dir(output_path: &Path) -> Result<(), Box<dyn Error>> {
+    if let Some(parent) = output_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn select_cell_size(
+    panel_width: u32,
+    panel_height: u32,
+    target_max_dimension: u32,
+) -> u32 {
     assert!(
         target_max_dimension > 0,
         "target_max_dimension must be positive"
@@ -151,11 +173,7 @@ fn write_panel_png<const W: usize, const H: usize>(
     preview_inverse_gamma: f32,
 ) -> Result<(), Box<dyn Error>> {
     let (width, height, pixels) = panel_pixels(frame, cell_size, led_margin, preview_inverse_gamma);
-    if let Some(parent) = output_path.parent() {
-        if !parent.as_os_str().is_empty() {
-            std::fs::create_dir_all(parent)?;
-        }
-    }
+    create_parent_dir(output_path)?;
 
     let file = File::create(output_path)?;
     let mut encoder = Encoder::new(BufWriter::new(file), width, height);
@@ -172,6 +190,29 @@ fn panel_pixels<const W: usize, const H: usize>(
     cell_size: u32,
     led_margin: u32,
     preview_inverse_gamma: f32,
+) -> (u32, u32, Vec<u8>) {
+    let leds = (0..H).flat_map(|y_index| {
+        (0..W).map(move |x_index| (x_index as u32, y_index as u32, frame.0[y_index][x_index]))
+    });
+    leds_pixels(
+        W as u32,
+        H as u32,
+        leds,
+        cell_size,
+        led_margin,
+        preview_inverse_gamma,
+    )
+}
+
+/// Render LEDs at `(x, y, color)` cells of a `panel_width × panel_height` grid as 16-bit
+/// linear RGB. Cells without an LED stay black.
+pub(crate) fn leds_pixels(
+    panel_width: u32,
+    panel_height: u32,
+    leds: impl IntoIterator<Item = (u32, u32, RGB8)>,
+    cell_size: u32,
+    led_margin: u32,
+    preview_inverse_gamma: f32,
 ) -> (u32, u32, Vec<u8>) {
     assert!(cell_size > 0, "cell_size must be positive");
     assert!(
@@ -189,52 +230,53 @@ fn panel_pixels<const W: usize, const H: usize>(
 
     let border = led_radius;
     assert!(border > 0, "border must be positive");
-    let width = (W as u32) * cell_size + border * 2;
-    let height = (H as u32) * cell_size + border * 2;
+    let width = panel_width * cell_size + border * 2;
+    let height = panel_height * cell_size + border * 2;
     let mut bytes = vec![0u8; (width * height * 3 * 2) as usize];
     let center = (cell_size - 1) as i32 / 2;
     let led_radius_f = led_radius as f32;
     let inner_radius_f = (led_radius - fade_width) as f32;
     let radius_sq = (led_radius as i32) * (led_radius as i32);
 
-    for y_index in 0..H {
-        for x_index in 0..W {
-            let pixel = frame.0[y_index][x_index];
-            let cell_origin_x = (x_index as u32) * cell_size;
-            let cell_origin_y = (y_index as u32) * cell_size;
-
-            for local_y in 0..cell_size {
-                let delta_y = local_y as i32 - center;
-                for local_x in 0..cell_size {
-                    let delta_x = local_x as i32 - center;
-                    let distance_sq = delta_x * delta_x + delta_y * delta_y;
-                    if distance_sq <= radius_sq {
-                        let distance = (distance_sq as f32).sqrt();
-                        let intensity = if distance <= inner_radius_f {
-                            1.0
-                        } else {
-                            let fade_span = led_radius_f - inner_radius_f;
-                            (1.0 - (distance - inner_radius_f) / fade_span).max(0.0)
-                        };
-                        let x = border + cell_origin_x + local_x;
-                        let y = border + cell_origin_y + local_y;
-                        let pixel_index = ((y * width + x) * 3 * 2) as usize;
-                        let red = linear_to_u16(
-                            inverse_gamma_to_linear(pixel.r, preview_inverse_gamma) * intensity,
-                        );
-                        let green = linear_to_u16(
-                            inverse_gamma_to_linear(pixel.g, preview_inverse_gamma) * intensity,
-                        );
-                        let blue = linear_to_u16(
-                            inverse_gamma_to_linear(pixel.b, preview_inverse_gamma) * intensity,
-                        );
-                        bytes[pixel_index] = (red >> 8) as u8;
-                        bytes[pixel_index + 1] = red as u8;
-                        bytes[pixel_index + 2] = (green >> 8) as u8;
-                        bytes[pixel_index + 3] = green as u8;
-                        bytes[pixel_index + 4] = (blue >> 8) as u8;
-                        bytes[pixel_index + 5] = blue as u8;
-                    }
+    for (x_index, y_index, pixel) in leds {
+        assert!(
+            x_index < panel_width && y_index < panel_height,
+            "LED must lie inside the panel"
+        );
+        let cell_origin_x = x_index * cell_size;
+        let cell_origin_y = y_index * cell_size;
+
+        for local_y in 0..cell_size {
+            let delta_y = local_y as i32 - center;
+            for local_x in 0..cell_size {
+                let delta_x = local_x as i32 - center;
+                let distance_sq = delta_x * delta_x + delta_y * delta_y;
+                if distance_sq <= radius_sq {
+                    let distance = (distance_sq as f32).sqrt();
+                    let intensity = if distance <= inner_radius_f {
+                        1.0
+                    } else {
+                        let fade_span = led_radius_f - inner_radius_f;
+                        (1.0 - (distance - inner_radius_f) / fade_span).max(0.0)
+                    };
+                    let x = border + cell_origin_x + local_x;
+                    let y = border + cell_origin_y + local_y;
+                    let pixel_index = ((y * width + x) * 3 * 2) as usize;
+                    let red = linear_to_u16(
+                        inverse_gamma_to_linear(pixel.r, preview_inverse_gamma) * intensity,
+                    );
+                    let green = linear_to_u16(
+                        inverse_gamma_to_linear(pixel.g, preview_inverse_gamma) * intensity,
+                    );
+                    let blue = linear_to_u16(
+                        inverse_gamma_to_linear(pixel.b, preview_inverse_gamma) * intensity,
+                    );
+                    bytes[pixel_index] = (red >> 8) as u8;
+                    bytes[pixel_index + 1] = red as u8;
+                    bytes[pixel_index + 2] = (green >> 8) as u8;
+                    bytes[pixel_index + 3] = green as u8;
+                    bytes[pixel_index + 4] = (blue >> 8) as u8;
+                    bytes[pixel_index + 5] = blue as u8;
                 }
             }
         }
@@ -0,0 +1,24 @@
+#![allow(missing_docs)]
+//! Compile-only negative test: `audio_player!` must reject `pwm_pin` together with I²S pins.
+//!
+//! This file is expected to fail compilation and is validated by `cargo check-all`.
+
+#![cfg(not(feature = "host"))]
+#![no_std]
+#![no_main]
+
+use device_envoy::audio_player::{VOICE_22050_HZ, audio_player};
+use embassy_executor::Spawner;
+
+audio_player! {
+    AudioPlayer8 {
+        data_pin: PIN_8,
+        bit_clock_pin: PIN_9,
+        word_select_pin: PIN_10,
+        pwm_pin: PIN_15,
+        sample_rate_hz: VOICE_22050_HZ,
+    }
+}
+
+#[embassy_executor::main]
+async fn main(_spawner: Spawner) {}
@@ -0,0 +1,44 @@
+#![allow(missing_docs)]
+#![cfg(feature = "host")]
+//! Host-side rendering of audio player sequences, used the way a downstream crate would.
+
+use core::time::Duration;
+
+use device_envoy::audio_player::{
+    AtEnd, AudioPlayer, AudioPlayerStatic, AudioRenderer, Playable, SilenceClip, VOICE_22050_HZ,
+    Volume,
+};
+use device_envoy::tone;
+
+const CHIME: &dyn Playable<VOICE_22050_HZ> =
+    &tone!(880, VOICE_22050_HZ, Duration::from_millis(140));
+const SILENCE_40MS: &dyn Playable<VOICE_22050_HZ> = &SilenceClip::new(Duration::from_millis(40));
+
+#[test]
+fn renders_a_sequence_to_wav() {
+    static AUDIO_PLAYER_STATIC: AudioPlayerStatic<4, VOICE_22050_HZ> =
+        AudioPlayer::new_static_with_max_volume(Volume::percent(10));
+    let audio_player = AudioPlayer::new(&AUDIO_PLAYER_STATIC);
+    let mut audio_renderer = AudioRenderer::<4, VOICE_22050_HZ>::new(&AUDIO_PLAYER_STATIC);
+
+    audio_player.play([CHIME, SILENCE_40MS, CHIME], AtEnd::Stop);
+    assert!(audio_renderer.render_until_idle(Duration::from_secs(1)));
+
+    // 320 ms of sequence, rounded up to whole 256-frame buffers.
+    let duration = audio_renderer.duration();
+    assert!(duration >= Duration::from_millis(320), "{duration:?}");
+    assert!(duration < Duration::from_millis(350), "{duration:?}");
+    let frames = audio_renderer.frames();
+    assert!(frames.iter().any(|&[left, _]| left != 0));
+
+    let wav_bytes = audio_renderer.wav_bytes();
+    assert_eq!(&wav_bytes[..4], b"RIFF");
+    assert_eq!(&wav_bytes[8..12], b"WAVE");
+    assert_eq!(wav_bytes.len(), 44 + frames.len() * 4);
+
+    let output_path = std::env::temp_dir()
+        .join("device_envoy_audio_render_tests")
+        .join("chime.wav");
+    audio_renderer.write_wav(&output_path).unwrap();
+    assert_eq!(std::fs::read(&output_path).unwrap(), wav_bytes);
+}
@@ -0,0 +1,114 @@
+#![allow(missing_docs)]
+#![cfg(feature = "host")]
+//! Host-side checks for Frame1d and Frame2d pixel operations.
+
+use device_envoy::led_strip::Frame1d;
+use device_envoy::led2d::{Frame2d, Point, Size};
+use smart_leds::{RGB8, colors};
+
+const BLACK: RGB8 = RGB8::new(0, 0, 0);
+
+/// 3×2 frame with a distinct color per pixel.
+const NUMBERED: Frame2d<3, 2> = Frame2d([
+    [RGB8::new(0, 0, 0), RGB8::new(1, 0, 0), RGB8::new(2, 0, 0)],
+    [RGB8::new(3, 0, 0), RGB8::new(4, 0, 0), RGB8::new(5, 0, 0)],
+]);
+
+fn reds<const W: usize, const H: usize>(frame: Frame2d<W, H>) -> [[u8; W]; H] {
+    frame.0.map(|row| row.map(|pixel| pixel.r))
+}
+
+fn reds_1d<const N: usize>(frame: Frame1d<N>) -> [u8; N] {
+    frame.0.map(|pixel| pixel.r)
+}
+
+#[test]
+fn frame2d_shift_fills_vacated_pixels() {
+    let fill = RGB8::new(9, 0, 0);
+    assert_eq!(reds(NUMBERED.shift(1, 0, fill)), [[9, 0, 1], [9, 3, 4]]);
+    assert_eq!(reds(NUMBERED.shift(-1, 1, fill)), [[9, 9, 9], [1, 2, 9]]);
+}
+
+#[test]
+fn frame2d_scroll_wraps() {
+    assert_eq!(reds(NUMBERED.scroll(1, 0)), [[2, 0, 1], [5, 3, 4]]);
+    assert_eq!(reds(NUMBERED.scroll(-4, 1)), [[4, 5, 3], [1, 2, 0]]);
+}
+
+#[test]
+fn frame2d_flip_and_rotate() {
+    assert_eq!(reds(NUMBERED.flip_h()), [[2, 1, 0], [5, 4, 3]]);
+    assert_eq!(reds(NUMBERED.flip_v()), [[3, 4, 5], [0, 1, 2]]);
+    assert_eq!(reds(NUMBERED.rotate_cw()), [[3, 0], [4, 1], [5, 2]]);
+    assert_eq!(reds(NUMBERED.rotate_ccw()), [[2, 5], [1, 4], [0, 3]]);
+    assert_eq!(reds(NUMBERED.rotate_180()), [[5, 4, 3], [2, 1, 0]]);
+    assert_eq!(reds(NUMBERED.rotate_cw().rotate_ccw()), reds(NUMBERED));
+}
+
+#[test]
+fn frame2d_blit_clips_to_both_frames() {
+    let mut frame = Frame2d::<4, 3>::filled(RGB8::new(9, 0, 0));
+    frame.blit(
+        &NUMBERED,
+        Point::new(1, 0),
+        Size::new(5, 2),
+        Point::new(2, 2),
+    );
+    assert_eq!(reds(frame), [[9, 9, 9, 9], [9, 9, 9, 9], [9, 9, 1, 2]]);
+
+    let mut frame = Frame2d::<2, 2>::new();
+    frame.blit(
+        &NUMBERED,
+        Point::new(-1, 0),
+        Size::new(2, 2),
+        Point::new(0, 0),
+    );
+    assert_eq!(reds(frame), [[0, 0], [0, 3]]);
+}
+
+#[test]
+fn frame2d_blend_add_and_fade() {
+    let red = Frame2d::<2, 1>::filled(colors::RED);
+    let blue = Frame2d::<2, 1>::filled(colors::BLUE);
+    assert_eq!(red.blend(blue, 0).0, red.0);
+    assert_eq!(red.blend(blue, 255).0, blue.0);
+    assert_eq!(red.blend(blue, 128).0[0][0], RGB8::new(127, 0, 128));
+    assert_eq!(
+        red.saturating_add(Frame2d::filled(RGB8::new(10, 20, 30))).0[0][0],
+        RGB8::new(255, 20, 30)
+    );
+    assert_eq!(red.fade(255).0, red.0);
+    assert_eq!(red.fade(0).0[0][0], BLACK);
+    assert_eq!(red.fade(51).0[0][0], RGB8::new(51, 0, 0));
+}
+
+#[test]
+fn frame2d_ops_are_const() {
+    const SHIFTED: Frame2d<3, 2> = NUMBERED.scroll(1, 1).flip_h().fade(255);
+    assert_eq!(reds(SHIFTED), [[4, 3, 5], [1, 0, 2]]);
+}
+
+#[test]
+fn frame1d_shift_scroll_and_reverse() {
+    let frame = Frame1d([0, 1, 2, 3].map(|value| RGB8::new(value, 0, 0)));
+    let fill = RGB8::new(9, 0, 0);
+    assert_eq!(reds_1d(frame.shift(2, fill)), [9, 9, 0, 1]);
+    assert_eq!(reds_1d(frame.shift(-1, fill)), [1, 2, 3, 9]);
+    assert_eq!(reds_1d(frame.scroll(1)), [3, 0, 1, 2]);
+    assert_eq!(reds_1d(frame.scroll(-5)), [1, 2, 3, 0]);
+    assert_eq!(reds_1d(frame.reverse()), [3, 2, 1, 0]);
+}
+
+#[test]
+fn frame1d_blit_blend_add_and_fade() {
+    let source = Frame1d([0, 1, 2, 3].map(|value| RGB8::new(value, 0, 0)));
+    let mut frame = Frame1d::<3>::filled(RGB8::new(9, 0, 0));
+    frame.blit(&source, 2, 5, 1);
+    assert_eq!(reds_1d(frame), [9, 2, 3]);
+
+    let red = Frame1d::<2>::filled(colors::RED);
+    let blue = Frame1d::<2>::filled(colors::BLUE);
+    assert_eq!(red.blend(blue, 128).0[1], RGB8::new(127, 0, 128));
+    assert_eq!(red.saturating_add(blue).0[0], colors::MAGENTA);
+    assert_eq!(red.fade(0).0[0], BLACK);
+}
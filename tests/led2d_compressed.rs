@@ -0,0 +1,113 @@
+#![allow(missing_docs)]
+#![cfg(feature = "host")]
+//! Host-side round-trip checks for compressed led2d animations.
+
+use device_envoy::compressed_animation;
+use device_envoy::led2d::CompressedAnimation;
+use embassy_time::Duration;
+use smart_leds::{RGB8, colors};
+
+include!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/examples/data/frame-data/video_frames_data.rs"
+));
+
+const BLINK_DURATION: Duration = Duration::from_millis(250);
+const BLINK_FRAMES: [([[RGB8; 4]; 3], Duration); 3] = [
+    (
+        [
+            [colors::RED, colors::RED, colors::BLACK, colors::BLACK],
+            [colors::BLACK; 4],
+            [colors::BLUE; 4],
+        ],
+        BLINK_DURATION,
+    ),
+    (
+        [
+            [colors::RED, colors::RED, colors::GREEN, colors::BLACK],
+            [colors::BLACK; 4],
+            [colors::BLUE; 4],
+        ],
+        Duration::from_millis(500),
+    ),
+    ([[colors::WHITE; 4]; 3], BLINK_DURATION),
+];
+
+compressed_animation! {
+    static BLINK_ANIMATION = BLINK_FRAMES;
+}
+
+compressed_animation! {
+    static SANTA_ANIMATION = SANTA_FRAMES;
+}
+
+const fn gradient_frames() -> [([[RGB8; 20]; 20], Duration); 2] {
+    let mut frames = [([[RGB8::new(0, 0, 0); 20]; 20], Duration::from_millis(40)); 2];
+    let mut y_index = 0;
+    while y_index < 20 {
+        let mut x_index = 0;
+        while x_index < 20 {
+            let value = (y_index * 20 + x_index) as u8;
+            frames[0].0[y_index][x_index] = RGB8::new(value, 255 - value, 7);
+            frames[1].0[y_index][x_index] = RGB8::new(7, value, 255 - value);
+            x_index += 1;
+        }
+        y_index += 1;
+    }
+    frames
+}
+
+const GRADIENT_FRAMES: [([[RGB8; 20]; 20], Duration); 2] = gradient_frames();
+
+compressed_animation! {
+    static GRADIENT_ANIMATION = GRADIENT_FRAMES;
+}
+
+fn assert_round_trip<const W: usize, const H: usize>(
+    animation: &CompressedAnimation<W, H>,
+    expected: &[([[RGB8; W]; H], Duration)],
+) {
+    assert_eq!(animation.frame_count(), expected.len());
+    let decoded: Vec<_> = animation.frames().collect();
+    assert_eq!(decoded.len(), expected.len());
+    for (frame_index, ((frame, duration), (expected_frame, expected_duration))) in
+        decoded.iter().zip(expected).enumerate()
+    {
+        assert_eq!(
+            duration, expected_duration,
+            "duration of frame {frame_index}"
+        );
+        assert_eq!(&frame.0, expected_frame, "pixels of frame {frame_index}");
+    }
+}
+
+#[test]
+fn compressed_blink_round_trips() {
+    assert_round_trip(&BLINK_ANIMATION, &BLINK_FRAMES);
+}
+
+#[test]
+fn compressed_blink_is_smaller_than_raw() {
+    let raw_len = BLINK_FRAMES.len() * 4 * 3 * 3;
+    assert!(BLINK_ANIMATION.data_len() < raw_len);
+}
+
+#[test]
+fn compressed_animation_with_more_than_palette_colors_round_trips() {
+    assert_round_trip(&GRADIENT_ANIMATION, &GRADIENT_FRAMES);
+}
+
+#[test]
+fn compressed_santa_video_round_trips() {
+    assert_round_trip(&SANTA_ANIMATION, &SANTA_FRAMES);
+    let raw_len = SANTA_FRAMES.len() * 12 * 8 * 3;
+    assert!(SANTA_ANIMATION.data_len() < raw_len);
+}
+
+#[test]
+fn compressed_frames_iterator_reports_exact_len() {
+    let mut frames = BLINK_ANIMATION.frames();
+    assert_eq!(frames.len(), 3);
+    frames.next();
+    assert_eq!(frames.len(), 2);
+}
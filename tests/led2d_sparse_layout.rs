@@ -0,0 +1,187 @@
+#![allow(missing_docs)]
+//! Host-level tests for sparse and ring layouts.
+
+use device_envoy::led2d::layout::LedLayout;
+
+#[test]
+fn new_sparse_accepts_matrix_with_gaps() {
+    // 3×2 grid with the middle of the bottom row missing.
+    const GAPPED: LedLayout<5, 3, 2> =
+        LedLayout::new_sparse([(0, 0), (1, 0), (2, 0), (2, 1), (0, 1)]);
+    assert!(GAPPED.is_sparse());
+    assert_eq!(GAPPED.len(), 5);
+    assert_eq!(GAPPED.index_at(2, 1), Some(3));
+    assert_eq!(GAPPED.index_at(1, 1), None);
+}
+
+#[test]
+fn new_sparse_accepts_dense_layout() {
+    const DENSE: LedLayout<4, 2, 2> = LedLayout::new_sparse([(0, 0), (1, 0), (1, 1), (0, 1)]);
+    assert!(!DENSE.is_sparse());
+    assert!(DENSE.equals(&LedLayout::serpentine_row_major()));
+}
+
+#[test]
+#[should_panic(expected = "duplicate (col,row) in mapping")]
+fn new_sparse_panics_on_duplicate_cell() {
+    let _ = LedLayout::<3, 3, 3>::new_sparse([(0, 0), (1, 1), (1, 1)]);
+}
+
+#[test]
+#[should_panic(expected = "row out of bounds")]
+fn new_sparse_panics_on_out_of_bounds_row() {
+    let _ = LedLayout::<2, 3, 3>::new_sparse([(0, 0), (0, 3)]);
+}
+
+#[test]
+#[should_panic(expected = "N must not exceed W*H")]
+fn new_sparse_panics_when_too_many_leds() {
+    let _ = LedLayout::<5, 2, 2>::new_sparse([(0, 0), (1, 0), (0, 1), (1, 1), (0, 0)]);
+}
+
+#[test]
+fn transforms_preserve_sparse_cells() {
+    const L_SHAPE: LedLayout<3, 2, 3> = LedLayout::new_sparse([(0, 0), (0, 1), (1, 2)]);
+
+    assert_eq!(L_SHAPE.rotate_cw().index_to_xy(), &[(2, 0), (1, 0), (0, 1)]);
+    assert_eq!(L_SHAPE.flip_h().index_to_xy(), &[(1, 0), (1, 1), (0, 2)]);
+    assert_eq!(L_SHAPE.flip_v().index_to_xy(), &[(0, 2), (0, 1), (1, 0)]);
+    assert!(L_SHAPE.rotate_180().rotate_180().equals(&L_SHAPE));
+    assert!(L_SHAPE.rotate_ccw().rotate_cw().equals(&L_SHAPE));
+
+    let combined = L_SHAPE.combine_h::<3, 6, 2, 4>(L_SHAPE);
+    assert_eq!(
+        combined.index_to_xy(),
+        &[(0, 0), (0, 1), (1, 2), (2, 0), (2, 1), (3, 2)]
+    );
+    assert!(combined.is_sparse());
+}
+
+#[test]
+fn reverse_flips_wiring_order() {
+    const RINGS: LedLayout<9, 5, 5> = LedLayout::concentric_rings([1, 8]);
+    let reversed = RINGS.reverse();
+    assert_eq!(reversed.index_to_xy()[0], RINGS.index_to_xy()[8]);
+    assert_eq!(reversed.index_to_xy()[8], (2, 2));
+    assert!(reversed.reverse().equals(&RINGS));
+}
+
+#[test]
+fn concentric_rings_places_leds_clockwise_from_top() {
+    const RINGS: LedLayout<9, 5, 5> = LedLayout::concentric_rings([1, 8]);
+    assert_eq!(
+        RINGS.index_to_xy(),
+        &[
+            (2, 2),
+            (2, 0),
+            (3, 1),
+            (4, 2),
+            (3, 3),
+            (2, 4),
+            (1, 3),
+            (0, 2),
+            (1, 1),
+        ]
+    );
+}
+
+#[test]
+fn concentric_rings_supports_common_ring_boards() {
+    // 61-LED board: center plus rings of 8, 12, 16, and 24.
+    const RINGS_61: LedLayout<61, 15, 15> = LedLayout::concentric_rings([1, 8, 12, 16, 24]);
+    assert!(RINGS_61.is_sparse());
+    assert_eq!(RINGS_61.index_to_xy()[0], (7, 7));
+    // Outer ring starts at 12 o'clock on the edge of the grid.
+    assert_eq!(RINGS_61.index_to_xy()[37], (7, 0));
+
+    // 241-LED board: center plus rings of 8 through 60.
+    const RINGS_241: LedLayout<241, 31, 31> =
+        LedLayout::concentric_rings([1, 8, 12, 16, 24, 32, 40, 48, 60]);
+    assert_eq!(RINGS_241.index_to_xy()[181], (15, 0));
+}
+
+#[test]
+fn concentric_rings_without_center_starts_one_step_out() {
+    const RING: LedLayout<4, 3, 3> = LedLayout::concentric_rings([4]);
+    assert_eq!(RING.index_to_xy(), &[(1, 0), (2, 1), (1, 2), (0, 1)]);
+}
+
+#[test]
+fn polar_coordinates_follow_ring_positions() {
+    const RINGS: LedLayout<61, 15, 15> = LedLayout::concentric_rings([1, 8, 12, 16, 24]);
+
+    let (radius, _) = RINGS.xy_to_polar(7, 7);
+    assert!(radius.abs() < 1e-6);
+
+    let (radius, angle_degrees) = RINGS.xy_to_polar(7, 0);
+    assert!((radius - 7.0).abs() < 1e-4);
+    assert!(angle_degrees.abs() < 1e-3);
+
+    let (radius, angle_degrees) = RINGS.xy_to_polar(14, 7);
+    assert!((radius - 7.0).abs() < 1e-4);
+    assert!((angle_degrees - 90.0).abs() < 1e-3);
+
+    let (_, angle_degrees) = RINGS.xy_to_polar(7, 14);
+    assert!((angle_degrees - 180.0).abs() < 1e-3);
+
+    let (_, angle_degrees) = RINGS.xy_to_polar(0, 0);
+    assert!((angle_degrees - 315.0).abs() < 1e-3);
+
+    // Every outer-ring LED round-trips through polar coordinates.
+    for &(x, y) in &RINGS.index_to_xy()[37..] {
+        let (radius, angle_degrees) = RINGS.xy_to_polar(x, y);
+        let point = RINGS.polar_to_xy(radius, angle_degrees);
+        assert_eq!((point.x, point.y), (i32::from(x), i32::from(y)));
+    }
+}
+
+#[test]
+fn try_new_accepts_valid_runtime_maps() {
+    let dense = LedLayout::<4, 2, 2>::try_new([(0, 0), (0, 1), (1, 1), (1, 0)]).unwrap();
+    assert!(dense.equals(&LedLayout::serpentine_column_major()));
+
+    let sparse = LedLayout::<2, 2, 2>::try_new([(1, 1), (0, 0)]).unwrap();
+    assert!(sparse.is_sparse());
+}
+
+#[test]
+fn try_new_rejects_invalid_runtime_maps() {
+    assert!(LedLayout::<2, 2, 2>::try_new([(0, 0), (2, 0)]).is_err());
+    assert!(LedLayout::<2, 2, 2>::try_new([(0, 0), (0, 2)]).is_err());
+    assert!(LedLayout::<2, 2, 2>::try_new([(1, 0), (1, 0)]).is_err());
+    assert!(LedLayout::<5, 2, 2>::try_new([(0, 0), (1, 0), (0, 1), (1, 1), (0, 0)]).is_err());
+}
+
+#[test]
+fn orientations_cover_flips_rotation_and_reversed_wiring() {
+    const LED_LAYOUT: LedLayout<6, 3, 2> = LedLayout::serpentine_column_major();
+    let orientations = LED_LAYOUT.orientations();
+
+    assert!(orientations[0].equals(&LED_LAYOUT));
+    assert!(orientations[1].equals(&LED_LAYOUT.flip_h()));
+    assert!(orientations[2].equals(&LED_LAYOUT.flip_v()));
+    assert!(orientations[3].equals(&LED_LAYOUT.rotate_180()));
+    assert!(orientations[4].equals(&LED_LAYOUT.reverse()));
+    assert!(orientations[7].equals(&LED_LAYOUT.rotate_180().reverse()));
+
+    // Any orientation leads back to the same set of candidates.
+    for orientation in orientations {
+        for candidate in orientation.orientations() {
+            assert!(orientations.iter().any(|known| known.equals(&candidate)));
+        }
+    }
+
+    // Serpentine wiring with an odd column count is symmetric: rotating 180° reverses it.
+    assert!(orientations[3].equals(&orientations[4]));
+}
+
+#[test]
+fn orientations_are_distinct_for_asymmetric_layouts() {
+    const L_SHAPE: LedLayout<3, 2, 3> = LedLayout::new_sparse([(0, 0), (0, 1), (1, 2)]);
+    let orientations = L_SHAPE.orientations();
+    for (index, orientation) in orientations.iter().enumerate() {
+        for other in &orientations[..index] {
+            assert!(!orientation.equals(other));
+        }
+    }
+}
@@ -0,0 +1,145 @@
+#![allow(missing_docs)]
+#![cfg(feature = "host")]
+//! Host-side checks for led2d sprites and tile maps.
+
+use device_envoy::led2d::Frame2d;
+use device_envoy::led2d::sprite::{EMPTY_TILE, Layer, Sprite, SpriteImage, TileMap, render_layers};
+use embedded_graphics::Drawable;
+use embedded_graphics::prelude::{Point, Size};
+use embedded_graphics::primitives::Rectangle;
+use smart_leds::{RGB8, colors};
+
+const ARROW: SpriteImage<3, 2> = SpriteImage::from_ascii(
+    ["RG.", "B.."],
+    &[
+        (b'R', colors::RED),
+        (b'G', colors::GREEN),
+        (b'B', colors::BLUE),
+    ],
+);
+const BLOCK: SpriteImage<2, 2> = SpriteImage::filled(colors::WHITE);
+const DOT: SpriteImage<1, 1> = SpriteImage::filled(colors::YELLOW);
+
+const E: u8 = EMPTY_TILE;
+const TILES: [SpriteImage<2, 2>; 2] = [
+    SpriteImage::filled(colors::GREEN),
+    SpriteImage::from_ascii(["B.", ".B"], &[(b'B', colors::BLUE)]),
+];
+const MAP: [[u8; 4]; 2] = [[0, E, 1, E], [E, 0, E, 0]];
+
+#[test]
+fn sprite_from_ascii_is_transparent_where_dotted() {
+    assert_eq!(ARROW.0[0], [Some(colors::RED), Some(colors::GREEN), None]);
+    assert_eq!(ARROW.0[1], [Some(colors::BLUE), None, None]);
+}
+
+#[test]
+fn sprite_draws_at_position_with_transparency() {
+    let background = RGB8::new(1, 2, 3);
+    let mut frame = Frame2d::<4, 3>::filled(background);
+    Sprite::new(&ARROW, Point::new(1, 1))
+        .draw(&mut frame)
+        .expect("drawing to a frame is infallible");
+    assert_eq!(frame[(1, 1)], colors::RED);
+    assert_eq!(frame[(2, 1)], colors::GREEN);
+    assert_eq!(frame[(3, 1)], background);
+    assert_eq!(frame[(1, 2)], colors::BLUE);
+    assert_eq!(frame[(2, 2)], background);
+    assert_eq!(frame[(0, 0)], background);
+}
+
+#[test]
+fn sprite_flip_matches_flipped_image() {
+    let flipped = Sprite::new(&ARROW, Point::new(0, 0)).with_flip(true, true);
+    let image = ARROW.flip_h().flip_v();
+    for y_index in 0..2 {
+        for x_index in 0..3 {
+            let point = Point::new(x_index, y_index);
+            assert_eq!(
+                flipped.pixel_at(point),
+                image.0[y_index as usize][x_index as usize]
+            );
+        }
+    }
+}
+
+#[test]
+fn render_layers_respects_z_order() {
+    let low = Sprite::new(&BLOCK, Point::new(0, 0)).with_z(5);
+    let high = Sprite::new(&DOT, Point::new(1, 1)).with_z(6);
+    let mut frame = Frame2d::<3, 3>::new();
+    // The higher sprite wins even though it comes first.
+    render_layers(&mut frame, &[&high, &low]);
+    assert_eq!(frame[(0, 0)], colors::WHITE);
+    assert_eq!(frame[(1, 1)], colors::YELLOW);
+    assert_eq!(frame[(2, 2)], RGB8::new(0, 0, 0));
+
+    // Equal z: later layers are on top.
+    let mut frame = Frame2d::<3, 3>::new();
+    render_layers(&mut frame, &[&high.with_z(5), &low]);
+    assert_eq!(frame[(1, 1)], colors::WHITE);
+}
+
+#[test]
+fn hidden_sprites_draw_nothing_and_never_collide() {
+    let mut block = Sprite::new(&BLOCK, Point::new(0, 0));
+    let dot = Sprite::new(&DOT, Point::new(1, 1));
+    assert!(block.collides_with(&dot));
+    block.set_visible(false);
+    assert!(!block.collides_with(&dot));
+    let mut frame = Frame2d::<2, 2>::new();
+    render_layers(&mut frame, &[&block]);
+    assert_eq!(frame.0, Frame2d::<2, 2>::new().0);
+}
+
+#[test]
+fn sprite_collision_is_pixel_accurate() {
+    let arrow = Sprite::new(&ARROW, Point::new(0, 0));
+    // Bounding boxes overlap at (1, 1), but the arrow is transparent there.
+    let mut dot = Sprite::new(&DOT, Point::new(1, 1));
+    assert!(!arrow.collides_with(&dot));
+    dot.move_by(-1, 0);
+    assert!(arrow.collides_with(&dot));
+    assert!(!arrow.overlaps(&Rectangle::new(Point::new(2, 0), Size::new(1, 1))));
+    assert!(arrow.overlaps(&Rectangle::new(Point::new(1, 0), Size::new(2, 2))));
+}
+
+#[test]
+fn tile_map_scrolls_and_clips() {
+    let mut tile_map = TileMap::new(&TILES, MAP);
+    assert_eq!(tile_map.pixel_at(Point::new(0, 0)), Some(colors::GREEN));
+    assert_eq!(tile_map.pixel_at(Point::new(2, 0)), None);
+    assert_eq!(tile_map.tile_at(Point::new(4, 0)), Some(1));
+    assert_eq!(tile_map.pixel_at(Point::new(5, 0)), None);
+
+    tile_map.scroll_by(4, 2);
+    assert_eq!(tile_map.pixel_at(Point::new(0, 0)), None);
+    assert_eq!(tile_map.tile_at(Point::new(2, 0)), Some(0));
+    assert_eq!(tile_map.cell_at(Point::new(4, 0)), None);
+
+    let wrapping = tile_map.with_wrap(true);
+    assert_eq!(wrapping.cell_at(Point::new(4, 0)), Some((0, 1)));
+}
+
+#[test]
+fn tile_map_sprite_collision_uses_solid_tiles() {
+    let tile_map = TileMap::new(&TILES, MAP);
+    let dot_on_grass = Sprite::new(&DOT, Point::new(1, 1));
+    let dot_on_gap = Sprite::new(&DOT, Point::new(2, 1));
+    assert!(tile_map.collides_with_sprite(&dot_on_grass, |tile| tile == 0));
+    assert!(!tile_map.collides_with_sprite(&dot_on_gap, |tile| tile == 0));
+    assert!(!tile_map.collides_with_sprite(&dot_on_grass, |tile| tile == 1));
+}
+
+#[test]
+fn tile_map_and_sprites_render_together() {
+    let tile_map = TileMap::new(&TILES, MAP);
+    let dot = Sprite::new(&DOT, Point::new(0, 0)).with_z(1);
+    let mut frame = Frame2d::<8, 4>::new();
+    render_layers(&mut frame, &[&tile_map, &dot]);
+    assert_eq!(frame[(0, 0)], colors::YELLOW);
+    assert_eq!(frame[(1, 0)], colors::GREEN);
+    assert_eq!(frame[(4, 0)], colors::BLUE);
+    assert_eq!(frame[(5, 1)], colors::BLUE);
+    assert_eq!(frame[(2, 2)], colors::GREEN);
+}
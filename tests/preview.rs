@@ -0,0 +1,273 @@
+#![allow(missing_docs)]
+#![cfg(feature = "host")]
+//! Host-level tests for the LED preview simulators.
+
+use device_envoy::compressed_animation;
+use device_envoy::led_strip::Frame1d;
+use device_envoy::led2d::{Frame2d, Led2d, Led2dFont, layout::LedLayout};
+use device_envoy::preview::{Command, Led2dSimulator, LedStrip};
+use embassy_time::Duration;
+use smart_leds::{RGB8, colors};
+use std::fs::File;
+use std::path::PathBuf;
+
+fn temp_output_path(filename: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join("device_envoy_preview_tests");
+    std::fs::create_dir_all(&dir).expect("temp dir must be creatable");
+    dir.join(filename)
+}
+
+fn colors_of<const N: usize>(playback: &[(Frame1d<N>, Duration)]) -> Vec<(RGB8, u64)> {
+    playback
+        .iter()
+        .map(|(frame, duration)| (frame.0[0], duration.as_millis()))
+        .collect()
+}
+
+#[test]
+fn strip_playback_follows_calls_and_clock() {
+    let strip = LedStrip::<4, 16>::new();
+    strip.advance(Duration::from_millis(100));
+    strip.write_frame(Frame1d::filled(colors::RED)).unwrap();
+    strip.advance(Duration::from_millis(500));
+    strip
+        .animate([
+            (Frame1d::filled(colors::GREEN), Duration::from_millis(300)),
+            (Frame1d::filled(colors::BLUE), Duration::from_millis(300)),
+        ])
+        .unwrap();
+    strip.advance(Duration::from_millis(1000));
+
+    let timeline = strip.timeline();
+    assert_eq!(timeline.events().len(), 2);
+    assert_eq!(timeline.events()[1].at, Duration::from_millis(600));
+    assert_eq!(timeline.now(), Duration::from_millis(1600));
+
+    // Dark until the first call; the animation loops and its last frame is cut short.
+    assert_eq!(
+        colors_of(&timeline.playback()),
+        [
+            (colors::BLACK, 100),
+            (colors::RED, 500),
+            (colors::GREEN, 300),
+            (colors::BLUE, 300),
+            (colors::GREEN, 300),
+            (colors::BLUE, 100),
+        ]
+    );
+}
+
+#[test]
+fn final_calls_get_natural_length_without_advance() {
+    let strip = LedStrip::<2, 16>::new();
+    strip.write_frame(Frame1d::filled(colors::RED)).unwrap();
+    assert_eq!(
+        colors_of(&strip.timeline().playback()),
+        [(colors::RED, 1000)]
+    );
+
+    let strip = LedStrip::<2, 16>::new();
+    strip
+        .animate([
+            (Frame1d::filled(colors::GREEN), Duration::from_millis(200)),
+            (Frame1d::filled(colors::BLUE), Duration::from_millis(50)),
+        ])
+        .unwrap();
+    assert_eq!(
+        colors_of(&strip.timeline().playback()),
+        [(colors::GREEN, 200), (colors::BLUE, 50)]
+    );
+}
+
+#[test]
+fn replaced_calls_are_skipped() {
+    let strip = LedStrip::<2, 16>::new();
+    strip.write_frame(Frame1d::filled(colors::RED)).unwrap();
+    strip.write_frame(Frame1d::filled(colors::BLUE)).unwrap();
+    strip.advance(Duration::from_millis(40));
+    assert_eq!(
+        colors_of(&strip.timeline().playback()),
+        [(colors::BLUE, 40)]
+    );
+}
+
+#[test]
+fn led2d_records_frames_in_strip_order() {
+    const LED_LAYOUT: LedLayout<6, 3, 2> = LedLayout::serpentine_column_major();
+    static LED_STRIP: LedStrip<6, 16> = LedStrip::new();
+    let panel = Led2dSimulator::new(&LED_STRIP, &LED_LAYOUT, Led2dFont::Font3x4Trim);
+
+    let mut frame = Frame2d::<3, 2>::new();
+    frame[(1, 0)] = colors::RED; // LED 3 in column-major serpentine wiring
+    panel.write_frame(frame).unwrap();
+
+    let timeline = panel.timeline();
+    let Command::WriteFrame(strip_frame) = &timeline.events()[0].command else {
+        panic!("expected a recorded write_frame");
+    };
+    let lit: Vec<usize> = (0..6)
+        .filter(|&led_index| strip_frame.0[led_index] != colors::BLACK)
+        .collect();
+    assert_eq!(lit, [3]);
+}
+
+#[test]
+fn led2d_panel_records_on_host_strip() {
+    // Code written against `Led2d` (as generated panel types are) records without changes.
+    const LED_LAYOUT: LedLayout<4, 2, 2> = LedLayout::serpentine_column_major();
+    static LED_STRIP: LedStrip<4, 2> = LedStrip::new();
+    let led2d = Led2d::new(&LED_STRIP, &LED_LAYOUT);
+
+    let mut frame = Frame2d::<2, 2>::new();
+    frame[(1, 1)] = colors::RED; // LED 2 in column-major serpentine wiring
+    led2d.write_frame(frame).unwrap();
+    LED_STRIP.advance(Duration::from_millis(100));
+    led2d
+        .animate([
+            (
+                Frame2d::<2, 2>::filled(colors::GREEN),
+                Duration::from_millis(50),
+            ),
+            (
+                Frame2d::<2, 2>::filled(colors::BLUE),
+                Duration::from_millis(50),
+            ),
+        ])
+        .unwrap();
+
+    let timeline = LED_STRIP.timeline();
+    assert_eq!(timeline.events().len(), 2);
+    let Command::WriteFrame(strip_frame) = &timeline.events()[0].command else {
+        panic!("expected a recorded write_frame");
+    };
+    assert_eq!(
+        strip_frame.0,
+        [colors::BLACK, colors::BLACK, colors::RED, colors::BLACK]
+    );
+    // `colors_of` samples LED 0, which the first frame leaves dark.
+    assert_eq!(
+        colors_of(&timeline.playback()),
+        [
+            (colors::BLACK, 100),
+            (colors::GREEN, 50),
+            (colors::BLUE, 50)
+        ]
+    );
+}
+
+#[test]
+fn strip_enforces_max_frames() {
+    let strip = LedStrip::<2, 0>::new();
+    assert!(
+        strip
+            .animate([(Frame1d::filled(colors::RED), Duration::from_millis(10))])
+            .is_err()
+    );
+    assert!(strip.write_frame(Frame1d::filled(colors::RED)).is_ok());
+}
+
+#[test]
+fn compressed_animation_records_one_loop() {
+    const FRAME_DURATION: Duration = Duration::from_millis(100);
+    const FRAMES: [([[RGB8; 2]; 1], Duration); 3] = [
+        ([[colors::RED; 2]], FRAME_DURATION),
+        ([[colors::GREEN, colors::RED]], FRAME_DURATION),
+        ([[colors::BLUE; 2]], FRAME_DURATION),
+    ];
+    compressed_animation! {
+        static ANIMATION = FRAMES;
+    }
+    const LED_LAYOUT: LedLayout<2, 2, 1> = LedLayout::linear_h();
+    static LED_STRIP: LedStrip<2, 0> = LedStrip::new();
+    let panel = Led2dSimulator::new(&LED_STRIP, &LED_LAYOUT, Led2dFont::Font3x4Trim);
+    panel.animate_compressed(&ANIMATION).unwrap();
+
+    let timeline = panel.timeline();
+    let Command::Animate(frames) = &timeline.events()[0].command else {
+        panic!("expected a recorded animation");
+    };
+    let decoded: Vec<[RGB8; 2]> = frames.iter().map(|(frame, _)| frame.0).collect();
+    assert_eq!(
+        decoded,
+        [
+            [colors::RED, colors::RED],
+            [colors::GREEN, colors::RED],
+            [colors::BLUE, colors::BLUE]
+        ]
+    );
+}
+
+#[test]
+fn led2d_records_write_text() {
+    const LED_LAYOUT: LedLayout<48, 12, 4> = LedLayout::serpentine_column_major();
+    static LED_STRIP: LedStrip<48, 16> = LedStrip::new();
+    let panel = Led2dSimulator::new(&LED_STRIP, &LED_LAYOUT, Led2dFont::Font3x4Trim);
+    embassy_futures::block_on(panel.write_text("Hi", &[colors::CYAN])).unwrap();
+
+    let timeline = panel.timeline();
+    let Command::WriteText { text, frame } = &timeline.events()[0].command else {
+        panic!("expected a recorded write_text");
+    };
+    assert_eq!(text, "Hi");
+    assert!(frame.0.contains(&colors::CYAN));
+}
+
+#[test]
+fn apng_export_matches_playback() {
+    const RINGS: LedLayout<9, 5, 5> = LedLayout::concentric_rings([1, 8]);
+    static LED_STRIP: LedStrip<9, 16> = LedStrip::new();
+    let rings = Led2dSimulator::new(&LED_STRIP, &RINGS, Led2dFont::Font3x4Trim);
+    rings.write_frame(Frame2d::filled(colors::RED)).unwrap();
+    rings.advance(Duration::from_millis(250));
+    rings
+        .animate([
+            (Frame2d::filled(colors::GREEN), Duration::from_millis(100)),
+            (Frame2d::new(), Duration::from_millis(100)),
+        ])
+        .unwrap();
+    rings.advance(Duration::from_millis(200));
+
+    let output_path = temp_output_path("rings.png");
+    rings.write_apng(&output_path, 200).unwrap();
+
+    let decoder = png::Decoder::new(File::open(&output_path).unwrap());
+    let mut reader = decoder.read_info().unwrap();
+    let animation = reader.info().animation_control().unwrap();
+    assert_eq!(animation.num_frames, 3);
+
+    // The center LED is lit red; the corner cell has no LED, so it stays black.
+    let (width, height) = reader.info().size();
+    let mut pixels = vec![0; reader.output_buffer_size()];
+    reader.next_frame(&mut pixels).unwrap();
+    let pixel_at = |x: u32, y: u32| {
+        let offset = ((y * width + x) * 6) as usize;
+        [pixels[offset], pixels[offset + 2], pixels[offset + 4]]
+    };
+    let center = pixel_at(width / 2, height / 2);
+    assert!(center[0] > 200 && center[1] == 0 && center[2] == 0);
+    let corner_cell = pixel_at(width / 10, height / 10);
+    assert_eq!(corner_cell, [0, 0, 0]);
+}
+
+#[test]
+fn gif_export_writes_one_image_per_playback_frame() {
+    let strip = LedStrip::<8, 16>::new();
+    strip
+        .animate([
+            (Frame1d::filled(colors::RED), Duration::from_millis(500)),
+            (Frame1d::filled(colors::BLUE), Duration::from_millis(250)),
+        ])
+        .unwrap();
+
+    let output_path = temp_output_path("strip.gif");
+    strip.write_gif(&output_path, 400).unwrap();
+
+    let mut decoder = gif::DecodeOptions::new()
+        .read_info(File::open(&output_path).unwrap())
+        .unwrap();
+    let mut delays = Vec::new();
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        delays.push(frame.delay);
+    }
+    assert_eq!(delays, [50, 25]);
+}
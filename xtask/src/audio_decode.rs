@@ -0,0 +1,261 @@
+//! Pure-Rust decoders for `audio-prep` source files: WAV (integer or float PCM), FLAC, and MP3
+//! through `symphonia`, plus raw 16-bit little-endian PCM (`.s16`).
+//!
+//! Every decoder returns [`DecodedAudio`]: one `Vec<f64>` per channel, scaled to `-1.0..1.0`.
+
+use std::path::Path;
+
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::DecoderOptions;
+use symphonia::core::errors::Error as SymphoniaError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+/// Decoded audio, one sample vector per channel, scaled to `-1.0..1.0`.
+pub struct DecodedAudio {
+    pub sample_rate_hz: u32,
+    pub channels: Vec<Vec<f64>>,
+}
+
+impl DecodedAudio {
+    pub fn frame_count(&self) -> usize {
+        self.channels.first().map_or(0, Vec::len)
+    }
+}
+
+/// Decodes `path`, choosing the decoder from the file contents.
+///
+/// Raw `.s16` files have no header, so they need `raw_format` as
+/// `(sample_rate_hz, channel_count)`.
+pub fn decode_file(path: &Path, raw_format: Option<(u32, usize)>) -> Result<DecodedAudio> {
+    let bytes =
+        std::fs::read(path).map_err(|error| format!("Cannot read {}: {error}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+
+    let decoded_audio = if bytes.starts_with(b"RIFF") {
+        decode_with_symphonia(&bytes, "wav", "WAV")
+    } else if bytes.starts_with(b"fLaC") {
+        decode_with_symphonia(&bytes, "flac", "FLAC")
+    } else if extension == "mp3" || bytes.starts_with(b"ID3") || is_mpeg_frame_sync(&bytes) {
+        decode_with_symphonia(&bytes, "mp3", "MP3")
+    } else if extension == "s16" || extension == "raw" || extension == "pcm" {
+        let (sample_rate_hz, channel_count) = raw_format.ok_or(
+            "Raw .s16 sources need `source_sample_rate_hz` (and `source_channels` if not mono)",
+        )?;
+        decode_s16le(&bytes, sample_rate_hz, channel_count)
+    } else {
+        Err("Unrecognized audio format (expected WAV, FLAC, MP3, or raw .s16)".into())
+    };
+    decoded_audio.map_err(|error| format!("{}: {error}", path.display()).into())
+}
+
+fn is_mpeg_frame_sync(bytes: &[u8]) -> bool {
+    bytes.len() >= 2 && bytes[0] == 0xFF && (bytes[1] & 0xE0) == 0xE0
+}
+
+/// Decodes interleaved signed 16-bit little-endian samples.
+pub fn decode_s16le(
+    bytes: &[u8],
+    sample_rate_hz: u32,
+    channel_count: usize,
+) -> Result<DecodedAudio> {
+    if channel_count == 0 {
+        return Err("Channel count must be > 0".into());
+    }
+    if bytes.len() % (2 * channel_count) != 0 {
+        return Err("Raw PCM length is not a whole number of 16-bit frames".into());
+    }
+    let mut channels = vec![Vec::with_capacity(bytes.len() / (2 * channel_count)); channel_count];
+    for (sample_index, sample_bytes) in bytes.chunks_exact(2).enumerate() {
+        let sample = i16::from_le_bytes([sample_bytes[0], sample_bytes[1]]);
+        channels[sample_index % channel_count].push(f64::from(sample) / 32_768.0);
+    }
+    Ok(DecodedAudio {
+        sample_rate_hz,
+        channels,
+    })
+}
+
+/// Decodes a WAV, FLAC, or MPEG-1/2 Layer III file with `symphonia`, trimming an MP3's
+/// encoder delay and padding when the file has a LAME/Xing header.
+///
+/// `extension` hints the container to `symphonia`; `format_name` prefixes error messages.
+pub fn decode_with_symphonia(
+    bytes: &[u8],
+    extension: &str,
+    format_name: &str,
+) -> Result<DecodedAudio> {
+    let media_source_stream = MediaSourceStream::new(
+        Box::new(std::io::Cursor::new(bytes.to_vec())),
+        Default::default(),
+    );
+    let format_options = FormatOptions {
+        enable_gapless: true,
+        ..Default::default()
+    };
+    let mut format_reader = symphonia::default::get_probe()
+        .format(
+            Hint::new().with_extension(extension),
+            media_source_stream,
+            &format_options,
+            &MetadataOptions::default(),
+        )
+        .map_err(|error| format!("{format_name} {error}"))?
+        .format;
+    let track = format_reader
+        .default_track()
+        .ok_or_else(|| format!("{format_name} has no audio track"))?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|error| format!("{format_name} {error}"))?;
+
+    let mut sample_rate_hz = track.codec_params.sample_rate;
+    let mut channels: Vec<Vec<f64>> = Vec::new();
+    loop {
+        let packet = match format_reader.next_packet() {
+            Ok(packet) => packet,
+            Err(SymphoniaError::IoError(error))
+                if error.kind() == std::io::ErrorKind::UnexpectedEof =>
+            {
+                break;
+            }
+            Err(error) => return Err(format!("{format_name} {error}").into()),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let audio_buffer = match decoder.decode(&packet) {
+            Ok(audio_buffer) => audio_buffer,
+            // A corrupt frame decodes as a gap, as players do.
+            Err(SymphoniaError::DecodeError(_)) => continue,
+            Err(error) => return Err(format!("{format_name} {error}").into()),
+        };
+        let frame_count = audio_buffer.frames();
+        if frame_count == 0 {
+            continue;
+        }
+        let spec = *audio_buffer.spec();
+        let channel_count = spec.channels.count();
+        if channels.is_empty() {
+            channels = vec![Vec::new(); channel_count];
+            sample_rate_hz = Some(spec.rate);
+        } else if channels.len() != channel_count || sample_rate_hz != Some(spec.rate) {
+            return Err(
+                format!("{format_name} changes channel count or sample rate mid-stream").into(),
+            );
+        }
+        let mut sample_buffer = SampleBuffer::<f64>::new(frame_count as u64, spec);
+        sample_buffer.copy_planar_ref(audio_buffer);
+        for (channel, planar_samples) in channels
+            .iter_mut()
+            .zip(sample_buffer.samples().chunks_exact(frame_count))
+        {
+            channel.extend_from_slice(planar_samples);
+        }
+    }
+
+    if channels.is_empty() {
+        return Err(format!("{format_name} has no decodable frames").into());
+    }
+    Ok(DecodedAudio {
+        sample_rate_hz: sample_rate_hz
+            .ok_or_else(|| format!("{format_name} has no sample rate"))?,
+        channels,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A canonical WAV file with a `fmt ` chunk for `format_tag` and `data_bytes` as its data.
+    fn wav_bytes(
+        format_tag: u16,
+        channel_count: u16,
+        sample_rate_hz: u32,
+        bits_per_sample: u16,
+        data_bytes: &[u8],
+    ) -> Vec<u8> {
+        let block_align = channel_count * bits_per_sample / 8;
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(b"RIFF");
+        bytes.extend_from_slice(&(36 + data_bytes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(b"WAVEfmt ");
+        bytes.extend_from_slice(&16_u32.to_le_bytes());
+        bytes.extend_from_slice(&format_tag.to_le_bytes());
+        bytes.extend_from_slice(&channel_count.to_le_bytes());
+        bytes.extend_from_slice(&sample_rate_hz.to_le_bytes());
+        bytes.extend_from_slice(&(sample_rate_hz * u32::from(block_align)).to_le_bytes());
+        bytes.extend_from_slice(&block_align.to_le_bytes());
+        bytes.extend_from_slice(&bits_per_sample.to_le_bytes());
+        bytes.extend_from_slice(b"data");
+        bytes.extend_from_slice(&(data_bytes.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(data_bytes);
+        bytes
+    }
+
+    #[test]
+    fn wav_decodes_integer_pcm() {
+        let data_bytes: Vec<u8> = [0_i16, 16_384, -32_768, 8_192]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        let bytes = wav_bytes(0x0001, 2, 22_050, 16, &data_bytes);
+        let decoded_audio = decode_with_symphonia(&bytes, "wav", "WAV").unwrap();
+        assert_eq!(decoded_audio.sample_rate_hz, 22_050);
+        assert_eq!(
+            decoded_audio.channels,
+            vec![vec![0.0, -1.0], vec![0.5, 0.25]]
+        );
+    }
+
+    #[test]
+    fn wav_decodes_float_pcm() {
+        let data_bytes: Vec<u8> = [0.5_f32, -0.25, 1.0]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        let bytes = wav_bytes(0x0003, 1, 8_000, 32, &data_bytes);
+        let decoded_audio = decode_with_symphonia(&bytes, "wav", "WAV").unwrap();
+        assert_eq!(decoded_audio.sample_rate_hz, 8_000);
+        assert_eq!(decoded_audio.channels, vec![vec![0.5, -0.25, 1.0]]);
+    }
+
+    #[test]
+    fn s16le_deinterleaves_channels() {
+        let bytes: Vec<u8> = [0_i16, 16_384, -32_768, 8_192]
+            .iter()
+            .flat_map(|sample| sample.to_le_bytes())
+            .collect();
+        let decoded_audio = decode_s16le(&bytes, 8_000, 2).unwrap();
+        assert_eq!(decoded_audio.frame_count(), 2);
+        assert_eq!(
+            decoded_audio.channels,
+            vec![vec![0.0, -1.0], vec![0.5, 0.25]]
+        );
+        assert!(decode_s16le(&bytes[..6], 8_000, 2).is_err());
+    }
+
+    #[test]
+    fn symphonia_errors_name_the_format() {
+        let error = decode_with_symphonia(b"ID3 but not really", "mp3", "MP3")
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.starts_with("MP3 "), "{error}");
+        let error = decode_with_symphonia(b"fLaC but not really", "flac", "FLAC")
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.starts_with("FLAC "), "{error}");
+    }
+}
@@ -46,7 +46,10 @@ pub type AudioPlayerGeneratedPlayable = dyn Playable<VOICE_22050_HZ>;
 #[cfg(doc)]
 use crate::Result;
 #[cfg(doc)]
-use crate::audio_player::{AtEnd, Playable, Volume, VOICE_22050_HZ};
+use crate::audio_player::{
+    AtEnd, AudioPlayer, AudioPlayerStatic, AudioVoice, Dsp, Echo, InterruptPolicy, Playable,
+    PlaybackEvent, PlaybackPosition, PlayOutcome, Priority, Volume, VOICE_22050_HZ,
+};
 
 #[cfg(doc)]
 impl AudioPlayerGenerated {
@@ -60,6 +63,15 @@ impl AudioPlayerGenerated {
     pub const INITIAL_VOLUME: Volume = Volume::MAX;
     /// Runtime volume ceiling for this generated player type.
     pub const MAX_VOLUME: Volume = Volume::MAX;
+    /// Number of mixer voices that can play at the same time.
+    ///
+    /// Set by the `voices` field of [`audio_player!`](macro@crate::audio_player) (default: `1`).
+    pub const VOICES: usize = 1;
+    /// Longest [`Echo`] delay this generated player type supports.
+    ///
+    /// Set by the `max_echo_delay` field of [`audio_player!`](macro@crate::audio_player)
+    /// (default: `Duration::ZERO`, no echo).
+    pub const MAX_ECHO_DELAY: core::time::Duration = core::time::Duration::ZERO;
 
     /// Creates and spawns the generated audio player instance.
     ///
@@ -77,10 +89,42 @@ impl AudioPlayerGenerated {
         Ok(&INSTANCE)
     }
 
-    /// Waits until current playback has fully stopped.
+    /// Waits until playback on voice `0` has fully stopped.
     pub async fn wait_until_stopped(&self) {}
 
-    /// Starts playback of one or more static audio clips.
+    /// Returns where voice `0` is within its clip sequence (clip index and sample
+    /// offset), or `None` if it is stopped.
+    ///
+    /// See the [`audio_player`](mod@crate::audio_player) module docs for usage.
+    #[must_use]
+    pub fn position(&self) -> Option<PlaybackPosition> {
+        None
+    }
+
+    /// Waits for the next clip-started, clip-ended, or sequence-ended event on voice `0`.
+    ///
+    /// See the [`audio_player`](mod@crate::audio_player) module docs for usage.
+    pub async fn wait_for_event(&self) -> PlaybackEvent {
+        PlaybackEvent::SequenceEnded
+    }
+
+    /// Returns a handle to one voice of the mixer.
+    ///
+    /// Each voice has its own clip queue, [`AtEnd`] behavior, and gain. Voices
+    /// play at the same time and are summed, with saturation, into the output.
+    /// [`Self::play`] and [`Self::stop`] control voice `0`.
+    ///
+    /// Panics if `voice_index` is not less than [`Self::VOICES`].
+    ///
+    /// See the [`audio_player`](mod@crate::audio_player) module docs for usage.
+    #[must_use]
+    pub fn voice(&self, voice_index: usize) -> AudioVoice<16, VOICE_22050_HZ> {
+        static AUDIO_PLAYER_STATIC: AudioPlayerStatic<16, VOICE_22050_HZ> =
+            AudioPlayer::new_static();
+        AudioPlayer::new(&AUDIO_PLAYER_STATIC).voice(voice_index)
+    }
+
+    /// Starts playback of one or more static audio clips on voice `0`.
     /// Playback runs in the background. If you need to know when playback is
     /// finished, use [`Self::wait_until_stopped`].
     ///
@@ -100,11 +144,53 @@ impl AudioPlayerGenerated {
         let _ = (audio_clips, at_end);
     }
 
-    /// Stops current playback as soon as possible.
+    /// Stops current playback on voice `0` as soon as possible.
     ///
     /// See the [`audio_player`](mod@crate::audio_player) module docs for usage.
     pub fn stop(&self) {}
 
+    /// Like [`Self::play`], but overlaps consecutive clips by `crossfade`, fading the
+    /// ending clip out while the next clip fades in.
+    ///
+    /// See the [`audio_player`](mod@crate::audio_player) module docs for usage.
+    pub fn play_with_crossfade<I>(
+        &self,
+        audio_clips: I,
+        at_end: AtEnd,
+        crossfade: core::time::Duration,
+    )
+    where
+        I: IntoIterator<Item = &'static dyn Playable<{ Self::SAMPLE_RATE_HZ }>>,
+    {
+        let _ = (audio_clips, at_end, crossfade);
+    }
+
+    /// Fades playback on voice `0` out over `duration`, then stops it.
+    ///
+    /// See the [`audio_player`](mod@crate::audio_player) module docs for usage.
+    pub fn stop_with_fade(&self, duration: core::time::Duration) {
+        let _ = duration;
+    }
+
+    /// Plays on voice `0` according to `priority` and `interrupt_policy`: enqueue after
+    /// the current sequence, preempt it only if higher priority, or duck other voices.
+    /// Returns whether the sequence was played, queued, or dropped.
+    ///
+    /// See the [`audio_player`](mod@crate::audio_player) module docs for usage.
+    pub async fn play_with_priority<I>(
+        &self,
+        audio_clips: I,
+        at_end: AtEnd,
+        priority: Priority,
+        interrupt_policy: InterruptPolicy,
+    ) -> PlayOutcome
+    where
+        I: IntoIterator<Item = &'static dyn Playable<{ Self::SAMPLE_RATE_HZ }>>,
+    {
+        let _ = (audio_clips, at_end, priority, interrupt_policy);
+        PlayOutcome::Played
+    }
+
     /// Sets runtime playback volume relative to [`Self::MAX_VOLUME`].
     /// This can take effect while a sequence of clips is already playing.
     ///
@@ -119,6 +205,14 @@ impl AudioPlayerGenerated {
         Volume::MAX
     }
 
+    /// Replaces the effects chain (equalizer, echo, and limiter) applied to the mixed
+    /// output of all voices. Pass [`Dsp::BYPASS`] to turn processing off.
+    ///
+    /// See the [`audio_player`](mod@crate::audio_player) module docs for usage.
+    pub fn set_dsp(&self, dsp: Dsp) {
+        let _ = dsp;
+    }
+
 }
 "#;
 
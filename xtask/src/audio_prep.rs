@@ -0,0 +1,1298 @@
+//! `cargo xtask audio-prep`: converts audio sources into clip assets plus a Rust module of
+//! `pcm_clip!`/`adpcm_clip!` declarations, without ffmpeg.
+//!
+//! The manifest is a small TOML file. Paths are relative to the manifest:
+//!
+//! ```toml
+//! out_dir = "examples/data/audio"  # where assets and the module go (default: ".")
+//! module = "audio_clips.rs"        # generated module file name (default: "audio_clips.rs")
+//! visibility = "pub"               # visibility of generated clip modules (default: "pub")
+//!
+//! [[clip]]
+//! name = "Nasa"                    # clip module name in the generated code
+//! source = "sources/nasa.flac"     # WAV (integer or float PCM), FLAC, MP3, or raw .s16
+//! sample_rate_hz = 22050           # output rate (default: the source rate)
+//! codec = "adpcm"                  # "pcm" (default) or "adpcm"
+//! channels = 1                     # 1 (default, downmixes) or 2
+//! trim_start_ms = 120              # optional, cut from the start
+//! trim_end_ms = 300                # optional, cut from the end
+//! loudness_lufs = -16.0            # optional, integrated loudness target (ITU-R BS.1770)
+//! # normalize_peak_dbfs = -1.0     # optional instead: scale so the peak hits this level
+//! ```
+//!
+//! Raw `.s16` sources also need `source_sample_rate_hz` (and `source_channels` if stereo).
+//!
+//! Every step is plain Rust arithmetic, so the same inputs always give the same assets.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::audio_decode::{self, DecodedAudio};
+
+type Result<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+const DEFAULT_MODULE_FILE_NAME: &str = "audio_clips.rs";
+/// Matches `ADPCM_ENCODE_BLOCK_ALIGN` in `audio_player`.
+const ADPCM_BLOCK_ALIGN_MONO: usize = 256;
+/// Matches `STEREO_ADPCM_ENCODE_BLOCK_ALIGN` in `audio_player`.
+const ADPCM_BLOCK_ALIGN_STEREO: usize = 512;
+/// Zero crossings of the resampling kernel on each side, at the lower of the two rates.
+const RESAMPLE_ZERO_CROSSINGS: f64 = 32.0;
+/// Passband edge as a fraction of the lower Nyquist frequency; the rest is the transition band.
+const RESAMPLE_CUTOFF: f64 = 0.94;
+const RESAMPLE_KAISER_BETA: f64 = 9.0;
+/// Largest polyphase table (phases times taps) the resampler builds.
+const RESAMPLE_MAX_TABLE_LEN: usize = 1 << 24;
+
+/// Runs every clip in the manifest at `manifest_path` and writes the assets and module.
+pub fn run(manifest_path: &Path) -> Result<()> {
+    let manifest_text = fs::read_to_string(manifest_path)
+        .map_err(|error| format!("Cannot read {}: {error}", manifest_path.display()))?;
+    let manifest = Manifest::parse(&manifest_text)
+        .map_err(|error| format!("{}: {error}", manifest_path.display()))?;
+    let manifest_dir = manifest_path.parent().unwrap_or(Path::new("."));
+    let out_dir = manifest_dir.join(&manifest.out_dir);
+    fs::create_dir_all(&out_dir)?;
+
+    let mut prepared_clips = Vec::with_capacity(manifest.clips.len());
+    for clip_spec in &manifest.clips {
+        let prepared_clip = prepare_clip(clip_spec, manifest_dir)
+            .map_err(|error| format!("clip `{}`: {error}", clip_spec.name))?;
+        let asset_path = out_dir.join(&prepared_clip.asset_file_name);
+        fs::write(&asset_path, &prepared_clip.asset_bytes)?;
+        eprintln!(
+            "{}: {} ({})",
+            clip_spec.name,
+            asset_path.display(),
+            prepared_clip.summary
+        );
+        prepared_clips.push(prepared_clip);
+    }
+
+    let module_path = out_dir.join(&manifest.module);
+    // Only the file name, so the module doesn't change with how the command was invoked.
+    let manifest_file_name = manifest_path
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy();
+    fs::write(
+        &module_path,
+        generate_module(&manifest, &prepared_clips, &manifest_file_name),
+    )?;
+    eprintln!("Wrote {}", module_path.display());
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Manifest
+// ---------------------------------------------------------------------------
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Codec {
+    Pcm,
+    Adpcm,
+}
+
+enum Level {
+    None,
+    PeakDbfs(f64),
+    LoudnessLufs(f64),
+}
+
+struct ClipSpec {
+    name: String,
+    source: PathBuf,
+    source_format: Option<(u32, usize)>,
+    sample_rate_hz: Option<u32>,
+    codec: Codec,
+    channel_count: usize,
+    trim_start_ms: f64,
+    trim_end_ms: f64,
+    level: Level,
+}
+
+struct Manifest {
+    out_dir: PathBuf,
+    module: String,
+    visibility: String,
+    clips: Vec<ClipSpec>,
+}
+
+enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+}
+
+impl Value {
+    fn as_str(&self, key: &str) -> Result<&str> {
+        match self {
+            Self::String(value) => Ok(value),
+            _ => Err(format!("`{key}` must be a string").into()),
+        }
+    }
+
+    fn as_u32(&self, key: &str) -> Result<u32> {
+        match self {
+            Self::Integer(value) => u32::try_from(*value)
+                .ok()
+                .filter(|value| *value > 0)
+                .ok_or_else(|| format!("`{key}` must be a positive integer").into()),
+            _ => Err(format!("`{key}` must be an integer").into()),
+        }
+    }
+
+    fn as_f64(&self, key: &str) -> Result<f64> {
+        match self {
+            Self::Integer(value) => Ok(*value as f64),
+            Self::Float(value) => Ok(*value),
+            Self::String(_) => Err(format!("`{key}` must be a number").into()),
+        }
+    }
+}
+
+impl Manifest {
+    /// Parses the TOML subset the manifest uses: `key = value` lines with string, integer, or
+    /// float values, `#` comments, and `[[clip]]` table headers.
+    fn parse(manifest_text: &str) -> Result<Self> {
+        let mut top_level: Vec<(String, Value)> = Vec::new();
+        let mut clip_tables: Vec<Vec<(String, Value)>> = Vec::new();
+        for (line_index, line) in manifest_text.lines().enumerate() {
+            let line_number = line_index + 1;
+            let line = strip_comment(line).trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line == "[[clip]]" {
+                clip_tables.push(Vec::new());
+                continue;
+            }
+            if line.starts_with('[') {
+                return Err(format!("line {line_number}: expected `[[clip]]`").into());
+            }
+            let (key, value) = line
+                .split_once('=')
+                .ok_or_else(|| format!("line {line_number}: expected `key = value`"))?;
+            let key = key.trim().to_string();
+            let value = parse_value(value.trim())
+                .map_err(|error| format!("line {line_number}: {error}"))?;
+            let table = clip_tables.last_mut().unwrap_or(&mut top_level);
+            if table.iter().any(|(existing_key, _)| *existing_key == key) {
+                return Err(format!("line {line_number}: duplicate key `{key}`").into());
+            }
+            table.push((key, value));
+        }
+
+        let mut manifest = Self {
+            out_dir: PathBuf::from("."),
+            module: DEFAULT_MODULE_FILE_NAME.to_string(),
+            visibility: "pub".to_string(),
+            clips: Vec::with_capacity(clip_tables.len()),
+        };
+        for (key, value) in &top_level {
+            match key.as_str() {
+                "out_dir" => manifest.out_dir = PathBuf::from(value.as_str(key)?),
+                "module" => manifest.module = value.as_str(key)?.to_string(),
+                "visibility" => manifest.visibility = value.as_str(key)?.to_string(),
+                _ => return Err(format!("unknown top-level key `{key}`").into()),
+            }
+        }
+        if !manifest.module.ends_with(".rs") {
+            return Err("`module` must name a `.rs` file".into());
+        }
+        if clip_tables.is_empty() {
+            return Err("manifest has no `[[clip]]` entries".into());
+        }
+
+        let mut clip_names = BTreeSet::new();
+        for clip_table in &clip_tables {
+            let clip_spec = ClipSpec::parse(clip_table)?;
+            if !clip_names.insert(clip_spec.name.clone()) {
+                return Err(format!("duplicate clip name `{}`", clip_spec.name).into());
+            }
+            manifest.clips.push(clip_spec);
+        }
+        Ok(manifest)
+    }
+}
+
+impl ClipSpec {
+    fn parse(clip_table: &[(String, Value)]) -> Result<Self> {
+        let mut name = None;
+        let mut source = None;
+        let mut source_sample_rate_hz = None;
+        let mut source_channel_count = 1;
+        let mut sample_rate_hz = None;
+        let mut codec = Codec::Pcm;
+        let mut channel_count = 1;
+        let mut trim_start_ms = 0.0;
+        let mut trim_end_ms = 0.0;
+        let mut peak_dbfs = None;
+        let mut loudness_lufs = None;
+        for (key, value) in clip_table {
+            match key.as_str() {
+                "name" => name = Some(value.as_str(key)?.to_string()),
+                "source" => source = Some(PathBuf::from(value.as_str(key)?)),
+                "source_sample_rate_hz" => source_sample_rate_hz = Some(value.as_u32(key)?),
+                "source_channels" => source_channel_count = value.as_u32(key)? as usize,
+                "sample_rate_hz" => sample_rate_hz = Some(value.as_u32(key)?),
+                "codec" => {
+                    codec = match value.as_str(key)? {
+                        "pcm" => Codec::Pcm,
+                        "adpcm" => Codec::Adpcm,
+                        other => {
+                            return Err(format!(
+                                "`codec` must be \"pcm\" or \"adpcm\", not {other:?}"
+                            )
+                            .into())
+                        }
+                    }
+                }
+                "channels" => channel_count = value.as_u32(key)? as usize,
+                "trim_start_ms" => trim_start_ms = value.as_f64(key)?,
+                "trim_end_ms" => trim_end_ms = value.as_f64(key)?,
+                "normalize_peak_dbfs" => peak_dbfs = Some(value.as_f64(key)?),
+                "loudness_lufs" => loudness_lufs = Some(value.as_f64(key)?),
+                _ => return Err(format!("unknown clip key `{key}`").into()),
+            }
+        }
+
+        let name = name.ok_or("clip is missing `name`")?;
+        if !is_rust_identifier(&name) {
+            return Err(format!("clip name `{name}` is not a Rust identifier").into());
+        }
+        let source = source.ok_or_else(|| format!("clip `{name}` is missing `source`"))?;
+        if channel_count != 1 && channel_count != 2 {
+            return Err(format!("clip `{name}`: `channels` must be 1 or 2").into());
+        }
+        if trim_start_ms < 0.0 || trim_end_ms < 0.0 {
+            return Err(format!("clip `{name}`: trims must be >= 0").into());
+        }
+        let level = match (peak_dbfs, loudness_lufs) {
+            (None, None) => Level::None,
+            (Some(peak_dbfs), None) if peak_dbfs <= 0.0 => Level::PeakDbfs(peak_dbfs),
+            (Some(_), None) => {
+                return Err(format!("clip `{name}`: `normalize_peak_dbfs` must be <= 0").into())
+            }
+            (None, Some(loudness_lufs)) => Level::LoudnessLufs(loudness_lufs),
+            (Some(_), Some(_)) => {
+                return Err(format!(
+                    "clip `{name}`: set `normalize_peak_dbfs` or `loudness_lufs`, not both"
+                )
+                .into())
+            }
+        };
+        Ok(Self {
+            name,
+            source,
+            source_format: source_sample_rate_hz
+                .map(|source_sample_rate_hz| (source_sample_rate_hz, source_channel_count)),
+            sample_rate_hz,
+            codec,
+            channel_count,
+            trim_start_ms,
+            trim_end_ms,
+            level,
+        })
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut previous_char = '\0';
+    for (char_index, char) in line.char_indices() {
+        match char {
+            '"' if previous_char != '\\' => in_string = !in_string,
+            '#' if !in_string => return &line[..char_index],
+            _ => {}
+        }
+        previous_char = char;
+    }
+    line
+}
+
+fn parse_value(value_text: &str) -> Result<Value> {
+    if let Some(string_body) = value_text.strip_prefix('"') {
+        let string_body = string_body.strip_suffix('"').ok_or("unterminated string")?;
+        let mut string = String::with_capacity(string_body.len());
+        let mut chars = string_body.chars();
+        while let Some(char) = chars.next() {
+            if char == '\\' {
+                match chars.next() {
+                    Some('\\') => string.push('\\'),
+                    Some('"') => string.push('"'),
+                    _ => return Err("only `\\\\` and `\\\"` escapes are supported".into()),
+                }
+            } else {
+                string.push(char);
+            }
+        }
+        return Ok(Value::String(string));
+    }
+    let number_text = value_text.replace('_', "");
+    if let Ok(integer) = number_text.parse::<i64>() {
+        return Ok(Value::Integer(integer));
+    }
+    if let Ok(float) = number_text.parse::<f64>() {
+        if float.is_finite() {
+            return Ok(Value::Float(float));
+        }
+    }
+    Err(format!("unsupported value `{value_text}` (expected a string or number)").into())
+}
+
+fn is_rust_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first_char| first_char.is_ascii_alphabetic() || first_char == '_')
+        && chars.all(|char| char.is_ascii_alphanumeric() || char == '_')
+        && name != "_"
+}
+
+// ---------------------------------------------------------------------------
+// Pipeline
+// ---------------------------------------------------------------------------
+
+struct PreparedClip {
+    name: String,
+    asset_file_name: String,
+    asset_bytes: Vec<u8>,
+    codec: Codec,
+    sample_rate_hz: u32,
+    channel_count: usize,
+    summary: String,
+}
+
+fn prepare_clip(clip_spec: &ClipSpec, manifest_dir: &Path) -> Result<PreparedClip> {
+    let source_path = manifest_dir.join(&clip_spec.source);
+    let decoded_audio = audio_decode::decode_file(&source_path, clip_spec.source_format)?;
+    if decoded_audio.frame_count() == 0 {
+        return Err("source has no samples".into());
+    }
+    let source_sample_rate_hz = decoded_audio.sample_rate_hz;
+    let sample_rate_hz = clip_spec.sample_rate_hz.unwrap_or(source_sample_rate_hz);
+
+    let channels = remix_channels(decoded_audio, clip_spec.channel_count)?;
+    let channels = trim_channels(
+        channels,
+        source_sample_rate_hz,
+        clip_spec.trim_start_ms,
+        clip_spec.trim_end_ms,
+    )?;
+    let mut channels: Vec<Vec<f64>> = channels
+        .iter()
+        .map(|channel| resample(channel, source_sample_rate_hz, sample_rate_hz))
+        .collect::<Result<_>>()?;
+
+    let gain = match clip_spec.level {
+        Level::None => 1.0,
+        Level::PeakDbfs(peak_dbfs) => {
+            let peak = peak_level(&channels);
+            if peak == 0.0 {
+                return Err("cannot normalize silence".into());
+            }
+            db_to_linear(peak_dbfs) / peak
+        }
+        Level::LoudnessLufs(loudness_lufs) => {
+            let measured_lufs = integrated_loudness(&channels, sample_rate_hz)
+                .ok_or("cannot set the loudness of silence")?;
+            db_to_linear(loudness_lufs - measured_lufs)
+        }
+    };
+    for channel in &mut channels {
+        for sample in channel.iter_mut() {
+            *sample *= gain;
+        }
+    }
+
+    let (interleaved_samples, clipped_count) = quantize_interleaved(&channels);
+    if clipped_count > 0 {
+        eprintln!(
+            "warning: clip `{}` clipped {clipped_count} samples; lower its level target",
+            clip_spec.name
+        );
+    }
+
+    let frame_count = channels[0].len();
+    let peak_dbfs = linear_to_db(peak_level(&channels));
+    let loudness_text = integrated_loudness(&channels, sample_rate_hz)
+        .map_or_else(|| "silent".to_string(), |lufs| format!("{lufs:.1} LUFS"));
+    let summary = format!(
+        "{:.3} s, {} Hz {}, {}, peak {peak_dbfs:.1} dBFS, {loudness_text}",
+        frame_count as f64 / f64::from(sample_rate_hz),
+        sample_rate_hz,
+        if clip_spec.channel_count == 1 {
+            "mono"
+        } else {
+            "stereo"
+        },
+        match clip_spec.codec {
+            Codec::Pcm => "PCM",
+            Codec::Adpcm => "IMA ADPCM",
+        },
+    );
+
+    let file_stem = to_snake_case(&clip_spec.name);
+    let (asset_file_name, asset_bytes) = match clip_spec.codec {
+        Codec::Pcm => (
+            format!("{file_stem}.s16"),
+            interleaved_samples
+                .iter()
+                .flat_map(|sample| sample.to_le_bytes())
+                .collect(),
+        ),
+        Codec::Adpcm => (
+            format!("{file_stem}_adpcm.wav"),
+            adpcm_wav_bytes(
+                &interleaved_samples,
+                clip_spec.channel_count,
+                sample_rate_hz,
+            ),
+        ),
+    };
+
+    Ok(PreparedClip {
+        name: clip_spec.name.clone(),
+        asset_file_name,
+        asset_bytes,
+        codec: clip_spec.codec,
+        sample_rate_hz,
+        channel_count: clip_spec.channel_count,
+        summary,
+    })
+}
+
+/// Averages all source channels to mono, or maps mono or stereo sources to stereo.
+fn remix_channels(decoded_audio: DecodedAudio, channel_count: usize) -> Result<Vec<Vec<f64>>> {
+    let source_channels = decoded_audio.channels;
+    match (source_channels.len(), channel_count) {
+        (1, 1) | (2, 2) => Ok(source_channels),
+        (_, 1) => {
+            let source_channel_count = source_channels.len() as f64;
+            let frame_count = source_channels[0].len();
+            Ok(vec![(0..frame_count)
+                .map(|frame_index| {
+                    source_channels
+                        .iter()
+                        .map(|channel| channel[frame_index])
+                        .sum::<f64>()
+                        / source_channel_count
+                })
+                .collect()])
+        }
+        (1, 2) => Ok(vec![source_channels[0].clone(), source_channels[0].clone()]),
+        (source_channel_count, _) => Err(format!(
+            "cannot make stereo from a {source_channel_count}-channel source; use `channels = 1`"
+        )
+        .into()),
+    }
+}
+
+fn trim_channels(
+    channels: Vec<Vec<f64>>,
+    sample_rate_hz: u32,
+    trim_start_ms: f64,
+    trim_end_ms: f64,
+) -> Result<Vec<Vec<f64>>> {
+    let frame_count = channels[0].len();
+    let ms_to_frames = |ms: f64| (ms * f64::from(sample_rate_hz) / 1000.0).round() as usize;
+    let start_frame = ms_to_frames(trim_start_ms);
+    let end_frame = frame_count.saturating_sub(ms_to_frames(trim_end_ms));
+    if start_frame >= end_frame {
+        return Err(format!(
+            "trims remove the whole clip ({:.3} s long)",
+            frame_count as f64 / f64::from(sample_rate_hz)
+        )
+        .into());
+    }
+    Ok(channels
+        .into_iter()
+        .map(|channel| channel[start_frame..end_frame].to_vec())
+        .collect())
+}
+
+/// Resamples with a Kaiser-windowed sinc kernel, using one exact set of taps per output
+/// phase.
+///
+/// The output length matches the rounding used by `pcm_clip!`'s `target_sample_rate_hz`.
+fn resample(samples: &[f64], from_hz: u32, to_hz: u32) -> Result<Vec<f64>> {
+    if from_hz == to_hz {
+        return Ok(samples.to_vec());
+    }
+    let rate_gcd = gcd(from_hz, to_hz);
+    let step = (from_hz / rate_gcd) as usize;
+    let phase_count = (to_hz / rate_gcd) as usize;
+
+    // Lowpass at the lower Nyquist frequency, in cycles per input sample.
+    let cutoff = RESAMPLE_CUTOFF * 0.5 * (f64::from(to_hz) / f64::from(from_hz)).min(1.0);
+    let half_width = RESAMPLE_ZERO_CROSSINGS * 0.5 / cutoff;
+    let tap_count = 2 * half_width.ceil() as usize;
+    if phase_count * tap_count > RESAMPLE_MAX_TABLE_LEN {
+        return Err(format!(
+            "resampling {from_hz} Hz to {to_hz} Hz needs too large a filter table; \
+             pick rates with a larger common divisor"
+        )
+        .into());
+    }
+
+    // Output sample `n` sits at input position `n * step / phase_count`.
+    let first_tap_offset = tap_count as isize / 2 - 1;
+    let kaiser_scale = 1.0 / bessel_i0(RESAMPLE_KAISER_BETA);
+    let phase_taps: Vec<Vec<f64>> = (0..phase_count)
+        .map(|phase| {
+            let fraction = phase as f64 / phase_count as f64;
+            let mut taps: Vec<f64> = (0..tap_count)
+                .map(|tap_index| {
+                    let distance = fraction + (first_tap_offset - tap_index as isize) as f64;
+                    let window_position = distance / half_width;
+                    if window_position.abs() >= 1.0 {
+                        return 0.0;
+                    }
+                    let window = bessel_i0(
+                        RESAMPLE_KAISER_BETA * (1.0 - window_position * window_position).sqrt(),
+                    ) * kaiser_scale;
+                    2.0 * cutoff * sinc(2.0 * cutoff * distance) * window
+                })
+                .collect();
+            // Unity gain at DC for every phase.
+            let tap_sum: f64 = taps.iter().sum();
+            for tap in &mut taps {
+                *tap /= tap_sum;
+            }
+            taps
+        })
+        .collect();
+
+    let output_len = ((samples.len() as u64 * u64::from(to_hz) + u64::from(from_hz) / 2)
+        / u64::from(from_hz)) as usize;
+    Ok((0..output_len)
+        .map(|output_index| {
+            let position = output_index * step;
+            let input_index = (position / phase_count) as isize;
+            let taps = &phase_taps[position % phase_count];
+            let first_input_index = input_index - first_tap_offset;
+            taps.iter()
+                .enumerate()
+                .filter_map(|(tap_index, tap)| {
+                    let sample_index =
+                        usize::try_from(first_input_index + tap_index as isize).ok()?;
+                    samples.get(sample_index).map(|sample| tap * sample)
+                })
+                .sum()
+        })
+        .collect())
+}
+
+fn sinc(x: f64) -> f64 {
+    if x == 0.0 {
+        1.0
+    } else {
+        let pi_x = std::f64::consts::PI * x;
+        pi_x.sin() / pi_x
+    }
+}
+
+/// Zeroth-order modified Bessel function of the first kind, by its power series.
+fn bessel_i0(x: f64) -> f64 {
+    let half_x_squared = (x / 2.0) * (x / 2.0);
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    let mut k = 1.0;
+    while term > sum * 1e-17 {
+        term *= half_x_squared / (k * k);
+        sum += term;
+        k += 1.0;
+    }
+    sum
+}
+
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+fn peak_level(channels: &[Vec<f64>]) -> f64 {
+    channels
+        .iter()
+        .flatten()
+        .fold(0.0, |peak: f64, sample| peak.max(sample.abs()))
+}
+
+fn db_to_linear(db: f64) -> f64 {
+    10_f64.powf(db / 20.0)
+}
+
+fn linear_to_db(linear: f64) -> f64 {
+    20.0 * linear.log10()
+}
+
+/// Returns integrated loudness in LUFS per ITU-R BS.1770-4, or `None` for silence.
+///
+/// Clips shorter than one 400 ms gating block are measured as a single block.
+fn integrated_loudness(channels: &[Vec<f64>], sample_rate_hz: u32) -> Option<f64> {
+    let weighted_channels: Vec<Vec<f64>> = channels
+        .iter()
+        .map(|channel| k_weight(channel, sample_rate_hz))
+        .collect();
+    let frame_count = weighted_channels[0].len();
+    let block_len = ((0.4 * f64::from(sample_rate_hz)) as usize).clamp(1, frame_count);
+    let hop_len = ((0.1 * f64::from(sample_rate_hz)) as usize).max(1);
+
+    // Mean square of each block, summed over channels (all weighted 1.0 for mono/stereo).
+    let mut block_powers = Vec::new();
+    let mut block_start = 0;
+    while block_start + block_len <= frame_count {
+        let block_power: f64 = weighted_channels
+            .iter()
+            .map(|channel| {
+                channel[block_start..block_start + block_len]
+                    .iter()
+                    .map(|sample| sample * sample)
+                    .sum::<f64>()
+                    / block_len as f64
+            })
+            .sum();
+        block_powers.push(block_power);
+        block_start += hop_len;
+    }
+
+    let loudness = |power: f64| -0.691 + 10.0 * power.log10();
+    let gated_mean = |threshold_lufs: f64| {
+        let gated_powers: Vec<f64> = block_powers
+            .iter()
+            .copied()
+            .filter(|block_power| *block_power > 0.0 && loudness(*block_power) > threshold_lufs)
+            .collect();
+        (!gated_powers.is_empty())
+            .then(|| gated_powers.iter().sum::<f64>() / gated_powers.len() as f64)
+    };
+    let absolute_gated_power = gated_mean(-70.0)?;
+    let relative_gated_power = gated_mean(loudness(absolute_gated_power) - 10.0)?;
+    Some(loudness(relative_gated_power))
+}
+
+/// Applies the BS.1770 K-weighting filter: a high shelf for head effects, then a high-pass.
+///
+/// The biquads are designed for `sample_rate_hz` from the analog prototypes, so they match
+/// the standard's 48 kHz coefficients at 48 kHz.
+fn k_weight(samples: &[f64], sample_rate_hz: u32) -> Vec<f64> {
+    let sample_rate_hz = f64::from(sample_rate_hz);
+    let shelf = Biquad::high_shelf(
+        sample_rate_hz,
+        1_681.974_450_955_533,
+        0.707_175_236_955_419_6,
+        3.999_843_853_973_347,
+    );
+    let high_pass = Biquad::high_pass(
+        sample_rate_hz,
+        38.135_470_876_024_44,
+        0.500_327_037_323_877_3,
+    );
+    high_pass.filter(&shelf.filter(samples))
+}
+
+struct Biquad {
+    b: [f64; 3],
+    a: [f64; 2],
+}
+
+impl Biquad {
+    fn high_shelf(sample_rate_hz: f64, center_hz: f64, q: f64, gain_db: f64) -> Self {
+        let k = (std::f64::consts::PI * center_hz / sample_rate_hz).tan();
+        let v_h = 10_f64.powf(gain_db / 20.0);
+        let v_b = v_h.powf(0.499_666_774_154_541_6);
+        let a0 = 1.0 + k / q + k * k;
+        Self {
+            b: [
+                (v_h + v_b * k / q + k * k) / a0,
+                2.0 * (k * k - v_h) / a0,
+                (v_h - v_b * k / q + k * k) / a0,
+            ],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        }
+    }
+
+    fn high_pass(sample_rate_hz: f64, center_hz: f64, q: f64) -> Self {
+        let k = (std::f64::consts::PI * center_hz / sample_rate_hz).tan();
+        let a0 = 1.0 + k / q + k * k;
+        Self {
+            b: [1.0, -2.0, 1.0],
+            a: [2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        }
+    }
+
+    fn filter(&self, samples: &[f64]) -> Vec<f64> {
+        let (mut x1, mut x2, mut y1, mut y2) = (0.0, 0.0, 0.0, 0.0);
+        samples
+            .iter()
+            .map(|&x0| {
+                let y0 = self.b[0] * x0 + self.b[1] * x1 + self.b[2] * x2
+                    - self.a[0] * y1
+                    - self.a[1] * y2;
+                (x2, x1, y2, y1) = (x1, x0, y1, y0);
+                y0
+            })
+            .collect()
+    }
+}
+
+/// Rounds to interleaved 16-bit samples, returning them and how many had to be clipped.
+fn quantize_interleaved(channels: &[Vec<f64>]) -> (Vec<i16>, usize) {
+    let frame_count = channels[0].len();
+    let mut clipped_count = 0;
+    let mut interleaved_samples = Vec::with_capacity(frame_count * channels.len());
+    for frame_index in 0..frame_count {
+        for channel in channels {
+            let scaled = (channel[frame_index] * 32_768.0).round();
+            if !(f64::from(i16::MIN)..=f64::from(i16::MAX)).contains(&scaled) {
+                clipped_count += 1;
+            }
+            interleaved_samples.push(scaled.clamp(f64::from(i16::MIN), f64::from(i16::MAX)) as i16);
+        }
+    }
+    (interleaved_samples, clipped_count)
+}
+
+// ---------------------------------------------------------------------------
+// IMA ADPCM (same encoder and block layout as `PcmClip::with_adpcm`)
+// ---------------------------------------------------------------------------
+
+const ADPCM_INDEX_TABLE: [i32; 16] = [-1, -1, -1, -1, 2, 4, 6, 8, -1, -1, -1, -1, 2, 4, 6, 8];
+const ADPCM_STEP_TABLE: [i32; 89] = [
+    7, 8, 9, 10, 11, 12, 13, 14, 16, 17, 19, 21, 23, 25, 28, 31, 34, 37, 41, 45, 50, 55, 60, 66,
+    73, 80, 88, 97, 107, 118, 130, 143, 157, 173, 190, 209, 230, 253, 279, 307, 337, 371, 408, 449,
+    494, 544, 598, 658, 724, 796, 876, 963, 1060, 1166, 1282, 1411, 1552, 1707, 1878, 2066, 2272,
+    2499, 2749, 3024, 3327, 3660, 4026, 4428, 4871, 5358, 5894, 6484, 7132, 7845, 8630, 9493,
+    10442, 11487, 12635, 13899, 15289, 16818, 18500, 20350, 22385, 24623, 27086, 29794, 32767,
+];
+
+struct AdpcmEncoder {
+    predictor: i32,
+    step_index: i32,
+}
+
+impl AdpcmEncoder {
+    fn encode(&mut self, target_sample: i32) -> u8 {
+        let step = ADPCM_STEP_TABLE[self.step_index as usize];
+        let mut diff = target_sample - self.predictor;
+        let mut nibble = 0_u8;
+        if diff < 0 {
+            nibble |= 0x08;
+            diff = -diff;
+        }
+        let mut delta = step >> 3;
+        if diff >= step {
+            nibble |= 0x04;
+            diff -= step;
+            delta += step;
+        }
+        if diff >= step >> 1 {
+            nibble |= 0x02;
+            diff -= step >> 1;
+            delta += step >> 1;
+        }
+        if diff >= step >> 2 {
+            nibble |= 0x01;
+            delta += step >> 2;
+        }
+        if nibble & 0x08 != 0 {
+            self.predictor -= delta;
+        } else {
+            self.predictor += delta;
+        }
+        self.predictor = self
+            .predictor
+            .clamp(i32::from(i16::MIN), i32::from(i16::MAX));
+        self.step_index = (self.step_index + ADPCM_INDEX_TABLE[nibble as usize]).clamp(0, 88);
+        nibble
+    }
+}
+
+/// Encodes interleaved samples as an IMA ADPCM WAV file that `adpcm_clip!` reads.
+///
+/// Each block starts from its first sample with step index 0, and the last block is padded
+/// by repeating the predictor, exactly like the crate's const encoder.
+fn adpcm_wav_bytes(
+    interleaved_samples: &[i16],
+    channel_count: usize,
+    sample_rate_hz: u32,
+) -> Vec<u8> {
+    let frame_count = interleaved_samples.len() / channel_count;
+    let (block_align, samples_per_block) = if channel_count == 1 {
+        (ADPCM_BLOCK_ALIGN_MONO, (ADPCM_BLOCK_ALIGN_MONO - 4) * 2 + 1)
+    } else {
+        (ADPCM_BLOCK_ALIGN_STEREO, ADPCM_BLOCK_ALIGN_STEREO - 7)
+    };
+    let block_count = frame_count.div_ceil(samples_per_block);
+    let mut data = vec![0_u8; block_count * block_align];
+
+    for block_index in 0..block_count {
+        let block_start = block_index * block_align;
+        let first_frame_index = block_index * samples_per_block;
+        for channel_index in 0..channel_count {
+            let sample_at = |frame_index: usize| {
+                i32::from(interleaved_samples[frame_index * channel_count + channel_index])
+            };
+            let mut adpcm_encoder = AdpcmEncoder {
+                predictor: sample_at(first_frame_index),
+                step_index: 0,
+            };
+            let header_start = block_start + channel_index * 4;
+            data[header_start..header_start + 2]
+                .copy_from_slice(&(adpcm_encoder.predictor as i16).to_le_bytes());
+
+            for nibble_index in 0..samples_per_block - 1 {
+                let frame_index = first_frame_index + 1 + nibble_index;
+                let target_sample = if frame_index < frame_count {
+                    sample_at(frame_index)
+                } else {
+                    adpcm_encoder.predictor
+                };
+                let nibble = adpcm_encoder.encode(target_sample);
+                // Mono packs nibbles in order; stereo interleaves 4-byte runs per channel.
+                let byte_offset = if channel_count == 1 {
+                    block_start + 4 + nibble_index / 2
+                } else {
+                    block_start
+                        + 8
+                        + (nibble_index / 8) * 8
+                        + channel_index * 4
+                        + (nibble_index % 8) / 2
+                };
+                data[byte_offset] |= nibble << ((nibble_index % 2) * 4);
+            }
+        }
+    }
+
+    let byte_rate =
+        (u64::from(sample_rate_hz) * block_align as u64 / samples_per_block as u64) as u32;
+    let mut wav_bytes = Vec::with_capacity(60 + data.len());
+    wav_bytes.extend_from_slice(b"RIFF");
+    wav_bytes.extend_from_slice(&((52 + data.len()) as u32).to_le_bytes());
+    wav_bytes.extend_from_slice(b"WAVE");
+    wav_bytes.extend_from_slice(b"fmt ");
+    wav_bytes.extend_from_slice(&20_u32.to_le_bytes());
+    wav_bytes.extend_from_slice(&0x0011_u16.to_le_bytes()); // IMA ADPCM
+    wav_bytes.extend_from_slice(&(channel_count as u16).to_le_bytes());
+    wav_bytes.extend_from_slice(&sample_rate_hz.to_le_bytes());
+    wav_bytes.extend_from_slice(&byte_rate.to_le_bytes());
+    wav_bytes.extend_from_slice(&(block_align as u16).to_le_bytes());
+    wav_bytes.extend_from_slice(&4_u16.to_le_bytes()); // bits per sample
+    wav_bytes.extend_from_slice(&2_u16.to_le_bytes()); // extra fmt bytes
+    wav_bytes.extend_from_slice(&(samples_per_block as u16).to_le_bytes());
+    wav_bytes.extend_from_slice(b"fact");
+    wav_bytes.extend_from_slice(&4_u32.to_le_bytes());
+    wav_bytes.extend_from_slice(&(frame_count as u32).to_le_bytes());
+    wav_bytes.extend_from_slice(b"data");
+    wav_bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    wav_bytes.extend_from_slice(&data);
+    wav_bytes
+}
+
+// ---------------------------------------------------------------------------
+// Code generation
+// ---------------------------------------------------------------------------
+
+fn generate_module(
+    manifest: &Manifest,
+    prepared_clips: &[PreparedClip],
+    manifest_file_name: &str,
+) -> String {
+    let mut module = String::new();
+    writeln!(
+        module,
+        "// @generated by `cargo xtask audio-prep` from `{manifest_file_name}`. Do not edit."
+    )
+    .unwrap();
+    writeln!(module, "//").unwrap();
+    for prepared_clip in prepared_clips {
+        writeln!(
+            module,
+            "// - {}: {}",
+            prepared_clip.name, prepared_clip.summary
+        )
+        .unwrap();
+    }
+    writeln!(module).unwrap();
+
+    let mut macro_names = BTreeSet::new();
+    for prepared_clip in prepared_clips {
+        macro_names.insert(match prepared_clip.codec {
+            Codec::Pcm => "pcm_clip",
+            Codec::Adpcm => "adpcm_clip",
+        });
+    }
+    let macro_names: Vec<&str> = macro_names.into_iter().collect();
+    if let [macro_name] = macro_names.as_slice() {
+        writeln!(module, "use device_envoy::audio_player::{macro_name};").unwrap();
+    } else {
+        writeln!(
+            module,
+            "use device_envoy::audio_player::{{{}}};",
+            macro_names.join(", ")
+        )
+        .unwrap();
+    }
+
+    let visibility = if manifest.visibility.is_empty() {
+        String::new()
+    } else {
+        format!("{} ", manifest.visibility)
+    };
+    for prepared_clip in prepared_clips {
+        writeln!(module).unwrap();
+        match prepared_clip.codec {
+            Codec::Pcm => {
+                writeln!(module, "pcm_clip! {{").unwrap();
+                writeln!(module, "    {visibility}{} {{", prepared_clip.name).unwrap();
+                writeln!(module, "        file: {:?},", prepared_clip.asset_file_name).unwrap();
+                writeln!(
+                    module,
+                    "        source_sample_rate_hz: {},",
+                    rust_integer_literal(prepared_clip.sample_rate_hz)
+                )
+                .unwrap();
+            }
+            Codec::Adpcm => {
+                writeln!(module, "adpcm_clip! {{").unwrap();
+                writeln!(module, "    {visibility}{} {{", prepared_clip.name).unwrap();
+                writeln!(module, "        file: {:?},", prepared_clip.asset_file_name).unwrap();
+            }
+        }
+        if prepared_clip.channel_count == 2 {
+            writeln!(module, "        channels: 2,").unwrap();
+        }
+        writeln!(module, "    }}").unwrap();
+        writeln!(module, "}}").unwrap();
+    }
+    module
+}
+
+/// Formats `value` with `_` thousands separators, as rustfmt-friendly code would.
+fn rust_integer_literal(value: u32) -> String {
+    let digits = value.to_string();
+    let mut literal = String::with_capacity(digits.len() + digits.len() / 3);
+    for (digit_index, digit) in digits.chars().enumerate() {
+        if digit_index > 0 && (digits.len() - digit_index) % 3 == 0 {
+            literal.push('_');
+        }
+        literal.push(digit);
+    }
+    literal
+}
+
+fn to_snake_case(name: &str) -> String {
+    let mut snake_case = String::with_capacity(name.len() + 4);
+    let mut previous_char: Option<char> = None;
+    for char in name.chars() {
+        if char.is_ascii_uppercase() {
+            if previous_char.is_some_and(|previous_char| {
+                previous_char.is_ascii_lowercase() || previous_char.is_ascii_digit()
+            }) {
+                snake_case.push('_');
+            }
+            snake_case.push(char.to_ascii_lowercase());
+        } else {
+            snake_case.push(char);
+        }
+        previous_char = Some(char);
+    }
+    snake_case
+}
+
+#[cfg(test)]
+mod tests {
+    use core::time::Duration;
+
+    use device_envoy::audio_player::{
+        __adpcm_clip_from_parts, __parse_adpcm_wav_header, __pcm_clip_from_samples, AtEnd,
+        AudioPlayer, AudioPlayerStatic, AudioRenderer, Playable,
+    };
+
+    use super::*;
+
+    const CLIP: &str = "[[clip]]\nname = \"Chime\"\nsource = \"chime.wav\"\n";
+
+    fn parse_error(manifest_text: &str) -> String {
+        Manifest::parse(manifest_text)
+            .err()
+            .expect("manifest should be rejected")
+            .to_string()
+    }
+
+    fn sine(frequency_hz: f64, amplitude: f64, sample_rate_hz: u32, len: usize) -> Vec<f64> {
+        (0..len)
+            .map(|sample_index| {
+                let time = sample_index as f64 / f64::from(sample_rate_hz);
+                amplitude * (2.0 * std::f64::consts::PI * frequency_hz * time).sin()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn manifest_parses_keys_and_defaults() {
+        let manifest = Manifest::parse(
+            "out_dir = \"assets\" # where it goes\n\
+             \n\
+             [[clip]]\n\
+             name = \"Nasa\"\n\
+             source = \"sources/nasa #1.flac\"\n\
+             sample_rate_hz = 22_050\n\
+             codec = \"adpcm\"\n\
+             loudness_lufs = -16\n\
+             [[clip]]\n\
+             name = \"Raw\"\n\
+             source = \"raw.s16\"\n\
+             source_sample_rate_hz = 8000\n\
+             source_channels = 2\n\
+             channels = 2\n\
+             normalize_peak_dbfs = -1.5\n",
+        )
+        .unwrap();
+        assert_eq!(manifest.out_dir, PathBuf::from("assets"));
+        assert_eq!(manifest.module, DEFAULT_MODULE_FILE_NAME);
+        assert_eq!(manifest.visibility, "pub");
+        let [nasa, raw] = manifest.clips.as_slice() else {
+            panic!("expected two clips");
+        };
+        assert_eq!(nasa.source, PathBuf::from("sources/nasa #1.flac"));
+        assert_eq!(nasa.sample_rate_hz, Some(22_050));
+        assert!(nasa.codec == Codec::Adpcm);
+        assert!(matches!(nasa.level, Level::LoudnessLufs(lufs) if lufs == -16.0));
+        assert_eq!(raw.source_format, Some((8_000, 2)));
+        assert_eq!(raw.channel_count, 2);
+        assert!(matches!(raw.level, Level::PeakDbfs(dbfs) if dbfs == -1.5));
+    }
+
+    #[test]
+    fn manifest_rejects_bad_syntax() {
+        assert_eq!(
+            parse_error(&format!("{CLIP}[clip]\n")),
+            "line 4: expected `[[clip]]`"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}codec\n")),
+            "line 4: expected `key = value`"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}codec = \"pcm\n")),
+            "line 4: unterminated string"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}codec = pcm\n")),
+            "line 4: unsupported value `pcm` (expected a string or number)"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}codec = \"p\\cm\"\n")),
+            "line 4: only `\\\\` and `\\\"` escapes are supported"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}name = \"Other\"\n")),
+            "line 4: duplicate key `name`"
+        );
+    }
+
+    #[test]
+    fn manifest_rejects_bad_values() {
+        assert_eq!(
+            parse_error("out_dir = \".\"\n"),
+            "manifest has no `[[clip]]` entries"
+        );
+        assert_eq!(
+            parse_error(&format!("module = \"clips.txt\"\n{CLIP}")),
+            "`module` must name a `.rs` file"
+        );
+        assert_eq!(
+            parse_error(&format!("outdir = \".\"\n{CLIP}")),
+            "unknown top-level key `outdir`"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}volume = 3\n")),
+            "unknown clip key `volume`"
+        );
+        assert_eq!(
+            parse_error("[[clip]]\nsource = \"chime.wav\"\n"),
+            "clip is missing `name`"
+        );
+        assert_eq!(
+            parse_error("[[clip]]\nname = \"Chime\"\n"),
+            "clip `Chime` is missing `source`"
+        );
+        assert_eq!(
+            parse_error("[[clip]]\nname = \"chime-1\"\nsource = \"chime.wav\"\n"),
+            "clip name `chime-1` is not a Rust identifier"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}{CLIP}")),
+            "duplicate clip name `Chime`"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}codec = \"mp3\"\n")),
+            "`codec` must be \"pcm\" or \"adpcm\", not \"mp3\""
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}sample_rate_hz = 0\n")),
+            "`sample_rate_hz` must be a positive integer"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}sample_rate_hz = 22050.5\n")),
+            "`sample_rate_hz` must be an integer"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}channels = 3\n")),
+            "clip `Chime`: `channels` must be 1 or 2"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}trim_end_ms = -5\n")),
+            "clip `Chime`: trims must be >= 0"
+        );
+        assert_eq!(
+            parse_error(&format!("{CLIP}normalize_peak_dbfs = 1.0\n")),
+            "clip `Chime`: `normalize_peak_dbfs` must be <= 0"
+        );
+        assert_eq!(
+            parse_error(&format!(
+                "{CLIP}normalize_peak_dbfs = -1.0\nloudness_lufs = -16.0\n"
+            )),
+            "clip `Chime`: set `normalize_peak_dbfs` or `loudness_lufs`, not both"
+        );
+    }
+
+    #[test]
+    fn resample_matches_pcm_clip_length() {
+        let samples = vec![0.0; 1_001];
+        assert_eq!(resample(&samples, 44_100, 22_050).unwrap().len(), 501);
+        assert_eq!(resample(&samples, 22_050, 48_000).unwrap().len(), 2_179);
+        assert_eq!(resample(&samples, 16_000, 16_000).unwrap().len(), 1_001);
+        // Coprime rates need one phase per output sample.
+        assert!(resample(&samples, 44_100, 1_000_003).is_err());
+    }
+
+    #[test]
+    fn resample_has_unity_dc_gain() {
+        for (from_hz, to_hz) in [(44_100, 22_050), (22_050, 48_000), (48_000, 16_000)] {
+            let output = resample(&vec![0.5; 4_000], from_hz, to_hz).unwrap();
+            // Skip the ends, where the kernel runs off the input.
+            let margin = output.len() / 10;
+            for sample in &output[margin..output.len() - margin] {
+                assert!(
+                    (sample - 0.5).abs() < 1e-9,
+                    "{from_hz} -> {to_hz}: {sample}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn loudness_of_reference_tone() {
+        // BS.1770: a 997 Hz sine at 0 dBFS in one channel reads -3.01 LUFS.
+        for sample_rate_hz in [48_000, 44_100, 22_050] {
+            let tone = sine(
+                997.0,
+                db_to_linear(-20.0),
+                sample_rate_hz,
+                2 * sample_rate_hz as usize,
+            );
+            let mono_lufs = integrated_loudness(&[tone.clone()], sample_rate_hz).unwrap();
+            assert!(
+                (mono_lufs + 23.01).abs() < 0.05,
+                "{sample_rate_hz} Hz: {mono_lufs}"
+            );
+            let stereo_lufs = integrated_loudness(&[tone.clone(), tone], sample_rate_hz).unwrap();
+            assert!(
+                (stereo_lufs + 20.0).abs() < 0.05,
+                "{sample_rate_hz} Hz: {stereo_lufs}"
+            );
+        }
+        assert_eq!(integrated_loudness(&[vec![0.0; 48_000]], 48_000), None);
+    }
+
+    #[test]
+    fn adpcm_round_trips_through_crate_decoder() {
+        const SAMPLE_RATE_HZ: u32 = 22_050;
+        // Not a whole number of 505-sample blocks, so the last block is padded.
+        const SAMPLE_COUNT: usize = 1_000;
+        const DATA_LEN: usize = 2 * ADPCM_BLOCK_ALIGN_MONO;
+
+        let samples: Vec<i16> = sine(440.0, 0.5, SAMPLE_RATE_HZ, SAMPLE_COUNT)
+            .iter()
+            .map(|sample| (sample * 32_768.0).round() as i16)
+            .collect();
+        let wav_bytes = adpcm_wav_bytes(&samples, 1, SAMPLE_RATE_HZ);
+
+        // The crate's `adpcm_clip!` parser accepts the file.
+        let header = __parse_adpcm_wav_header(&wav_bytes);
+        assert_eq!(header.block_align, ADPCM_BLOCK_ALIGN_MONO);
+        assert_eq!(header.samples_per_block, 505);
+        assert_eq!(header.data_chunk_len, DATA_LEN);
+        let data: [u8; DATA_LEN] = wav_bytes
+            [header.data_chunk_start..header.data_chunk_start + DATA_LEN]
+            .try_into()
+            .unwrap();
+        let xtask_clip: &'static dyn Playable<SAMPLE_RATE_HZ> = Box::leak(Box::new(
+            __adpcm_clip_from_parts::<SAMPLE_RATE_HZ, DATA_LEN>(
+                header.block_align as u16,
+                header.samples_per_block as u16,
+                data,
+            ),
+        ));
+        let sample_array: [i16; SAMPLE_COUNT] = samples.clone().try_into().unwrap();
+        let crate_clip: &'static dyn Playable<SAMPLE_RATE_HZ> = Box::leak(Box::new(
+            __pcm_clip_from_samples::<SAMPLE_RATE_HZ, SAMPLE_COUNT>(sample_array)
+                .with_adpcm::<DATA_LEN>(),
+        ));
+
+        // Full volume plays samples unchanged, so the renders are the decoded samples.
+        let render = |clip: &'static dyn Playable<SAMPLE_RATE_HZ>| {
+            static AUDIO_PLAYER_STATIC: AudioPlayerStatic<1, SAMPLE_RATE_HZ> =
+                AudioPlayer::new_static();
+            let audio_player = AudioPlayer::new(&AUDIO_PLAYER_STATIC);
+            let mut audio_renderer = AudioRenderer::<1, SAMPLE_RATE_HZ>::new(&AUDIO_PLAYER_STATIC);
+            audio_player.play([clip], AtEnd::Stop);
+            assert!(audio_renderer.render_until_idle(Duration::from_secs(1)));
+            audio_renderer
+                .frames()
+                .iter()
+                .map(|&[left, _]| left)
+                .collect::<Vec<i16>>()
+        };
+        let xtask_decoded = render(xtask_clip);
+        assert_eq!(xtask_decoded, render(crate_clip));
+
+        let error_power = samples
+            .iter()
+            .zip(&xtask_decoded)
+            .map(|(&sample, &decoded)| (f64::from(sample) - f64::from(decoded)).powi(2))
+            .sum::<f64>();
+        let signal_power = samples
+            .iter()
+            .map(|&sample| f64::from(sample).powi(2))
+            .sum::<f64>();
+        let snr_db = 10.0 * (signal_power / error_power).log10();
+        assert!(snr_db > 20.0, "ADPCM round trip SNR {snr_db:.1} dB");
+    }
+
+    #[test]
+    fn module_header_names_only_the_manifest_file() {
+        let manifest = Manifest::parse(CLIP).unwrap();
+        let prepared_clip = PreparedClip {
+            name: "Chime".to_string(),
+            asset_file_name: "chime.s16".to_string(),
+            asset_bytes: Vec::new(),
+            codec: Codec::Pcm,
+            sample_rate_hz: 22_050,
+            channel_count: 1,
+            summary: "0.140 s".to_string(),
+        };
+        let module = generate_module(&manifest, &[prepared_clip], "audio_prep.toml");
+        assert!(module.starts_with(
+            "// @generated by `cargo xtask audio-prep` from `audio_prep.toml`. Do not edit.\n"
+        ));
+        assert!(module.contains("        source_sample_rate_hz: 22_050,\n"));
+    }
+}
@@ -54,7 +54,7 @@ led2d! {
 pub struct Led2dGenerated;
 
 #[cfg(doc)]
-use crate::led2d::{Frame2d, Point, Size};
+use crate::led2d::{CompressedAnimation, Frame2d, Point, Size};
 #[cfg(doc)]
 use crate::led_strip::RGB8;
 #[cfg(doc)]
@@ -95,7 +95,7 @@ impl Led2dGenerated {
     ///
     /// For [`embedded-graphics`](https://docs.rs/embedded-graphics) drawing operation.
     pub const BOTTOM_RIGHT: Point = Frame2d::<12, 4>::BOTTOM_RIGHT;
-    /// Total LEDs in this panel (width × height).
+    /// Total LEDs in this panel (width × height, or fewer for a sparse layout).
     pub const LEN: usize = 48;
     /// Maximum brightness level, automatically limited by the power budget specified in `max_current`.
     ///
@@ -188,6 +188,62 @@ impl Led2dGenerated {
         let _ = frames;
         Ok(())
     }
+
+    /// Animate a compressed animation on the LED panel, decoding one frame at a time.
+    ///
+    /// See [`CompressedAnimation`] for usage.
+    pub fn animate_compressed(
+        &self,
+        animation: &'static CompressedAnimation<{ Self::WIDTH }, { Self::HEIGHT }>,
+    ) -> Result<()> {
+        let _ = animation;
+        Ok(())
+    }
+
+    /// Return the LED layout currently in use.
+    ///
+    /// This is the macro's `led_layout` unless replaced by [`set_led_layout`](Self::set_led_layout),
+    /// [`load_led_layout`](Self::load_led_layout), or [`calibrate_led_layout`](Self::calibrate_led_layout).
+    pub fn led_layout(&self) -> LedLayout<{ Self::LEN }, { Self::WIDTH }, { Self::HEIGHT }> {
+        LED_LAYOUT
+    }
+
+    /// Replace the LED layout in use, for example with one built at runtime.
+    ///
+    /// Takes effect with the next `write_frame` or `animate` call.
+    pub fn set_led_layout(
+        &mut self,
+        led_layout: &LedLayout<{ Self::LEN }, { Self::WIDTH }, { Self::HEIGHT }>,
+    ) {
+        let _ = led_layout;
+    }
+
+    /// Use the LED layout saved in a [`FlashBlock`](crate::flash_array::FlashBlock), if any.
+    ///
+    /// Returns `Ok(true)` if a saved layout was loaded. Otherwise keeps the macro's const
+    /// `led_layout` as the fallback and returns `Ok(false)`.
+    pub fn load_led_layout(
+        &mut self,
+        flash_block: &mut crate::flash_array::FlashBlock,
+    ) -> Result<bool> {
+        let _ = flash_block;
+        Ok(false)
+    }
+
+    /// Interactively choose the panel orientation with a button and save it to flash.
+    ///
+    /// Lights each LED in turn in reading order (left to right, top to bottom), starting
+    /// with a green LED. A short press tries the next of the layout's
+    /// [`orientations`](LedLayout::orientations); a long press confirms, saves the layout to
+    /// `flash_block` (see [`load_led_layout`](Self::load_led_layout)), and returns it.
+    pub async fn calibrate_led_layout(
+        &mut self,
+        button: &mut crate::button::Button<'_>,
+        flash_block: &mut crate::flash_array::FlashBlock,
+    ) -> Result<LedLayout<{ Self::LEN }, { Self::WIDTH }, { Self::HEIGHT }>> {
+        let _ = (button, flash_block);
+        Ok(LED_LAYOUT)
+    }
 }
 "#;
 
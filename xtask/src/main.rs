@@ -3,7 +3,10 @@
 //! Run with: `cargo xtask <command>`
 
 mod adpcm_clip_generated;
+mod audio_decode;
 mod audio_player_generated;
+mod audio_prep;
+mod audio_recorder_generated;
 mod led2d_generated;
 mod led_strip_generated;
 mod pcm_clip_generated;
@@ -48,6 +51,11 @@ enum Commands {
     HandFramesGen,
     /// Generate clock video frames from video file
     ClockFramesGen,
+    /// Convert audio sources listed in a manifest into clip assets and a Rust module
+    AudioPrep {
+        /// Path to the audio manifest (e.g., examples/data/audio/audio_prep.toml)
+        manifest: PathBuf,
+    },
     /// Build library with specified features
     Build {
         #[arg(long, default_value = "pico1")]
@@ -164,6 +172,14 @@ fn main() -> ExitCode {
                 ExitCode::SUCCESS
             }
         }
+        Commands::AudioPrep { manifest } => {
+            if let Err(e) = audio_prep::run(&manifest) {
+                eprintln!("Error preparing audio clips: {}", e);
+                ExitCode::FAILURE
+            } else {
+                ExitCode::SUCCESS
+            }
+        }
         Commands::Build { board, arch, wifi } => build_lib(board, arch, wifi),
         Commands::Example {
             name,
@@ -194,6 +210,10 @@ fn check_quick() -> ExitCode {
         eprintln!("Error generating audio_player_generated.rs: {}", err);
         return ExitCode::FAILURE;
     }
+    if let Err(err) = audio_recorder_generated::generate_audio_recorder_generated(&workspace_root) {
+        eprintln!("Error generating audio_recorder_generated.rs: {}", err);
+        return ExitCode::FAILURE;
+    }
     if let Err(err) = led2d_generated::generate_led2d_generated(&workspace_root) {
         eprintln!("Error generating led2d_generated.rs: {}", err);
         return ExitCode::FAILURE;
@@ -351,6 +371,10 @@ fn check_all() -> ExitCode {
         eprintln!("Error generating audio_player_generated.rs: {}", err);
         return ExitCode::FAILURE;
     }
+    if let Err(err) = audio_recorder_generated::generate_audio_recorder_generated(&workspace_root) {
+        eprintln!("Error generating audio_recorder_generated.rs: {}", err);
+        return ExitCode::FAILURE;
+    }
     if let Err(err) = led2d_generated::generate_led2d_generated(&workspace_root) {
         eprintln!("Error generating led2d_generated.rs: {}", err);
         return ExitCode::FAILURE;
@@ -659,6 +683,10 @@ fn check_docs() -> ExitCode {
         eprintln!("Error generating audio_player_generated.rs: {}", err);
         return ExitCode::FAILURE;
     }
+    if let Err(err) = audio_recorder_generated::generate_audio_recorder_generated(&workspace_root) {
+        eprintln!("Error generating audio_recorder_generated.rs: {}", err);
+        return ExitCode::FAILURE;
+    }
     if let Err(err) = led2d_generated::generate_led2d_generated(&workspace_root) {
         eprintln!("Error generating led2d_generated.rs: {}", err);
         return ExitCode::FAILURE;
@@ -1189,6 +1217,17 @@ fn check_generated_doc_stubs(workspace_root: &Path) -> Result<(), String> {
                 "pub fn volume(&self) -> Volume",
             ],
         },
+        GeneratedDocStubExpectation {
+            relative_path: "src/audio_recorder/audio_recorder_generated.rs",
+            required_fragments: &[
+                "pub const SAMPLE_RATE_HZ: u32",
+                "pub const CAPACITY: usize",
+                "pub async fn read(&self, samples: &mut [i16]) -> usize",
+                "pub fn level(&self) -> AudioLevel",
+                "pub async fn wait_for_level(&self, threshold: Volume) -> AudioLevel",
+                "pub async fn record_to_flash<const BLOCK_COUNT: usize>(",
+            ],
+        },
         GeneratedDocStubExpectation {
             relative_path: "src/led_strip/led_strip_generated.rs",
             required_fragments: &[
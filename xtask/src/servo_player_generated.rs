@@ -32,7 +32,11 @@ pub struct ServoPlayerGenerated;
 #[cfg(doc)]
 use crate::Result;
 #[cfg(doc)]
-use crate::servo_player::AtEnd;
+use crate::flash_array::FlashBlock;
+#[cfg(doc)]
+use crate::servo::ServoCalibration;
+#[cfg(doc)]
+use crate::servo_player::{AtEnd, MotionLimits};
 
 #[cfg(doc)]
 // Must be public for macro expansion in downstream crates, but not user-facing API.
@@ -90,6 +94,45 @@ impl ServoPlayerGenerated {
         let _ = degrees;
     }
 
+    /// Limit the speed and acceleration of moves, or pass `None` to jump straight to each
+    /// target (the default).
+    ///
+    /// Takes effect immediately, including for a move already in progress, and applies to
+    /// both `set_degrees` and `animate` steps.
+    ///
+    /// See the [`servo_player`](mod@crate::servo_player) module docs for usage.
+    pub fn set_motion_limits(&self, motion_limits: Option<MotionLimits>) {
+        let _ = motion_limits;
+    }
+
+    /// The angle the servo was most recently driven to, rounded to the nearest degree.
+    ///
+    /// During a move limited by [`MotionLimits`] or a [`ServoGroup`](crate::servo_player::ServoGroup)
+    /// timeline, this follows the servo along the way.
+    #[must_use]
+    pub fn degrees(&self) -> u16 {
+        0
+    }
+
+    /// Map angles with `calibration` instead of the macro's `min_us`/`max_us`.
+    ///
+    /// Takes effect immediately, without interrupting a move or animation in progress.
+    ///
+    /// See [`Servo::calibrate`](crate::servo::Servo::calibrate) for measuring a calibration.
+    pub fn set_calibration(&self, calibration: ServoCalibration) {
+        let _ = calibration;
+    }
+
+    /// Use the calibration saved in `flash_block` by
+    /// [`Servo::calibrate`](crate::servo::Servo::calibrate), if there is one.
+    ///
+    /// Returns `Ok(true)` if a saved calibration was loaded. Returns `Ok(false)` and keeps
+    /// the macro's `min_us`/`max_us` if the block is empty.
+    pub fn load_calibration(&self, flash_block: &mut FlashBlock) -> Result<bool> {
+        let _ = flash_block;
+        Ok(false)
+    }
+
     /// Hold the servo at its current position.
     ///
     /// See the [`servo_player`](mod@crate::servo_player) module docs for usage.